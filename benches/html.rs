@@ -0,0 +1,48 @@
+//! HTML generation benchmarks over the test fixtures (`cargo bench --bench
+//! html`)
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use mtg_gen::Card;
+use mtg_gen::render::card_to_html;
+use std::hint::black_box;
+
+/// Every fixture card, with its file stem
+fn fixtures() -> Vec<(String, Card)> {
+    let mut paths: Vec<_> = std::fs::read_dir("tests/fixtures")
+        .expect("the fixtures directory exists")
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "yaml"))
+        .collect();
+    paths.sort();
+    paths
+        .iter()
+        .map(|path| {
+            let name = path.file_stem().unwrap().to_string_lossy().into_owned();
+            let card = facet_yaml::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+            (name, card)
+        })
+        .collect()
+}
+
+fn bench_html(c: &mut Criterion) {
+    let cards = fixtures();
+
+    c.bench_function("card_to_html/all fixtures", |b| {
+        b.iter(|| {
+            for (_, card) in &cards {
+                black_box(card_to_html(black_box(card)));
+            }
+        })
+    });
+
+    let mut group = c.benchmark_group("card_to_html");
+    for (name, card) in &cards {
+        group.bench_function(name.as_str(), |b| {
+            b.iter(|| black_box(card_to_html(black_box(card))))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_html);
+criterion_main!(benches);
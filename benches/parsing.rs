@@ -0,0 +1,62 @@
+//! Parsing benchmarks over the test fixtures (`cargo bench --bench parsing`)
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use mtg_gen::*;
+use std::hint::black_box;
+
+/// Every fixture card's YAML
+fn fixtures() -> Vec<String> {
+    let mut paths: Vec<_> = std::fs::read_dir("tests/fixtures")
+        .expect("the fixtures directory exists")
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "yaml"))
+        .collect();
+    paths.sort();
+    paths
+        .iter()
+        .map(|path| std::fs::read_to_string(path).unwrap())
+        .collect()
+}
+
+fn bench_parsing(c: &mut Criterion) {
+    let yaml = fixtures();
+    let cards: Vec<Card> = yaml
+        .iter()
+        .map(|yaml| facet_yaml::from_str(yaml).unwrap())
+        .collect();
+    let costs: Vec<String> = cards
+        .iter()
+        .flat_map(|card| card.base().mana_cost.as_ref())
+        .map(ToString::to_string)
+        .collect();
+    let rules_text: Vec<String> = cards
+        .iter()
+        .flat_map(|card| card.base().rules_text.as_ref())
+        .map(ToString::to_string)
+        .collect();
+
+    c.bench_function("CastingManaCost::parse", |b| {
+        b.iter(|| {
+            for cost in &costs {
+                black_box(CastingManaCost::parse(black_box(cost)).unwrap());
+            }
+        })
+    });
+    c.bench_function("RulesText::parse", |b| {
+        b.iter(|| {
+            for text in &rules_text {
+                black_box(RulesText::parse(black_box(text)).unwrap());
+            }
+        })
+    });
+    c.bench_function("card YAML", |b| {
+        b.iter(|| {
+            for yaml in &yaml {
+                black_box(facet_yaml::from_str::<Card>(black_box(yaml)).unwrap());
+            }
+        })
+    });
+}
+
+criterion_group!(benches, bench_parsing);
+criterion_main!(benches);
@@ -0,0 +1,14 @@
+//! Any card that parses from YAML can be validated and rendered to HTML
+//! without panicking.
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use mtg_gen::Card;
+use mtg_gen::render::card_to_html;
+
+fuzz_target!(|input: &str| {
+    if let Ok(card) = facet_yaml::from_str::<Card>(input) {
+        let _ = card.validate();
+        let _ = card_to_html(&card);
+    }
+});
@@ -0,0 +1,15 @@
+//! Mana and action costs parse without panicking, and a parsed cost reads
+//! back the same from its `to_string()`.
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use mtg_gen::{ActionCost, CastingManaCost};
+
+fuzz_target!(|input: &str| {
+    if let Ok(cost) = CastingManaCost::parse(input) {
+        assert_eq!(CastingManaCost::parse(&cost.to_string()), Ok(cost));
+    }
+    if let Ok(cost) = ActionCost::parse(input) {
+        assert_eq!(ActionCost::parse(&cost.to_string()), Ok(cost));
+    }
+});
@@ -0,0 +1,15 @@
+//! Structured inputs from the `fuzz` feature's `arbitrary` impls: costs and
+//! rules text parse back from their `to_string()`, and any card renders.
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use mtg_gen::render::card_to_html;
+use mtg_gen::{Card, CastingManaCost, RulesText};
+
+fuzz_target!(|input: (CastingManaCost, RulesText, Card)| {
+    let (cost, text, card) = input;
+    assert_eq!(CastingManaCost::parse(&cost.to_string()), Ok(cost));
+    assert_eq!(RulesText::parse(&text.to_string()), Ok(text));
+    let _ = card.validate();
+    let _ = card_to_html(&card);
+});
@@ -0,0 +1,14 @@
+//! Rules text and Scryfall oracle text parse without panicking, and parsed
+//! rules text reads back the same from its `to_string()`.
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use mtg_gen::RulesText;
+use mtg_gen::scryfall::oracle_text_to_rules_text;
+
+fuzz_target!(|input: &str| {
+    if let Ok(text) = RulesText::parse(input) {
+        assert_eq!(RulesText::parse(&text.to_string()), Ok(text));
+    }
+    let _ = oracle_text_to_rules_text(input);
+});
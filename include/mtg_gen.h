@@ -0,0 +1,48 @@
+/*
+ * C interface to mtg-gen, built with `--features ffi`:
+ *
+ *     cargo rustc --release --lib --features ffi --crate-type cdylib
+ *
+ * Functions that can fail return NULL and, unless `error` is NULL, set
+ * `*error` to a message to free with mtg_gen_string_free. Everything
+ * returned is owned by the caller and freed with the matching _free
+ * function. Strings are UTF-8.
+ */
+#ifndef MTG_GEN_H
+#define MTG_GEN_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef struct MtgGenCard MtgGenCard;
+typedef struct MtgGenRenderer MtgGenRenderer;
+
+/* Parse and validate a card from YAML. */
+MtgGenCard *mtg_gen_card_parse_yaml(const char *yaml, char **error);
+/* The card's name. */
+char *mtg_gen_card_name(const MtgGenCard *card);
+void mtg_gen_card_free(MtgGenCard *card);
+
+/* Start Chrome for rendering. Blocks; don't call from an async runtime. */
+MtgGenRenderer *mtg_gen_renderer_new(char **error);
+/* Close Chrome and free the renderer. */
+void mtg_gen_renderer_free(MtgGenRenderer *renderer);
+
+/* Render a card to a PNG, storing its size in `*len`. */
+uint8_t *mtg_gen_render_card_to_png_bytes(const MtgGenRenderer *renderer,
+                                          const MtgGenCard *card,
+                                          size_t *len,
+                                          char **error);
+void mtg_gen_bytes_free(uint8_t *bytes, size_t len);
+
+void mtg_gen_string_free(char *string);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* MTG_GEN_H */
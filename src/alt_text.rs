@@ -0,0 +1,355 @@
+//! Screen-reader descriptions of cards (`--alt-text`)
+//!
+//! Builds a plain-language description of a card from its structured
+//! model: the name, the mana cost spelled out, the type line, stats and the
+//! full rules text, with every symbol replaced by Scryfall's English name
+//! for it (`{T}` reads as "tap this permanent"). Written next to each card
+//! image as a `.txt` or `.json` sidecar for publishing sets online.
+
+use crate::card::{Card, CardBase, CardFace};
+use crate::mana::{
+    CastingManaCost, ManaSymbol, ReminderSegment, RulesText, RulesTextSegment, strip_markup,
+};
+use crate::symbology;
+use crate::utils::to_roman_numeral;
+use facet::Facet;
+use std::fmt;
+
+/// Sidecar format for `--alt-text`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AltTextFormat {
+    Txt,
+    Json,
+}
+
+impl AltTextFormat {
+    /// Parse a format name: `txt` or `json`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error for any other name.
+    #[must_use = "parsing returns a Result that should be handled"]
+    pub fn parse(s: &str) -> Result<Self, String> {
+        match s.trim().to_lowercase().as_str() {
+            "txt" | "text" => Ok(AltTextFormat::Txt),
+            "json" => Ok(AltTextFormat::Json),
+            other => Err(format!(
+                "Unknown alt text format: {} (expected txt or json)",
+                other
+            )),
+        }
+    }
+
+    /// The sidecar file extension, without a leading dot
+    #[must_use]
+    pub fn extension(self) -> &'static str {
+        match self {
+            AltTextFormat::Txt => "txt",
+            AltTextFormat::Json => "json",
+        }
+    }
+}
+
+/// The description of one face of a card
+#[derive(Facet, Debug, Clone, Default, PartialEq, Eq)]
+pub struct AltTextFace {
+    pub name: String,
+    /// The mana cost spelled out, e.g. "2 generic mana, one blue mana"
+    #[facet(default)]
+    pub mana_cost: Option<String>,
+    #[facet(default)]
+    pub type_line: Option<String>,
+    /// Power and toughness, loyalty or defense
+    #[facet(default)]
+    pub stats: Option<String>,
+    /// Rules text, one entry per paragraph or ability
+    pub rules_text: Vec<String>,
+    #[facet(default)]
+    pub flavor_text: Option<String>,
+}
+
+/// The description of a whole card
+#[derive(Facet, Debug, Clone, Default, PartialEq, Eq)]
+pub struct AltText {
+    /// Every face of the card, the main one first
+    pub faces: Vec<AltTextFace>,
+    /// The whole description as one line, for an image's `alt` attribute
+    pub description: String,
+}
+
+impl AltText {
+    /// Describe `card`. Cards with several faces describe each face, in
+    /// order, rather than the card as a whole.
+    #[must_use]
+    pub fn from_card(card: &Card) -> Self {
+        if let Some(faces) = card_faces(card) {
+            return Self::from_faces(faces.iter().map(face).collect());
+        }
+
+        let mut main = base_face(card.base());
+        let mut faces = Vec::new();
+
+        match card {
+            Card::Normal(_) | Card::Token(_) => {}
+            Card::Planeswalker(card) => {
+                main.stats = Some(format!("Loyalty {}", card.loyalty));
+                for ability in &card.loyalty_abilities {
+                    main.rules_text.push(format!(
+                        "{}: {}",
+                        ability.cost,
+                        spoken_text(&ability.text)
+                    ));
+                }
+            }
+            Card::Saga(card) => {
+                for chapter in &card.chapters {
+                    let numbers: Vec<String> = chapter
+                        .chapters
+                        .iter()
+                        .map(|&n| to_roman_numeral(n))
+                        .collect();
+                    main.rules_text.push(format!(
+                        "Chapter {}: {}",
+                        numbers.join(", "),
+                        spoken_text(&chapter.text)
+                    ));
+                }
+            }
+            Card::Class(card) => {
+                for level in &card.levels {
+                    let text = spoken_text(&level.text);
+                    main.rules_text.push(match &level.cost {
+                        Some(cost) => {
+                            format!("Level {}, for {}: {}", level.level, spoken_cost(cost), text)
+                        }
+                        None => format!("Level {}: {}", level.level, text),
+                    });
+                }
+            }
+            Card::Adventure(card) => faces.push(AltTextFace {
+                name: card.adventure.name.clone(),
+                mana_cost: Some(spoken_cost(&card.adventure.mana_cost)),
+                type_line: Some(card.adventure.type_line.clone()),
+                rules_text: paragraphs(&card.adventure.rules_text),
+                ..AltTextFace::default()
+            }),
+            Card::Split(_)
+            | Card::Flip(_)
+            | Card::Transform(_)
+            | Card::ModalDfc(_)
+            | Card::Meld(_) => {}
+            Card::Battle(card) => {
+                main.stats = Some(format!("Defense {}", card.defense));
+                faces.push(AltTextFace {
+                    name: card.backside_name.clone(),
+                    type_line: Some(card.backside_type_line.clone()),
+                    rules_text: paragraphs(&card.backside_rules_text),
+                    ..AltTextFace::default()
+                });
+            }
+            Card::Leveler(card) => {
+                for range in &card.leveler_ranges {
+                    let mut line = format!("Level {}", range.range);
+                    if let (Some(power), Some(toughness)) = (&range.power, &range.toughness) {
+                        line.push_str(&format!(", {}/{}", power, toughness));
+                    }
+                    if let Some(text) = &range.text {
+                        line.push_str(&format!(": {}", spoken_text(text)));
+                    }
+                    main.rules_text.push(line);
+                }
+            }
+            Card::Prototype(card) => {
+                let mut prototype = face(&card.prototype);
+                prototype.name = format!("Prototype of {}", main.name);
+                faces.push(prototype);
+            }
+        }
+
+        faces.insert(0, main);
+        Self::from_faces(faces)
+    }
+
+    fn from_faces(faces: Vec<AltTextFace>) -> Self {
+        let description = faces
+            .iter()
+            .map(describe_face)
+            .collect::<Vec<_>>()
+            .join(" // ");
+        Self { faces, description }
+    }
+
+    /// Serialize the description to JSON.
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        facet_json::to_string(self)
+    }
+}
+
+/// One line per field, with a blank line between faces
+impl fmt::Display for AltText {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, face) in self.faces.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            writeln!(f, "{}", face.name)?;
+            if let Some(cost) = &face.mana_cost {
+                writeln!(f, "Mana cost: {}", cost)?;
+            }
+            if let Some(type_line) = &face.type_line {
+                writeln!(f, "{}", type_line)?;
+            }
+            if let Some(stats) = &face.stats {
+                writeln!(f, "{}", stats)?;
+            }
+            for paragraph in &face.rules_text {
+                writeln!(f, "{}", paragraph)?;
+            }
+            if let Some(flavor) = &face.flavor_text {
+                writeln!(f, "Flavor text: {}", flavor)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Returns the faces of a card printed as several named halves or sides.
+fn card_faces(card: &Card) -> Option<&[CardFace]> {
+    match card {
+        Card::Split(card) => Some(&card.faces),
+        Card::Flip(card) => Some(&card.faces),
+        Card::Transform(card) => Some(&card.faces),
+        Card::ModalDfc(card) => Some(&card.faces),
+        Card::Meld(card) => Some(&card.faces),
+        _ => None,
+    }
+}
+
+fn base_face(base: &CardBase) -> AltTextFace {
+    AltTextFace {
+        name: base.name.clone(),
+        mana_cost: base.mana_cost.as_ref().map(spoken_cost),
+        type_line: Some(base.type_line.clone()),
+        stats: power_toughness(&base.power, &base.toughness),
+        rules_text: base.rules_text.as_ref().map(paragraphs).unwrap_or_default(),
+        flavor_text: base.flavor_text.as_deref().map(strip_markup),
+    }
+}
+
+fn face(face: &CardFace) -> AltTextFace {
+    AltTextFace {
+        name: face.name.clone().unwrap_or_default(),
+        mana_cost: face.mana_cost.as_ref().map(spoken_cost),
+        type_line: face.type_line.clone(),
+        stats: power_toughness(&face.power, &face.toughness),
+        rules_text: face.rules_text.as_ref().map(paragraphs).unwrap_or_default(),
+        flavor_text: face.flavor_text.as_deref().map(strip_markup),
+    }
+}
+
+fn power_toughness(power: &Option<String>, toughness: &Option<String>) -> Option<String> {
+    match (power, toughness) {
+        (Some(power), Some(toughness)) => Some(format!("Power {}, toughness {}", power, toughness)),
+        _ => None,
+    }
+}
+
+/// Returns a face as sentences on one line.
+fn describe_face(face: &AltTextFace) -> String {
+    let mut sentences = vec![sentence(&face.name)];
+    if let Some(cost) = &face.mana_cost {
+        sentences.push(sentence(&format!("Mana cost: {}", cost)));
+    }
+    sentences.extend(face.type_line.iter().map(|t| sentence(t)));
+    sentences.extend(face.stats.iter().map(|s| sentence(s)));
+    sentences.extend(face.rules_text.iter().map(|p| sentence(p)));
+    if let Some(flavor) = &face.flavor_text {
+        sentences.push(sentence(&format!("Flavor text: {}", flavor)));
+    }
+    sentences.join(" ")
+}
+
+/// End `s` with a full stop unless it already ends in punctuation.
+fn sentence(s: &str) -> String {
+    let s = s.trim();
+    if s.ends_with(['.', '!', '?', ')', '"']) {
+        s.to_string()
+    } else {
+        format!("{}.", s)
+    }
+}
+
+/// Spell out a mana cost, e.g. `{2}{U}{U}` as "2 generic mana, one blue
+/// mana, one blue mana".
+#[must_use]
+pub fn spoken_cost(cost: &CastingManaCost) -> String {
+    cost.symbols
+        .iter()
+        .map(|symbol| spoken_symbol(&symbol.to_string()))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Returns `text` with every symbol spelled out, paragraphs joined by spaces.
+#[must_use]
+pub fn spoken_text(text: &RulesText) -> String {
+    paragraphs(text).join(" ")
+}
+
+/// Returns each paragraph of `text` with every symbol spelled out.
+fn paragraphs(text: &RulesText) -> Vec<String> {
+    let mut paragraphs = vec![String::new()];
+    for segment in &text.segments {
+        let current = paragraphs.last_mut().expect("paragraphs is never empty");
+        match segment {
+            RulesTextSegment::Text(text)
+            | RulesTextSegment::AbilityWord(text)
+            | RulesTextSegment::Italic(text)
+            | RulesTextSegment::Bold(text) => {
+                current.push_str(text);
+            }
+            RulesTextSegment::Symbol(symbol) => push_symbol(current, *symbol),
+            RulesTextSegment::Reminder(reminder) => {
+                current.push('(');
+                for part in reminder {
+                    match part {
+                        ReminderSegment::Text(text) => current.push_str(text),
+                        ReminderSegment::Symbol(symbol) => push_symbol(current, *symbol),
+                    }
+                }
+                current.push(')');
+            }
+            RulesTextSegment::ParagraphBreak => paragraphs.push(String::new()),
+        }
+    }
+    paragraphs.retain(|p| !p.trim().is_empty());
+    paragraphs
+}
+
+/// Append a symbol's name, capitalized when it starts a paragraph.
+fn push_symbol(paragraph: &mut String, symbol: ManaSymbol) {
+    let spoken = spoken_symbol(&symbol.to_string());
+    if paragraph.trim().is_empty() {
+        let mut chars = spoken.chars();
+        if let Some(first) = chars.next() {
+            paragraph.extend(first.to_uppercase());
+            paragraph.push_str(chars.as_str());
+        }
+    } else {
+        paragraph.push_str(&spoken);
+    }
+}
+
+/// Returns Scryfall's English name for a symbol code like `{T}`, or the code
+/// itself for a symbol the snapshot doesn't list.
+fn spoken_symbol(code: &str) -> String {
+    let content = code
+        .strip_prefix('{')
+        .and_then(|rest| rest.strip_suffix('}'))
+        .unwrap_or(code);
+    ManaSymbol::parse_symbol(content)
+        .ok()
+        .and_then(symbology::lookup)
+        .map_or_else(|| code.to_string(), |entry| entry.english.to_string())
+}
@@ -0,0 +1,113 @@
+//! Art downloaded from `https://` URLs
+//!
+//! Built with `--features remote-art`. A card's `art` may be a URL instead
+//! of a path; each URL is downloaded once into a cache directory, named by a
+//! hash of the URL, and every later render of it reads the cached file.
+//! Clearing the cache directory makes the next run download the art again.
+
+use anyhow::{Context, Result, bail};
+use sha2::{Digest, Sha256};
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+/// Downloads art and keeps it on disk between runs
+pub struct ArtCache {
+    dir: PathBuf,
+    client: reqwest::Client,
+}
+
+impl ArtCache {
+    /// A cache of art in `dir`, which is created on the first download.
+    #[must_use]
+    pub fn new(dir: PathBuf) -> Self {
+        Self {
+            dir,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Returns the cache directory used when none is given:
+    /// `$XDG_CACHE_HOME/mtg-gen/art`, or `~/.cache/mtg-gen/art`. `env` looks
+    /// up an environment variable.
+    #[must_use]
+    pub fn default_dir(env: impl Fn(&str) -> Option<String>) -> PathBuf {
+        let cache = env("XDG_CACHE_HOME")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| env("HOME").map(|home| Path::new(&home).join(".cache")))
+            .unwrap_or_else(|| PathBuf::from(".cache"));
+        cache.join("mtg-gen").join("art")
+    }
+
+    /// Returns where the art at `url` is cached: the SHA-256 of the URL,
+    /// keeping the image's extension so it's easy to tell what it is.
+    #[must_use]
+    pub fn path_for(&self, url: &str) -> PathBuf {
+        let mut name = String::with_capacity(68);
+        for byte in Sha256::digest(url.as_bytes()) {
+            let _ = write!(name, "{:02x}", byte);
+        }
+        if let Some(extension) = extension(url) {
+            name.push('.');
+            name.push_str(&extension);
+        }
+        self.dir.join(name)
+    }
+
+    /// Returns the cached copy of the art at `url`, downloading it first if
+    /// it isn't cached yet.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `url` isn't `https://`, the download fails, or
+    /// the art can't be written to the cache.
+    pub async fn fetch(&self, url: &str) -> Result<PathBuf> {
+        let path = self.path_for(url);
+        if path.is_file() {
+            return Ok(path);
+        }
+        if !url.starts_with("https://") {
+            bail!("Art URLs must use https: {}", url);
+        }
+
+        let body = self
+            .client
+            .get(url)
+            .header("User-Agent", concat!("mtg-gen/", env!("CARGO_PKG_VERSION")))
+            .send()
+            .await?
+            .error_for_status()?
+            .bytes()
+            .await
+            .with_context(|| format!("Failed to download {}", url))?;
+        if body.is_empty() {
+            bail!("Downloaded art is empty: {}", url);
+        }
+
+        // Written aside and renamed, so a run rendering the same art in
+        // another job never reads half a file
+        tokio::fs::create_dir_all(&self.dir).await?;
+        let temp = path.with_extension(format!("{}.part", std::process::id()));
+        tokio::fs::write(&temp, &body)
+            .await
+            .with_context(|| format!("Failed to write {:?}", temp))?;
+        tokio::fs::rename(&temp, &path)
+            .await
+            .with_context(|| format!("Failed to write {:?}", path))?;
+        Ok(path)
+    }
+}
+
+/// The lowercase extension of the last segment of a URL's path, if it has
+/// a short alphanumeric one
+fn extension(url: &str) -> Option<String> {
+    let url = url.split(['?', '#']).next()?;
+    // Skip the host, whose domain isn't an extension
+    let (_, path) = url.split_once("://")?.1.split_once('/')?;
+    let segment = path.rsplit('/').next()?;
+    let (_, extension) = segment.rsplit_once('.')?;
+    (!extension.is_empty()
+        && extension.len() <= 5
+        && extension.chars().all(|c| c.is_ascii_alphanumeric()))
+    .then(|| extension.to_ascii_lowercase())
+}
@@ -0,0 +1,247 @@
+//! Warnings for art that doesn't match the shape of its art box
+//!
+//! Art is drawn with `art_fit: cover` unless a card says otherwise, so an
+//! image wider or taller than its layout's art box (see
+//! [`StyleConfig::art_region`]) loses its edges. [`art_crops`] reads the
+//! size of each of a card's images from its header and returns the part
+//! of it the card shows, for a warning telling the artist what's cut off.
+//! With the `art-crops` feature, [`write_cropped`] saves that part as a
+//! file of its own.
+
+use crate::card::{ArtFit, Card};
+use crate::style::{ArtRegion, StyleConfig};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Share of an image's width or height a crop may cut off without a
+/// warning; a few pixels off the edge aren't worth mentioning.
+const TOLERANCE: f64 = 0.01;
+
+/// The part of an image that's visible in its art box, in image pixels
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Crop {
+    /// Pixels cut off the left edge
+    pub x: u32,
+    /// Pixels cut off the top edge
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Crop {
+    /// Returns the centered part of a `width` × `height` image that fills
+    /// `region` under `art_fit: cover`, or `None` when the image already
+    /// has about the region's aspect ratio.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mtg_gen::art_crop::Crop;
+    /// use mtg_gen::style::ArtRegion;
+    ///
+    /// let crop = Crop::cover(1000, 1000, &ArtRegion::STANDARD).unwrap();
+    /// assert_eq!((crop.x, crop.y, crop.width, crop.height), (0, 157, 1000, 685));
+    /// assert_eq!(Crop::cover(672, 460, &ArtRegion::STANDARD), None);
+    /// ```
+    #[must_use]
+    pub fn cover(width: u32, height: u32, region: &ArtRegion) -> Option<Crop> {
+        if width == 0 || height == 0 {
+            return None;
+        }
+        let aspect = region.aspect_ratio();
+        let (w, h) = (f64::from(width), f64::from(height));
+        let crop = if w / h > aspect {
+            let visible = ((h * aspect).round() as u32).min(width);
+            Crop {
+                x: (width - visible) / 2,
+                y: 0,
+                width: visible,
+                height,
+            }
+        } else {
+            let visible = ((w / aspect).round() as u32).min(height);
+            Crop {
+                x: 0,
+                y: (height - visible) / 2,
+                width,
+                height: visible,
+            }
+        };
+        let cut = 1.0 - f64::from(crop.width) / w + 1.0 - f64::from(crop.height) / h;
+        (cut > TOLERANCE).then_some(crop)
+    }
+}
+
+impl fmt::Display for Crop {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}×{} at ({}, {})",
+            self.width, self.height, self.x, self.y
+        )
+    }
+}
+
+/// An image that's cropped to fit its art box
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArtCrop {
+    /// The image, as given in the card
+    pub path: String,
+    /// The image's size, in pixels
+    pub width: u32,
+    pub height: u32,
+    /// The layout whose art box it's drawn in
+    pub layout: &'static str,
+    /// The art box, in CSS pixels
+    pub region: ArtRegion,
+    /// The part of the image the card shows
+    pub crop: Crop,
+}
+
+impl ArtCrop {
+    /// Returns the lint warning for the image, naming what the card shows.
+    #[must_use]
+    pub fn warning(&self) -> String {
+        format!(
+            "art {} is {}×{} but the {} art box is {}×{}, so only {} is visible",
+            self.path,
+            self.width,
+            self.height,
+            self.layout,
+            self.region.width,
+            self.region.height,
+            self.crop
+        )
+    }
+}
+
+/// Returns the card's images that `art_fit: cover` crops, with what's
+/// left of each. `art_fit: contain` and `fill` show the whole image, and
+/// split cards are skipped, since their halves' art boxes aren't
+/// [`ArtRegion`]s. Art paths should be resolved, see
+/// [`Card::resolve_art_paths`]; images that can't be read or whose format
+/// isn't PNG, JPEG, GIF or WebP are skipped.
+#[must_use]
+pub fn art_crops(card: &Card, style: &StyleConfig) -> Vec<ArtCrop> {
+    if card.base().art_fit.unwrap_or_default() != ArtFit::Cover || matches!(card, Card::Split(_)) {
+        return Vec::new();
+    }
+    let layout = card.layout_name();
+    let region = style.art_region(layout);
+    let mut crops: Vec<ArtCrop> = Vec::new();
+    for path in card.art_paths() {
+        if crops.iter().any(|crop| crop.path == path) {
+            continue;
+        }
+        let Some((width, height)) = std::fs::read(path)
+            .ok()
+            .and_then(|bytes| image_size(&bytes))
+        else {
+            continue;
+        };
+        if let Some(crop) = Crop::cover(width, height, &region) {
+            crops.push(ArtCrop {
+                path: path.to_string(),
+                width,
+                height,
+                layout,
+                region,
+                crop,
+            });
+        }
+    }
+    crops
+}
+
+/// Returns the size of a PNG, JPEG, GIF or WebP image, read from its
+/// header, or `None` for anything else.
+///
+/// # Examples
+///
+/// ```
+/// use mtg_gen::art_crop::image_size;
+///
+/// let gif = b"GIF89a\x20\x03\xc8\x00";
+/// assert_eq!(image_size(gif), Some((800, 200)));
+/// assert_eq!(image_size(b"not an image"), None);
+/// ```
+#[must_use]
+pub fn image_size(bytes: &[u8]) -> Option<(u32, u32)> {
+    let be16 = |at: usize| Some(u16::from_be_bytes(bytes.get(at..at + 2)?.try_into().ok()?));
+    let le16 = |at: usize| Some(u16::from_le_bytes(bytes.get(at..at + 2)?.try_into().ok()?));
+    let le24 = |at: usize| {
+        let b = bytes.get(at..at + 3)?;
+        Some(u32::from(b[0]) | (u32::from(b[1]) << 8) | (u32::from(b[2]) << 16))
+    };
+
+    if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+        let be32 = |at: usize| Some(u32::from_be_bytes(bytes.get(at..at + 4)?.try_into().ok()?));
+        return Some((be32(16)?, be32(20)?));
+    }
+    if bytes.starts_with(b"GIF87a") || bytes.starts_with(b"GIF89a") {
+        return Some((le16(6)?.into(), le16(8)?.into()));
+    }
+    if bytes.starts_with(b"RIFF") && bytes.get(8..12) == Some(&b"WEBP"[..]) {
+        return match bytes.get(12..16)? {
+            b"VP8 " => Some(((le16(26)? & 0x3fff).into(), (le16(28)? & 0x3fff).into())),
+            b"VP8L" => {
+                let bits = u32::from_le_bytes(bytes.get(21..25)?.try_into().ok()?);
+                Some(((bits & 0x3fff) + 1, ((bits >> 14) & 0x3fff) + 1))
+            }
+            b"VP8X" => Some((le24(24)? + 1, le24(27)? + 1)),
+            _ => None,
+        };
+    }
+    if bytes.starts_with(&[0xff, 0xd8]) {
+        // Walk the segments to the frame header, which holds the size
+        let mut at = 2;
+        while at + 4 <= bytes.len() {
+            if bytes[at] != 0xff {
+                return None;
+            }
+            let marker = bytes[at + 1];
+            match marker {
+                // Padding before a marker
+                0xff => at += 1,
+                // Markers without a length
+                0x01 | 0xd0..=0xd7 => at += 2,
+                // Start of frame, other than DHT, JPG and DAC
+                0xc0..=0xcf if !matches!(marker, 0xc4 | 0xc8 | 0xcc) => {
+                    return Some((be16(at + 7)?.into(), be16(at + 5)?.into()));
+                }
+                _ => at += 2 + usize::from(be16(at + 2)?),
+            }
+        }
+    }
+    None
+}
+
+/// Returns where the cropped copy of `path` goes: beside it, with `.crop`
+/// before the extension, e.g. `art/dragon.crop.png`.
+#[must_use]
+pub fn cropped_path(path: &Path) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    match path.extension() {
+        Some(extension) => {
+            path.with_file_name(format!("{}.crop.{}", stem, extension.to_string_lossy()))
+        }
+        None => path.with_file_name(format!("{}.crop", stem)),
+    }
+}
+
+/// Write the part of the image the card shows to [`cropped_path`],
+/// returning where it went. PNG, JPEG and WebP images can be cropped.
+///
+/// # Errors
+///
+/// Returns an error if the image can't be read, decoded or written.
+#[cfg(feature = "art-crops")]
+pub fn write_cropped(art: &ArtCrop) -> anyhow::Result<PathBuf> {
+    let path = Path::new(&art.path);
+    let image = image::open(path)?;
+    let output = cropped_path(path);
+    image
+        .crop_imm(art.crop.x, art.crop.y, art.crop.width, art.crop.height)
+        .save(&output)?;
+    Ok(output)
+}
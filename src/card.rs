@@ -1,10 +1,15 @@
 use crate::mana::{
     CastingManaCost, CastingManaCostProxy, LoyaltyCost, LoyaltyCostProxy, LoyaltyValue, RulesText,
-    RulesTextProxy,
+    RulesTextProxy, RulesTextSegment,
 };
 use facet::Facet;
+use std::collections::BTreeMap;
+use std::convert::Infallible;
+use std::fmt;
+use std::path::Path;
 
 #[derive(Facet, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzz", derive(arbitrary::Arbitrary))]
 #[repr(u8)]
 pub enum Rarity {
     #[facet(rename = "common")]
@@ -15,10 +20,326 @@ pub enum Rarity {
     Rare,
     #[facet(rename = "mythic")]
     Mythic,
+    /// Masterpieces, timeshifted and other cards outside the normal rarities
+    #[facet(rename = "special")]
+    Special,
+    /// Cards from a bonus sheet
+    #[facet(rename = "bonus")]
+    Bonus,
+    /// Cards printed for the land slot of a booster
+    #[facet(rename = "land")]
+    Land,
+}
+
+impl Rarity {
+    /// Every rarity, from most to least common
+    pub const ALL: [Rarity; 7] = [
+        Rarity::Land,
+        Rarity::Common,
+        Rarity::Uncommon,
+        Rarity::Rare,
+        Rarity::Mythic,
+        Rarity::Special,
+        Rarity::Bonus,
+    ];
+
+    /// Returns the rarity as written in YAML.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Rarity::Common => "common",
+            Rarity::Uncommon => "uncommon",
+            Rarity::Rare => "rare",
+            Rarity::Mythic => "mythic",
+            Rarity::Special => "special",
+            Rarity::Bonus => "bonus",
+            Rarity::Land => "land",
+        }
+    }
+}
+
+impl Rarity {
+    /// Returns the letter printed for the rarity on the collector line.
+    #[must_use]
+    pub fn letter(self) -> char {
+        match self {
+            Rarity::Common => 'C',
+            Rarity::Uncommon => 'U',
+            Rarity::Rare => 'R',
+            Rarity::Mythic => 'M',
+            Rarity::Special => 'S',
+            Rarity::Bonus => 'B',
+            Rarity::Land => 'L',
+        }
+    }
+
+    /// Returns true for the rarities of a set's main sheet: common,
+    /// uncommon, rare and mythic.
+    #[must_use]
+    pub fn is_main_sheet(self) -> bool {
+        matches!(
+            self,
+            Rarity::Common | Rarity::Uncommon | Rarity::Rare | Rarity::Mythic
+        )
+    }
+}
+
+impl fmt::Display for Rarity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Structural problems in a card that parsed successfully but can't be
+/// rendered as a real card.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum CardValidationError {
+    #[error(
+        "class levels must start at 1 and be contiguous: expected level {expected}, found level {found}"
+    )]
+    NonContiguousClassLevels { expected: u32, found: u32 },
+    #[error("class level {level} is missing a level-up cost")]
+    MissingClassLevelCost { level: u32 },
+    #[error("leveler range {next} must start after range {previous}")]
+    OverlappingLevelRanges {
+        previous: LevelRange,
+        next: LevelRange,
+    },
+    #[error("a token has no mana cost")]
+    TokenManaCost,
+    #[error("{name:?} isn't a color: expected white, blue, black, red or green")]
+    UnknownTokenColor { name: String },
+}
+
+/// Controls whether the renderer adds reminder text the card doesn't spell out
+#[derive(Facet, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzz", derive(arbitrary::Arbitrary))]
+#[repr(u8)]
+pub enum ReminderTextMode {
+    /// Add standard reminder text for mechanics detected on the card
+    #[facet(rename = "auto")]
+    Auto,
+    /// Only render the text written in the YAML
+    #[facet(rename = "none")]
+    None,
+}
+
+/// Where a card's frame color comes from
+#[derive(Facet, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzz", derive(arbitrary::Arbitrary))]
+#[repr(u8)]
+pub enum FrameColors {
+    /// The colors of the mana cost
+    #[facet(rename = "cost")]
+    Cost,
+    /// The colors of the mana cost, of mana symbols in the rules text and
+    /// of a face's color indicator, so a land that adds `{U}{U}` gets a
+    /// blue frame
+    #[facet(rename = "identity")]
+    Identity,
+}
+
+impl FrameColors {
+    /// Returns the mode as written in YAML.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            FrameColors::Cost => "cost",
+            FrameColors::Identity => "identity",
+        }
+    }
+}
+
+/// The pair of icons marking the faces of a transform card
+#[derive(Facet, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzz", derive(arbitrary::Arbitrary))]
+#[repr(u8)]
+pub enum DfcIcons {
+    /// A sun on the front and a moon on the back, as on most transform cards
+    #[facet(rename = "sun_moon")]
+    SunMoon,
+    /// A compass on the front and a land on the back, for cards that
+    /// transform into lands (Ixalan)
+    #[facet(rename = "compass_land")]
+    CompassLand,
+    /// A spark on the front and a planeswalker icon on the back, for
+    /// creatures that transform into planeswalkers (Magic Origins)
+    #[facet(rename = "spark")]
+    Spark,
+    /// A full moon on the front and an Eldrazi icon on the back (Eldritch
+    /// Moon)
+    #[facet(rename = "moon_eldrazi")]
+    MoonEldrazi,
+}
+
+impl DfcIcons {
+    /// Returns the pair as written in YAML.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DfcIcons::SunMoon => "sun_moon",
+            DfcIcons::CompassLand => "compass_land",
+            DfcIcons::Spark => "spark",
+            DfcIcons::MoonEldrazi => "moon_eldrazi",
+        }
+    }
+
+    /// Returns the pair a card transforming into a face with `back_type_line`
+    /// usually has: planeswalkers get the spark, lands the compass and
+    /// Eldrazi the Eldritch Moon icons; anything else the sun and moon.
+    #[must_use]
+    pub fn for_back_type_line(back_type_line: &str) -> Self {
+        let (types, subtypes) = back_type_line
+            .split_once('—')
+            .unwrap_or((back_type_line, ""));
+        if types.contains("Planeswalker") {
+            DfcIcons::Spark
+        } else if types.contains("Land") {
+            DfcIcons::CompassLand
+        } else if subtypes.contains("Eldrazi") {
+            DfcIcons::MoonEldrazi
+        } else {
+            DfcIcons::SunMoon
+        }
+    }
+}
+
+/// How supplied art fills its art box
+#[derive(Facet, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzz", derive(arbitrary::Arbitrary))]
+#[repr(u8)]
+pub enum ArtFit {
+    /// Fill the box, cropping whatever doesn't match its aspect ratio
+    #[default]
+    #[facet(rename = "cover")]
+    Cover,
+    /// Show the whole image, leaving bars where the aspect ratios differ
+    #[facet(rename = "contain")]
+    Contain,
+    /// Stretch the image to the box
+    #[facet(rename = "fill")]
+    Fill,
+}
+
+impl ArtFit {
+    /// Returns the fit as written in YAML, which is also its CSS
+    /// `object-fit` value.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ArtFit::Cover => "cover",
+            ArtFit::Contain => "contain",
+            ArtFit::Fill => "fill",
+        }
+    }
+}
+
+/// An aging effect laid over a card's frame, see [`crate::frame_effects`]
+#[derive(Facet, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzz", derive(arbitrary::Arbitrary))]
+#[repr(u8)]
+pub enum FrameEffect {
+    /// Edges and corners rubbed pale, as on a card shuffled for years
+    #[facet(rename = "worn")]
+    Worn,
+    /// A dirty, mottled texture over the whole card
+    #[facet(rename = "grunge")]
+    Grunge,
+}
+
+impl FrameEffect {
+    /// Returns the effect as written in YAML.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            FrameEffect::Worn => "worn",
+            FrameEffect::Grunge => "grunge",
+        }
+    }
+
+    /// Opacity of the effect's layer when the style file doesn't set one
+    #[must_use]
+    pub fn default_opacity(self) -> f64 {
+        match self {
+            FrameEffect::Worn => 0.7,
+            FrameEffect::Grunge => 0.35,
+        }
+    }
+}
+
+/// Physical size a card is rendered and printed at. Every size keeps the
+/// standard card's proportions, so the layout is the same at each.
+#[derive(Facet, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzz", derive(arbitrary::Arbitrary))]
+#[repr(u8)]
+pub enum CardSize {
+    /// 63 × 88 mm, like every card in a booster
+    #[default]
+    #[facet(rename = "standard")]
+    Standard,
+    /// Two thirds of standard, for mini cards and punchout tokens
+    #[facet(rename = "mini")]
+    Mini,
+    /// Twice standard, for oversized commanders and display cards
+    #[facet(rename = "jumbo")]
+    Jumbo,
+}
+
+impl CardSize {
+    /// Parse a size name: `standard`, `mini` or `jumbo`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error for any other name.
+    #[must_use = "parsing returns a Result that should be handled"]
+    pub fn parse(s: &str) -> Result<Self, String> {
+        match s.trim().to_lowercase().as_str() {
+            "standard" => Ok(CardSize::Standard),
+            "mini" => Ok(CardSize::Mini),
+            "jumbo" | "oversized" => Ok(CardSize::Jumbo),
+            other => Err(format!(
+                "Unknown card size: {} (expected standard, mini or jumbo)",
+                other
+            )),
+        }
+    }
+
+    /// Returns the size as written in YAML.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            CardSize::Standard => "standard",
+            CardSize::Mini => "mini",
+            CardSize::Jumbo => "jumbo",
+        }
+    }
+
+    /// Width and height in millimetres
+    #[must_use]
+    pub fn dimensions_mm(self) -> (f32, f32) {
+        match self {
+            CardSize::Standard => (63.0, 88.0),
+            CardSize::Mini => (42.0, 58.7),
+            CardSize::Jumbo => (126.0, 176.0),
+        }
+    }
+
+    /// How many times wider than a standard card this size is
+    #[must_use]
+    pub fn scale(self) -> f32 {
+        self.dimensions_mm().0 / 63.0
+    }
+}
+
+/// Returns whether `art` is a URL to download rather than a file path.
+#[must_use]
+pub fn is_art_url(art: &str) -> bool {
+    art.starts_with("https://") || art.starts_with("http://")
 }
 
 /// A single chapter in a saga
 #[derive(Facet, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzz", derive(arbitrary::Arbitrary))]
 pub struct SagaChapter {
     /// Chapter numbers this ability applies to
     pub chapters: Vec<u32>,
@@ -28,7 +349,8 @@ pub struct SagaChapter {
 }
 
 /// A level in a class enchantment
-#[derive(Facet, Debug, Clone)]
+#[derive(Facet, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzz", derive(arbitrary::Arbitrary))]
 pub struct ClassLevel {
     /// The level number (1, 2, 3)
     pub level: u32,
@@ -41,7 +363,8 @@ pub struct ClassLevel {
 }
 
 /// An adventure spell (the left side of an adventure card)
-#[derive(Facet, Debug, Clone)]
+#[derive(Facet, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzz", derive(arbitrary::Arbitrary))]
 pub struct AdventureSpell {
     /// The adventure spell name
     pub name: String,
@@ -53,10 +376,17 @@ pub struct AdventureSpell {
     /// The adventure spell's rules text
     #[facet(proxy = RulesTextProxy)]
     pub rules_text: RulesText,
+    /// The adventure spell's flavor text
+    #[facet(default)]
+    pub flavor_text: Option<String>,
+    /// Artist credited under the adventure spell's text
+    #[facet(default)]
+    pub artist: Option<String>,
 }
 
 /// A loyalty ability on a planeswalker
-#[derive(Facet, Debug, Clone)]
+#[derive(Facet, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzz", derive(arbitrary::Arbitrary))]
 pub struct LoyaltyAbility {
     /// Cost (e.g., "+2", "-1", "0")
     #[facet(proxy = LoyaltyCostProxy)]
@@ -66,11 +396,108 @@ pub struct LoyaltyAbility {
     pub text: RulesText,
 }
 
+/// An inclusive range of levels on a leveler creature, written as "1-3" or "4+"
+#[derive(Facet, Debug, Clone, Copy, PartialEq, Eq)]
+#[facet(proxy = LevelRangeProxy)]
+pub struct LevelRange {
+    /// Lowest level in the range
+    pub min: u32,
+    /// Highest level in the range, or `None` for open-ended ranges like "8+"
+    pub max: Option<u32>,
+}
+
+impl LevelRange {
+    /// Parse a level range from a string like "0-3", "4+", or "5"
+    #[must_use = "parsing returns a Result that should be handled"]
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let v = s.trim();
+        let parse_level = |n: &str| {
+            n.trim()
+                .parse::<u32>()
+                .map_err(|_| format!("Invalid level range: {}", v))
+        };
+
+        let range = if let Some(min) = v.strip_suffix('+') {
+            LevelRange {
+                min: parse_level(min)?,
+                max: None,
+            }
+        } else if let Some((min, max)) = v.split_once('-') {
+            LevelRange {
+                min: parse_level(min)?,
+                max: Some(parse_level(max)?),
+            }
+        } else {
+            let level = parse_level(v)?;
+            LevelRange {
+                min: level,
+                max: Some(level),
+            }
+        };
+
+        if range.max.is_some_and(|max| max < range.min) {
+            return Err(format!("Level range is backwards: {}", v));
+        }
+        Ok(range)
+    }
+
+    /// Returns true if the given level falls inside this range.
+    #[must_use]
+    pub fn contains(&self, level: u32) -> bool {
+        level >= self.min && self.max.is_none_or(|max| level <= max)
+    }
+}
+
+impl fmt::Display for LevelRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.max {
+            None => write!(f, "{}+", self.min),
+            Some(max) if max == self.min => write!(f, "{}", self.min),
+            Some(max) => write!(f, "{}-{}", self.min, max),
+        }
+    }
+}
+
+/// Generates only ranges that [`LevelRange::parse`] accepts, with `max` no
+/// lower than `min`.
+#[cfg(feature = "fuzz")]
+impl<'a> arbitrary::Arbitrary<'a> for LevelRange {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let min: u32 = u.arbitrary()?;
+        let max = if u.arbitrary()? {
+            Some(u.int_in_range(min..=u32::MAX)?)
+        } else {
+            None
+        };
+        Ok(LevelRange { min, max })
+    }
+}
+
+#[derive(Facet)]
+#[facet(transparent)]
+pub struct LevelRangeProxy(pub String);
+
+impl TryFrom<LevelRangeProxy> for LevelRange {
+    type Error = String;
+    fn try_from(proxy: LevelRangeProxy) -> Result<Self, Self::Error> {
+        LevelRange::parse(&proxy.0)
+    }
+}
+
+impl TryFrom<&LevelRange> for LevelRangeProxy {
+    type Error = Infallible;
+    fn try_from(v: &LevelRange) -> Result<Self, Self::Error> {
+        Ok(LevelRangeProxy(v.to_string()))
+    }
+}
+
 /// A level range for leveler creatures
-#[derive(Facet, Debug, Clone)]
+#[derive(Facet, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzz", derive(arbitrary::Arbitrary))]
 pub struct LevelerRange {
-    /// Level range (e.g., 0..3 means 0-3)
-    pub range: Vec<Option<u32>>,
+    /// Level range (e.g., "0-3" or "8+")
+    #[facet(proxy = LevelRangeProxy)]
+    pub range: LevelRange,
     /// Power for this range
     #[facet(default)]
     pub power: Option<String>,
@@ -83,7 +510,8 @@ pub struct LevelerRange {
 }
 
 /// A card face (for DFC, split, flip, etc.)
-#[derive(Facet, Debug, Clone)]
+#[derive(Facet, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzz", derive(arbitrary::Arbitrary))]
 pub struct CardFace {
     /// Face name
     #[facet(default)]
@@ -109,10 +537,36 @@ pub struct CardFace {
     /// Color indicator (for colorless spells or multi-colored cards without mana cost)
     #[facet(default)]
     pub color_indicator: Option<Vec<String>>,
+    /// Path to the face's art, relative to the card's YAML file
+    #[facet(default)]
+    pub art: Option<String>,
+    /// Artist of the face's art, credited on split card halves
+    #[facet(default)]
+    pub artist: Option<String>,
+}
+
+/// A card's text in another language. Fields left out keep the card's own
+/// text, so a translation only needs what differs.
+#[derive(Facet, Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzz", derive(arbitrary::Arbitrary))]
+pub struct Translation {
+    /// Translated card name
+    #[facet(default)]
+    pub name: Option<String>,
+    /// Translated type line
+    #[facet(default)]
+    pub type_line: Option<String>,
+    /// Translated rules text
+    #[facet(default, proxy = RulesTextProxy)]
+    pub rules_text: Option<RulesText>,
+    /// Translated flavor text
+    #[facet(default)]
+    pub flavor_text: Option<String>,
 }
 
 /// Common fields shared by all card types
-#[derive(Facet, Debug, Clone)]
+#[derive(Facet, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzz", derive(arbitrary::Arbitrary))]
 pub struct CardBase {
     /// Card name
     pub name: String,
@@ -135,6 +589,88 @@ pub struct CardBase {
     pub toughness: Option<String>,
     /// Card rarity
     pub rarity: Rarity,
+    /// Collector number within the set, e.g. 12 or 12a
+    #[facet(default)]
+    pub collector_number: Option<String>,
+    /// Whether to add automatic reminder text (defaults to none)
+    #[facet(default)]
+    pub reminder_text: Option<ReminderTextMode>,
+    /// Translations keyed by language code, e.g. `de` or `jp`
+    #[facet(default)]
+    pub translations: BTreeMap<String, Translation>,
+    /// Path to the card's art, relative to the card's YAML file
+    #[facet(default)]
+    pub art: Option<String>,
+    /// How the art fills its art box (defaults to cover)
+    #[facet(default)]
+    pub art_fit: Option<ArtFit>,
+    /// Who illustrated the card's art, credited on its collector line
+    #[facet(default)]
+    pub artist: Option<String>,
+    /// Size to render and print the card at (defaults to the run's --size)
+    #[facet(default)]
+    pub size: Option<CardSize>,
+    /// Aging effects laid over the frame, e.g. `[worn, grunge]`
+    #[facet(default)]
+    pub frame_effects: Vec<FrameEffect>,
+    /// Expansion code of the card's set, e.g. `DOM`
+    #[facet(default)]
+    pub set_code: Option<String>,
+    /// Path to the set symbol (SVG or PNG), relative to the card's YAML
+    /// file. Its shape is printed tinted by rarity.
+    #[facet(default)]
+    pub set_symbol: Option<String>,
+    /// Where the frame color comes from (defaults to cost)
+    #[facet(default)]
+    pub frame_colors: Option<FrameColors>,
+}
+
+impl CardBase {
+    /// Returns true if automatic reminder text is enabled for this card.
+    #[must_use]
+    pub fn auto_reminder_text(&self) -> bool {
+        self.reminder_text == Some(ReminderTextMode::Auto)
+    }
+
+    /// Returns true if the frame color follows the card's color identity
+    /// rather than only its mana cost.
+    #[must_use]
+    pub fn frame_from_identity(&self) -> bool {
+        self.frame_colors == Some(FrameColors::Identity)
+    }
+
+    /// Characters per line of the standard text box at the default rules
+    /// text size, for [`RulesText::estimated_lines`]
+    pub const TEXT_BOX_WIDTH_CHARS: usize = 54;
+
+    /// Lines of rules text the standard text box fits at the default size
+    pub const TEXT_BOX_LINES: usize = 8;
+
+    /// Estimate how many lines the rules and flavor text wrap to when a
+    /// line fits `width_chars` characters, see [`RulesText::estimated_lines`].
+    #[must_use]
+    pub fn estimated_text_lines(&self, width_chars: usize) -> usize {
+        let rules = self
+            .rules_text
+            .as_ref()
+            .map_or(0, |text| text.estimated_lines(width_chars));
+        let flavor = self.flavor_text.as_deref().filter(|text| !text.is_empty());
+        let flavor = flavor.map_or(0, |text| {
+            RulesText {
+                segments: vec![RulesTextSegment::Text(text.to_string())],
+            }
+            .estimated_lines(width_chars)
+        });
+        rules + flavor
+    }
+
+    /// How full the standard text box is at the default text size: the
+    /// estimated lines of rules and flavor text over
+    /// [`CardBase::TEXT_BOX_LINES`]. Above 1.0 the text likely overflows.
+    #[must_use]
+    pub fn text_density(&self) -> f64 {
+        self.estimated_text_lines(Self::TEXT_BOX_WIDTH_CHARS) as f64 / Self::TEXT_BOX_LINES as f64
+    }
 }
 
 // ============================================================================
@@ -142,14 +678,16 @@ pub struct CardBase {
 // ============================================================================
 
 /// A standard card (creature, instant, sorcery, enchantment, artifact)
-#[derive(Facet, Debug, Clone)]
+#[derive(Facet, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzz", derive(arbitrary::Arbitrary))]
 pub struct NormalCard {
     #[facet(flatten)]
     pub base: CardBase,
 }
 
 /// A planeswalker card with loyalty abilities
-#[derive(Facet, Debug, Clone)]
+#[derive(Facet, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzz", derive(arbitrary::Arbitrary))]
 pub struct PlaneswalkerCard {
     #[facet(flatten)]
     pub base: CardBase,
@@ -158,23 +696,73 @@ pub struct PlaneswalkerCard {
 }
 
 /// A saga enchantment with chapter abilities
-#[derive(Facet, Debug, Clone)]
+#[derive(Facet, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzz", derive(arbitrary::Arbitrary))]
 pub struct SagaCard {
     #[facet(flatten)]
     pub base: CardBase,
     pub chapters: Vec<SagaChapter>,
+    /// Read ahead sagas let the controller choose which chapter to start on
+    #[facet(default)]
+    pub read_ahead: Option<bool>,
+}
+
+impl SagaCard {
+    /// Returns the highest chapter number across all chapter abilities.
+    ///
+    /// The saga is sacrificed after this chapter resolves.
+    #[must_use]
+    pub fn final_chapter(&self) -> u32 {
+        self.chapters
+            .iter()
+            .flat_map(|chapter| chapter.chapters.iter().copied())
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Returns true if this saga has the read ahead keyword.
+    #[must_use]
+    pub fn is_read_ahead(&self) -> bool {
+        self.read_ahead.unwrap_or(false)
+    }
 }
 
 /// A class enchantment with level-up abilities
-#[derive(Facet, Debug, Clone)]
+#[derive(Facet, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzz", derive(arbitrary::Arbitrary))]
 pub struct ClassCard {
     #[facet(flatten)]
     pub base: CardBase,
     pub levels: Vec<ClassLevel>,
 }
 
+impl ClassCard {
+    /// Checks that levels start at 1, are contiguous, and that every level
+    /// after the first has a level-up cost.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first structural problem found in the level list.
+    pub fn validate(&self) -> Result<(), CardValidationError> {
+        for (index, level) in self.levels.iter().enumerate() {
+            let expected = index as u32 + 1;
+            if level.level != expected {
+                return Err(CardValidationError::NonContiguousClassLevels {
+                    expected,
+                    found: level.level,
+                });
+            }
+            if level.level > 1 && level.cost.is_none() {
+                return Err(CardValidationError::MissingClassLevelCost { level: level.level });
+            }
+        }
+        Ok(())
+    }
+}
+
 /// An adventure card (creature with an adventure spell)
-#[derive(Facet, Debug, Clone)]
+#[derive(Facet, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzz", derive(arbitrary::Arbitrary))]
 pub struct AdventureCard {
     #[facet(flatten)]
     pub base: CardBase,
@@ -182,7 +770,8 @@ pub struct AdventureCard {
 }
 
 /// A split card (two spells side-by-side, like Fire // Ice)
-#[derive(Facet, Debug, Clone)]
+#[derive(Facet, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzz", derive(arbitrary::Arbitrary))]
 pub struct SplitCard {
     #[facet(flatten)]
     pub base: CardBase,
@@ -194,7 +783,8 @@ pub struct SplitCard {
 }
 
 /// A flip card (Kamigawa-style, rotated bottom half)
-#[derive(Facet, Debug, Clone)]
+#[derive(Facet, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzz", derive(arbitrary::Arbitrary))]
 pub struct FlipCard {
     #[facet(flatten)]
     pub base: CardBase,
@@ -202,15 +792,32 @@ pub struct FlipCard {
 }
 
 /// A transform double-faced card (like Delver of Secrets)
-#[derive(Facet, Debug, Clone)]
+#[derive(Facet, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzz", derive(arbitrary::Arbitrary))]
 pub struct TransformCard {
     #[facet(flatten)]
     pub base: CardBase,
     pub faces: Vec<CardFace>,
+    /// Icons marking the two faces, chosen from the back face's type line
+    /// when not set
+    #[facet(default)]
+    pub dfc_icons: Option<DfcIcons>,
+}
+
+impl TransformCard {
+    /// Returns the icons marking the card's faces.
+    #[must_use]
+    pub fn icons(&self) -> DfcIcons {
+        self.dfc_icons.unwrap_or_else(|| {
+            let back_type_line = self.faces.get(1).and_then(|face| face.type_line.as_deref());
+            DfcIcons::for_back_type_line(back_type_line.unwrap_or_default())
+        })
+    }
 }
 
 /// A modal double-faced card (either side playable)
-#[derive(Facet, Debug, Clone)]
+#[derive(Facet, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzz", derive(arbitrary::Arbitrary))]
 pub struct ModalDfcCard {
     #[facet(flatten)]
     pub base: CardBase,
@@ -218,7 +825,8 @@ pub struct ModalDfcCard {
 }
 
 /// A battle card with defense counter
-#[derive(Facet, Debug, Clone)]
+#[derive(Facet, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzz", derive(arbitrary::Arbitrary))]
 pub struct BattleCard {
     #[facet(flatten)]
     pub base: CardBase,
@@ -229,8 +837,20 @@ pub struct BattleCard {
     pub backside_rules_text: RulesText,
 }
 
+impl BattleCard {
+    /// Returns true if this battle has the Siege subtype.
+    #[must_use]
+    pub fn is_siege(&self) -> bool {
+        self.base
+            .type_line
+            .split_once('—')
+            .is_some_and(|(_, subtypes)| subtypes.split_whitespace().any(|s| s == "Siege"))
+    }
+}
+
 /// A meld card (two cards that combine into one)
-#[derive(Facet, Debug, Clone)]
+#[derive(Facet, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzz", derive(arbitrary::Arbitrary))]
 pub struct MeldCard {
     #[facet(flatten)]
     pub base: CardBase,
@@ -238,21 +858,78 @@ pub struct MeldCard {
 }
 
 /// A leveler creature (Rise of the Eldrazi style)
-#[derive(Facet, Debug, Clone)]
+#[derive(Facet, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzz", derive(arbitrary::Arbitrary))]
 pub struct LevelerCard {
     #[facet(flatten)]
     pub base: CardBase,
     pub leveler_ranges: Vec<LevelerRange>,
 }
 
+impl LevelerCard {
+    /// Checks that level ranges are in ascending order and don't overlap.
+    ///
+    /// Open-ended ranges ("8+") can therefore only appear last.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first pair of ranges that are out of order or overlap.
+    pub fn validate(&self) -> Result<(), CardValidationError> {
+        for pair in self.leveler_ranges.windows(2) {
+            let (previous, next) = (pair[0].range, pair[1].range);
+            if previous.max.is_none_or(|max| next.min <= max) {
+                return Err(CardValidationError::OverlappingLevelRanges { previous, next });
+            }
+        }
+        Ok(())
+    }
+}
+
 /// A prototype card (two casting costs/stats)
-#[derive(Facet, Debug, Clone)]
+#[derive(Facet, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzz", derive(arbitrary::Arbitrary))]
 pub struct PrototypeCard {
     #[facet(flatten)]
     pub base: CardBase,
     pub prototype: CardFace,
 }
 
+/// A token, made by another card: no mana cost, and a curved title plate
+/// over art reaching to the top of the card
+#[derive(Facet, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzz", derive(arbitrary::Arbitrary))]
+pub struct TokenCard {
+    #[facet(flatten)]
+    pub base: CardBase,
+    /// The token's colors, e.g. `["green"]`, by name or letter; colorless
+    /// when empty
+    #[facet(default)]
+    pub colors: Vec<String>,
+}
+
+impl TokenCard {
+    /// Checks that the token has no mana cost and only names real colors.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CardValidationError::TokenManaCost`] if it has a mana
+    /// cost, or [`CardValidationError::UnknownTokenColor`] for the first
+    /// name that isn't a color.
+    pub fn validate(&self) -> Result<(), CardValidationError> {
+        if self.base.mana_cost.is_some() {
+            return Err(CardValidationError::TokenManaCost);
+        }
+        match self
+            .colors
+            .iter()
+            .find(|name| crate::color_chips::Color::from_name(name).is_none())
+        {
+            Some(name) => Err(CardValidationError::UnknownTokenColor { name: name.clone() }),
+            None => Ok(()),
+        }
+    }
+}
+
 // ============================================================================
 // Card Enum (for parsing)
 // ============================================================================
@@ -261,7 +938,8 @@ pub struct PrototypeCard {
 ///
 /// This enum represents all possible card layouts. Each variant wraps a
 /// specific card type struct that contains the layout-specific data.
-#[derive(Facet, Debug, Clone)]
+#[derive(Facet, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzz", derive(arbitrary::Arbitrary))]
 #[facet(tag = "type")]
 #[repr(C)]
 pub enum Card {
@@ -303,13 +981,17 @@ pub enum Card {
 
     #[facet(rename = "prototype")]
     Prototype(#[facet(flatten)] PrototypeCard),
+
+    #[facet(rename = "token")]
+    Token(#[facet(flatten)] TokenCard),
 }
 
 impl Card {
     /// Returns a reference to the common card base fields.
     ///
     /// This provides access to name, mana_cost, type_line, rules_text, flavor_text,
-    /// power, toughness, and rarity without needing to pattern match on the variant.
+    /// power, toughness, rarity, and collector_number without needing to pattern
+    /// match on the variant.
     #[must_use]
     pub fn base(&self) -> &CardBase {
         match self {
@@ -326,18 +1008,159 @@ impl Card {
             Card::Meld(card) => &card.base,
             Card::Leveler(card) => &card.base,
             Card::Prototype(card) => &card.base,
+            Card::Token(card) => &card.base,
         }
     }
 
+    /// Returns a mutable reference to the common card base fields.
+    pub fn base_mut(&mut self) -> &mut CardBase {
+        match self {
+            Card::Normal(card) => &mut card.base,
+            Card::Planeswalker(card) => &mut card.base,
+            Card::Saga(card) => &mut card.base,
+            Card::Class(card) => &mut card.base,
+            Card::Adventure(card) => &mut card.base,
+            Card::Split(card) => &mut card.base,
+            Card::Flip(card) => &mut card.base,
+            Card::Transform(card) => &mut card.base,
+            Card::ModalDfc(card) => &mut card.base,
+            Card::Battle(card) => &mut card.base,
+            Card::Meld(card) => &mut card.base,
+            Card::Leveler(card) => &mut card.base,
+            Card::Prototype(card) => &mut card.base,
+            Card::Token(card) => &mut card.base,
+        }
+    }
+
+    /// Returns the card's art paths: the card's own, then each face's.
+    pub fn art_paths(&self) -> impl Iterator<Item = &str> {
+        let faces: &[CardFace] = match self {
+            Card::Split(card) => &card.faces,
+            Card::Flip(card) => &card.faces,
+            Card::Transform(card) => &card.faces,
+            Card::ModalDfc(card) => &card.faces,
+            Card::Meld(card) => &card.faces,
+            Card::Prototype(card) => std::slice::from_ref(&card.prototype),
+            _ => &[],
+        };
+        self.base()
+            .art
+            .as_deref()
+            .into_iter()
+            .chain(faces.iter().filter_map(|face| face.art.as_deref()))
+    }
+
+    /// Resolve relative art and set symbol paths against `dir`, the
+    /// directory of the card's YAML file, so the card renders from
+    /// anywhere. Art URLs are left as they are.
+    pub fn resolve_art_paths(&mut self, dir: &Path) {
+        self.replace_art(|art| {
+            (!is_art_url(art) && Path::new(art).is_relative())
+                .then(|| dir.join(art).display().to_string())
+        });
+        if let Some(symbol) = &mut self.base_mut().set_symbol
+            && Path::new(symbol).is_relative()
+        {
+            *symbol = dir.join(&*symbol).display().to_string();
+        }
+    }
+
+    /// Replace the card's and its faces' art with what `replace` returns
+    /// for each, keeping the art it returns `None` for.
+    pub fn replace_art(&mut self, mut replace: impl FnMut(&str) -> Option<String>) {
+        let mut replace = |art: &mut Option<String>| {
+            if let Some(new) = art.as_deref().and_then(&mut replace) {
+                *art = Some(new);
+            }
+        };
+        replace(&mut self.base_mut().art);
+        let faces: &mut [CardFace] = match self {
+            Card::Split(card) => &mut card.faces,
+            Card::Flip(card) => &mut card.faces,
+            Card::Transform(card) => &mut card.faces,
+            Card::ModalDfc(card) => &mut card.faces,
+            Card::Meld(card) => &mut card.faces,
+            Card::Prototype(card) => std::slice::from_mut(&mut card.prototype),
+            _ => &mut [],
+        };
+        for face in faces {
+            replace(&mut face.art);
+        }
+    }
+
+    /// Returns the language codes the card has translations for, in order.
+    pub fn languages(&self) -> impl Iterator<Item = &str> {
+        self.base().translations.keys().map(String::as_str)
+    }
+
+    /// Returns the card with the text translated into `language`, or `None`
+    /// if it has no translation for that language. Everything else, cost
+    /// and frame included, stays the same.
+    #[must_use]
+    pub fn translated(&self, language: &str) -> Option<Card> {
+        let translation = self.base().translations.get(language)?.clone();
+        let mut card = self.clone();
+        let base = card.base_mut();
+        base.translations.clear();
+        if let Some(name) = translation.name {
+            base.name = name;
+        }
+        if let Some(type_line) = translation.type_line {
+            base.type_line = type_line;
+        }
+        if let Some(rules_text) = translation.rules_text {
+            base.rules_text = Some(rules_text);
+        }
+        if let Some(flavor_text) = translation.flavor_text {
+            base.flavor_text = Some(flavor_text);
+        }
+        Some(card)
+    }
+
     /// Returns the card's name.
     #[must_use]
     pub fn name(&self) -> &str {
         &self.base().name
     }
 
+    /// Returns the layout name, as written in the card's `type` field.
+    #[must_use]
+    pub fn layout_name(&self) -> &'static str {
+        match self {
+            Card::Normal(_) => "normal",
+            Card::Planeswalker(_) => "planeswalker",
+            Card::Saga(_) => "saga",
+            Card::Class(_) => "class",
+            Card::Adventure(_) => "adventure",
+            Card::Split(_) => "split",
+            Card::Flip(_) => "flip",
+            Card::Transform(_) => "transform",
+            Card::ModalDfc(_) => "modal_dfc",
+            Card::Battle(_) => "battle",
+            Card::Meld(_) => "meld",
+            Card::Leveler(_) => "leveler",
+            Card::Prototype(_) => "prototype",
+            Card::Token(_) => "token",
+        }
+    }
+
     /// Returns the card's rarity.
     #[must_use]
     pub fn rarity(&self) -> Rarity {
         self.base().rarity
     }
+
+    /// Checks layout-specific invariants that the YAML schema can't express.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`CardValidationError`] describing the first problem found.
+    pub fn validate(&self) -> Result<(), CardValidationError> {
+        match self {
+            Card::Class(card) => card.validate(),
+            Card::Leveler(card) => card.validate(),
+            Card::Token(card) => card.validate(),
+            _ => Ok(()),
+        }
+    }
 }
@@ -0,0 +1,543 @@
+//! Custom set files for Cockatrice (`mtg-gen export cockatrice`)
+//!
+//! Cockatrice loads extra cards from XML files in its `customsets` folder.
+//! [`CockatriceSet`] collects a set's cards into one such file, in
+//! Cockatrice's version 4 format: each card's name, cost, types, rules text
+//! and stats, and the rendered image it's shown with. Cards printed as two
+//! halves on one side (split, flip, adventure) are one entry with both
+//! halves' text; double-faced cards get an entry per face, front linked to
+//! back, so Cockatrice can transform them.
+
+use crate::card::{Card, CardBase, CardFace, Rarity};
+use crate::color_chips::{Color, card_colors, indicator_colors};
+use crate::mana::{CastingManaCost, ReminderSegment, RulesText, RulesTextSegment};
+use crate::output::{OutputKind, OutputNaming};
+use crate::render::back_face;
+use crate::utils::to_roman_numeral;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+/// Separates the halves of a card in its Cockatrice text, as in
+/// Cockatrice's own card database
+const HALF_SEPARATOR: &str = "\n\n---\n\n";
+
+/// Major card types, in the order Cockatrice's `maintype` prefers them
+const MAIN_TYPES: &[&str] = &[
+    "Planeswalker",
+    "Battle",
+    "Creature",
+    "Land",
+    "Sorcery",
+    "Instant",
+    "Artifact",
+    "Enchantment",
+];
+
+/// One entry of a Cockatrice card database
+#[derive(Debug, Clone, PartialEq)]
+pub struct CockatriceCard {
+    pub name: String,
+    /// Rules text, with symbols in braces and paragraphs on lines of their own
+    pub text: String,
+    /// Scryfall's name for the card's layout, e.g. `normal` or `transform`
+    pub layout: &'static str,
+    /// `front`, or `back` for the back face of a double-faced card
+    pub side: &'static str,
+    pub type_line: String,
+    /// The mana cost without braces, e.g. `2WU`
+    pub mana_cost: Option<String>,
+    pub mana_value: f64,
+    /// The face's colors, as letters in WUBRG order
+    pub colors: String,
+    /// The whole card's colors, as letters in WUBRG order
+    pub color_identity: String,
+    /// Power and toughness, e.g. `2/3`
+    pub pt: Option<String>,
+    /// Starting loyalty or defense
+    pub loyalty: Option<String>,
+    pub rarity: Rarity,
+    pub collector_number: Option<String>,
+    /// The rendered image the card is shown with
+    pub image: String,
+    /// The other faces of a double-faced card, by name
+    pub related: Vec<String>,
+    /// Whether the related faces are what this one transforms into
+    pub transforms: bool,
+    pub token: bool,
+}
+
+impl CockatriceCard {
+    /// Returns the row Cockatrice puts the card on the battlefield in:
+    /// 0 for lands, 2 for creatures, 3 for instants and sorceries and 1
+    /// for every other permanent.
+    #[must_use]
+    pub fn table_row(&self) -> u8 {
+        match self.main_type() {
+            "Land" => 0,
+            "Creature" => 2,
+            "Instant" | "Sorcery" => 3,
+            _ => 1,
+        }
+    }
+
+    /// Returns the card's most important major type, e.g. `Creature` for an
+    /// artifact creature. Empty when the type line has none.
+    #[must_use]
+    pub fn main_type(&self) -> &'static str {
+        let types = self.type_line.split('—').next().unwrap_or_default();
+        MAIN_TYPES
+            .iter()
+            .find(|main| types.split_whitespace().any(|word| word == **main))
+            .copied()
+            .unwrap_or_default()
+    }
+}
+
+/// A custom set of cards for Cockatrice
+///
+/// # Examples
+///
+/// ```
+/// use mtg_gen::Card;
+/// use mtg_gen::cockatrice::CockatriceSet;
+///
+/// let card: Card = facet_yaml::from_str(
+///     "name: Bolt\ntype: normal\nmana_cost: \"{R}\"\ntype_line: Instant\nrarity: common\n",
+/// )
+/// .unwrap();
+/// let mut set = CockatriceSet::new("TST", "Test Set");
+/// set.add(&card, &["bolt.png".to_string()]);
+/// let xml = set.to_xml();
+/// assert!(xml.contains("<name>Bolt</name>"));
+/// assert!(xml.contains("<manacost>R</manacost>"));
+/// assert!(xml.contains("picurl=\"bolt.png\""));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CockatriceSet {
+    /// Short code of the set, e.g. `DOM`
+    pub code: String,
+    /// Full name of the set
+    pub name: String,
+    pub cards: Vec<CockatriceCard>,
+}
+
+impl CockatriceSet {
+    /// An empty set
+    #[must_use]
+    pub fn new(code: &str, name: &str) -> Self {
+        Self {
+            code: code.to_string(),
+            name: name.to_string(),
+            cards: Vec::new(),
+        }
+    }
+
+    /// Add `card`, shown with its rendered `images`: one for most cards,
+    /// front then back for double-faced ones. A face without an image of
+    /// its own is shown with the first.
+    pub fn add(&mut self, card: &Card, images: &[String]) {
+        self.cards.extend(cockatrice_cards(card, images));
+    }
+
+    /// Returns the set as a Cockatrice card database.
+    #[must_use]
+    pub fn to_xml(&self) -> String {
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        xml.push_str("<cockatrice_carddatabase version=\"4\">\n");
+        xml.push_str("  <sets>\n    <set>\n");
+        let _ = writeln!(xml, "      <name>{}</name>", escape(&self.code));
+        let _ = writeln!(xml, "      <longname>{}</longname>", escape(&self.name));
+        xml.push_str("      <settype>Custom</settype>\n    </set>\n  </sets>\n  <cards>\n");
+        for card in &self.cards {
+            self.write_card(&mut xml, card);
+        }
+        xml.push_str("  </cards>\n</cockatrice_carddatabase>\n");
+        xml
+    }
+
+    fn write_card(&self, xml: &mut String, card: &CockatriceCard) {
+        let element = |xml: &mut String, indent: &str, name: &str, value: &str| {
+            let _ = writeln!(xml, "{}<{}>{}</{}>", indent, name, escape(value), name);
+        };
+        xml.push_str("    <card>\n");
+        element(xml, "      ", "name", &card.name);
+        element(xml, "      ", "text", &card.text);
+        xml.push_str("      <prop>\n");
+        let prop = "        ";
+        element(xml, prop, "layout", card.layout);
+        element(xml, prop, "side", card.side);
+        element(xml, prop, "type", &card.type_line);
+        element(xml, prop, "maintype", card.main_type());
+        if let Some(cost) = &card.mana_cost {
+            element(xml, prop, "manacost", cost);
+        }
+        element(xml, prop, "cmc", &card.mana_value.to_string());
+        if !card.colors.is_empty() {
+            element(xml, prop, "colors", &card.colors);
+        }
+        if !card.color_identity.is_empty() {
+            element(xml, prop, "coloridentity", &card.color_identity);
+        }
+        if let Some(pt) = &card.pt {
+            element(xml, prop, "pt", pt);
+        }
+        if let Some(loyalty) = &card.loyalty {
+            element(xml, prop, "loyalty", loyalty);
+        }
+        xml.push_str("      </prop>\n");
+
+        let _ = write!(xml, "      <set rarity=\"{}\"", card.rarity);
+        if let Some(number) = &card.collector_number {
+            let _ = write!(xml, " num=\"{}\"", escape(number));
+        }
+        let _ = writeln!(
+            xml,
+            " picurl=\"{}\">{}</set>",
+            escape(&card.image),
+            escape(&self.code)
+        );
+        for related in &card.related {
+            let attach = if card.transforms {
+                " attach=\"transform\""
+            } else {
+                ""
+            };
+            let _ = writeln!(
+                xml,
+                "      <related{}>{}</related>",
+                attach,
+                escape(related)
+            );
+        }
+        if card.token {
+            element(xml, "      ", "token", "1");
+        }
+        element(xml, "      ", "tablerow", &card.table_row().to_string());
+        xml.push_str("    </card>\n");
+    }
+}
+
+/// Returns the images `card` is rendered to with `naming`, where its image
+/// would otherwise be `card_path`: its own image, or the front and then the
+/// back of a double-faced card.
+///
+/// # Examples
+///
+/// ```
+/// use mtg_gen::cockatrice::image_paths;
+/// use mtg_gen::{Card, OutputNaming};
+/// use std::path::{Path, PathBuf};
+///
+/// let card: Card = facet_yaml::from_str(
+///     "name: Bolt\ntype: normal\ntype_line: Instant\nrarity: common\n",
+/// )
+/// .unwrap();
+/// let naming = OutputNaming { subfolders: true, ..OutputNaming::default() };
+/// assert_eq!(
+///     image_paths(&card, Path::new("red/bolt.png"), &naming),
+///     [PathBuf::from("red/cards/bolt.png")]
+/// );
+/// ```
+#[must_use]
+pub fn image_paths(card: &Card, card_path: &Path, naming: &OutputNaming) -> Vec<PathBuf> {
+    match back_face(card) {
+        Some(_) => vec![
+            naming.path_for(card_path, OutputKind::Front, 0, 1),
+            naming.path_for(card_path, OutputKind::Back, 0, 1),
+        ],
+        None => vec![naming.path_for(card_path, OutputKind::Card, 0, 1)],
+    }
+}
+
+/// Returns the Cockatrice entries of `card`, see [`CockatriceSet::add`].
+#[must_use]
+pub fn cockatrice_cards(card: &Card, images: &[String]) -> Vec<CockatriceCard> {
+    let base = card.base();
+    let image = |i: usize| {
+        images
+            .get(i)
+            .or(images.first())
+            .cloned()
+            .unwrap_or_default()
+    };
+    let identity = letters(&card_colors(card));
+    let mut entry = base_entry(card, base, identity.clone(), image(0));
+
+    match card {
+        Card::Normal(_) | Card::Token(_) => {}
+        Card::Planeswalker(card) => {
+            entry.layout = "normal";
+            entry.loyalty = Some(card.loyalty.to_string());
+            for ability in &card.loyalty_abilities {
+                push_line(
+                    &mut entry.text,
+                    &format!("{}: {}", ability.cost, plain_text(&ability.text)),
+                );
+            }
+        }
+        Card::Saga(card) => {
+            for chapter in &card.chapters {
+                let numbers: Vec<String> = chapter
+                    .chapters
+                    .iter()
+                    .map(|&n| to_roman_numeral(n))
+                    .collect();
+                push_line(
+                    &mut entry.text,
+                    &format!("{} — {}", numbers.join(", "), plain_text(&chapter.text)),
+                );
+            }
+        }
+        Card::Class(card) => {
+            for level in &card.levels {
+                if let Some(cost) = &level.cost {
+                    push_line(&mut entry.text, &format!("{}: Level {}", cost, level.level));
+                }
+                push_line(&mut entry.text, &plain_text(&level.text));
+            }
+        }
+        Card::Adventure(card) => {
+            let adventure = &card.adventure;
+            entry.text.push_str(HALF_SEPARATOR);
+            entry.text.push_str(&format!(
+                "{} {}\n{}\n{}",
+                adventure.name,
+                adventure.mana_cost,
+                adventure.type_line,
+                plain_text(&adventure.rules_text)
+            ));
+        }
+        Card::Leveler(card) => {
+            for range in &card.leveler_ranges {
+                push_line(&mut entry.text, &format!("LEVEL {}", range.range));
+                if let Some(pt) = power_toughness(&range.power, &range.toughness) {
+                    push_line(&mut entry.text, &pt);
+                }
+                if let Some(text) = &range.text {
+                    push_line(&mut entry.text, &plain_text(text));
+                }
+            }
+        }
+        Card::Battle(card) => {
+            entry.loyalty = Some(card.defense.to_string());
+            entry.text.push_str(HALF_SEPARATOR);
+            entry.text.push_str(&format!(
+                "{}\n{}\n{}",
+                card.backside_name,
+                card.backside_type_line,
+                plain_text(&card.backside_rules_text)
+            ));
+        }
+        Card::Prototype(card) => {
+            let prototype = &card.prototype;
+            let mut line = String::from("Prototype");
+            if let Some(cost) = &prototype.mana_cost {
+                line.push_str(&format!(" {}", cost));
+            }
+            if let Some(pt) = power_toughness(&prototype.power, &prototype.toughness) {
+                line.push_str(&format!(" — {}", pt));
+            }
+            push_line(&mut entry.text, &line);
+        }
+        Card::Split(card) => join_halves(&mut entry, &card.faces, true),
+        Card::Flip(card) => join_halves(&mut entry, &card.faces, false),
+        Card::Transform(card) => return face_entries(&entry, &card.faces, true, image),
+        Card::ModalDfc(card) => return face_entries(&entry, &card.faces, true, image),
+        Card::Meld(card) => return face_entries(&entry, &card.faces, false, image),
+    }
+    vec![entry]
+}
+
+/// The entry of a card's base fields, which most layouts add text to
+fn base_entry(card: &Card, base: &CardBase, identity: String, image: String) -> CockatriceCard {
+    CockatriceCard {
+        name: base.name.clone(),
+        text: base.rules_text.as_ref().map(plain_text).unwrap_or_default(),
+        layout: card.layout_name(),
+        side: "front",
+        type_line: base.type_line.clone(),
+        mana_cost: base.mana_cost.as_ref().map(braceless),
+        mana_value: base
+            .mana_cost
+            .as_ref()
+            .map_or(0.0, CastingManaCost::mana_value),
+        colors: identity.clone(),
+        color_identity: identity,
+        pt: power_toughness(&base.power, &base.toughness),
+        loyalty: None,
+        rarity: base.rarity,
+        collector_number: base.collector_number.clone(),
+        image,
+        related: Vec::new(),
+        transforms: false,
+        token: matches!(card, Card::Token(_)),
+    }
+}
+
+/// Make `entry` both halves of a split or flip card, named `A // B`. A
+/// split card's mana value is both halves'; a flip card's is its top's.
+fn join_halves(entry: &mut CockatriceCard, faces: &[CardFace], add_costs: bool) {
+    let join = |part: &dyn Fn(&CardFace) -> String| {
+        faces.iter().map(part).collect::<Vec<_>>().join(" // ")
+    };
+    entry.name = join(&|face| face.name.clone().unwrap_or_default());
+    entry.type_line = join(&|face| face.type_line.clone().unwrap_or_default());
+    if faces.iter().any(|face| face.mana_cost.is_some()) {
+        entry.mana_cost = Some(join(&|face| {
+            face.mana_cost.as_ref().map(braceless).unwrap_or_default()
+        }));
+    }
+    let costs = faces.iter().map(|face| {
+        face.mana_cost
+            .as_ref()
+            .map_or(0.0, CastingManaCost::mana_value)
+    });
+    entry.mana_value = if add_costs {
+        costs.sum()
+    } else {
+        costs.take(1).sum()
+    };
+    entry.text = faces
+        .iter()
+        .map(|face| face.rules_text.as_ref().map(plain_text).unwrap_or_default())
+        .collect::<Vec<_>>()
+        .join(HALF_SEPARATOR);
+    if let Some(pt) = faces
+        .iter()
+        .find_map(|face| power_toughness(&face.power, &face.toughness))
+    {
+        entry.pt = Some(pt);
+    }
+}
+
+/// Returns an entry for each face of a double-faced card, the first the
+/// front, each linked to the others. Faces take what they share, like
+/// rarity, from the card's entry.
+fn face_entries(
+    card: &CockatriceCard,
+    faces: &[CardFace],
+    transforms: bool,
+    image: impl Fn(usize) -> String,
+) -> Vec<CockatriceCard> {
+    let names: Vec<String> = faces
+        .iter()
+        .map(|face| face.name.clone().unwrap_or_else(|| card.name.clone()))
+        .collect();
+    let colors: Vec<Vec<Color>> = faces
+        .iter()
+        .map(|face| match indicator_colors(face) {
+            colors if !colors.is_empty() => colors,
+            _ => cost_colors(face.mana_cost.as_ref()),
+        })
+        .collect();
+    let mut identity: Vec<Color> = colors.iter().flatten().copied().collect();
+    identity.sort();
+    identity.dedup();
+    faces
+        .iter()
+        .enumerate()
+        .map(|(i, face)| {
+            CockatriceCard {
+                name: names[i].clone(),
+                text: face.rules_text.as_ref().map(plain_text).unwrap_or_default(),
+                side: if i == 0 { "front" } else { "back" },
+                type_line: face.type_line.clone().unwrap_or_default(),
+                mana_cost: face.mana_cost.as_ref().map(braceless),
+                // A back face has the mana value of its front
+                mana_value: faces[0]
+                    .mana_cost
+                    .as_ref()
+                    .map_or(0.0, CastingManaCost::mana_value),
+                colors: letters(&colors[i]),
+                color_identity: letters(&identity),
+                pt: power_toughness(&face.power, &face.toughness),
+                image: image(i),
+                related: names
+                    .iter()
+                    .enumerate()
+                    .filter(|&(j, _)| j != i)
+                    .map(|(_, name)| name.clone())
+                    .collect(),
+                transforms,
+                ..card.clone()
+            }
+        })
+        .collect()
+}
+
+/// Returns rules text as Cockatrice shows it: symbols in braces, one
+/// paragraph per line and no italic or bold markers.
+fn plain_text(text: &RulesText) -> String {
+    let mut out = String::new();
+    for segment in &text.segments {
+        match segment {
+            RulesTextSegment::Text(text)
+            | RulesTextSegment::AbilityWord(text)
+            | RulesTextSegment::Italic(text)
+            | RulesTextSegment::Bold(text) => out.push_str(text),
+            RulesTextSegment::Symbol(symbol) => {
+                let _ = write!(out, "{}", symbol);
+            }
+            RulesTextSegment::Reminder(reminder) => {
+                out.push('(');
+                for part in reminder {
+                    match part {
+                        ReminderSegment::Text(text) => out.push_str(text),
+                        ReminderSegment::Symbol(symbol) => {
+                            let _ = write!(out, "{}", symbol);
+                        }
+                    }
+                }
+                out.push(')');
+            }
+            RulesTextSegment::ParagraphBreak => out.push('\n'),
+        }
+    }
+    out.trim().to_string()
+}
+
+/// Append `line` to `text` on a line of its own.
+fn push_line(text: &mut String, line: &str) {
+    if line.is_empty() {
+        return;
+    }
+    if !text.is_empty() {
+        text.push('\n');
+    }
+    text.push_str(line);
+}
+
+fn power_toughness(power: &Option<String>, toughness: &Option<String>) -> Option<String> {
+    match (power, toughness) {
+        (Some(power), Some(toughness)) => Some(format!("{}/{}", power, toughness)),
+        _ => None,
+    }
+}
+
+/// Returns a mana cost as Cockatrice writes it, e.g. `{2}{W}{U}` as `2WU`.
+fn braceless(cost: &CastingManaCost) -> String {
+    cost.to_string().replace(['{', '}'], "")
+}
+
+fn cost_colors(cost: Option<&CastingManaCost>) -> Vec<Color> {
+    let mut colors: Vec<Color> = cost
+        .iter()
+        .flat_map(|cost| &cost.symbols)
+        .flat_map(|symbol| symbol.colors().iter().copied())
+        .collect();
+    colors.sort();
+    colors.dedup();
+    colors
+}
+
+fn letters(colors: &[Color]) -> String {
+    colors.iter().map(|color| color.letter()).collect()
+}
+
+/// Escape text for an XML element or attribute.
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -0,0 +1,223 @@
+//! Collector numbers (`--assign-collector-numbers`, `--auto-number`)
+//!
+//! Sets number their cards in a canonical order: each color of the color
+//! wheel in turn, then multicolor cards, then colorless cards and artifacts,
+//! then lands, alphabetically within each group. `--assign-collector-numbers`
+//! writes the numbers into the cards' YAML files; `--auto-number` assigns
+//! them for one render, see [`auto_numbers`], so adding a card mid-set
+//! renumbers everything after it.
+
+use crate::card::{Card, CardFace};
+use crate::mana::CastingManaCost;
+use crate::order::{SortEntry, SortOrder, sort_entries};
+use crate::render::derive_frame_color;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+/// Name of the file in the output directory listing the numbers
+/// `--auto-number` assigned
+pub const NUMBERING_FILE: &str = "numbering.csv";
+
+/// The group a card is numbered in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ColorGroup {
+    White,
+    Blue,
+    Black,
+    Red,
+    Green,
+    Multicolor,
+    /// Colorless cards, artifacts included
+    Artifact,
+    Land,
+}
+
+impl ColorGroup {
+    /// Returns the group of `card`, from the colors of its casting costs.
+    ///
+    /// Every face that can be cast counts: both halves of a split card, the
+    /// adventure of an adventure card, and the front of a double-faced card.
+    #[must_use]
+    pub fn of(card: &Card) -> Self {
+        let mut groups: Vec<ColorGroup> = casting_costs(card)
+            .into_iter()
+            .filter_map(|cost| match derive_frame_color(&Some(cost.clone())) {
+                "white" => Some(ColorGroup::White),
+                "blue" => Some(ColorGroup::Blue),
+                "black" => Some(ColorGroup::Black),
+                "red" => Some(ColorGroup::Red),
+                "green" => Some(ColorGroup::Green),
+                "gold" => Some(ColorGroup::Multicolor),
+                _ => None,
+            })
+            .collect();
+        groups.sort();
+        groups.dedup();
+
+        match groups.as_slice() {
+            [] if is_land(card) => ColorGroup::Land,
+            [] => ColorGroup::Artifact,
+            [group] => *group,
+            _ => ColorGroup::Multicolor,
+        }
+    }
+}
+
+impl ColorGroup {
+    /// Returns the group's name, as listed in [`NUMBERING_FILE`].
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ColorGroup::White => "white",
+            ColorGroup::Blue => "blue",
+            ColorGroup::Black => "black",
+            ColorGroup::Red => "red",
+            ColorGroup::Green => "green",
+            ColorGroup::Multicolor => "multicolor",
+            ColorGroup::Artifact => "artifact",
+            ColorGroup::Land => "land",
+        }
+    }
+}
+
+/// Returns the casting costs that count towards a card's colors.
+pub(crate) fn casting_costs(card: &Card) -> Vec<&CastingManaCost> {
+    let faces: &[CardFace] = match card {
+        Card::Split(card) => &card.faces,
+        Card::Flip(card) => first(&card.faces),
+        Card::Transform(card) => first(&card.faces),
+        Card::ModalDfc(card) => first(&card.faces),
+        Card::Meld(card) => first(&card.faces),
+        _ => &[],
+    };
+
+    let mut costs: Vec<&CastingManaCost> = card.base().mana_cost.iter().collect();
+    costs.extend(faces.iter().filter_map(|face| face.mana_cost.as_ref()));
+    if let Card::Adventure(card) = card {
+        costs.push(&card.adventure.mana_cost);
+    }
+    costs
+}
+
+/// The front face of a double-faced card
+fn first(faces: &[CardFace]) -> &[CardFace] {
+    &faces[..faces.len().min(1)]
+}
+
+fn is_land(card: &Card) -> bool {
+    card.base()
+        .type_line
+        .split(|c: char| !c.is_alphabetic())
+        .any(|word| word == "Land")
+}
+
+/// Returns `yaml` with its top-level `collector_number` set to `number`.
+///
+/// An existing `collector_number` line is replaced; otherwise the field is
+/// added after `rarity`, or at the end. Everything else, comments included,
+/// is left as it was.
+#[must_use]
+pub fn set_collector_number(yaml: &str, number: &str) -> String {
+    let field = format!("collector_number: \"{}\"", number.replace('"', "\\\""));
+    let mut lines: Vec<&str> = yaml.lines().collect();
+
+    if let Some(line) = lines
+        .iter_mut()
+        .find(|line| line.starts_with("collector_number:"))
+    {
+        *line = &field;
+    } else if let Some(i) = lines.iter().position(|line| line.starts_with("rarity:")) {
+        lines.insert(i + 1, &field);
+    } else {
+        lines.push(&field);
+    }
+
+    let mut updated = lines.join("\n");
+    updated.push('\n');
+    updated
+}
+
+/// A collector number assigned for one render
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AutoNumber {
+    /// The card's file
+    pub path: PathBuf,
+    pub name: String,
+    pub group: ColorGroup,
+    /// The number as printed, out of the set's size, e.g. `12/250`
+    pub number: String,
+}
+
+/// Number the cards of `entries` in canonical order, the way printed sets
+/// do: `1/250`, `2/250`, ... Files that didn't parse aren't numbered or
+/// counted.
+///
+/// # Examples
+///
+/// ```
+/// use mtg_gen::collector::auto_numbers;
+/// use mtg_gen::order::SortEntry;
+/// use mtg_gen::Card;
+///
+/// let card = |name: &str, cost: &str| -> Card {
+///     facet_yaml::from_str(&format!(
+///         "name: {name}\ntype: normal\ntype_line: Instant\nrarity: common\nmana_cost: \"{cost}\"\n"
+///     ))
+///     .unwrap()
+/// };
+/// let entries = vec![
+///     SortEntry::new("shock.yaml".into(), Some(&card("Shock", "{R}"))),
+///     SortEntry::new("opt.yaml".into(), Some(&card("Opt", "{U}"))),
+///     SortEntry::new("broken.yaml".into(), None),
+/// ];
+/// let numbers = auto_numbers(entries);
+/// assert_eq!(numbers[0].name, "Opt");
+/// assert_eq!(numbers[0].number, "1/2");
+/// assert_eq!(numbers[1].number, "2/2");
+/// ```
+#[must_use]
+pub fn auto_numbers(mut entries: Vec<SortEntry>) -> Vec<AutoNumber> {
+    sort_entries(&mut entries, SortOrder::Canonical);
+    let cards: Vec<(PathBuf, String, ColorGroup)> = entries
+        .into_iter()
+        .filter_map(|entry| Some((entry.path, entry.name?, entry.color_group?)))
+        .collect();
+    let total = cards.len();
+    (1..)
+        .zip(cards)
+        .map(|(number, (path, name, group))| AutoNumber {
+            path,
+            name,
+            group,
+            number: format!("{}/{}", number, total),
+        })
+        .collect()
+}
+
+/// Returns [`NUMBERING_FILE`]: a CSV line per card with its number, name,
+/// color group and file, relative to `input`, in collector order.
+#[must_use]
+pub fn numbering_csv(numbers: &[AutoNumber], input: &Path) -> String {
+    let mut csv = String::from("number,name,group,source\n");
+    for number in numbers {
+        let source = number.path.strip_prefix(input).unwrap_or(&number.path);
+        let _ = writeln!(
+            csv,
+            "{},{},{},{}",
+            csv_field(&number.number),
+            csv_field(&number.name),
+            number.group.as_str(),
+            csv_field(&source.to_string_lossy().replace('\\', "/"))
+        );
+    }
+    csv
+}
+
+/// Quote a CSV field that holds a comma, quote or line break.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
@@ -0,0 +1,168 @@
+//! Color identity chips (`--color-chips`)
+//!
+//! Frame colors can be hard to tell apart, especially for colorblind
+//! playtesters and on printed proxies. With chips turned on, every card gets
+//! a small lettered chip for each of its colors (W, U, B, R, G) just below
+//! the title bar.
+
+use crate::card::{Card, CardFace};
+use crate::collector::casting_costs;
+use maud::{Markup, PreEscaped, html};
+
+/// One of the five colors of Magic
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Color {
+    White,
+    Blue,
+    Black,
+    Red,
+    Green,
+}
+
+impl Color {
+    /// The letter printed on the chip, as in mana symbols
+    #[must_use]
+    pub fn letter(self) -> char {
+        match self {
+            Color::White => 'W',
+            Color::Blue => 'U',
+            Color::Black => 'B',
+            Color::Red => 'R',
+            Color::Green => 'G',
+        }
+    }
+
+    /// The color's name as written in card YAML, e.g. `blue`
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Color::White => "white",
+            Color::Blue => "blue",
+            Color::Black => "black",
+            Color::Red => "red",
+            Color::Green => "green",
+        }
+    }
+
+    /// Parse a color from its name or letter, e.g. `blue` or `U`, in any
+    /// case.
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Color> {
+        [
+            Color::White,
+            Color::Blue,
+            Color::Black,
+            Color::Red,
+            Color::Green,
+        ]
+        .into_iter()
+        .find(|color| {
+            name.eq_ignore_ascii_case(color.name())
+                || name.eq_ignore_ascii_case(&color.letter().to_string())
+        })
+    }
+
+    fn css_class(self) -> &'static str {
+        match self {
+            Color::White => "chip-white",
+            Color::Blue => "chip-blue",
+            Color::Black => "chip-black",
+            Color::Red => "chip-red",
+            Color::Green => "chip-green",
+        }
+    }
+}
+
+/// Returns the colors of `card` in WUBRG order, from the casting costs on
+/// its rendered face: both halves of a split card, the adventure of an
+/// adventure card, and the front of a double-faced card. A token's colors
+/// are its `colors`.
+#[must_use]
+pub fn card_colors(card: &Card) -> Vec<Color> {
+    if let Card::Token(token) = card {
+        return named_colors(&token.colors);
+    }
+    let mut colors: Vec<Color> = casting_costs(card)
+        .into_iter()
+        .flat_map(|cost| &cost.symbols)
+        .flat_map(|symbol| symbol.colors().iter().copied())
+        .collect();
+    colors.sort();
+    colors.dedup();
+    colors
+}
+
+/// Returns the colors of a face's color indicator in WUBRG order, ignoring
+/// names that aren't colors. Empty when the face has no indicator.
+#[must_use]
+pub fn indicator_colors(face: &CardFace) -> Vec<Color> {
+    named_colors(face.color_indicator.iter().flatten())
+}
+
+/// Returns the colors named in `names` in WUBRG order, ignoring names that
+/// aren't colors.
+#[must_use]
+pub fn named_colors<'a>(names: impl IntoIterator<Item = &'a String>) -> Vec<Color> {
+    let mut colors: Vec<Color> = names
+        .into_iter()
+        .filter_map(|name| Color::from_name(name))
+        .collect();
+    colors.sort();
+    colors.dedup();
+    colors
+}
+
+/// Render the chips for `colors`, positioned over the card below its title
+/// bar. Renders nothing for a colorless card.
+#[must_use]
+pub fn render_color_chips(colors: &[Color]) -> Markup {
+    html! {
+        @if !colors.is_empty() {
+            style { (PreEscaped(CHIP_CSS)) }
+            div.color-chips {
+                @for color in colors {
+                    div class=(format!("color-chip {}", color.css_class())) { (color.letter()) }
+                }
+            }
+        }
+    }
+}
+
+/// Returns a rendered card document with chips for `colors` added at the end
+/// of its body.
+#[must_use]
+pub fn with_color_chips(html: &str, colors: &[Color]) -> String {
+    let chips = render_color_chips(colors).into_string();
+    match html.rfind("</body>") {
+        Some(end) => format!("{}{}{}", &html[..end], chips, &html[end..]),
+        None => format!("{}{}", html, chips),
+    }
+}
+
+const CHIP_CSS: &str = r#"
+    .color-chips {
+        position: absolute;
+        top: 76px;
+        left: 48px;
+        display: flex;
+        gap: 6px;
+        z-index: 30;
+    }
+    .color-chip {
+        width: 34px;
+        height: 34px;
+        border-radius: 50%;
+        border: 3px solid #fff;
+        box-shadow: 0 1px 3px rgba(0,0,0,0.6);
+        font-family: 'Beleren', sans-serif;
+        font-weight: bold;
+        font-size: 20px;
+        line-height: 28px;
+        text-align: center;
+    }
+    .chip-white { background: #f8f6d8; color: #000; }
+    .chip-blue { background: #0e68ab; color: #fff; }
+    .chip-black { background: #150b00; color: #fff; }
+    .chip-red { background: #d3202a; color: #fff; }
+    .chip-green { background: #00733e; color: #fff; }
+"#;
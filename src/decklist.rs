@@ -0,0 +1,252 @@
+//! Decklists for `mtg-gen proxy`
+//!
+//! Accepts the plain text format most deckbuilders export: one card per
+//! line, with an optional count and an optional set code and collector
+//! number, e.g. `4 Lightning Bolt` or `1x Counterspell (MH2) 267`. Blank
+//! lines, `#` and `//` comments, and section headers like `Sideboard` are
+//! skipped.
+//!
+//! Names are looked up in a [`CardLibrary`] of local card files first, so a
+//! deck can mix custom cards with real ones fetched from Scryfall.
+
+use crate::utils::sanitize_card_name;
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+/// One line of a decklist
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecklistEntry {
+    /// How many copies to print
+    pub count: u32,
+    /// The card's name
+    pub name: String,
+    /// Set code of a specific printing, e.g. `MH2`
+    pub set: Option<String>,
+    /// Collector number of a specific printing, within `set`
+    pub collector_number: Option<String>,
+}
+
+/// Section headers some deckbuilders put between groups of cards
+const SECTION_HEADERS: &[&str] = &[
+    "deck",
+    "main",
+    "maindeck",
+    "mainboard",
+    "sideboard",
+    "commander",
+    "companion",
+    "maybeboard",
+];
+
+const SCRYFALL_API: &str = "https://api.scryfall.com";
+
+/// A decklist line that isn't a card
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("line {line}: {message}")]
+pub struct DecklistError {
+    /// 1-based line number
+    pub line: usize,
+    pub message: String,
+}
+
+/// Parse a decklist.
+///
+/// # Errors
+///
+/// Returns an error for a line with a count of zero or no card name.
+pub fn parse_decklist(text: &str) -> Result<Vec<DecklistEntry>, DecklistError> {
+    let mut entries = Vec::new();
+    for (i, line) in text.lines().enumerate() {
+        let line = line.trim();
+        let line = line.strip_prefix("SB:").map_or(line, str::trim_start);
+        if line.is_empty() || line.starts_with('#') || line.starts_with("//") {
+            continue;
+        }
+        let header = line.trim_end_matches(':').to_lowercase();
+        if SECTION_HEADERS.contains(&header.as_str()) {
+            continue;
+        }
+
+        let error = |message: &str| DecklistError {
+            line: i + 1,
+            message: message.to_string(),
+        };
+        let (count, rest) = split_count(line);
+        if count == Some(0) {
+            return Err(error("count must be at least 1"));
+        }
+        let (name, set, collector_number) = split_printing(rest);
+        if name.is_empty() {
+            return Err(error("missing card name"));
+        }
+        entries.push(DecklistEntry {
+            count: count.unwrap_or(1),
+            name: name.to_string(),
+            set,
+            collector_number,
+        });
+    }
+    Ok(entries)
+}
+
+/// Split a leading `4` or `4x` count from a line.
+fn split_count(line: &str) -> (Option<u32>, &str) {
+    let Some((first, rest)) = line.split_once(char::is_whitespace) else {
+        return (None, line);
+    };
+    let digits = first.strip_suffix(['x', 'X']).unwrap_or(first);
+    match digits.parse() {
+        Ok(count) => (Some(count), rest.trim_start()),
+        Err(_) => (None, line),
+    }
+}
+
+/// Split a trailing `(SET)` or `(SET) 123` from a card name.
+fn split_printing(rest: &str) -> (&str, Option<String>, Option<String>) {
+    let Some(open) = rest.rfind(" (") else {
+        return (rest.trim(), None, None);
+    };
+    let Some((set, number)) = rest[open + 2..].split_once(')') else {
+        return (rest.trim(), None, None);
+    };
+    let number = number.trim();
+    (
+        rest[..open].trim(),
+        Some(set.trim().to_lowercase()),
+        (!number.is_empty()).then(|| number.to_string()),
+    )
+}
+
+impl DecklistEntry {
+    /// Returns the Scryfall API URL for this card: the exact printing when a
+    /// set and collector number are given, otherwise a lookup by exact name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mtg_gen::decklist::parse_decklist;
+    ///
+    /// let deck = parse_decklist("4 Lightning Bolt\n1 Counterspell (MH2) 267").unwrap();
+    /// assert_eq!(
+    ///     deck[0].scryfall_url(),
+    ///     "https://api.scryfall.com/cards/named?exact=Lightning%20Bolt"
+    /// );
+    /// assert_eq!(deck[1].scryfall_url(), "https://api.scryfall.com/cards/mh2/267");
+    /// ```
+    #[must_use]
+    pub fn scryfall_url(&self) -> String {
+        match (&self.set, &self.collector_number) {
+            (Some(set), Some(number)) => format!(
+                "{}/cards/{}/{}",
+                SCRYFALL_API,
+                percent_encode(set),
+                percent_encode(number)
+            ),
+            (Some(set), None) => format!(
+                "{}/cards/named?exact={}&set={}",
+                SCRYFALL_API,
+                percent_encode(&self.name),
+                percent_encode(set)
+            ),
+            _ => format!(
+                "{}/cards/named?exact={}",
+                SCRYFALL_API,
+                percent_encode(&self.name)
+            ),
+        }
+    }
+
+    /// Returns the file name of this line's proxy of `card_name`, prefixed
+    /// with the count so the copies to print can be told at a glance.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mtg_gen::decklist::parse_decklist;
+    ///
+    /// let deck = parse_decklist("4 Lightning Bolt").unwrap();
+    /// assert_eq!(deck[0].file_name("Lightning Bolt"), "4x_lightning_bolt.png");
+    /// ```
+    #[must_use]
+    pub fn file_name(&self, card_name: &str) -> String {
+        format!("{}x_{}.png", self.count, sanitize_card_name(card_name))
+    }
+}
+
+/// Local card files by card name, looked up before Scryfall
+#[derive(Debug, Clone, Default)]
+pub struct CardLibrary {
+    /// Card files, keyed by lowercased name
+    files: HashMap<String, PathBuf>,
+}
+
+impl CardLibrary {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the card named `name` in `file`. A card with faces is found by
+    /// its full name or by any face's, so `Fire // Ice` and `Fire` both
+    /// work. A name added twice keeps its first file.
+    pub fn insert(&mut self, name: &str, file: PathBuf) {
+        let faces = name.split(" // ").filter(|face| *face != name);
+        for key in std::iter::once(name).chain(faces) {
+            self.files
+                .entry(library_key(key))
+                .or_insert_with(|| file.clone());
+        }
+    }
+
+    /// Returns the file of the card a decklist line names, ignoring case
+    /// and any printing it asks for.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mtg_gen::decklist::{CardLibrary, parse_decklist};
+    /// use std::path::{Path, PathBuf};
+    ///
+    /// let mut library = CardLibrary::new();
+    /// library.insert("Fire // Ice", PathBuf::from("cards/fire_ice.yaml"));
+    /// let deck = parse_decklist("2 fire\n1 Lightning Bolt").unwrap();
+    /// assert_eq!(library.find(&deck[0]), Some(Path::new("cards/fire_ice.yaml")));
+    /// assert_eq!(library.find(&deck[1]), None);
+    /// ```
+    #[must_use]
+    pub fn find(&self, entry: &DecklistEntry) -> Option<&Path> {
+        self.files
+            .get(&library_key(&entry.name))
+            .map(PathBuf::as_path)
+    }
+
+    /// Returns how many names are known.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+}
+
+/// Card names match ignoring case and surrounding space
+fn library_key(name: &str) -> String {
+    name.trim().to_lowercase()
+}
+
+/// Percent-encode everything but unreserved URL characters.
+fn percent_encode(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for byte in s.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-_.~".contains(&byte) {
+            out.push(byte as char);
+        } else {
+            let _ = write!(out, "%{:02X}", byte);
+        }
+    }
+    out
+}
@@ -0,0 +1,200 @@
+//! Environment checks for `mtg-gen doctor`
+//!
+//! Each check reports what it found and, when something is wrong, the
+//! command or setting that fixes it.
+
+use crate::render::assets_base;
+#[cfg(feature = "browser")]
+use crate::{card::Card, render::Renderer};
+use std::fmt;
+use std::path::Path;
+
+/// Assets every render needs, relative to the mtgrender asset directory
+pub const REQUIRED_ASSETS: &[&str] = &[
+    "fonts/beleren-bold_P1.01.ttf",
+    "fonts/belerensmallcaps-bold.ttf",
+    "fonts/mplantin.ttf",
+    "fonts/MPlantin-Italic.ttf",
+    "fonts/MatrixBold.ttf",
+    "img/frames/W.png",
+    "img/bg/W.png",
+    "img/boxes/W.png",
+    "img/pt_boxes/W.png",
+    "img/symbols/W.svg",
+    "img/symbols/T.svg",
+];
+
+/// A card rendered by the doctor to check the whole pipeline end to end
+#[cfg(feature = "browser")]
+const TEST_CARD: &str = r#"
+name: "Doctor's Test Card"
+type: normal
+mana_cost: "{1}{W}"
+type_line: "Creature — Human Cleric"
+rules_text: "{T}: You gain 1 life."
+power: "1"
+toughness: "1"
+rarity: common
+"#;
+
+/// Outcome of a single check
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckStatus {
+    Pass,
+    Warn,
+    Fail,
+}
+
+/// The result of one doctor check
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckResult {
+    /// Short name of what was checked
+    pub name: &'static str,
+    pub status: CheckStatus,
+    /// What the check found
+    pub detail: String,
+    /// How to fix a warning or failure
+    pub fix: Option<String>,
+}
+
+impl CheckResult {
+    fn pass(name: &'static str, detail: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Pass,
+            detail: detail.into(),
+            fix: None,
+        }
+    }
+
+    fn fail(name: &'static str, detail: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Fail,
+            detail: detail.into(),
+            fix: Some(fix.into()),
+        }
+    }
+}
+
+impl fmt::Display for CheckResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self.status {
+            CheckStatus::Pass => "ok",
+            CheckStatus::Warn => "warn",
+            CheckStatus::Fail => "FAIL",
+        };
+        write!(f, "[{}] {}: {}", label, self.name, self.detail)?;
+        if let Some(fix) = &self.fix {
+            write!(f, "\n       fix: {}", fix)?;
+        }
+        Ok(())
+    }
+}
+
+/// Check that the mtgrender assets (fonts, frames, symbols) exist under `base`.
+#[must_use]
+pub fn check_assets(base: &Path) -> Vec<CheckResult> {
+    if !base.is_dir() {
+        return vec![CheckResult::fail(
+            "assets",
+            format!("asset directory {} does not exist", base.display()),
+            "run mtg-gen from the repository root, with the mtgrender assets checked out in ./mtgrender",
+        )];
+    }
+
+    let missing: Vec<&str> = REQUIRED_ASSETS
+        .iter()
+        .copied()
+        .filter(|asset| !base.join(asset).is_file())
+        .collect();
+    let (fonts, images): (Vec<&str>, Vec<&str>) = missing
+        .into_iter()
+        .partition(|asset| asset.starts_with("fonts/"));
+
+    let fonts = if fonts.is_empty() {
+        CheckResult::pass("fonts", "Beleren, MPlantin and Matrix fonts found")
+    } else {
+        CheckResult::fail(
+            "fonts",
+            format!("missing {}", fonts.join(", ")),
+            format!("restore the font files in {}", base.join("fonts").display()),
+        )
+    };
+    let images = if images.is_empty() {
+        CheckResult::pass("images", "frames, text boxes and mana symbols found")
+    } else {
+        CheckResult::fail(
+            "images",
+            format!("missing {}", images.join(", ")),
+            "update the mtgrender checkout in ./mtgrender",
+        )
+    };
+    vec![fonts, images]
+}
+
+/// Run every check, including launching Chrome and rendering a test card.
+#[cfg(feature = "browser")]
+pub async fn run_checks() -> Vec<CheckResult> {
+    let mut results = check_assets(&assets_base());
+
+    // Mana symbols are local SVGs, so rendering never needs the network
+    results.push(CheckResult::pass(
+        "symbols",
+        "mana symbols are loaded from local SVG files; no network access needed",
+    ));
+
+    let renderer = match Renderer::new().await {
+        Ok(renderer) => {
+            results.push(CheckResult::pass("chrome", "Chrome launched"));
+            renderer
+        }
+        Err(e) => {
+            results.push(CheckResult::fail(
+                "chrome",
+                format!("Chrome failed to launch: {}", e),
+                "install Chrome or Chromium, or set CHROME_PATH to its executable",
+            ));
+            return results;
+        }
+    };
+
+    results.push(check_test_render(&renderer).await);
+    results
+}
+
+#[cfg(feature = "browser")]
+async fn check_test_render(renderer: &Renderer) -> CheckResult {
+    let card: Card = match facet_yaml::from_str(TEST_CARD) {
+        Ok(card) => card,
+        Err(e) => {
+            return CheckResult::fail(
+                "test render",
+                format!("built-in test card failed to parse: {}", e),
+                "this is a bug in mtg-gen; please report it",
+            );
+        }
+    };
+
+    let output = std::env::temp_dir().join(format!("mtg_gen_doctor_{}.png", std::process::id()));
+    let result = match renderer.render_card(&card, &output).await {
+        Ok(_) => match std::fs::metadata(&output) {
+            Ok(meta) if meta.len() > 0 => CheckResult::pass(
+                "test render",
+                format!("rendered a test card ({} bytes)", meta.len()),
+            ),
+            _ => CheckResult::fail(
+                "test render",
+                "the screenshot was empty",
+                "check that Chrome can run headless on this machine, or try a newer Chrome",
+            ),
+        },
+        Err(e) => CheckResult::fail(
+            "test render",
+            format!("rendering failed: {}", e),
+            "check that Chrome can run headless on this machine",
+        ),
+    };
+    let _ = std::fs::remove_file(&output);
+    result
+}
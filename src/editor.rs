@@ -0,0 +1,278 @@
+//! Field-by-field card editing for `mtg-gen edit`
+//!
+//! A [`CardForm`] holds the top-level fields of one card layout, in the
+//! order [`crate::schema`] documents them. Single-value fields (name, mana
+//! cost, rules text, ...) are edited one at a time; lists and objects such as
+//! loyalty abilities or faces are kept verbatim as YAML and edited in the file
+//! itself. Comments in a loaded file are not preserved.
+//!
+//! The terminal UI on top of this lives in [`tui`] and needs the `edit`
+//! feature.
+
+use crate::card::Card;
+use crate::examples::example_yaml;
+use crate::mana::{CastingManaCost, LoyaltyValue, RulesText};
+use crate::schema::{FieldInfo, layout_fields, layouts};
+
+#[cfg(feature = "edit")]
+pub mod tui;
+
+/// Problems loading a card into the editor
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum EditorError {
+    #[error("the card has no `type` field")]
+    MissingType,
+    #[error("unknown card type {0:?}")]
+    UnknownLayout(String),
+    #[error("{layout} cards have no field {field:?}")]
+    UnknownField { layout: String, field: String },
+}
+
+/// The value of one form field
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FieldValue {
+    /// A single value, empty when the field is unset
+    Scalar(String),
+    /// Raw YAML following `key:`, for lists, objects and block scalars
+    Block(String),
+}
+
+/// One field of a [`CardForm`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormField {
+    pub info: FieldInfo,
+    pub value: FieldValue,
+}
+
+impl FormField {
+    /// Returns true if the field has no value.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        match &self.value {
+            FieldValue::Scalar(value) => value.is_empty(),
+            FieldValue::Block(raw) => raw.trim().is_empty(),
+        }
+    }
+
+    /// Check the field's value on its own, returning a message describing
+    /// the problem. Mana costs and rules text are parsed, so a typo in a
+    /// symbol shows up while typing.
+    #[must_use]
+    pub fn problem(&self) -> Option<String> {
+        if self.is_empty() {
+            return self
+                .info
+                .required
+                .then(|| format!("{} is required", self.info.name));
+        }
+        let FieldValue::Scalar(value) = &self.value else {
+            return None;
+        };
+
+        match self.info.name {
+            "mana_cost" => CastingManaCost::parse(value).err().map(|e| e.to_string()),
+            "rules_text" | "backside_rules_text" => {
+                RulesText::parse(value).err().map(|e| e.to_string())
+            }
+            "loyalty" => LoyaltyValue::parse(value).err(),
+            "defense" => value
+                .parse::<u32>()
+                .err()
+                .map(|_| format!("defense must be a whole number, not {:?}", value)),
+            _ => None,
+        }
+    }
+}
+
+/// An editable card: its layout and top-level fields
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CardForm {
+    pub layout: &'static str,
+    pub fields: Vec<FormField>,
+}
+
+impl CardForm {
+    /// A new card of `layout`, pre-filled from the layout's example.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error for an unknown layout.
+    pub fn new(layout: &str) -> Result<Self, EditorError> {
+        let yaml = example_yaml(layout).ok_or_else(|| EditorError::UnknownLayout(layout.into()))?;
+        Self::from_yaml(yaml)
+    }
+
+    /// Load a card from YAML.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the YAML has no known `type` or uses a field the
+    /// layout doesn't have.
+    pub fn from_yaml(yaml: &str) -> Result<Self, EditorError> {
+        let entries = top_level_entries(yaml);
+        let layout = entries
+            .iter()
+            .find(|(key, _)| *key == "type")
+            .and_then(|(_, raw)| scalar_value(raw))
+            .ok_or(EditorError::MissingType)?;
+        let (Some(layout), Some(infos)) =
+            (layouts().find(|l| *l == layout), layout_fields(&layout))
+        else {
+            return Err(EditorError::UnknownLayout(layout));
+        };
+
+        let mut fields: Vec<FormField> = infos
+            .into_iter()
+            .map(|info| FormField {
+                info,
+                value: if info.nested {
+                    FieldValue::Block(String::new())
+                } else {
+                    FieldValue::Scalar(String::new())
+                },
+            })
+            .collect();
+
+        for (key, raw) in entries {
+            let field = fields
+                .iter_mut()
+                .find(|f| f.info.name == key)
+                .ok_or_else(|| EditorError::UnknownField {
+                    layout: layout.to_string(),
+                    field: key.to_string(),
+                })?;
+            field.value = match scalar_value(&raw) {
+                Some(value) if !field.info.nested => FieldValue::Scalar(value),
+                _ => FieldValue::Block(raw),
+            };
+        }
+
+        Ok(Self { layout, fields })
+    }
+
+    /// Returns the field called `name`.
+    #[must_use]
+    pub fn field(&self, name: &str) -> Option<&FormField> {
+        self.fields.iter().find(|f| f.info.name == name)
+    }
+
+    /// Set a single-value field. Returns false if there is no such field.
+    pub fn set(&mut self, name: &str, value: impl Into<String>) -> bool {
+        match self.fields.iter_mut().find(|f| f.info.name == name) {
+            Some(field) => {
+                field.value = FieldValue::Scalar(value.into());
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns every field with a problem, with its message.
+    #[must_use]
+    pub fn problems(&self) -> Vec<(&'static str, String)> {
+        self.fields
+            .iter()
+            .filter_map(|f| f.problem().map(|p| (f.info.name, p)))
+            .collect()
+    }
+
+    /// Write the card as YAML, skipping unset fields.
+    #[must_use]
+    pub fn to_yaml(&self) -> String {
+        let mut out = String::new();
+        for field in self.fields.iter().filter(|f| !f.is_empty()) {
+            match &field.value {
+                FieldValue::Scalar(value) if field.info.plain => {
+                    out.push_str(&format!("{}: {}\n", field.info.name, value));
+                }
+                FieldValue::Scalar(value) => {
+                    out.push_str(&format!("{}: {}\n", field.info.name, quote(value)));
+                }
+                FieldValue::Block(raw) => {
+                    out.push_str(&format!("{}:{}\n", field.info.name, raw.trim_end()));
+                }
+            }
+        }
+        out
+    }
+
+    /// Parse and validate the card as it would be rendered.
+    ///
+    /// # Errors
+    ///
+    /// Returns the parse or validation error as a message.
+    pub fn to_card(&self) -> Result<Card, String> {
+        let card: Card = facet_yaml::from_str(&self.to_yaml()).map_err(|e| e.to_string())?;
+        card.validate().map_err(|e| e.to_string())?;
+        Ok(card)
+    }
+}
+
+/// Split YAML into top-level keys and the raw text after each `key:`,
+/// including any indented lines below it. Comment lines are dropped.
+fn top_level_entries(yaml: &str) -> Vec<(&str, String)> {
+    let mut entries: Vec<(&str, String)> = Vec::new();
+    for line in yaml.lines() {
+        if line.trim_start().starts_with('#') || line.trim().is_empty() {
+            continue;
+        }
+        if !line.starts_with([' ', '\t', '-']) {
+            if let Some((key, rest)) = line.split_once(':') {
+                entries.push((key.trim(), rest.to_string()));
+                continue;
+            }
+        }
+        if let Some((_, raw)) = entries.last_mut() {
+            raw.push('\n');
+            raw.push_str(line);
+        }
+    }
+    entries
+}
+
+/// Returns the value of a single-line scalar, unquoting it, or `None` for
+/// lists, objects and block scalars.
+fn scalar_value(raw: &str) -> Option<String> {
+    let value = raw.trim();
+    if raw.contains('\n') || value.starts_with(['|', '>', '[', '{']) {
+        return None;
+    }
+
+    if let Some(inner) = value.strip_prefix('"').and_then(|v| v.strip_suffix('"')) {
+        let mut out = String::new();
+        let mut chars = inner.chars();
+        while let Some(c) = chars.next() {
+            if c != '\\' {
+                out.push(c);
+                continue;
+            }
+            match chars.next() {
+                Some('n') => out.push('\n'),
+                Some('t') => out.push('\t'),
+                Some(other) => out.push(other),
+                None => out.push('\\'),
+            }
+        }
+        Some(out)
+    } else if let Some(inner) = value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')) {
+        Some(inner.replace("''", "'"))
+    } else {
+        Some(value.to_string())
+    }
+}
+
+/// Double-quote a value for YAML.
+fn quote(value: &str) -> String {
+    let mut out = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
@@ -0,0 +1,279 @@
+//! Terminal UI for `mtg-gen edit`
+//!
+//! Built with `--features edit`. Fields are listed with any problem next to
+//! them, and the selected field is edited on the input line below. The
+//! renderer is started once when the editor opens, so a preview render only
+//! costs the screenshot itself.
+//!
+//! Keys: `↑`/`↓` select a field, `Enter` edits it (and `Enter` again keeps
+//! the edit, `Esc` drops it), `s` saves, `p` renders a preview next to the
+//! card file and `q` quits.
+
+use super::{CardForm, FieldValue, FormField};
+use crate::render::Renderer;
+use anyhow::Result;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph};
+use ratatui::{DefaultTerminal, Frame};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// What a key press asks the editor to do
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Action {
+    None,
+    Save,
+    Preview,
+    Quit,
+}
+
+struct App {
+    path: PathBuf,
+    form: CardForm,
+    selected: usize,
+    /// The value being typed, while a field is being edited
+    input: Option<String>,
+    status: String,
+    dirty: bool,
+    confirm_quit: bool,
+}
+
+/// Returns where the preview of the card at `path` is rendered.
+#[must_use]
+pub fn preview_path(path: &Path) -> PathBuf {
+    path.with_extension("preview.png")
+}
+
+/// Edit the card at `path`, starting from `form`, until the user quits.
+///
+/// # Errors
+///
+/// Returns an error if the terminal can't be set up or the card can't be
+/// saved.
+pub async fn run(path: &Path, form: CardForm) -> Result<()> {
+    // Start Chrome before the first preview is asked for
+    let renderer = Renderer::new().await;
+
+    let mut app = App {
+        path: path.to_path_buf(),
+        form,
+        selected: 0,
+        input: None,
+        status: match &renderer {
+            Ok(_) => "Enter: edit  s: save  p: preview  q: quit".to_string(),
+            Err(e) => format!("Preview unavailable, Chrome failed to launch: {}", e),
+        },
+        dirty: !path.exists(),
+        confirm_quit: false,
+    };
+
+    let mut terminal = ratatui::init();
+    let result = event_loop(&mut terminal, &mut app, renderer.as_ref().ok()).await;
+    ratatui::restore();
+    result
+}
+
+async fn event_loop(
+    terminal: &mut DefaultTerminal,
+    app: &mut App,
+    renderer: Option<&Renderer>,
+) -> Result<()> {
+    loop {
+        terminal.draw(|frame| draw(frame, app))?;
+
+        if !event::poll(Duration::from_millis(250))? {
+            continue;
+        }
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+
+        match app.handle_key(key) {
+            Action::None => {}
+            Action::Save => app.save()?,
+            Action::Preview => {
+                app.preview(renderer).await;
+                // Chrome may have written to the terminal behind our back
+                terminal.clear()?;
+            }
+            Action::Quit => return Ok(()),
+        }
+    }
+}
+
+impl App {
+    fn handle_key(&mut self, key: KeyEvent) -> Action {
+        if let Some(input) = &mut self.input {
+            match key.code {
+                KeyCode::Enter => {
+                    let value = std::mem::take(input);
+                    let name = self.form.fields[self.selected].info.name;
+                    self.form.set(name, value);
+                    self.input = None;
+                    self.dirty = true;
+                }
+                KeyCode::Esc => self.input = None,
+                KeyCode::Backspace => {
+                    input.pop();
+                }
+                KeyCode::Char(c) => input.push(c),
+                _ => {}
+            }
+            return Action::None;
+        }
+
+        let quitting = std::mem::take(&mut self.confirm_quit);
+        match key.code {
+            KeyCode::Up => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Down => self.selected = (self.selected + 1).min(self.form.fields.len() - 1),
+            KeyCode::Enter => {
+                let field = &self.form.fields[self.selected];
+                match &field.value {
+                    _ if field.info.name == "type" => {
+                        self.status = "The card type can't be changed here".to_string();
+                    }
+                    FieldValue::Scalar(value) => self.input = Some(value.clone()),
+                    FieldValue::Block(_) => {
+                        self.status = format!(
+                            "{} is a list or block; edit it in {}",
+                            field.info.name,
+                            self.path.display()
+                        );
+                    }
+                }
+            }
+            KeyCode::Char('s') => return Action::Save,
+            KeyCode::Char('p') => return Action::Preview,
+            KeyCode::Char('q') | KeyCode::Esc => {
+                if !self.dirty || quitting {
+                    return Action::Quit;
+                }
+                self.confirm_quit = true;
+                self.status = "Unsaved changes; press q again to quit without saving".to_string();
+            }
+            _ => {}
+        }
+        Action::None
+    }
+
+    fn save(&mut self) -> Result<()> {
+        std::fs::write(&self.path, self.form.to_yaml())?;
+        self.dirty = false;
+        self.status = match self.form.to_card() {
+            Ok(_) => format!("Saved {}", self.path.display()),
+            Err(e) => format!(
+                "Saved {}, but the card doesn't parse: {}",
+                self.path.display(),
+                e
+            ),
+        };
+        Ok(())
+    }
+
+    async fn preview(&mut self, renderer: Option<&Renderer>) {
+        let Some(renderer) = renderer else {
+            self.status = "Preview unavailable: Chrome isn't running".to_string();
+            return;
+        };
+        let card = match self.form.to_card() {
+            Ok(card) => card,
+            Err(e) => {
+                self.status = format!("Can't preview: {}", e);
+                return;
+            }
+        };
+
+        let output = preview_path(&self.path);
+        self.status = match renderer.render_card(&card, &output).await {
+            Ok(outcome) => match outcome.warnings.first() {
+                Some(warning) => format!("Preview written to {} ({})", output.display(), warning),
+                None => format!("Preview written to {}", output.display()),
+            },
+            Err(e) => format!("Preview failed: {}", e),
+        };
+    }
+}
+
+fn draw(frame: &mut Frame, app: &App) {
+    let [fields_area, input_area, status_area] = Layout::vertical([
+        Constraint::Min(1),
+        Constraint::Length(3),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+
+    let items: Vec<ListItem> = app
+        .form
+        .fields
+        .iter()
+        .map(|field| {
+            let value = match &field.value {
+                FieldValue::Scalar(value) => value.replace('\n', "\\n"),
+                FieldValue::Block(raw) if raw.trim().is_empty() => String::new(),
+                FieldValue::Block(raw) => format!("({} lines of YAML)", raw.trim().lines().count()),
+            };
+            let mut spans = vec![
+                Span::styled(
+                    format!("{:<20}", field.info.name),
+                    Style::default().add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(value),
+            ];
+            if let Some(problem) = field.problem() {
+                spans.push(Span::styled(
+                    format!("  ! {}", problem),
+                    Style::default().fg(Color::Red),
+                ));
+            }
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+
+    let title = format!(
+        " {} ({}){} ",
+        app.path.display(),
+        app.form.layout,
+        if app.dirty { " *" } else { "" }
+    );
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .highlight_style(Style::default().bg(Color::DarkGray));
+    let mut state = ListState::default().with_selected(Some(app.selected));
+    frame.render_stateful_widget(list, fields_area, &mut state);
+
+    let field = &app.form.fields[app.selected];
+    let input = match &app.input {
+        Some(input) => {
+            // Check the value as it's typed, so a bad mana symbol shows up at once
+            let draft = FormField {
+                info: field.info,
+                value: FieldValue::Scalar(input.clone()),
+            };
+            let mut spans = vec![Span::raw(format!("{}_", input))];
+            if let Some(problem) = draft.problem() {
+                spans.push(Span::styled(
+                    format!("  ! {}", problem),
+                    Style::default().fg(Color::Red),
+                ));
+            }
+            Paragraph::new(Line::from(spans))
+        }
+        None => Paragraph::new(field.info.description).style(Style::default().fg(Color::Gray)),
+    };
+    frame.render_widget(
+        input.block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!(" {} ", field.info.name)),
+        ),
+        input_area,
+    );
+
+    frame.render_widget(Paragraph::new(app.status.as_str()), status_area);
+}
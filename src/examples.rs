@@ -0,0 +1,317 @@
+//! Commented example YAML for every card layout
+//!
+//! Used by `mtg-gen example --type <layout>` to give new users a working
+//! starting point. Every example is parsed and validated against the card
+//! model by the test suite, and [`Card::layout_name`](crate::Card::layout_name)
+//! is an exhaustive match, so a new layout or a renamed field fails the build
+//! or the tests instead of leaving a stale example behind.
+
+/// Returns the example YAML for a layout name (`normal`, `planeswalker`, ...).
+#[must_use]
+pub fn example_yaml(layout: &str) -> Option<&'static str> {
+    EXAMPLES
+        .iter()
+        .find(|(name, _)| *name == layout)
+        .map(|(_, yaml)| *yaml)
+}
+
+/// Returns the layout names that have an example, in the order they are
+/// documented.
+pub fn layouts() -> impl Iterator<Item = &'static str> {
+    EXAMPLES.iter().map(|(name, _)| *name)
+}
+
+/// Example YAML for every layout, keyed by the `type` value
+pub const EXAMPLES: &[(&str, &str)] = &[
+    ("normal", NORMAL),
+    ("planeswalker", PLANESWALKER),
+    ("saga", SAGA),
+    ("class", CLASS),
+    ("adventure", ADVENTURE),
+    ("split", SPLIT),
+    ("flip", FLIP),
+    ("transform", TRANSFORM),
+    ("modal_dfc", MODAL_DFC),
+    ("battle", BATTLE),
+    ("meld", MELD),
+    ("leveler", LEVELER),
+    ("prototype", PROTOTYPE),
+    ("token", TOKEN),
+];
+
+const NORMAL: &str = r#"# Normal card: creatures, instants, sorceries, artifacts, enchantments, lands
+type: normal
+
+# Card name, printed in the title bar
+name: "Llanowar Elves"
+
+# Mana cost in Scryfall notation; leave out for lands
+mana_cost: "{G}"
+
+# Full type line, with an em dash before subtypes
+type_line: "Creature — Elf Druid"
+
+# Rules text; symbols like {T} and {G} render as icons, \n starts a new paragraph
+rules_text: "{T}: Add {G}."
+
+# Flavor text, printed in italics below the rules text (optional)
+flavor_text: "One bone broken for every twig snapped underfoot."
+
+# Power and toughness, for creatures (strings, so "*" and "1+*" work)
+power: "1"
+toughness: "1"
+
+# common | uncommon | rare | mythic | special | bonus | land
+rarity: common
+"#;
+
+const PLANESWALKER: &str = r#"# Planeswalker card
+type: planeswalker
+name: "Jace, the Mind Sculptor"
+mana_cost: "{2}{U}{U}"
+type_line: "Legendary Planeswalker — Jace"
+rarity: mythic
+
+# Starting loyalty: a number or X
+loyalty: 3
+
+# Loyalty abilities, top to bottom; costs are "+N", "-N", "0", "+X" or "-X"
+loyalty_abilities:
+  - cost: "+2"
+    text: "Look at the top card of target player's library. You may put that card on the bottom of that player's library."
+  - cost: "0"
+    text: "Draw three cards, then put two cards from your hand on top of your library in any order."
+  - cost: "-1"
+    text: "Return target creature to its owner's hand."
+  - cost: "-12"
+    text: 'You get an emblem with "Whenever an opponent draws a card, they lose 1 life."'
+"#;
+
+const SAGA: &str = r#"# Saga card
+type: saga
+name: "The Eldest Reborn"
+mana_cost: "{4}{B}"
+type_line: "Enchantment — Saga"
+rarity: uncommon
+
+# Set to true for read ahead sagas (optional)
+read_ahead: false
+
+# Chapter abilities; one ability can cover several chapters, e.g. [2, 3]
+chapters:
+  - chapters: [1]
+    text: "Each opponent sacrifices a creature or planeswalker."
+  - chapters: [2]
+    text: "Each opponent discards a card."
+  - chapters: [3]
+    text: "Put target creature or planeswalker card from a graveyard onto the battlefield under your control."
+"#;
+
+const CLASS: &str = r#"# Class card
+type: class
+name: "Ranger Class"
+mana_cost: "{1}{G}"
+type_line: "Enchantment — Class"
+rarity: rare
+
+# Levels must start at 1 and count up; every level after 1 needs a cost
+levels:
+  - level: 1
+    text: "When Ranger Class enters, create a 2/2 green Wolf creature token."
+  - level: 2
+    cost: "{1}{G}"
+    text: "Whenever you attack, put a +1/+1 counter on target attacking creature."
+  - level: 3
+    cost: "{3}{G}"
+    text: "You may look at the top card of your library any time. You may cast creature spells from the top of your library."
+"#;
+
+const ADVENTURE: &str = r#"# Adventure card: the main fields are the creature, `adventure` is the spell
+type: adventure
+name: "Bonecrusher Giant"
+mana_cost: "{2}{R}"
+type_line: "Creature — Giant"
+rules_text: "Whenever Bonecrusher Giant becomes the target of a spell, Bonecrusher Giant deals 2 damage to that spell's controller."
+power: "4"
+toughness: "3"
+rarity: rare
+
+# The adventure spell, printed in the left half of the text box
+adventure:
+  name: "Stomp"
+  mana_cost: "{1}{R}"
+  type_line: "Instant — Adventure"
+  rules_text: "Damage can't be prevented this turn. Stomp deals 2 damage to any target."
+"#;
+
+const SPLIT: &str = r#"# Split card: two spells side by side
+type: split
+name: "Fire // Ice"
+type_line: "Instant // Instant"
+rarity: uncommon
+
+# Set to true for fuse or aftermath split cards (optional)
+fuse: false
+aftermath: false
+
+# The two halves, left then right
+faces:
+  - name: "Fire"
+    mana_cost: "{1}{R}"
+    type_line: "Instant"
+    rules_text: "Fire deals 2 damage divided as you choose among one or two targets."
+  - name: "Ice"
+    mana_cost: "{1}{U}"
+    type_line: "Instant"
+    rules_text: "Tap target permanent.\nDraw a card."
+"#;
+
+const FLIP: &str = r#"# Flip card (Kamigawa style): the second face is printed upside down
+type: flip
+name: "Akki Lavarunner"
+type_line: "Creature — Goblin Warrior"
+rarity: rare
+faces:
+  - name: "Akki Lavarunner"
+    mana_cost: "{3}{R}"
+    type_line: "Creature — Goblin Warrior"
+    rules_text: "Haste\nWhenever Akki Lavarunner deals damage to an opponent, flip it."
+    power: "1"
+    toughness: "1"
+  - name: "Tok-Tok, Volcano Born"
+    type_line: "Legendary Creature — Goblin Shaman"
+    rules_text: "Protection from red\nIf a red source would deal damage to a player, it deals that much damage plus 1 to that player instead."
+    power: "2"
+    toughness: "2"
+"#;
+
+const TRANSFORM: &str = r#"# Transforming double-faced card
+type: transform
+name: "Delver of Secrets"
+type_line: "Creature — Human Wizard"
+rarity: common
+
+# Front face first, then the back face
+faces:
+  - name: "Delver of Secrets"
+    mana_cost: "{U}"
+    type_line: "Creature — Human Wizard"
+    rules_text: "At the beginning of your upkeep, look at the top card of your library. You may reveal that card. If an instant or sorcery card is revealed this way, transform Delver of Secrets."
+    power: "1"
+    toughness: "1"
+  - name: "Insectile Aberration"
+    type_line: "Creature — Human Insect"
+    rules_text: "Flying"
+    power: "3"
+    toughness: "2"
+    # Back faces have no mana cost; the color indicator gives their color
+    color_indicator: ["blue"]
+"#;
+
+const MODAL_DFC: &str = r#"# Modal double-faced card: either face can be played
+type: modal_dfc
+name: "Emeria's Call"
+type_line: "Sorcery // Land"
+rarity: mythic
+faces:
+  - name: "Emeria's Call"
+    mana_cost: "{4}{W}{W}{W}"
+    type_line: "Sorcery"
+    rules_text: "Create two 4/4 white Angel Warrior creature tokens with flying. Non-Angel creatures you control gain indestructible until your next turn."
+  - name: "Emeria, Shattered Skyclave"
+    type_line: "Land"
+    rules_text: "As Emeria, Shattered Skyclave enters, you may pay 3 life. If you don't, it enters tapped.\n{T}: Add {W}."
+"#;
+
+const BATTLE: &str = r#"# Battle card
+type: battle
+name: "Invasion of Gobakhan"
+mana_cost: "{1}{W}"
+type_line: "Battle — Siege"
+rarity: rare
+rules_text: "When Invasion of Gobakhan enters, look at target opponent's hand. You may exile a nonland card from it. For as long as that card remains exiled, its owner may play it. A spell cast this way costs {2} more to cast."
+
+# Starting defense counters
+defense: 3
+
+# auto adds the Siege reminder text; none renders only what's written here
+reminder_text: auto
+
+# The back face, shown in the hint bar
+backside_name: "Lightshield Array"
+backside_type_line: "Enchantment"
+backside_rules_text: "At the beginning of your end step, put a +1/+1 counter on each creature that attacked this turn."
+"#;
+
+const MELD: &str = r#"# Meld card: one half of a pair that melds into a single back face
+type: meld
+name: "Bruna, the Fading Light"
+type_line: "Legendary Creature — Angel Horror"
+rarity: rare
+
+# This card's front face, then the melded back face
+faces:
+  - name: "Bruna, the Fading Light"
+    mana_cost: "{5}{W}{W}"
+    type_line: "Legendary Creature — Angel Horror"
+    rules_text: "When you cast this spell, you may return target Angel or Human creature card from your graveyard to the battlefield.\nFlying, vigilance"
+    power: "5"
+    toughness: "7"
+  - name: "Brisela, Voice of Nightmares"
+    type_line: "Legendary Creature — Eldrazi Angel"
+    rules_text: "Flying, first strike, vigilance, lifelink\nYour opponents can't cast spells with mana value 3 or less."
+    power: "9"
+    toughness: "10"
+"#;
+
+const LEVELER: &str = r#"# Leveler card
+type: leveler
+name: "Kargan Dragonlord"
+mana_cost: "{R}{R}"
+type_line: "Creature — Human Warrior"
+rarity: mythic
+rules_text: "Level up {R}"
+
+# Level ranges in order: "0-3", "4-7", "8+"; each range has its own P/T and text
+leveler_ranges:
+  - range: "0-3"
+    power: "2"
+    toughness: "2"
+  - range: "4-7"
+    power: "4"
+    toughness: "4"
+    text: "Flying"
+  - range: "8+"
+    power: "8"
+    toughness: "8"
+    text: "Flying, trample\n{R}: Kargan Dragonlord gets +1/+0 until end of turn."
+"#;
+
+const PROTOTYPE: &str = r#"# Prototype card: the main fields are the full card, `prototype` the smaller version
+type: prototype
+name: "Phyrexian Fleshgorger"
+mana_cost: "{7}"
+type_line: "Artifact Creature — Phyrexian Wurm"
+rarity: mythic
+rules_text: "Prototype {1}{B}{B} — 3/3\nMenace, lifelink\nWard—Pay life equal to Phyrexian Fleshgorger's power."
+power: "7"
+toughness: "5"
+
+# Cost and stats of the prototype, shown in the inset box
+prototype:
+  mana_cost: "{1}{B}{B}"
+  power: "3"
+  toughness: "3"
+"#;
+
+const TOKEN: &str = r#"# Token: no mana cost, so its frame color comes from `colors`
+type: token
+name: "Saproling"
+type_line: "Token Creature — Saproling"
+rarity: common
+power: "1"
+toughness: "1"
+
+# Colors by name (white, blue, black, red, green); leave out for colorless
+colors: ["green"]
+"#;
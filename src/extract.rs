@@ -0,0 +1,201 @@
+//! Extraction of the extra game objects a card creates
+//!
+//! Some abilities create objects that need their own printed card, like the
+//! emblem a planeswalker's ultimate gives its controller. This module finds
+//! them in a card's rules text so they can be rendered alongside the card,
+//! along with the overlay cards standing in for cards played face down.
+
+use crate::card::{Card, CardFace, PlaneswalkerCard};
+use crate::mana::RulesText;
+
+/// The phrases that introduce an emblem's text, with each quote style
+/// oracle text uses
+const EMBLEM_MARKERS: &[(&str, char)] = &[
+    ("You get an emblem with \"", '"'),
+    ("You get an emblem with “", '”'),
+];
+
+/// An emblem created by a planeswalker ability
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Emblem {
+    /// Name of the card that creates the emblem
+    pub source_name: String,
+    /// Planeswalker subtype printed on the emblem's type line (e.g. `Jace`)
+    pub subtype: Option<String>,
+    /// The emblem's abilities
+    pub rules_text: RulesText,
+}
+
+impl Emblem {
+    /// Returns the emblem's type line, e.g. `Emblem — Elspeth`.
+    #[must_use]
+    pub fn type_line(&self) -> String {
+        match &self.subtype {
+            Some(subtype) => format!("Emblem — {}", subtype),
+            None => "Emblem".to_string(),
+        }
+    }
+}
+
+/// Find every emblem a card's abilities create.
+///
+/// Only planeswalker loyalty abilities are searched; emblem text is taken
+/// from between the quotes in "You get an emblem with "…"".
+///
+/// # Examples
+///
+/// ```
+/// use mtg_gen::{Card, extract::extract_emblems};
+///
+/// let yaml = r#"
+/// name: "Elspeth, Knight-Errant"
+/// type: planeswalker
+/// type_line: "Legendary Planeswalker — Elspeth"
+/// rarity: mythic
+/// loyalty: 4
+/// loyalty_abilities:
+///   - cost: "-8"
+///     text: 'You get an emblem with "Artifacts, creatures, enchantments, and lands you control have indestructible."'
+/// "#;
+/// let card: Card = facet_yaml::from_str(yaml).unwrap();
+/// let emblems = extract_emblems(&card);
+/// assert_eq!(emblems.len(), 1);
+/// assert_eq!(emblems[0].type_line(), "Emblem — Elspeth");
+/// ```
+#[must_use]
+pub fn extract_emblems(card: &Card) -> Vec<Emblem> {
+    match card {
+        Card::Planeswalker(planeswalker) => planeswalker_emblems(planeswalker),
+        _ => Vec::new(),
+    }
+}
+
+fn planeswalker_emblems(planeswalker: &PlaneswalkerCard) -> Vec<Emblem> {
+    let subtype = planeswalker
+        .base
+        .type_line
+        .split_once('—')
+        .map(|(_, subtypes)| subtypes.trim().to_string())
+        .filter(|subtypes| !subtypes.is_empty());
+
+    planeswalker
+        .loyalty_abilities
+        .iter()
+        .filter_map(|ability| emblem_text(&ability.text.to_string()))
+        .filter_map(|text| RulesText::parse(&text).ok())
+        .map(|rules_text| Emblem {
+            source_name: planeswalker.base.name.clone(),
+            subtype: subtype.clone(),
+            rules_text,
+        })
+        .collect()
+}
+
+/// Returns the quoted emblem text in an ability, if it creates an emblem.
+fn emblem_text(ability: &str) -> Option<String> {
+    EMBLEM_MARKERS.iter().find_map(|(marker, close)| {
+        let start = ability.find(marker)? + marker.len();
+        // The emblem's own text can quote abilities, so use the last closing quote
+        let end = start + ability[start..].rfind(*close)?;
+        Some(ability[start..end].to_string())
+    })
+}
+
+/// Keywords that turn cards face down, with the overlay each needs
+const FACE_DOWN_KEYWORDS: &[(&str, FaceDownKind)] = &[
+    ("morph", FaceDownKind::Morph),
+    ("megamorph", FaceDownKind::Morph),
+    ("manifest", FaceDownKind::Morph),
+    ("disguise", FaceDownKind::Disguise),
+    ("cloak", FaceDownKind::Disguise),
+];
+
+/// Which overlay a face-down card needs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum FaceDownKind {
+    /// A plain 2/2, for morph, megamorph and manifest
+    Morph,
+    /// A 2/2 with ward {2}, for disguise and cloak
+    Disguise,
+}
+
+/// The overlay card for a creature played face down: a nameless, colorless
+/// 2/2
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FaceDown {
+    pub kind: FaceDownKind,
+}
+
+impl FaceDown {
+    /// Returns the title printed on the overlay, e.g. `Disguise`.
+    #[must_use]
+    pub fn title(&self) -> &'static str {
+        match self.kind {
+            FaceDownKind::Morph => "Morph",
+            FaceDownKind::Disguise => "Disguise",
+        }
+    }
+
+    /// Returns the overlay's rules text: the ward a disguised card has,
+    /// then a reminder of what a face-down permanent is.
+    #[must_use]
+    pub fn rules_text(&self) -> RulesText {
+        let reminder = "(This face-down permanent is a 2/2 creature with no name, mana cost, creature types, or abilities.)";
+        let text = match self.kind {
+            FaceDownKind::Morph => reminder.to_string(),
+            FaceDownKind::Disguise => format!("Ward {{2}}\n{}", reminder),
+        };
+        RulesText::parse(&text).unwrap_or_default()
+    }
+}
+
+/// Returns the face-down overlay a card needs, if its rules text mentions
+/// playing cards face down (morph, disguise, manifest, ...). A card
+/// mentioning both kinds gets the disguise overlay, whose ward covers both.
+///
+/// # Examples
+///
+/// ```
+/// use mtg_gen::{Card, extract::{FaceDownKind, extract_face_down}};
+///
+/// let yaml = r#"
+/// name: "Hidden Dragon"
+/// type: normal
+/// type_line: "Creature — Dragon"
+/// rarity: rare
+/// rules_text: "Flying\nMorph {4}{R}{R}"
+/// "#;
+/// let card: Card = facet_yaml::from_str(yaml).unwrap();
+/// assert_eq!(extract_face_down(&card).unwrap().kind, FaceDownKind::Morph);
+/// ```
+#[must_use]
+pub fn extract_face_down(card: &Card) -> Option<FaceDown> {
+    let faces: &[CardFace] = match card {
+        Card::Split(card) => &card.faces,
+        Card::Flip(card) => &card.faces,
+        Card::Transform(card) => &card.faces,
+        Card::ModalDfc(card) => &card.faces,
+        Card::Meld(card) => &card.faces,
+        _ => &[],
+    };
+    card.base()
+        .rules_text
+        .iter()
+        .chain(faces.iter().filter_map(|face| face.rules_text.as_ref()))
+        .flat_map(|text| face_down_kinds(&text.to_string()))
+        .max()
+        .map(|kind| FaceDown { kind })
+}
+
+/// Returns the kind of each face-down keyword in `text`, matching whole
+/// words in any case.
+fn face_down_kinds(text: &str) -> Vec<FaceDownKind> {
+    text.split(|c: char| !c.is_alphabetic())
+        .filter_map(|word| {
+            FACE_DOWN_KEYWORDS
+                .iter()
+                .find(|(keyword, _)| word.eq_ignore_ascii_case(keyword))
+                .map(|&(_, kind)| kind)
+        })
+        .collect()
+}
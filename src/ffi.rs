@@ -0,0 +1,195 @@
+//! C ABI for the card model and renderer
+//!
+//! Built with `--features ffi`, for set-building tools and notebooks in
+//! other languages. Build the shared library with
+//!
+//! ```bash
+//! cargo rustc --release --lib --features ffi --crate-type cdylib
+//! ```
+//!
+//! and declare the functions from `include/mtg_gen.h`.
+//!
+//! Functions that can fail take `error`, where a message is stored on
+//! failure: they return null and, unless `error` is null, set `*error` to a
+//! string the caller frees with [`mtg_gen_string_free`]. Everything returned
+//! is owned by the caller and freed with the matching `_free` function.
+//!
+//! Rendering blocks until the image is ready, so call it from a plain
+//! thread, never from inside an async runtime.
+
+use crate::card::Card;
+use crate::render::Renderer;
+use anyhow::{Result, anyhow};
+use std::ffi::{CStr, CString, c_char};
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::ptr;
+
+/// Parse and validate a card from YAML in UTF-8.
+///
+/// Returns the card, to free with [`mtg_gen_card_free`], or null.
+///
+/// # Safety
+///
+/// `yaml` must be a valid NUL-terminated string, and `error` null or valid
+/// for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn mtg_gen_card_parse_yaml(
+    yaml: *const c_char,
+    error: *mut *mut c_char,
+) -> *mut Card {
+    let result = guard(|| {
+        let yaml = unsafe { str_arg(yaml) }?;
+        let card: Card = facet_yaml::from_str(yaml).map_err(|e| anyhow!("{}", e))?;
+        card.validate()?;
+        Ok(Box::into_raw(Box::new(card)))
+    });
+    unsafe { finish(result, error) }.unwrap_or(ptr::null_mut())
+}
+
+/// Returns the card's name, to free with [`mtg_gen_string_free`].
+///
+/// # Safety
+///
+/// `card` must come from [`mtg_gen_card_parse_yaml`] and not be freed yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn mtg_gen_card_name(card: *const Card) -> *mut c_char {
+    match unsafe { card.as_ref() } {
+        Some(card) => c_string(card.name()),
+        None => ptr::null_mut(),
+    }
+}
+
+/// Free a card. Null is ignored.
+///
+/// # Safety
+///
+/// `card` must come from [`mtg_gen_card_parse_yaml`] and not be freed yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn mtg_gen_card_free(card: *mut Card) {
+    if !card.is_null() {
+        drop(unsafe { Box::from_raw(card) });
+    }
+}
+
+/// Start Chrome for rendering.
+///
+/// Returns the renderer, to free with [`mtg_gen_renderer_free`], or null.
+///
+/// # Safety
+///
+/// `error` must be null or valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn mtg_gen_renderer_new(error: *mut *mut c_char) -> *mut Renderer {
+    let result = guard(|| Ok(Box::into_raw(Box::new(Renderer::new_blocking()?))));
+    unsafe { finish(result, error) }.unwrap_or(ptr::null_mut())
+}
+
+/// Close Chrome and free a renderer. Null is ignored.
+///
+/// # Safety
+///
+/// `renderer` must come from [`mtg_gen_renderer_new`] and not be freed yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn mtg_gen_renderer_free(renderer: *mut Renderer) {
+    if !renderer.is_null() {
+        let renderer = unsafe { Box::from_raw(renderer) };
+        let _ = catch_unwind(AssertUnwindSafe(|| renderer.close_blocking()));
+    }
+}
+
+/// Render a card to a PNG.
+///
+/// Returns the PNG's bytes and stores their count in `*len`, to free with
+/// [`mtg_gen_bytes_free`], or returns null. Warnings, like an image that
+/// failed to load, aren't reported.
+///
+/// # Safety
+///
+/// `renderer` and `card` must be live values from this library, `len`
+/// valid for writes, and `error` null or valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn mtg_gen_render_card_to_png_bytes(
+    renderer: *const Renderer,
+    card: *const Card,
+    len: *mut usize,
+    error: *mut *mut c_char,
+) -> *mut u8 {
+    let result = guard(|| {
+        let renderer = unsafe { renderer.as_ref() }.ok_or_else(|| anyhow!("renderer is null"))?;
+        let card = unsafe { card.as_ref() }.ok_or_else(|| anyhow!("card is null"))?;
+        if len.is_null() {
+            return Err(anyhow!("len is null"));
+        }
+        let png = renderer
+            .render_card_png_blocking(card)?
+            .bytes
+            .into_boxed_slice();
+        unsafe { *len = png.len() };
+        Ok(Box::into_raw(png).cast::<u8>())
+    });
+    unsafe { finish(result, error) }.unwrap_or(ptr::null_mut())
+}
+
+/// Free bytes returned by [`mtg_gen_render_card_to_png_bytes`]. Null is
+/// ignored.
+///
+/// # Safety
+///
+/// `bytes` and `len` must be as returned, and `bytes` not freed yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn mtg_gen_bytes_free(bytes: *mut u8, len: usize) {
+    if !bytes.is_null() {
+        drop(unsafe { Box::from_raw(ptr::slice_from_raw_parts_mut(bytes, len)) });
+    }
+}
+
+/// Free a string returned by this library. Null is ignored.
+///
+/// # Safety
+///
+/// `string` must come from this library and not be freed yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn mtg_gen_string_free(string: *mut c_char) {
+    if !string.is_null() {
+        drop(unsafe { CString::from_raw(string) });
+    }
+}
+
+/// Run `f`, turning a panic into an error so it never unwinds into C.
+fn guard<T>(f: impl FnOnce() -> Result<T>) -> Result<T> {
+    catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|_| Err(anyhow!("mtg-gen panicked")))
+}
+
+/// Returns the value of `result`, or stores its error in `*error`.
+///
+/// # Safety
+///
+/// `error` must be null or valid for writes.
+unsafe fn finish<T>(result: Result<T>, error: *mut *mut c_char) -> Option<T> {
+    match result {
+        Ok(value) => Some(value),
+        Err(e) => {
+            if !error.is_null() {
+                unsafe { *error = c_string(&format!("{:#}", e)) };
+            }
+            None
+        }
+    }
+}
+
+/// # Safety
+///
+/// `string` must be null or a valid NUL-terminated string.
+unsafe fn str_arg<'a>(string: *const c_char) -> Result<&'a str> {
+    if string.is_null() {
+        return Err(anyhow!("string is null"));
+    }
+    Ok(unsafe { CStr::from_ptr(string) }.to_str()?)
+}
+
+/// Copy `string` into a C string, dropping any NUL bytes it contains.
+fn c_string(string: &str) -> *mut c_char {
+    CString::new(string.replace('\0', ""))
+        .unwrap_or_default()
+        .into_raw()
+}
@@ -0,0 +1,193 @@
+//! Picking out part of a set (`--only`, `--type`, `--rarity`)
+//!
+//! A designer reworking a handful of cards shouldn't have to rebuild the
+//! whole set. A [`CardFilter`] keeps only the cards whose name matches a
+//! glob, whose layout is one of a few and whose rarity is in a range, e.g.
+//! `--only "Grave*" --type planeswalker --rarity rare+`. Every part is
+//! optional; an empty filter keeps every card.
+
+use crate::card::{Card, Rarity};
+
+/// Which cards a run renders or counts
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CardFilter {
+    /// Glob the card's name must match, ignoring case
+    pub name: Option<String>,
+    /// Layouts, by their YAML `type`, the card must be one of
+    pub layouts: Vec<String>,
+    /// Rarities the card must be one of
+    pub rarities: Vec<Rarity>,
+}
+
+impl CardFilter {
+    /// Build a filter from the `--only`, `--type` and `--rarity` arguments.
+    /// `layouts` and `rarities` may list several, separated by commas.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error for an unknown layout or rarity.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mtg_gen::filter::CardFilter;
+    ///
+    /// let filter = CardFilter::parse(Some("Grave*"), Some("planeswalker"), Some("rare+")).unwrap();
+    /// assert_eq!(filter.layouts, vec!["planeswalker"]);
+    /// assert!(CardFilter::parse(None, Some("sorcery"), None).is_err());
+    /// ```
+    #[must_use = "parsing returns a Result that should be handled"]
+    pub fn parse(
+        name: Option<&str>,
+        layouts: Option<&str>,
+        rarities: Option<&str>,
+    ) -> Result<Self, String> {
+        let layouts = layouts
+            .map(|layouts| {
+                layouts
+                    .split(',')
+                    .map(|layout| parse_layout(layout).map(str::to_string))
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .transpose()?
+            .unwrap_or_default();
+        Ok(Self {
+            name: name.map(str::to_string),
+            layouts,
+            rarities: rarities
+                .map(parse_rarities)
+                .transpose()?
+                .unwrap_or_default(),
+        })
+    }
+
+    /// Whether the filter keeps every card
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.name.is_none() && self.layouts.is_empty() && self.rarities.is_empty()
+    }
+
+    /// Whether `card` passes the filter.
+    #[must_use]
+    pub fn matches(&self, card: &Card) -> bool {
+        self.name
+            .as_deref()
+            .is_none_or(|pattern| glob_match(pattern, card.name()))
+            && (self.layouts.is_empty()
+                || self
+                    .layouts
+                    .iter()
+                    .any(|layout| layout == card.layout_name()))
+            && (self.rarities.is_empty() || self.rarities.contains(&card.base().rarity))
+    }
+}
+
+/// Returns the layout named `s`, as written in YAML.
+fn parse_layout(s: &str) -> Result<&'static str, String> {
+    let s = s.trim().to_lowercase();
+    crate::schema::layouts()
+        .find(|layout| *layout == s)
+        .ok_or_else(|| {
+            let layouts: Vec<&str> = crate::schema::layouts().collect();
+            format!(
+                "Unknown card type: {} (expected one of: {})",
+                s,
+                layouts.join(", ")
+            )
+        })
+}
+
+/// Parse a list of rarities separated by commas, e.g. `common,uncommon`. A
+/// main-sheet rarity followed by `+` also takes the rarer ones: `rare+` is
+/// rare and mythic.
+///
+/// # Errors
+///
+/// Returns an error for an unknown rarity, or a `+` after a rarity outside
+/// the main sheet.
+///
+/// # Examples
+///
+/// ```
+/// use mtg_gen::{Rarity, filter::parse_rarities};
+///
+/// assert_eq!(parse_rarities("rare+"), Ok(vec![Rarity::Rare, Rarity::Mythic]));
+/// assert_eq!(parse_rarities("common, land"), Ok(vec![Rarity::Common, Rarity::Land]));
+/// ```
+#[must_use = "parsing returns a Result that should be handled"]
+pub fn parse_rarities(s: &str) -> Result<Vec<Rarity>, String> {
+    const MAIN_SHEET: [Rarity; 4] = [
+        Rarity::Common,
+        Rarity::Uncommon,
+        Rarity::Rare,
+        Rarity::Mythic,
+    ];
+
+    let mut rarities = Vec::new();
+    for part in s.split(',') {
+        let part = part.trim().to_lowercase();
+        let (name, and_rarer) = match part.strip_suffix('+') {
+            Some(name) => (name.trim(), true),
+            None => (part.as_str(), false),
+        };
+        let rarity = Rarity::ALL
+            .into_iter()
+            .find(|rarity| rarity.as_str() == name)
+            .ok_or_else(|| format!("Unknown rarity: {}", name))?;
+        if and_rarer {
+            let start = MAIN_SHEET
+                .iter()
+                .position(|&r| r == rarity)
+                .ok_or_else(|| format!("{}+ isn't a range of rarities", name))?;
+            rarities.extend_from_slice(&MAIN_SHEET[start..]);
+        } else {
+            rarities.push(rarity);
+        }
+    }
+    rarities.dedup();
+    Ok(rarities)
+}
+
+/// Whether `text` matches the glob `pattern`, ignoring case. `*` matches
+/// any run of characters and `?` any one character.
+///
+/// # Examples
+///
+/// ```
+/// use mtg_gen::filter::glob_match;
+///
+/// assert!(glob_match("*bolt", "Lightning Bolt"));
+/// assert!(glob_match("Jace, ?he*", "Jace, the Mind Sculptor"));
+/// assert!(!glob_match("bolt", "Lightning Bolt"));
+/// ```
+#[must_use]
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.to_lowercase().chars().collect();
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+
+    // Where to resume after the last `*`: the pattern after it, and the
+    // text it has swallowed up to
+    let mut star: Option<(usize, usize)> = None;
+    let (mut p, mut t) = (0, 0);
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p + 1, t));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match star {
+                Some((after, swallowed)) => {
+                    p = after;
+                    t = swallowed + 1;
+                    star = Some((after, swallowed + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
@@ -0,0 +1,116 @@
+//! Worn and grunge overlays (`frame_effects`)
+//!
+//! Retro-styled sets can age their cards: each effect in a card's
+//! `frame_effects` is a layer laid over the finished card, art and frame
+//! alike, blended so the card shows through. `worn` rubs the edges and
+//! corners pale, `grunge` mottles the whole card. How strongly each shows is
+//! set with `frame_effects` in the style file, see
+//! [`StyleConfig::frame_effect_opacity`]. The textures are generated, so no
+//! extra assets are needed.
+//!
+//! Every card gets the same textures unless the run has a seed (`--seed`):
+//! then each card's are drawn from the seed and its name, see
+//! [`card_seed`], so cards are scuffed differently but the same seed always
+//! gives the same images.
+
+use crate::card::FrameEffect;
+use crate::style::StyleConfig;
+use maud::{Markup, PreEscaped, html};
+
+/// Noise seeds of the worn and grunge textures without a seed
+const DEFAULT_SEEDS: (u64, u64) = (7, 3);
+
+/// Returns the texture seed of the card named `name` in a run seeded with
+/// `run_seed`. It's a stable hash, so it's the same on every machine and
+/// mtg-gen version.
+///
+/// # Examples
+///
+/// ```
+/// use mtg_gen::frame_effects::card_seed;
+///
+/// assert_eq!(card_seed(42, "Bolt"), card_seed(42, "Bolt"));
+/// assert_ne!(card_seed(42, "Bolt"), card_seed(43, "Bolt"));
+/// assert_ne!(card_seed(42, "Bolt"), card_seed(42, "Shock"));
+/// ```
+#[must_use]
+pub fn card_seed(run_seed: u64, name: &str) -> u64 {
+    // FNV-1a
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in run_seed.to_le_bytes().into_iter().chain(name.bytes()) {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    hash
+}
+
+/// Render a layer for each of `effects`, positioned over the whole card,
+/// with textures drawn from `seed` (see [`card_seed`]), or the built-in
+/// ones without it. Renders nothing when there are none.
+#[must_use]
+pub fn render_frame_effects(
+    effects: &[FrameEffect],
+    style: &StyleConfig,
+    seed: Option<u64>,
+) -> Markup {
+    // Plain integers, which feTurbulence takes as they are
+    let (worn, grunge) = seed.map_or(DEFAULT_SEEDS, |seed| (seed & 0xffff, (seed >> 16) & 0xffff));
+    let css = EFFECT_CSS
+        .replace("WORN_SEED", &worn.to_string())
+        .replace("GRUNGE_SEED", &grunge.to_string());
+    html! {
+        @if !effects.is_empty() {
+            style { (PreEscaped(css)) }
+            @for &effect in effects {
+                div
+                    class=(format!("frame-effect frame-effect-{}", effect.as_str()))
+                    style=(format!("opacity: {};", style.frame_effect_opacity(effect))) {}
+            }
+        }
+    }
+}
+
+/// Returns a rendered card document with layers for `effects` added at the
+/// end of its body, over everything else on the card, see
+/// [`render_frame_effects`].
+#[must_use]
+pub fn with_frame_effects(
+    html: &str,
+    effects: &[FrameEffect],
+    style: &StyleConfig,
+    seed: Option<u64>,
+) -> String {
+    let layers = render_frame_effects(effects, style, seed).into_string();
+    match html.rfind("</body>") {
+        Some(end) => format!("{}{}{}", &html[..end], layers, &html[end..]),
+        None => format!("{}{}", html, layers),
+    }
+}
+
+const EFFECT_CSS: &str = r#"
+    .frame-effect {
+        position: absolute;
+        top: 0;
+        left: 0;
+        width: 744px;
+        height: 1040px;
+        border-radius: 37px;
+        pointer-events: none;
+        z-index: 40;
+    }
+    /* Pale, scuffed edges fading into the card */
+    .frame-effect-worn {
+        mix-blend-mode: screen;
+        background:
+            radial-gradient(ellipse 75% 70% at 50% 50%, transparent 70%, rgba(230, 220, 195, 0.6) 100%),
+            url("data:image/svg+xml;utf8,<svg xmlns='http://www.w3.org/2000/svg' width='744' height='1040'><filter id='f'><feTurbulence type='fractalNoise' baseFrequency='0.035' numOctaves='4' seed='WORN_SEED'/><feColorMatrix values='0 0 0 0 0.9  0 0 0 0 0.87  0 0 0 0 0.8  0 0 0 -9 4.6'/></filter><rect width='100%' height='100%' filter='url(%23f)'/></svg>");
+        box-shadow:
+            inset 0 0 0 5px rgba(235, 228, 205, 0.8),
+            inset 0 0 26px 12px rgba(235, 228, 205, 0.5);
+    }
+    /* Fine, dirty mottling over everything */
+    .frame-effect-grunge {
+        mix-blend-mode: multiply;
+        background: url("data:image/svg+xml;utf8,<svg xmlns='http://www.w3.org/2000/svg' width='744' height='1040'><filter id='f'><feTurbulence type='fractalNoise' baseFrequency='0.9' numOctaves='3' seed='GRUNGE_SEED'/><feColorMatrix values='0 0 0 0 0.35  0 0 0 0 0.28  0 0 0 0 0.2  0 0 0 1.4 -0.3'/></filter><rect width='100%' height='100%' filter='url(%23f)'/></svg>");
+    }
+"#;
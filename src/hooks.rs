@@ -0,0 +1,124 @@
+//! User commands run on each rendered image (`--post-render`)
+//!
+//! The command is a shell command line in which `{output}` is replaced by
+//! the path of the rendered image, e.g. `pngquant --force --ext .png
+//! {output}`. Commands run while later cards are still rendering, with at
+//! most `jobs` of them at a time.
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+/// A post-render command that failed for one image
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HookFailure {
+    /// The image the command ran on
+    pub output: PathBuf,
+    /// The exit status or spawn error
+    pub reason: String,
+    /// What the command wrote to stderr
+    pub stderr: String,
+}
+
+/// Returns the shell command line for `template` run on `output`.
+///
+/// The path is quoted for the platform shell, so paths with spaces or quotes
+/// are passed as a single argument.
+#[must_use]
+pub fn command_line(template: &str, output: &Path) -> String {
+    template.replace("{output}", &shell_quote(&output.display().to_string()))
+}
+
+#[cfg(not(windows))]
+fn shell_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', r"'\''"))
+}
+
+#[cfg(windows)]
+fn shell_quote(s: &str) -> String {
+    format!("\"{}\"", s.replace('"', "\"\""))
+}
+
+#[cfg(not(windows))]
+fn shell_command(command_line: &str) -> tokio::process::Command {
+    let mut command = tokio::process::Command::new("sh");
+    command.arg("-c").arg(command_line).kill_on_drop(true);
+    command
+}
+
+#[cfg(windows)]
+fn shell_command(command_line: &str) -> tokio::process::Command {
+    let mut command = tokio::process::Command::new("cmd");
+    command.arg("/C").arg(command_line).kill_on_drop(true);
+    command
+}
+
+/// Runs a post-render command on each image as it's rendered.
+///
+/// Dropping the hook without [`PostRenderHook::finish`] kills any commands
+/// still running.
+pub struct PostRenderHook {
+    template: String,
+    permits: Arc<Semaphore>,
+    tasks: JoinSet<Result<(), HookFailure>>,
+}
+
+impl PostRenderHook {
+    /// A hook running `template` on at most `jobs` images at a time.
+    #[must_use]
+    pub fn new(template: impl Into<String>, jobs: usize) -> Self {
+        Self {
+            template: template.into(),
+            permits: Arc::new(Semaphore::new(jobs.max(1))),
+            tasks: JoinSet::new(),
+        }
+    }
+
+    /// Queue the command for `output`. It starts as soon as a job slot is
+    /// free.
+    pub fn run(&mut self, output: PathBuf) {
+        let command_line = command_line(&self.template, &output);
+        let permits = Arc::clone(&self.permits);
+        self.tasks.spawn(async move {
+            let _permit = permits
+                .acquire_owned()
+                .await
+                .expect("the semaphore is never closed");
+            let failure = |reason: String, stderr: String| HookFailure {
+                output: output.clone(),
+                reason,
+                stderr,
+            };
+
+            match shell_command(&command_line).output().await {
+                Ok(result) if result.status.success() => Ok(()),
+                Ok(result) => Err(failure(
+                    result.status.to_string(),
+                    String::from_utf8_lossy(&result.stderr).trim().to_string(),
+                )),
+                Err(e) => Err(failure(format!("failed to start: {}", e), String::new())),
+            }
+        });
+    }
+
+    /// Wait for every queued command, returning the number that ran and the
+    /// ones that failed.
+    pub async fn finish(mut self) -> (usize, Vec<HookFailure>) {
+        let mut ran = 0;
+        let mut failures = Vec::new();
+        while let Some(result) = self.tasks.join_next().await {
+            ran += 1;
+            match result {
+                Ok(Ok(())) => {}
+                Ok(Err(failure)) => failures.push(failure),
+                Err(e) => failures.push(HookFailure {
+                    output: PathBuf::new(),
+                    reason: format!("hook task failed: {}", e),
+                    stderr: String::new(),
+                }),
+            }
+        }
+        (ran, failures)
+    }
+}
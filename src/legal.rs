@@ -0,0 +1,132 @@
+//! The legal line along the bottom of a card (`legal` in the style file)
+//!
+//! Printed cards carry a line like `™ & © 2026 Wizards of the Coast` in the
+//! bottom-right corner of the border. A set's style file gives the line as
+//! a template, see [`LegalStyle`], optionally one per set code; `{year}`,
+//! `{studio}` and `{set}` are filled in for each card. Proxies are marked
+//! `NOT FOR SALE` on the same line, whether or not there's a template.
+
+use crate::style::LegalStyle;
+use maud::{Markup, PreEscaped, html};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// What proxies are marked with
+pub const NOT_FOR_SALE: &str = "NOT FOR SALE";
+
+impl LegalStyle {
+    /// Returns the template for a card of the set `set_code`: the set's own,
+    /// or the default one.
+    #[must_use]
+    pub fn template_for(&self, set_code: Option<&str>) -> Option<&str> {
+        set_code
+            .and_then(|code| {
+                self.sets
+                    .iter()
+                    .find(|(set, _)| set.eq_ignore_ascii_case(code))
+                    .map(|(_, template)| template.as_str())
+            })
+            .or(self.template.as_deref())
+    }
+
+    /// Fill `template`'s `{year}`, `{studio}` and `{set}` in for a card of
+    /// the set `set_code`. `{year}` defaults to `current_year`, and the
+    /// others to nothing.
+    #[must_use]
+    pub fn fill(&self, template: &str, set_code: Option<&str>, current_year: u32) -> String {
+        template
+            .replace("{year}", &self.year.unwrap_or(current_year).to_string())
+            .replace("{studio}", self.studio.as_deref().unwrap_or_default())
+            .replace("{set}", set_code.unwrap_or_default())
+            .trim()
+            .to_string()
+    }
+}
+
+/// Returns the legal line of a card of the set `set_code`, or `None` when
+/// it has none: the filled-in template from `style`, followed by
+/// [`NOT_FOR_SALE`] when `not_for_sale`.
+///
+/// # Examples
+///
+/// ```
+/// use mtg_gen::legal::legal_line;
+/// use mtg_gen::style::LegalStyle;
+///
+/// let style = LegalStyle {
+///     template: Some("™ & © {year} {studio}".to_string()),
+///     studio: Some("Cramt Games".to_string()),
+///     ..LegalStyle::default()
+/// };
+/// assert_eq!(
+///     legal_line(Some(&style), None, false, 2026).as_deref(),
+///     Some("™ & © 2026 Cramt Games")
+/// );
+/// assert_eq!(legal_line(None, None, true, 2026).as_deref(), Some("NOT FOR SALE"));
+/// assert_eq!(legal_line(None, None, false, 2026), None);
+/// ```
+#[must_use]
+pub fn legal_line(
+    style: Option<&LegalStyle>,
+    set_code: Option<&str>,
+    not_for_sale: bool,
+    current_year: u32,
+) -> Option<String> {
+    let text = style.and_then(|style| {
+        style
+            .template_for(set_code)
+            .map(|template| style.fill(template, set_code, current_year))
+    });
+    match (text, not_for_sale) {
+        (Some(text), true) if !text.is_empty() => Some(format!("{} — {}", text, NOT_FOR_SALE)),
+        (_, true) => Some(NOT_FOR_SALE.to_string()),
+        (text, false) => text.filter(|text| !text.is_empty()),
+    }
+}
+
+/// Returns the current year, in UTC.
+#[must_use]
+pub fn current_year() -> u32 {
+    let days = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() / 86_400)
+        .unwrap_or_default();
+    let (year, _, _) = crate::utils::civil_from_days(days as i64);
+    u32::try_from(year).unwrap_or_default()
+}
+
+/// Render the legal line, positioned in the bottom-right corner of the
+/// border.
+#[must_use]
+pub fn render_legal_line(text: &str) -> Markup {
+    html! {
+        style { (PreEscaped(LEGAL_CSS)) }
+        div.legal-line { (text) }
+    }
+}
+
+/// Returns a rendered card document with the legal line `text` added at the
+/// end of its body.
+#[must_use]
+pub fn with_legal_line(html: &str, text: &str) -> String {
+    let line = render_legal_line(text).into_string();
+    match html.rfind("</body>") {
+        Some(end) => format!("{}{}{}", &html[..end], line, &html[end..]),
+        None => format!("{}{}", html, line),
+    }
+}
+
+const LEGAL_CSS: &str = r#"
+    .legal-line {
+        position: absolute;
+        bottom: 16px;
+        right: 30px;
+        max-width: 400px;
+        font-size: 10px;
+        font-family: 'MPlantin', serif;
+        color: #fff;
+        text-align: right;
+        white-space: nowrap;
+        overflow: hidden;
+        z-index: 25;
+    }
+"#;
@@ -0,0 +1,106 @@
+//! Symbol reference cards (`mtg-gen legend`)
+//!
+//! A set that leans on unusual symbols, like energy, chaos or half mana, can
+//! ship with reference cards explaining them. Each legend card lists symbols
+//! next to their meaning, and a long list is split over as many cards as it
+//! needs.
+
+use crate::mana::{ManaSymbol, RulesText, RulesTextSegment};
+use crate::symbology::{self, SYMBOLOGY};
+use anyhow::{Result, bail};
+
+/// Symbols listed on one legend card, in two columns
+pub const SYMBOLS_PER_CARD: usize = 24;
+
+/// One symbol on a legend card
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LegendEntry {
+    pub symbol: ManaSymbol,
+    /// What the symbol means, from Scryfall's description of it
+    pub meaning: String,
+}
+
+impl LegendEntry {
+    /// The entry for `symbol`, explained by its symbology description.
+    /// Symbols without one, like large generic amounts, are explained by
+    /// their code.
+    #[must_use]
+    pub fn new(symbol: ManaSymbol) -> Self {
+        let meaning = symbology::lookup(symbol)
+            .map(|entry| entry.english.to_string())
+            .unwrap_or_else(|| symbol.to_string());
+        Self { symbol, meaning }
+    }
+}
+
+/// One reference card of a legend
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SymbolLegend {
+    pub entries: Vec<LegendEntry>,
+    /// Which card of the legend this is, from 1
+    pub page: usize,
+    /// How many cards the legend takes
+    pub pages: usize,
+}
+
+impl SymbolLegend {
+    /// Returns the card's page label, e.g. `2 / 4`, or `None` when the
+    /// legend fits on one card.
+    #[must_use]
+    pub fn page_label(&self) -> Option<String> {
+        (self.pages > 1).then(|| format!("{} / {}", self.page, self.pages))
+    }
+}
+
+/// Returns an entry for every symbol mtg-gen can render, in the symbology
+/// table's order.
+#[must_use]
+pub fn supported_symbols() -> Vec<LegendEntry> {
+    let mut symbols: Vec<ManaSymbol> = Vec::new();
+    for symbol in SYMBOLOGY.iter().filter_map(|entry| entry.parse().ok()) {
+        // Some symbols are listed under more than one code
+        if !symbols.contains(&symbol) {
+            symbols.push(symbol);
+        }
+    }
+    symbols.into_iter().map(LegendEntry::new).collect()
+}
+
+/// Parse symbols written as in card text, e.g. `{E}{CHAOS}{H}`, into
+/// entries in the order given.
+///
+/// # Errors
+///
+/// Returns an error if a symbol can't be parsed, or there is text between
+/// the symbols.
+pub fn parse_symbols(codes: &str) -> Result<Vec<LegendEntry>> {
+    let text = RulesText::parse(codes).map_err(|e| anyhow::anyhow!("{}", e))?;
+    let mut entries: Vec<LegendEntry> = Vec::new();
+    for segment in text.segments {
+        match segment {
+            RulesTextSegment::Symbol(symbol) => {
+                if !entries.iter().any(|entry| entry.symbol == symbol) {
+                    entries.push(LegendEntry::new(symbol));
+                }
+            }
+            RulesTextSegment::Text(text) if text.trim().is_empty() => {}
+            _ => bail!("Expected only symbols, like {{E}}{{CHAOS}}: {:?}", codes),
+        }
+    }
+    Ok(entries)
+}
+
+/// Split `entries` into legend cards of at most [`SYMBOLS_PER_CARD`] each.
+#[must_use]
+pub fn legend_cards(entries: Vec<LegendEntry>) -> Vec<SymbolLegend> {
+    let pages = entries.len().div_ceil(SYMBOLS_PER_CARD);
+    entries
+        .chunks(SYMBOLS_PER_CARD)
+        .enumerate()
+        .map(|(i, chunk)| SymbolLegend {
+            entries: chunk.to_vec(),
+            page: i + 1,
+            pages,
+        })
+        .collect()
+}
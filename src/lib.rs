@@ -1,24 +1,88 @@
+pub mod alt_text;
+#[cfg(feature = "remote-art")]
+pub mod art_cache;
+pub mod art_crop;
 pub mod card;
+pub mod cockatrice;
+pub mod collector;
+pub mod color_chips;
+pub mod decklist;
+pub mod doctor;
+pub mod editor;
+pub mod examples;
+pub mod extract;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod filter;
+pub mod frame_effects;
+#[cfg(feature = "cli")]
+pub mod hooks;
+pub mod legal;
+pub mod legend;
 pub mod mana;
+#[cfg(feature = "cli")]
+pub mod optimize;
+pub mod order;
+pub mod output;
+#[cfg(feature = "cli")]
+pub mod package;
+pub mod playtest;
+pub mod print;
+pub mod profile;
+pub mod progress;
+pub mod provenance;
 pub mod render;
+#[cfg(feature = "cli")]
+pub mod render_cache;
+pub mod schema;
+pub mod scryfall;
+#[cfg(feature = "cli")]
+pub mod serve;
+pub mod set_defaults;
+pub mod stats;
+pub mod style;
+pub mod symbology;
+pub mod testcard;
+#[cfg(feature = "upload")]
+pub mod upload;
 pub mod utils;
+pub mod yaml;
 
 // Re-export main types from card module
 pub use card::{
-    AdventureCard, AdventureSpell, BattleCard, Card, CardBase, CardFace, ClassCard, ClassLevel,
-    FlipCard, LevelerCard, LevelerRange, LoyaltyAbility, MeldCard, ModalDfcCard, NormalCard,
-    PlaneswalkerCard, PrototypeCard, Rarity, SagaCard, SagaChapter, SplitCard, TransformCard,
+    AdventureCard, AdventureSpell, ArtFit, BattleCard, Card, CardBase, CardFace, CardSize,
+    CardValidationError, ClassCard, ClassLevel, DfcIcons, FlipCard, FrameColors, FrameEffect,
+    LevelRange, LevelRangeProxy, LevelerCard, LevelerRange, LoyaltyAbility, MeldCard, ModalDfcCard,
+    NormalCard, PlaneswalkerCard, PrototypeCard, Rarity, ReminderTextMode, SagaCard, SagaChapter,
+    SplitCard, TokenCard, TransformCard, Translation,
 };
 
 // Re-export mana types
 pub use mana::{
     ActionCost, ActionCostProxy, CastingManaCost, CastingManaCostProxy, CastingManaSymbol,
-    LoyaltyCost, LoyaltyCostProxy, LoyaltyValue, LoyaltyValueProxy, ManaCostParseError, ManaSymbol,
-    RulesText, RulesTextProxy, RulesTextSegment,
+    CustomMana, LoyaltyCost, LoyaltyCostProxy, LoyaltyValue, LoyaltyValueProxy, ManaCostParseError,
+    ManaSymbol, ReminderSegment, RulesText, RulesTextProxy, RulesTextSegment,
 };
 
+// Re-export extracted game objects
+pub use extract::{Emblem, FaceDown, FaceDownKind, extract_emblems, extract_face_down};
+pub use legend::{LegendEntry, SymbolLegend};
+pub use playtest::{PlaytestCard, PlaytestFace};
+
+// Re-export output naming and manifest types
+pub use output::{
+    ImageFormat, LOCK_FILE_NAME, MANIFEST_FILE_NAME, ManifestEntry, ManifestFailure,
+    ManifestOutput, OutputKind, OutputLock, OutputNaming, RenderManifest,
+};
+pub use profile::RenderTimings;
+
+// Re-export symbology snapshot
+pub use symbology::{SYMBOLOGY, SymbologyEntry};
+
 // Re-export renderer and rendering utilities
-pub use render::{RenderableCard, Renderer};
+pub use render::{BackFace, CardHtml, RenderableCard, back_face};
+#[cfg(feature = "browser")]
+pub use render::{BrowserPreset, RenderOutcome, RenderedImage, Renderer};
 
 // Re-export utilities
-pub use utils::sanitize_card_name;
+pub use utils::{sanitize_card_name, to_roman_numeral};
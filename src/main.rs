@@ -1,7 +1,7 @@
 use facet::Facet;
+use futures::StreamExt;
 use mtg_gen::*;
 use std::path::{Path, PathBuf};
-use walkdir::WalkDir;
 
 #[derive(Facet, Debug)]
 struct Args {
@@ -16,62 +16,1999 @@ struct Args {
     /// DPI for output images (300 or 600)
     #[facet(facet_args::named, default = 300)]
     dpi: u32,
+
+    /// Image format to render cards to: png (default), jpeg or webp
+    #[facet(facet_args::named, default)]
+    format: Option<String>,
+
+    /// Quality JPEG and WebP images are encoded at, from 0 to 100
+    #[facet(facet_args::named, default = 90)]
+    quality: u8,
+
+    /// Size of cards that don't set their own: standard (default), mini or jumbo
+    #[facet(facet_args::named, default)]
+    size: Option<String>,
+
+    /// Add print bleed for MakePlayingCards: 816×1110 images with the black border extended
+    #[facet(facet_args::named, default)]
+    bleed: bool,
+
+    /// Also lay the rendered images out 3×3 on the pages of this printable PDF
+    #[facet(facet_args::named, default)]
+    pdf_sheet: Option<PathBuf>,
+
+    /// Paper size of the --pdf-sheet: a4 (default) or letter
+    #[facet(facet_args::named, default)]
+    paper: Option<String>,
+
+    /// Draw thin cut lines between the cards of the --pdf-sheet
+    #[facet(facet_args::named, default)]
+    cut_lines: bool,
+
+    /// Also render emblems created by planeswalker abilities
+    #[facet(facet_args::named, default)]
+    generate_emblems: bool,
+
+    /// Add a lettered chip for each color below the title bar, for colorblind players
+    #[facet(facet_args::named, default)]
+    color_chips: bool,
+
+    /// Print the rarity as a letter on the collector line instead of the rarity symbol
+    #[facet(facet_args::named, default)]
+    rarity_letter: bool,
+
+    /// Enlarge rules and flavor text for visually impaired players (tune it with `large_print` in the style file)
+    #[facet(facet_args::named, default)]
+    large_print: bool,
+
+    /// Fail cards with images or fonts that don't load, instead of warning
+    #[facet(facet_args::named, default)]
+    strict: bool,
+
+    /// Render plain black-and-white text-only cards for playtesting, without frames or art
+    #[facet(facet_args::named, default)]
+    playtest: bool,
+
+    /// YAML file of style overrides, such as inline symbol sizes
+    #[facet(facet_args::named, default)]
+    style: Option<PathBuf>,
+
+    /// Write the part of art its art box crops out next to it, as name.crop.png (needs the `art-crops` feature)
+    #[facet(facet_args::named, default)]
+    write_art_crops: bool,
+
+    /// Write a screen-reader description next to each image (txt or json)
+    #[facet(facet_args::named, default)]
+    alt_text: Option<String>,
+
+    /// Write each card's data and image paths to name.card.json beside its images, for static sites
+    #[facet(facet_args::named, default)]
+    card_json: bool,
+
+    /// File name template for card faces ({name}, {face}, {index})
+    #[facet(facet_args::named, default = default_face_template())]
+    face_template: String,
+
+    /// File name template for emblems ({name}, {face}, {index})
+    #[facet(facet_args::named, default = default_emblem_template())]
+    emblem_template: String,
+
+    /// Put each kind of output (cards, backs, emblems) in its own subfolder
+    #[facet(facet_args::named, default)]
+    kind_subfolders: bool,
+
+    /// Bundle the rendered images and manifest into an archive (zip or tar.gz)
+    #[facet(facet_args::named, default)]
+    package: Option<String>,
+
+    /// Only render cards whose name matches this glob, e.g. "Grave*"
+    #[facet(facet_args::named, default)]
+    only: Option<String>,
+
+    /// Only render cards of these layouts, e.g. planeswalker or saga,transform
+    #[facet(facet_args::named, default, rename = "type")]
+    layout: Option<String>,
+
+    /// Only render cards of these rarities, e.g. rare+ or common,uncommon
+    #[facet(facet_args::named, default)]
+    rarity: Option<String>,
+
+    /// Order cards are processed in: path (default), name or collector
+    #[facet(facet_args::named, default)]
+    order: Option<String>,
+
+    /// Number the cards in canonical order, writing the numbers into their YAML files
+    #[facet(facet_args::named, default)]
+    assign_collector_numbers: bool,
+
+    /// Number a directory's cards in canonical order for this render only, listed in numbering.csv
+    #[facet(facet_args::named, default)]
+    auto_number: bool,
+
+    /// Set code used to name the archive (defaults to the output directory name)
+    #[facet(facet_args::named, default)]
+    set_code: Option<String>,
+
+    /// Upload outputs to s3://bucket/prefix or an http(s) URL (needs the `upload` feature)
+    #[facet(facet_args::named, default)]
+    upload: Option<String>,
+
+    /// Base URL the uploaded files are publicly served from
+    #[facet(facet_args::named, default)]
+    public_url: Option<String>,
+
+    /// Losslessly recompress each rendered PNG to make it smaller
+    #[facet(facet_args::named, default)]
+    optimize: bool,
+
+    /// Where art downloaded from URLs is cached (defaults to ~/.cache/mtg-gen/art)
+    #[facet(facet_args::named, default)]
+    art_cache: Option<PathBuf>,
+
+    /// Write the set's git commit and the mtg-gen version into each PNG
+    #[facet(facet_args::named, default)]
+    provenance_metadata: bool,
+
+    /// Vary generated textures, like worn frames, card by card, the same way for the same seed
+    #[facet(facet_args::named, default)]
+    seed: Option<u64>,
+
+    /// Print a timing and memory report for the rendering pipeline at the end
+    #[facet(facet_args::named, default)]
+    profile: bool,
+
+    /// Time rendering each card several times instead of writing any outputs
+    #[facet(facet_args::named, default)]
+    bench_render: bool,
+
+    /// Number of cards rendered at once, each in its own Chrome page
+    #[facet(facet_args::named, default = 1)]
+    jobs: usize,
+
+    /// Restart Chrome after this many cards to keep its memory use bounded
+    #[facet(facet_args::named, default = 200)]
+    recycle_every: usize,
+
+    /// Lay out this many cards rendered at once on one Chrome page and crop them apart (needs the `megapage` feature)
+    #[facet(facet_args::named, default = 1)]
+    cards_per_page: usize,
+
+    /// Launch Chrome with the flags for docker, wsl, macos or low-memory
+    #[facet(facet_args::named, default)]
+    browser_preset: Option<String>,
+
+    /// Extra Chromium flags to launch Chrome with, separated by spaces
+    #[facet(facet_args::named, default)]
+    browser_arg: Option<String>,
+
+    /// Give up on a card whose page takes longer than this many seconds to render
+    #[facet(facet_args::named, default = 60)]
+    card_timeout: u64,
+
+    /// Skip cards already rendered by an interrupted run into the same output directory
+    #[facet(facet_args::named, default)]
+    resume: bool,
+
+    /// Skip cards whose YAML, art and render settings haven't changed since they were last rendered
+    #[facet(facet_args::named, default)]
+    skip_existing: bool,
+
+    /// Shell command to run on each rendered image, e.g. 'pngquant --ext .png --force {output}'
+    #[facet(facet_args::named, default)]
+    post_render: Option<String>,
+
+    /// How many post-render commands may run at once (defaults to the number of CPUs)
+    #[facet(facet_args::named, default)]
+    post_render_jobs: Option<usize>,
 }
 
 fn default_output() -> PathBuf {
     PathBuf::from("./output")
 }
 
+fn default_face_template() -> String {
+    OutputNaming::default().face_template
+}
+
+fn default_emblem_template() -> String {
+    OutputNaming::default().emblem_template
+}
+
+impl Args {
+    fn naming(&self) -> OutputNaming {
+        OutputNaming {
+            face_template: self.face_template.clone(),
+            emblem_template: self.emblem_template.clone(),
+            subfolders: self.kind_subfolders,
+        }
+    }
+
+    /// Returns the settings that change how cards' images come out, for
+    /// `--skip-existing`: a card last rendered with other settings, or
+    /// other `set` defaults, is rendered again.
+    fn render_settings(&self, set: &set_defaults::SetDefaults) -> anyhow::Result<String> {
+        let style = match &self.style {
+            Some(path) => std::fs::read_to_string(path)
+                .map_err(|e| anyhow::anyhow!("Failed to read style {}: {}", path.display(), e))?,
+            None => String::new(),
+        };
+        Ok(format!(
+            "dpi={} format={:?} quality={} size={:?} bleed={} color_chips={} rarity_letter={} \
+             playtest={} emblems={} alt_text={:?} face={:?} emblem={:?} subfolders={} \
+             optimize={} provenance={} large_print={} seed={:?} style={:?} set={:?}",
+            self.dpi,
+            self.format,
+            self.quality,
+            self.size,
+            self.bleed,
+            self.color_chips,
+            self.rarity_letter,
+            self.playtest,
+            self.generate_emblems,
+            self.alt_text,
+            self.face_template,
+            self.emblem_template,
+            self.kind_subfolders,
+            self.optimize,
+            self.provenance_metadata,
+            self.large_print,
+            self.seed,
+            style,
+            set
+        ))
+    }
+
+    fn image_format(&self) -> anyhow::Result<ImageFormat> {
+        Ok(self
+            .format
+            .as_deref()
+            .map(ImageFormat::parse)
+            .transpose()
+            .map_err(anyhow::Error::msg)?
+            .unwrap_or_default())
+    }
+
+    /// The Chromium flags from `--browser-preset` and then `--browser-arg`
+    fn browser_args(&self) -> anyhow::Result<Vec<String>> {
+        let preset = self
+            .browser_preset
+            .as_deref()
+            .map(BrowserPreset::parse)
+            .transpose()
+            .map_err(anyhow::Error::msg)?;
+        let preset_args = preset.map(BrowserPreset::args).unwrap_or_default();
+        let extra_args = self.browser_arg.as_deref().unwrap_or_default();
+        Ok(preset_args
+            .iter()
+            .map(|arg| arg.to_string())
+            .chain(extra_args.split_whitespace().map(String::from))
+            .collect())
+    }
+
+    fn filter(&self) -> anyhow::Result<filter::CardFilter> {
+        filter::CardFilter::parse(
+            self.only.as_deref(),
+            self.layout.as_deref(),
+            self.rarity.as_deref(),
+        )
+        .map_err(anyhow::Error::msg)
+    }
+
+    fn alt_text_format(&self) -> anyhow::Result<Option<alt_text::AltTextFormat>> {
+        self.alt_text
+            .as_deref()
+            .map(alt_text::AltTextFormat::parse)
+            .transpose()
+            .map_err(anyhow::Error::msg)
+    }
+}
+
+/// Arguments for `mtg-gen example`
+#[derive(Facet, Debug)]
+struct ExampleArgs {
+    /// Card layout to write an example for (normal, planeswalker, saga, ...)
+    #[facet(facet_args::named, rename = "type")]
+    layout: String,
+
+    /// Where to write the example (defaults to ./<type>.yaml)
+    #[facet(facet_args::named, facet_args::short = 'o', default)]
+    output: Option<PathBuf>,
+}
+
+/// Arguments for `mtg-gen edit`
+#[cfg(feature = "edit")]
+#[derive(Facet, Debug)]
+struct EditArgs {
+    /// Card YAML file to edit (created if it doesn't exist)
+    #[facet(facet_args::positional)]
+    file: PathBuf,
+
+    /// Card layout for a new file (defaults to normal)
+    #[facet(facet_args::named, rename = "type", default)]
+    layout: Option<String>,
+}
+
+/// Arguments for `mtg-gen schema`
+#[derive(Facet, Debug)]
+struct SchemaArgs {
+    /// Only emit the schema for this card layout (normal, planeswalker, ...)
+    #[facet(facet_args::named, rename = "type", default)]
+    layout: Option<String>,
+
+    /// Write the schema to a file instead of stdout
+    #[facet(facet_args::named, facet_args::short = 'o', default)]
+    output: Option<PathBuf>,
+}
+
+/// Arguments for `mtg-gen proxy`
+#[cfg(feature = "proxy")]
+#[derive(Facet, Debug)]
+struct ProxyArgs {
+    /// Decklist with one card per line, e.g. "4 Lightning Bolt"
+    #[facet(facet_args::positional)]
+    decklist: PathBuf,
+
+    /// Where to write the printable PDF
+    #[facet(facet_args::named, default = default_proxy_pdf())]
+    pdf: PathBuf,
+
+    /// Directory for the rendered card images
+    #[facet(facet_args::named, facet_args::short = 'o', default = default_proxy_output())]
+    output: PathBuf,
+
+    /// Paper size: a4 (default) or letter
+    #[facet(facet_args::named, default)]
+    paper: Option<String>,
+
+    /// Card size: standard (default), mini or jumbo
+    #[facet(facet_args::named, default)]
+    size: Option<String>,
+
+    /// Add a lettered chip for each color below the title bar, for colorblind players
+    #[facet(facet_args::named, default)]
+    color_chips: bool,
+
+    /// Print the rarity as a letter on the collector line instead of the rarity symbol
+    #[facet(facet_args::named, default)]
+    rarity_letter: bool,
+
+    /// Enlarge rules and flavor text for visually impaired players (tune it with `large_print` in the style file)
+    #[facet(facet_args::named, default)]
+    large_print: bool,
+
+    /// Fail cards with images or fonts that don't load, instead of warning
+    #[facet(facet_args::named, default)]
+    strict: bool,
+
+    /// YAML file of style overrides, such as inline symbol sizes
+    #[facet(facet_args::named, default)]
+    style: Option<PathBuf>,
+
+    /// Directory of card YAML files whose cards are used instead of fetching them from Scryfall
+    #[facet(facet_args::named, default)]
+    cards: Option<PathBuf>,
+}
+
+/// Arguments for `mtg-gen fetch`
+#[cfg(feature = "proxy")]
+#[derive(Facet, Debug)]
+struct FetchArgs {
+    /// Card to fetch, e.g. "Lightning Bolt" or "Counterspell (MH2) 267" for a printing
+    #[facet(facet_args::positional)]
+    name: String,
+
+    /// Where to write the image (defaults to the card's name, in the current directory)
+    #[facet(facet_args::named, facet_args::short = 'o', default)]
+    output: Option<PathBuf>,
+
+    /// Also write the imported card as YAML, to check or edit the import
+    #[facet(facet_args::named, default)]
+    yaml: Option<PathBuf>,
+
+    /// Card size: standard (default), mini or jumbo
+    #[facet(facet_args::named, default)]
+    size: Option<String>,
+
+    /// YAML file of style overrides, such as inline symbol sizes
+    #[facet(facet_args::named, default)]
+    style: Option<PathBuf>,
+}
+
+#[cfg(feature = "proxy")]
+fn default_proxy_pdf() -> PathBuf {
+    PathBuf::from("proxies.pdf")
+}
+
+#[cfg(feature = "proxy")]
+fn default_proxy_output() -> PathBuf {
+    PathBuf::from("./proxies")
+}
+
+/// Arguments for `mtg-gen provenance`
+#[derive(Facet, Debug)]
+struct ProvenanceArgs {
+    /// A PNG rendered with --provenance-metadata
+    #[facet(facet_args::positional)]
+    image: PathBuf,
+}
+
+/// Arguments for `mtg-gen stats`
+#[derive(Facet, Debug)]
+struct StatsArgs {
+    /// Path to a YAML file or directory containing YAML files
+    #[facet(facet_args::positional)]
+    input: PathBuf,
+
+    /// Also print the signpost uncommons and commons of each two-color pair
+    #[facet(facet_args::named, default)]
+    archetypes: bool,
+
+    /// Only count cards whose name matches this glob
+    #[facet(facet_args::named, default)]
+    only: Option<String>,
+
+    /// Only count cards of these layouts
+    #[facet(facet_args::named, default, rename = "type")]
+    layout: Option<String>,
+
+    /// Only count cards of these rarities, e.g. rare+
+    #[facet(facet_args::named, default)]
+    rarity: Option<String>,
+}
+
+/// Arguments for `mtg-gen export cockatrice`
+#[derive(Facet, Debug)]
+struct ExportArgs {
+    /// Path to a YAML file or directory containing YAML files
+    #[facet(facet_args::positional)]
+    input: PathBuf,
+
+    /// Where to write the set
+    #[facet(facet_args::named, facet_args::short = 'o', default = default_cockatrice_output())]
+    output: PathBuf,
+
+    /// Set code (defaults to the cards' set_code, or the input directory name)
+    #[facet(facet_args::named, default)]
+    set_code: Option<String>,
+
+    /// Full name of the set (defaults to the set code)
+    #[facet(facet_args::named, default)]
+    set_name: Option<String>,
+
+    /// URL the rendered images are served from, put before each image's path
+    #[facet(facet_args::named, default)]
+    image_url: Option<String>,
+
+    /// Image format the cards were rendered to: png (default), jpeg or webp
+    #[facet(facet_args::named, default)]
+    format: Option<String>,
+
+    /// File name template the card faces were rendered with ({name}, {face}, {index})
+    #[facet(facet_args::named, default = default_face_template())]
+    face_template: String,
+
+    /// Whether the cards were rendered with --kind-subfolders
+    #[facet(facet_args::named, default)]
+    kind_subfolders: bool,
+
+    /// Only export cards whose name matches this glob
+    #[facet(facet_args::named, default)]
+    only: Option<String>,
+
+    /// Only export cards of these layouts
+    #[facet(facet_args::named, default, rename = "type")]
+    layout: Option<String>,
+
+    /// Only export cards of these rarities, e.g. rare+
+    #[facet(facet_args::named, default)]
+    rarity: Option<String>,
+}
+
+fn default_cockatrice_output() -> PathBuf {
+    PathBuf::from("cards.xml")
+}
+
+/// Arguments for `mtg-gen legend`
+#[derive(Facet, Debug)]
+struct LegendArgs {
+    /// Only list these symbols, written as in card text, e.g. "{E}{CHAOS}"
+    #[facet(facet_args::named, default)]
+    symbols: Option<String>,
+
+    /// Directory for the legend card images
+    #[facet(facet_args::named, facet_args::short = 'o', default = default_legend_output())]
+    output: PathBuf,
+}
+
+/// Arguments for `mtg-gen serve`
+#[derive(Facet, Debug)]
+struct ServeArgs {
+    /// Directory containing the set's YAML files
+    #[facet(facet_args::positional)]
+    input: PathBuf,
+
+    /// Address to listen on; use 0.0.0.0 to let others on the network in
+    #[facet(facet_args::named, default = default_serve_host())]
+    host: String,
+
+    /// Port to listen on
+    #[facet(facet_args::named, default = 8080)]
+    port: u16,
+
+    /// Number of cards to render at once
+    #[facet(facet_args::named, facet_args::short = 'j', default = 2)]
+    jobs: usize,
+
+    /// YAML file of style overrides, such as inline symbol sizes
+    #[facet(facet_args::named, default)]
+    style: Option<PathBuf>,
+}
+
+fn default_serve_host() -> String {
+    "127.0.0.1".to_string()
+}
+
+fn default_legend_output() -> PathBuf {
+    PathBuf::from("./legend")
+}
+
+/// Arguments for `mtg-gen testcard`
+#[derive(Facet, Debug)]
+struct TestCardArgs {
+    /// Where to write the image
+    #[facet(facet_args::named, facet_args::short = 'o', default = default_testcard_output())]
+    output: PathBuf,
+
+    /// YAML file of style overrides, such as inline symbol sizes
+    #[facet(facet_args::named, default)]
+    style: Option<PathBuf>,
+}
+
+fn default_testcard_output() -> PathBuf {
+    PathBuf::from("./testcard.png")
+}
+
+/// Arguments for `mtg-gen symbols sync`
+#[cfg(feature = "symbols-sync")]
+#[derive(Facet, Debug)]
+struct SymbolsSyncArgs {
+    /// Write a regenerated symbology table to this path
+    #[facet(facet_args::named, default)]
+    emit: Option<PathBuf>,
+}
+
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-    let args: Args = facet_args::from_std_args()?;
+    let raw_args: Vec<String> = std::env::args().skip(1).collect();
+
+    match raw_args.first().map(String::as_str) {
+        #[cfg(feature = "symbols-sync")]
+        Some("symbols") => match raw_args.get(1).map(String::as_str) {
+            Some("sync") => symbols_sync(parse_subcommand_args(&raw_args[2..])?).await,
+            _ => anyhow::bail!("Usage: mtg-gen symbols sync [--emit <path>]"),
+        },
+        Some("doctor") => doctor().await,
+        #[cfg(feature = "edit")]
+        Some("edit") => edit(parse_subcommand_args(&raw_args[1..])?).await,
+        #[cfg(feature = "proxy")]
+        Some("proxy") => proxy(parse_subcommand_args(&raw_args[1..])?).await,
+        #[cfg(feature = "proxy")]
+        Some("fetch") => fetch(parse_subcommand_args(&raw_args[1..])?).await,
+        Some("example") => write_example(parse_subcommand_args(&raw_args[1..])?).await,
+        Some("schema") => write_schema(parse_subcommand_args(&raw_args[1..])?).await,
+        Some("stats") => print_stats(parse_subcommand_args(&raw_args[1..])?).await,
+        Some("export") => match raw_args.get(1).map(String::as_str) {
+            Some("cockatrice") => export_cockatrice(parse_subcommand_args(&raw_args[2..])?).await,
+            _ => anyhow::bail!("Usage: mtg-gen export cockatrice <input> [-o cards.xml]"),
+        },
+        Some("provenance") => print_provenance(parse_subcommand_args(&raw_args[1..])?),
+        Some("legend") => render_legend(parse_subcommand_args(&raw_args[1..])?).await,
+        Some("testcard") => render_test_card(parse_subcommand_args(&raw_args[1..])?).await,
+        Some("serve") => serve(parse_subcommand_args(&raw_args[1..])?).await,
+        _ => match render(facet_args::from_std_args()?).await {
+            Err(e) if e.is::<Interrupted>() => std::process::exit(INTERRUPTED_EXIT_CODE),
+            result => result,
+        },
+    }
+}
+
+/// Parse the arguments that follow a subcommand name.
+fn parse_subcommand_args<T: Facet<'static>>(args: &[String]) -> anyhow::Result<T> {
+    // CLI arguments live for the whole process, so leaking them is fine
+    let args: Vec<&'static str> = args
+        .iter()
+        .map(|a| -> &'static str { String::leak(a.clone()) })
+        .collect();
+    let args: &'static [&'static str] = Vec::leak(args);
+    facet_args::from_slice(args).map_err(|e| anyhow::anyhow!("{}", e))
+}
+
+/// Read the `--style` file, or the built-in style when there is none.
+fn load_style(path: Option<&Path>) -> anyhow::Result<style::StyleConfig> {
+    let Some(path) = path else {
+        return Ok(style::StyleConfig::default());
+    };
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| anyhow::anyhow!("Failed to read style {}: {}", path.display(), e))?;
+    let mut style: style::StyleConfig = facet_yaml::from_str(&content)
+        .map_err(|e| anyhow::anyhow!("Invalid style {}: {}", path.display(), e))?;
+    // Custom color images are relative to the style file
+    let style_dir = path
+        .parent()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    style.resolve_paths(&style_dir.canonicalize()?);
+    Ok(style)
+}
+
+/// Exit code of a run stopped by Ctrl-C (128 + SIGINT, as shells report it)
+const INTERRUPTED_EXIT_CODE: i32 = 130;
+
+/// Returned by a run stopped by Ctrl-C, once partial results are saved
+#[derive(Debug, thiserror::Error)]
+#[error("interrupted")]
+struct Interrupted;
+
+/// Everything a render run accumulates, kept outside the render loop so it
+/// survives an interrupted run
+struct RunState {
+    manifest: RenderManifest,
+    journal: progress::ProgressJournal,
+    hook: Option<hooks::PostRenderHook>,
+    /// Every card's content hash, with `--skip-existing`
+    cache: Option<render_cache::RenderCache>,
+    optimized: optimize::OptimizeStats,
+    /// The cards rendered this run by source, with `--card-json`
+    cards: std::collections::HashMap<String, Card>,
+}
+
+/// What a run fills into each card beyond what its file says
+#[derive(Debug, Default)]
+struct CardDefaults {
+    /// The input's `set.yaml`
+    set: set_defaults::SetDefaults,
+    /// The collector number `--auto-number` gave each card file
+    numbers: std::collections::HashMap<PathBuf, String>,
+}
+
+impl CardDefaults {
+    /// Fill in what the card in `file` leaves out.
+    fn apply(&self, file: &Path, card: &mut Card) {
+        self.set.apply(card);
+        if let Some(number) = self.numbers.get(file) {
+            card.base_mut().collector_number = Some(number.clone());
+        }
+    }
+}
 
+async fn render(args: Args) -> anyhow::Result<()> {
     println!("MTG Card Generator");
     println!("Input: {:?}", args.input);
     println!("Output: {:?}", args.output);
     println!("DPI: {}", args.dpi);
 
-    let renderer = Renderer::new().await?;
-    let mut files = Vec::new();
+    // Check the package format before spending time rendering
+    let package_format = args
+        .package
+        .as_deref()
+        .map(package::PackageFormat::parse)
+        .transpose()
+        .map_err(anyhow::Error::msg)?;
+    args.alt_text_format()?;
+    let filter = args.filter()?;
+    let format = args.image_format()?;
+    let browser_args = args.browser_args()?;
+    if format != ImageFormat::Png {
+        if args.optimize {
+            anyhow::bail!("--optimize only applies to PNG output");
+        }
+        if args.provenance_metadata {
+            anyhow::bail!("--provenance-metadata only applies to PNG output");
+        }
+    }
+    let size = parse_card_size(args.size.as_deref())?;
+    let paper = parse_paper_size(args.paper.as_deref())?;
+    if args.bleed && args.pdf_sheet.is_some() {
+        anyhow::bail!("--pdf-sheet prints cards at their trimmed size, so it can't use --bleed");
+    }
+    let order = args
+        .order
+        .as_deref()
+        .map(order::SortOrder::parse)
+        .transpose()
+        .map_err(anyhow::Error::msg)?;
 
-    if args.input.is_file() {
-        files.push(args.input.clone());
+    let mut style = load_style(args.style.as_deref())?;
+    if args.large_print {
+        style.large_print.get_or_insert_default();
+    }
+    let set = set_defaults::SetDefaults::load(&args.input)?.unwrap_or_default();
+    set.apply_to_style(&mut style);
+
+    // Held until the run ends, so another run into the same directory
+    // waits instead of interleaving its images and manifest with ours
+    let _lock = match OutputLock::try_acquire(&args.output)? {
+        Some(lock) => lock,
+        None => {
+            println!("Waiting for another run writing to {:?}", args.output);
+            OutputLock::acquire(&args.output)?
+        }
+    };
+
+    let mut renderer = Renderer::with_browser_args(args.jobs, browser_args).await?;
+    renderer.set_color_chips(args.color_chips);
+    renderer.set_rarity_letter(args.rarity_letter);
+    renderer.set_strict(args.strict);
+    renderer.set_style(style);
+    renderer.set_card_size(size);
+    renderer.set_image_format(format, args.quality);
+    renderer.set_seed(args.seed);
+    renderer.set_bleed(args.bleed);
+    renderer.set_timeout(std::time::Duration::from_secs(args.card_timeout.max(1)));
+    if args.cards_per_page > 1 {
+        tile_cards(&mut renderer, args.cards_per_page)?;
+    }
+    let files = collect_card_files(&args.input)?;
+    let numbering = if args.auto_number {
+        set_defaults::Numbering::Auto
+    } else if args.assign_collector_numbers {
+        set_defaults::Numbering::Canonical
+    } else {
+        set.numbering
+    };
+    let mut numbers = Vec::new();
+    let order = match numbering {
+        set_defaults::Numbering::Canonical => {
+            assign_collector_numbers(&files)?;
+            order.unwrap_or(order::SortOrder::Collector)
+        }
+        set_defaults::Numbering::Auto if args.input.is_file() => {
+            println!(
+                "Not numbering {:?}: only directories are numbered",
+                args.input
+            );
+            order.unwrap_or_default()
+        }
+        set_defaults::Numbering::Auto => {
+            let entries = read_sort_entries(files.clone(), order::SortOrder::Canonical);
+            numbers = collector::auto_numbers(entries);
+            println!("Numbered {} card(s)", numbers.len());
+            order.unwrap_or(order::SortOrder::Canonical)
+        }
+        set_defaults::Numbering::Manual => order.unwrap_or_default(),
+    };
+    let defaults = CardDefaults {
+        set,
+        numbers: numbers
+            .iter()
+            .map(|number| (number.path.clone(), number.number.clone()))
+            .collect(),
+    };
+    // Every card is numbered, even those the filter leaves out
+    let files = order_card_files(filter_card_files(files, &filter, &defaults), order);
+    if args.bench_render {
+        return bench_render(&files, &args, &defaults, renderer).await;
+    }
+
+    let hook = args.post_render.as_ref().map(|template| {
+        let jobs = args.post_render_jobs.unwrap_or_else(|| {
+            std::thread::available_parallelism().map_or(1, std::num::NonZeroUsize::get)
+        });
+        hooks::PostRenderHook::new(template.clone(), jobs)
+    });
+
+    let (journal, done) = progress::ProgressJournal::open(&args.output, args.resume)?;
+    if args.resume {
+        println!("Resuming: {} card(s) already rendered", done.len());
+    }
+    let files: Vec<PathBuf> = files
+        .into_iter()
+        .filter(|file| {
+            let source = source_path(file, &args).map(|p| p.display().to_string());
+            !done
+                .iter()
+                .any(|entry| source.as_ref().ok() == Some(&entry.source))
+        })
+        .collect();
+
+    let skip = if args.skip_existing {
+        Some(SkipExisting {
+            earlier: render_cache::RenderCache::load(&args.output),
+            settings: args.render_settings(&defaults.set)?,
+        })
     } else {
-        for entry in WalkDir::new(&args.input) {
-            let entry = entry?;
-            if entry.file_type().is_file() {
-                if let Some(ext) = entry.path().extension() {
-                    if ext == "yaml" || ext == "yml" {
-                        files.push(entry.path().to_path_buf());
+        None
+    };
+    let mut state = RunState {
+        manifest: RenderManifest {
+            provenance: Some(provenance::Provenance::detect(&args.input)),
+            seed: args.seed,
+            uses_face_down: false,
+            cards: done,
+            failed: Vec::new(),
+        },
+        journal,
+        hook,
+        cache: skip.as_ref().map(|skip| skip.earlier.clone()),
+        optimized: optimize::OptimizeStats::default(),
+        cards: std::collections::HashMap::new(),
+    };
+
+    // Dropping the render loop on Ctrl-C aborts the renders in flight
+    let interrupted = tokio::select! {
+        result = render_files(&files, &args, &renderer, skip.as_ref(), &defaults, &mut state) => {
+            result?;
+            false
+        }
+        _ = tokio::signal::ctrl_c() => true,
+    };
+    if interrupted {
+        eprintln!("Interrupted; closing Chrome and saving partial results");
+        // Kills any post-render commands still running
+        drop(state.hook.take());
+        renderer.close().await?;
+        state.manifest.save(&args.output)?;
+        if let Some(cache) = &state.cache {
+            cache.save(&args.output)?;
+        }
+        eprintln!(
+            "Saved {} rendered card(s); run again with --resume to continue",
+            state.manifest.cards.len()
+        );
+        return Err(Interrupted.into());
+    }
+
+    println!(
+        "Rendered {} card(s), {} failed",
+        state.manifest.cards.len(),
+        state.manifest.failed.len()
+    );
+    if args.optimize {
+        println!("{}", state.optimized);
+    }
+    if args.profile {
+        let mut report = profile::ProfileReport::from_manifest(&state.manifest);
+        report.peak_rss_bytes = profile::peak_rss_bytes();
+        print!("{}", report);
+    }
+    // Post-render commands may rewrite the images, so finish them before
+    // anything is uploaded or packaged
+    if let Some(hook) = state.hook.take() {
+        let (ran, failures) = hook.finish().await;
+        println!(
+            "Post-render command ran on {} image(s), {} failed",
+            ran,
+            failures.len()
+        );
+        for failure in &failures {
+            eprintln!("  {:?}: {}", failure.output, failure.reason);
+            for line in failure.stderr.lines() {
+                eprintln!("    {}", line);
+            }
+        }
+    }
+    if let Some(pdf) = &args.pdf_sheet {
+        print_sheet(pdf, &state.manifest, &args, &renderer, paper, size).await?;
+    }
+    renderer.close().await?;
+
+    let mut manifest = state.manifest;
+    tokio::fs::create_dir_all(&args.output).await?;
+    if let Some(destination) = &args.upload {
+        publish(destination, &args, &mut manifest).await?;
+    }
+    // After uploading, so the files have the images' URLs
+    if args.card_json {
+        write_card_json(&args, &manifest, &state.cards, &defaults).await?;
+    }
+    // Cards from earlier runs into this directory stay in the manifest, and
+    // in the package
+    let manifest = manifest.save(&args.output)?;
+    if let Some(cache) = &state.cache {
+        cache.save(&args.output)?;
+    }
+    if !numbers.is_empty() {
+        let path = args.output.join(collector::NUMBERING_FILE);
+        std::fs::write(&path, collector::numbering_csv(&numbers, &args.input))?;
+        println!("Wrote collector numbers to {:?}", path);
+    }
+
+    if let Some(format) = package_format {
+        let name = args.set_code.clone().unwrap_or_else(|| {
+            args.output
+                .canonicalize()
+                .ok()
+                .and_then(|dir| dir.file_name().map(|n| n.to_string_lossy().into_owned()))
+                .unwrap_or_else(|| "cards".to_string())
+        });
+        let archive = package::archive_path(&args.output, &name, format);
+        package::package_outputs(&args.output, &manifest, format, &archive)?;
+        println!("Packaged outputs into {:?}", archive);
+    }
+
+    state.journal.finish()?;
+    Ok(())
+}
+
+/// What `--skip-existing` compares each card against
+struct SkipExisting {
+    /// The cache written by the last run into the output directory
+    earlier: render_cache::RenderCache,
+    /// This run's render settings, see [`Args::render_settings`]
+    settings: String,
+}
+
+/// Render `files`, recording each card in `state` as it completes.
+async fn render_files(
+    files: &[PathBuf],
+    args: &Args,
+    renderer: &Renderer,
+    skip: Option<&SkipExisting>,
+    defaults: &CardDefaults,
+    state: &mut RunState,
+) -> anyhow::Result<()> {
+    // Render in chunks, restarting Chrome between them so its memory use
+    // stays bounded on sets of thousands of cards
+    for (i, chunk) in files.chunks(args.recycle_every.max(1)).enumerate() {
+        if i > 0 {
+            renderer.recycle().await?;
+        }
+
+        // Tiled cards share a page only when they're rendered at once
+        let mut results = futures::stream::iter(chunk)
+            .map(|file| async move {
+                (
+                    file,
+                    process_file(file, args, renderer, skip, defaults).await,
+                )
+            })
+            .buffered(args.jobs.max(args.cards_per_page).max(1));
+        while let Some((file, result)) = results.next().await {
+            match result {
+                Ok(Processed {
+                    mut entry,
+                    card,
+                    hash,
+                    reused,
+                }) => {
+                    if reused {
+                        println!("Unchanged {:?}", file);
+                    } else {
+                        println!("Processed {:?}", file);
+                        for output in &entry.outputs {
+                            print_warnings(&output.path, &output.warnings);
+                        }
+                        finish_outputs(args, &mut entry, state).await?;
+                    }
+                    if let (Some(cache), Some(hash)) = (&mut state.cache, hash) {
+                        cache.insert(hash, entry.clone());
                     }
+                    state.journal.record(&entry)?;
+                    if args.card_json {
+                        state.cards.insert(entry.source.clone(), card);
+                    }
+                    state.manifest.cards.push(entry);
+                }
+                Err(e) => {
+                    eprintln!("Error processing {:?}: {}", file, e);
+                    // Listed in the manifest, so the batch carries on and
+                    // the card can be fixed afterwards
+                    state.manifest.failed.push(ManifestFailure {
+                        source: source_path(file, args)?.display().to_string(),
+                        error: e.to_string(),
+                    });
                 }
             }
         }
     }
+    Ok(())
+}
 
-    for file in files {
-        match process_file(&file, &args, &renderer).await {
-            Ok(_) => println!("Processed {:?}", file),
-            Err(e) => eprintln!("Error processing {:?}: {}", file, e),
+/// Write the data of each card in the manifest beside its images, for
+/// `--card-json`: the card as rendered this run, or for cards rendered by
+/// the run this one resumes, read again with the same `defaults`.
+async fn write_card_json(
+    args: &Args,
+    manifest: &RenderManifest,
+    cards: &std::collections::HashMap<String, Card>,
+    defaults: &CardDefaults,
+) -> anyhow::Result<()> {
+    for entry in &manifest.cards {
+        let card = match cards.get(&entry.source) {
+            Some(card) => card.clone(),
+            None => {
+                let file = if args.input.is_file() {
+                    args.input.clone()
+                } else {
+                    args.input.join(&entry.source)
+                };
+                read_card(&file, defaults).await?
+            }
+        };
+        let path = args.output.join(output::CardJson::path(&entry.source));
+        if let Some(dir) = path.parent() {
+            tokio::fs::create_dir_all(dir).await?;
         }
+        tokio::fs::write(&path, output::CardJson::new(entry, card).to_json()).await?;
+    }
+    Ok(())
+}
+
+/// Print the warnings an image was rendered despite, naming it by `path`.
+fn print_warnings(path: impl std::fmt::Debug, warnings: &[String]) {
+    for warning in warnings {
+        eprintln!("Warning: {:?}: {}", path, warning);
     }
+}
 
+/// Optimize, stamp and run the post-render command on a card's freshly
+/// rendered images.
+async fn finish_outputs(
+    args: &Args,
+    entry: &mut ManifestEntry,
+    state: &mut RunState,
+) -> anyhow::Result<()> {
+    if args.optimize {
+        optimize_outputs(&args.output, entry, &mut state.optimized).await?;
+    }
+    if let Some(provenance) = state
+        .manifest
+        .provenance
+        .as_ref()
+        .filter(|_| args.provenance_metadata)
+    {
+        for output in &entry.outputs {
+            if let Err(e) = provenance.embed(&args.output.join(&output.path)) {
+                eprintln!("{}", e);
+            }
+        }
+    }
+    if let Some(hook) = &mut state.hook {
+        for output in &entry.outputs {
+            hook.run(args.output.join(&output.path));
+        }
+    }
     Ok(())
 }
 
-async fn process_file(file: &Path, args: &Args, renderer: &Renderer) -> anyhow::Result<()> {
+/// Print every image in `manifest` on a sheet at `pdf`, for `--pdf-sheet`.
+async fn print_sheet(
+    pdf: &Path,
+    manifest: &RenderManifest,
+    args: &Args,
+    renderer: &Renderer,
+    paper: print::PaperSize,
+    size: CardSize,
+) -> anyhow::Result<()> {
+    let output_dir = args.output.canonicalize()?;
+    let images: Vec<PathBuf> = manifest
+        .cards
+        .iter()
+        .flat_map(|entry| &entry.outputs)
+        .map(|output| output_dir.join(&output.path))
+        .collect();
+    renderer
+        .render_pdf(print::sheet_html(&images, paper, size, args.cut_lines), pdf)
+        .await?;
+    println!(
+        "Wrote {} image(s) on {} page(s) to {:?}",
+        images.len(),
+        print::page_count(images.len(), paper, size),
+        pdf
+    );
+    Ok(())
+}
+
+/// Optimize an entry's images before anything else touches them. The time
+/// spent counts towards each image's encode phase.
+async fn optimize_outputs(
+    output_dir: &Path,
+    entry: &mut ManifestEntry,
+    stats: &mut optimize::OptimizeStats,
+) -> anyhow::Result<()> {
+    for output in &mut entry.outputs {
+        let path = output_dir.join(&output.path);
+        let start = std::time::Instant::now();
+        match tokio::task::spawn_blocking(move || optimize::optimize_png(&path)).await? {
+            Ok((before, after)) => stats.add(before, after),
+            Err(e) => eprintln!("{}", e),
+        }
+        if let Some(timings) = &mut output.timings {
+            timings.encode_ms += start.elapsed().as_millis() as u64;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(feature = "megapage")]
+fn tile_cards(renderer: &mut Renderer, cards_per_page: usize) -> anyhow::Result<()> {
+    renderer.set_cards_per_page(cards_per_page);
+    Ok(())
+}
+
+#[cfg(not(feature = "megapage"))]
+fn tile_cards(_renderer: &mut Renderer, _cards_per_page: usize) -> anyhow::Result<()> {
+    anyhow::bail!("--cards-per-page requires mtg-gen to be built with the `megapage` feature")
+}
+
+#[cfg(feature = "upload")]
+async fn publish(
+    destination: &str,
+    args: &Args,
+    manifest: &mut RenderManifest,
+) -> anyhow::Result<()> {
+    let target =
+        upload::UploadTarget::from_destination(destination, |name| std::env::var(name).ok())?;
+    let uploader = upload::Uploader::new(target, args.public_url.clone());
+    uploader.publish(&args.output, manifest).await?;
+    println!("Uploaded outputs to {}", destination);
+    Ok(())
+}
+
+#[cfg(not(feature = "upload"))]
+async fn publish(
+    _destination: &str,
+    _args: &Args,
+    _manifest: &mut RenderManifest,
+) -> anyhow::Result<()> {
+    anyhow::bail!("--upload requires mtg-gen to be built with the `upload` feature")
+}
+
+/// How many skipped files [`collect_card_files`] names before summing up
+/// the rest
+const SKIPPED_FILES_LISTED: usize = 5;
+
+/// Find the card YAML files at `input`, which may be a file or a directory,
+/// see [`yaml::find_card_files`]. A directory's YAML files that aren't
+/// cards, like a style file, are skipped with a one-line summary.
+fn collect_card_files(input: &Path) -> anyhow::Result<Vec<PathBuf>> {
+    let files = yaml::find_card_files(input)?;
+    let skipped = &files.skipped;
+    if !skipped.is_empty() {
+        let mut listed = skipped[..skipped.len().min(SKIPPED_FILES_LISTED)]
+            .iter()
+            .map(|path| path.display().to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        if skipped.len() > SKIPPED_FILES_LISTED {
+            listed.push_str(&format!(
+                " and {} more",
+                skipped.len() - SKIPPED_FILES_LISTED
+            ));
+        }
+        eprintln!(
+            "Skipped {} YAML file(s) that aren't cards: {}",
+            skipped.len(),
+            listed
+        );
+    }
+    Ok(files.cards)
+}
+
+/// Read what card files sort by, parsing the cards only when `order` needs
+/// them. Files that don't parse sort last and fail when they're rendered.
+fn read_sort_entries(files: Vec<PathBuf>, order: order::SortOrder) -> Vec<order::SortEntry> {
+    files
+        .into_iter()
+        .map(|path| {
+            let card = order
+                .needs_cards()
+                .then(|| std::fs::read_to_string(&path).ok())
+                .flatten()
+                .and_then(|content| facet_yaml::from_str::<Card>(&content).ok());
+            order::SortEntry::new(path, card.as_ref())
+        })
+        .collect()
+}
+
+/// Keep the card files `filter` matches, with `defaults` filled in. Files
+/// that don't parse are kept, so their errors are still reported.
+fn filter_card_files(
+    files: Vec<PathBuf>,
+    filter: &filter::CardFilter,
+    defaults: &CardDefaults,
+) -> Vec<PathBuf> {
+    if filter.is_empty() {
+        return files;
+    }
+    files
+        .into_iter()
+        .filter(|path| {
+            std::fs::read_to_string(path)
+                .ok()
+                .and_then(|content| facet_yaml::from_str::<Card>(&content).ok())
+                .is_none_or(|mut card| {
+                    defaults.apply(path, &mut card);
+                    filter.matches(&card)
+                })
+        })
+        .collect()
+}
+
+/// Sort card files by `order`.
+fn order_card_files(files: Vec<PathBuf>, order: order::SortOrder) -> Vec<PathBuf> {
+    let mut entries = read_sort_entries(files, order);
+    order::sort_entries(&mut entries, order);
+    entries.into_iter().map(|entry| entry.path).collect()
+}
+
+/// Number cards in canonical order, writing each number into its YAML file.
+fn assign_collector_numbers(files: &[PathBuf]) -> anyhow::Result<()> {
+    let mut entries = read_sort_entries(files.to_vec(), order::SortOrder::Canonical);
+    order::sort_entries(&mut entries, order::SortOrder::Canonical);
+
+    // Files that don't parse are left unnumbered
+    let mut assigned = 0;
+    for (number, entry) in (1..).zip(entries.iter().filter(|e| e.color_group.is_some())) {
+        let content = std::fs::read_to_string(&entry.path)?;
+        let updated = collector::set_collector_number(&content, &number.to_string());
+        std::fs::write(&entry.path, updated)?;
+        assigned = number;
+    }
+    println!("Assigned collector numbers to {} card(s)", assigned);
+    Ok(())
+}
+
+/// Returns the path of a card file relative to the input.
+fn source_path<'a>(file: &'a Path, args: &Args) -> anyhow::Result<&'a Path> {
+    if args.input.is_file() {
+        Ok(Path::new(file.file_name().unwrap_or_default()))
+    } else {
+        Ok(file.strip_prefix(&args.input)?)
+    }
+}
+
+/// How many times `--bench-render` times each card
+const BENCH_ITERATIONS: usize = 5;
+
+/// Render each card [`BENCH_ITERATIONS`] times into a scratch directory and
+/// print its median time, for `--bench-render`. Nothing is written to the
+/// output directory.
+async fn bench_render(
+    files: &[PathBuf],
+    args: &Args,
+    defaults: &CardDefaults,
+    renderer: Renderer,
+) -> anyhow::Result<()> {
+    let scratch = std::env::temp_dir().join(format!("mtg-gen-bench-{}", std::process::id()));
+    let path = scratch.join(format!("card.{}", args.image_format()?.extension()));
+    let mut report = profile::ProfileReport::default();
+    for file in files {
+        let result = async {
+            let card = load_card(file, args, defaults).await?;
+            // The first render warms Chrome's caches of fonts and assets
+            renderer.render_card(&card, &path).await?;
+            let mut samples = Vec::new();
+            for _ in 0..BENCH_ITERATIONS {
+                samples.push(renderer.render_card(&card, &path).await?.timings);
+            }
+            anyhow::Ok((card.name().to_string(), samples))
+        }
+        .await;
+        match result {
+            Ok((name, samples)) => {
+                let median = profile::median_timings(&samples).unwrap_or_default();
+                println!("{:>8} ms  {}", median.total_ms(), name);
+                report.samples.push((name, median));
+            }
+            Err(e) => eprintln!("Error benchmarking {:?}: {}", file, e),
+        }
+    }
+    renderer.close().await?;
+    let _ = std::fs::remove_dir_all(&scratch);
+    print!("{}", report);
+    Ok(())
+}
+
+/// Read, validate and resolve the art of the card in `file`, filling in
+/// what it leaves out from `defaults`.
+async fn load_card(file: &Path, args: &Args, defaults: &CardDefaults) -> anyhow::Result<Card> {
+    prepare_card(file, read_card(file, defaults).await?, args).await
+}
+
+/// Read the card in `file`, filling in what it leaves out from `defaults`.
+async fn read_card(file: &Path, defaults: &CardDefaults) -> anyhow::Result<Card> {
     let content = tokio::fs::read_to_string(file).await?;
-    let card: Card = facet_yaml::from_str(&content)?;
+    let mut card: Card = facet_yaml::from_str(&content)?;
+    defaults.apply(file, &mut card);
+    Ok(card)
+}
+
+/// Validate `card`, read from `file`, and resolve its art for rendering.
+async fn prepare_card(file: &Path, mut card: Card, args: &Args) -> anyhow::Result<Card> {
+    card.validate()?;
+    // Art paths are relative to the card's file
+    let card_dir = file
+        .parent()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    card.resolve_art_paths(&card_dir.canonicalize()?);
+    if card.art_paths().any(card::is_art_url) {
+        download_art(&mut card, args).await?;
+    }
+    if let Some(missing) = card.art_paths().find(|art| !Path::new(art).is_file()) {
+        anyhow::bail!("Art not found: {}", missing);
+    }
+    Ok(card)
+}
+
+/// A card file's images, rendered or left from an earlier run
+struct Processed {
+    entry: ManifestEntry,
+    /// The card as rendered, before its art is resolved, for `--card-json`
+    card: Card,
+    /// The card's content hash, with `--skip-existing`
+    hash: Option<String>,
+    /// Whether the card was unchanged, so its earlier images were kept
+    reused: bool,
+}
+
+async fn process_file(
+    file: &Path,
+    args: &Args,
+    renderer: &Renderer,
+    skip: Option<&SkipExisting>,
+    defaults: &CardDefaults,
+) -> anyhow::Result<Processed> {
+    let data = read_card(file, defaults).await?;
+    let card = prepare_card(file, data.clone(), args).await?;
+    let relative_path = source_path(file, args)?;
+    let source = relative_path.display().to_string();
+    let hash = match skip {
+        Some(skip) => {
+            let yaml = tokio::fs::read_to_string(file).await?;
+            let hash = render_cache::content_hash(&yaml, &card, &skip.settings);
+            if let Some(entry) = skip.earlier.fresh(&source, &hash, &args.output) {
+                return Ok(Processed {
+                    entry: entry.clone(),
+                    card: data,
+                    hash: Some(hash),
+                    reused: true,
+                });
+            }
+            Some(hash)
+        }
+        None => None,
+    };
+    check_art(file, &card, renderer.card_html().style(), args);
+    let card_path = relative_path.with_extension(args.image_format()?.extension());
+    let mut entry = ManifestEntry {
+        source,
+        name: card.name().to_string(),
+        outputs: Vec::new(),
+    };
+
+    render_outputs(&card, &card_path, None, args, renderer, &mut entry).await?;
+    // Each translation renders like its own card, with a language suffix
+    for language in card.languages() {
+        if let Some(translated) = card.translated(language) {
+            let path = output::language_card_path(&card_path, language);
+            render_outputs(
+                &translated,
+                &path,
+                Some(language),
+                args,
+                renderer,
+                &mut entry,
+            )
+            .await?;
+        }
+    }
+
+    Ok(Processed {
+        entry,
+        card: data,
+        hash,
+        reused: false,
+    })
+}
 
-    let relative_path = if args.input.is_file() {
-        Path::new(file.file_name().unwrap())
+/// Warn about art its art box crops, naming the part that's visible, and
+/// with `--write-art-crops` write that part next to the art.
+fn check_art(file: &Path, card: &Card, style: &style::StyleConfig, args: &Args) {
+    let mut warnings = Vec::new();
+    for art in art_crop::art_crops(card, style) {
+        warnings.push(art.warning());
+        if args.write_art_crops {
+            match write_art_crop(&art) {
+                Ok(path) => warnings.push(format!(
+                    "wrote the visible part of {} to {}",
+                    art.path,
+                    path.display()
+                )),
+                Err(e) => warnings.push(format!("couldn't crop {}: {}", art.path, e)),
+            }
+        }
+    }
+    print_warnings(file, &warnings);
+}
+
+#[cfg(feature = "art-crops")]
+fn write_art_crop(art: &art_crop::ArtCrop) -> anyhow::Result<PathBuf> {
+    art_crop::write_cropped(art)
+}
+
+#[cfg(not(feature = "art-crops"))]
+fn write_art_crop(_art: &art_crop::ArtCrop) -> anyhow::Result<PathBuf> {
+    anyhow::bail!("--write-art-crops requires mtg-gen to be built with the `art-crops` feature")
+}
+
+/// Point a card's art URLs at their cached downloads.
+#[cfg(feature = "remote-art")]
+async fn download_art(card: &mut Card, args: &Args) -> anyhow::Result<()> {
+    let cache = art_cache::ArtCache::new(
+        args.art_cache
+            .clone()
+            .unwrap_or_else(|| art_cache::ArtCache::default_dir(|name| std::env::var(name).ok())),
+    );
+    let urls: Vec<String> = card
+        .art_paths()
+        .filter(|art| card::is_art_url(art))
+        .map(String::from)
+        .collect();
+    let mut downloaded = std::collections::HashMap::new();
+    for url in urls {
+        let path = cache.fetch(&url).await?;
+        downloaded.insert(url, path.display().to_string());
+    }
+    card.replace_art(|art| downloaded.get(art).cloned());
+    Ok(())
+}
+
+#[cfg(not(feature = "remote-art"))]
+async fn download_art(_card: &mut Card, _args: &Args) -> anyhow::Result<()> {
+    anyhow::bail!("Art URLs require mtg-gen to be built with the `remote-art` feature")
+}
+
+/// Render a card's images, adding them to `entry`.
+async fn render_outputs(
+    card: &Card,
+    card_path: &Path,
+    language: Option<&str>,
+    args: &Args,
+    renderer: &Renderer,
+    entry: &mut ManifestEntry,
+) -> anyhow::Result<()> {
+    let naming = args.naming();
+    if args.playtest {
+        // Every face is written on the one playtest card
+        let path = naming.path_for(card_path, OutputKind::Card, 0, 1);
+        let outcome = renderer
+            .render_card(&PlaytestCard::from_card(card), &args.output.join(&path))
+            .await?;
+        entry.outputs.push(ManifestOutput {
+            kind: OutputKind::Card,
+            path: path.display().to_string(),
+            url: None,
+            timings: Some(outcome.timings),
+            language: language.map(str::to_string),
+            warnings: outcome.warnings,
+        });
+        if let Some(format) = args.alt_text_format()? {
+            write_alt_text(card, &args.output.join(&path), format).await?;
+        }
+        return Ok(());
+    }
+
+    // Double-faced cards get an image per face
+    let back = back_face(card);
+    let kind = match back {
+        Some(_) => OutputKind::Front,
+        None => OutputKind::Card,
+    };
+    let path = naming.path_for(card_path, kind, 0, 1);
+    let outcome = renderer.render_card(card, &args.output.join(&path)).await?;
+    entry.outputs.push(ManifestOutput {
+        kind,
+        path: path.display().to_string(),
+        url: None,
+        timings: Some(outcome.timings),
+        language: language.map(str::to_string),
+        warnings: outcome.warnings,
+    });
+    if let Some(format) = args.alt_text_format()? {
+        write_alt_text(card, &args.output.join(&path), format).await?;
+    }
+
+    if let Some(back) = back {
+        let path = naming.path_for(card_path, OutputKind::Back, 0, 1);
+        let outcome = renderer
+            .render_card(&back, &args.output.join(&path))
+            .await?;
+        entry.outputs.push(ManifestOutput {
+            kind: OutputKind::Back,
+            path: path.display().to_string(),
+            url: None,
+            timings: Some(outcome.timings),
+            language: language.map(str::to_string),
+            warnings: outcome.warnings,
+        });
+    }
+
+    if let Some(face_down) = extract_face_down(card) {
+        let path = naming.path_for(card_path, OutputKind::FaceDown, 0, 1);
+        let outcome = renderer
+            .render_card(&face_down, &args.output.join(&path))
+            .await?;
+        entry.outputs.push(ManifestOutput {
+            kind: OutputKind::FaceDown,
+            path: path.display().to_string(),
+            url: None,
+            timings: Some(outcome.timings),
+            language: language.map(str::to_string),
+            warnings: outcome.warnings,
+        });
+    }
+
+    if args.generate_emblems {
+        let emblems = extract_emblems(card);
+        for (i, emblem) in emblems.iter().enumerate() {
+            let path = naming.path_for(card_path, OutputKind::Emblem, i, emblems.len());
+            let outcome = renderer
+                .render_card(emblem, &args.output.join(&path))
+                .await?;
+            entry.outputs.push(ManifestOutput {
+                kind: OutputKind::Emblem,
+                path: path.display().to_string(),
+                url: None,
+                timings: Some(outcome.timings),
+                language: language.map(str::to_string),
+                warnings: outcome.warnings,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Write the screen-reader description of `card` next to its image.
+async fn write_alt_text(
+    card: &Card,
+    image_path: &Path,
+    format: alt_text::AltTextFormat,
+) -> anyhow::Result<()> {
+    let alt = alt_text::AltText::from_card(card);
+    let contents = match format {
+        alt_text::AltTextFormat::Txt => alt.to_string(),
+        alt_text::AltTextFormat::Json => alt.to_json(),
+    };
+    tokio::fs::write(image_path.with_extension(format.extension()), contents).await?;
+    Ok(())
+}
+
+/// Parse `--size`, defaulting to standard.
+fn parse_card_size(size: Option<&str>) -> anyhow::Result<CardSize> {
+    Ok(size
+        .map(CardSize::parse)
+        .transpose()
+        .map_err(anyhow::Error::msg)?
+        .unwrap_or_default())
+}
+
+/// Parse `--paper`, defaulting to A4.
+fn parse_paper_size(paper: Option<&str>) -> anyhow::Result<print::PaperSize> {
+    Ok(paper
+        .map(print::PaperSize::parse)
+        .transpose()
+        .map_err(anyhow::Error::msg)?
+        .unwrap_or_default())
+}
+
+async fn write_example(args: ExampleArgs) -> anyhow::Result<()> {
+    let Some(yaml) = examples::example_yaml(&args.layout) else {
+        let layouts: Vec<&str> = examples::layouts().collect();
+        anyhow::bail!(
+            "Unknown card type {:?}; expected one of: {}",
+            args.layout,
+            layouts.join(", ")
+        );
+    };
+
+    let path = args
+        .output
+        .unwrap_or_else(|| PathBuf::from(format!("{}.yaml", args.layout)));
+    if path.exists() {
+        anyhow::bail!("{:?} already exists; pass -o to choose another path", path);
+    }
+    tokio::fs::write(&path, yaml).await?;
+    println!("Wrote {} example to {:?}", args.layout, path);
+    Ok(())
+}
+
+#[cfg(feature = "edit")]
+async fn edit(args: EditArgs) -> anyhow::Result<()> {
+    let form = if args.file.exists() {
+        if args.layout.is_some() {
+            anyhow::bail!(
+                "{:?} already exists; --type only applies to new files",
+                args.file
+            );
+        }
+        editor::CardForm::from_yaml(&tokio::fs::read_to_string(&args.file).await?)?
     } else {
-        file.strip_prefix(&args.input)?
+        editor::CardForm::new(args.layout.as_deref().unwrap_or("normal"))?
+    };
+    editor::tui::run(&args.file, form).await
+}
+
+#[cfg(feature = "proxy")]
+async fn proxy(args: ProxyArgs) -> anyhow::Result<()> {
+    let paper = parse_paper_size(args.paper.as_deref())?;
+    let size = parse_card_size(args.size.as_deref())?;
+    let decklist = tokio::fs::read_to_string(&args.decklist).await?;
+    let entries = decklist::parse_decklist(&decklist)?;
+    let mut style = load_style(args.style.as_deref())?;
+    if args.large_print {
+        style.large_print.get_or_insert_default();
+    }
+
+    let mut renderer = Renderer::new().await?;
+    renderer.set_color_chips(args.color_chips);
+    renderer.set_rarity_letter(args.rarity_letter);
+    renderer.set_strict(args.strict);
+    renderer.set_style(style);
+    renderer.set_card_size(size);
+    renderer.set_not_for_sale(true);
+    let mut client = scryfall::fetch::ScryfallClient::new();
+    let library = match &args.cards {
+        Some(dir) => card_library(dir)?,
+        None => decklist::CardLibrary::new(),
+    };
+    tokio::fs::create_dir_all(&args.output).await?;
+    let output_dir = args.output.canonicalize()?;
+
+    // One image per card, repeated on the sheet for each copy
+    let mut images = Vec::new();
+    let mut failed = 0;
+    for entry in &entries {
+        let result = async {
+            let card = match library.find(entry) {
+                Some(file) => load_local_card(file).await?,
+                None => client.fetch(entry).await?.to_card()?,
+            };
+            let path = output_dir.join(entry.file_name(card.name()));
+            let outcome = renderer.render_card(&card, &path).await?;
+            print_warnings(&path, &outcome.warnings);
+            anyhow::Ok(path)
+        }
+        .await;
+        match result {
+            Ok(path) => {
+                println!("Rendered {} x{}", entry.name, entry.count);
+                images.extend(std::iter::repeat_n(path, entry.count as usize));
+            }
+            Err(e) => {
+                eprintln!("Error proxying {:?}: {}", entry.name, e);
+                failed += 1;
+            }
+        }
+    }
+
+    renderer
+        .render_pdf(print::sheet_html(&images, paper, size, true), &args.pdf)
+        .await?;
+    renderer.close().await?;
+    println!(
+        "Wrote {} card(s) on {} page(s) to {:?}, {} failed",
+        images.len(),
+        print::page_count(images.len(), paper, size),
+        args.pdf,
+        failed
+    );
+    Ok(())
+}
+
+/// Index the cards in `dir` by name. Files that don't parse are skipped
+/// with a warning, so one broken card doesn't stop a deck printing.
+#[cfg(feature = "proxy")]
+fn card_library(dir: &Path) -> anyhow::Result<decklist::CardLibrary> {
+    let mut library = decklist::CardLibrary::new();
+    for file in collect_card_files(dir)? {
+        let card = std::fs::read_to_string(&file)
+            .map_err(anyhow::Error::from)
+            .and_then(|yaml| {
+                facet_yaml::from_str::<Card>(&yaml).map_err(|e| anyhow::anyhow!("{}", e))
+            });
+        match card {
+            Ok(card) => library.insert(card.name(), file),
+            Err(e) => eprintln!("Warning: skipping {:?}: {}", file, e),
+        }
+    }
+    Ok(library)
+}
+
+/// Read and validate a card from a local file, with its art resolved
+/// against the file's directory.
+#[cfg(feature = "proxy")]
+async fn load_local_card(file: &Path) -> anyhow::Result<Card> {
+    let yaml = tokio::fs::read_to_string(file).await?;
+    let mut card: Card = facet_yaml::from_str(&yaml)?;
+    card.validate()?;
+    let card_dir = file
+        .parent()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    card.resolve_art_paths(&card_dir.canonicalize()?);
+    Ok(card)
+}
+
+#[cfg(feature = "proxy")]
+async fn fetch(args: FetchArgs) -> anyhow::Result<()> {
+    let size = parse_card_size(args.size.as_deref())?;
+    let style = load_style(args.style.as_deref())?;
+    // Read like a decklist line, so a printing can be asked for
+    let entry = decklist::parse_decklist(&args.name)?
+        .into_iter()
+        .next()
+        .ok_or_else(|| anyhow::anyhow!("No card name given"))?;
+    let card = scryfall::fetch::ScryfallClient::new()
+        .fetch(&entry)
+        .await?
+        .to_card()?;
+    if let Some(yaml_path) = &args.yaml {
+        tokio::fs::write(yaml_path, yaml::card_to_yaml(&card)).await?;
+        println!("Wrote {:?}", yaml_path);
+    }
+
+    let path = args
+        .output
+        .unwrap_or_else(|| PathBuf::from(format!("{}.png", sanitize_card_name(card.name()))));
+    let mut renderer = Renderer::new().await?;
+    renderer.set_style(style);
+    renderer.set_card_size(size);
+    renderer.set_not_for_sale(true);
+    let mut rendered = vec![path.clone()];
+    let outcome = renderer.render_card(&card, &path).await?;
+    print_warnings(&path, &outcome.warnings);
+    if let Some(back) = back_face(&card) {
+        let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+        let back_path = path.with_file_name(format!("{}_back.png", stem));
+        let outcome = renderer.render_card(&back, &back_path).await?;
+        print_warnings(&back_path, &outcome.warnings);
+        rendered.push(back_path);
+    }
+    for path in &rendered {
+        println!("Rendered {:?}", path);
+    }
+    renderer.close().await
+}
+
+async fn write_schema(args: SchemaArgs) -> anyhow::Result<()> {
+    let json = match &args.layout {
+        Some(layout) => schema::layout_schema(layout).ok_or_else(|| {
+            let layouts: Vec<&str> = schema::layouts().collect();
+            anyhow::anyhow!(
+                "Unknown card type {:?}; expected one of: {}",
+                layout,
+                layouts.join(", ")
+            )
+        })?,
+        None => schema::card_schema(),
+    };
+
+    match args.output {
+        Some(path) => {
+            tokio::fs::write(&path, json).await?;
+            println!("Wrote schema to {:?}", path);
+        }
+        None => print!("{}", json),
+    }
+    Ok(())
+}
+
+async fn doctor() -> anyhow::Result<()> {
+    let results = doctor::run_checks().await;
+    for result in &results {
+        println!("{}", result);
+    }
+
+    let failures = results
+        .iter()
+        .filter(|r| r.status == doctor::CheckStatus::Fail)
+        .count();
+    if failures > 0 {
+        anyhow::bail!("{} check(s) failed", failures);
+    }
+    println!("Everything looks good");
+    Ok(())
+}
+
+async fn print_stats(args: StatsArgs) -> anyhow::Result<()> {
+    let filter = filter::CardFilter::parse(
+        args.only.as_deref(),
+        args.layout.as_deref(),
+        args.rarity.as_deref(),
+    )
+    .map_err(anyhow::Error::msg)?;
+    let defaults = CardDefaults {
+        set: set_defaults::SetDefaults::load(&args.input)?.unwrap_or_default(),
+        ..CardDefaults::default()
+    };
+    let mut cards = Vec::new();
+
+    for file in collect_card_files(&args.input)? {
+        match read_card(&file, &defaults).await {
+            Ok(card) if filter.matches(&card) => cards.push(card),
+            Ok(_) => {}
+            Err(e) => eprintln!("Error parsing {:?}: {}", file, e),
+        }
+    }
+
+    print!("{}", stats::RarityCounts::from_cards(&cards));
+    if args.archetypes {
+        print!("{}", stats::ArchetypeGrid::from_cards(&cards));
+    }
+    Ok(())
+}
+
+/// Write the cards in `args.input` as a Cockatrice custom set, each shown
+/// with the image `mtg-gen` renders it to with the same naming settings.
+async fn export_cockatrice(args: ExportArgs) -> anyhow::Result<()> {
+    let extension = args
+        .format
+        .as_deref()
+        .map(ImageFormat::parse)
+        .transpose()
+        .map_err(anyhow::Error::msg)?
+        .unwrap_or_default()
+        .extension();
+    let naming = OutputNaming {
+        face_template: args.face_template.clone(),
+        subfolders: args.kind_subfolders,
+        ..OutputNaming::default()
+    };
+    let filter = filter::CardFilter::parse(
+        args.only.as_deref(),
+        args.layout.as_deref(),
+        args.rarity.as_deref(),
+    )
+    .map_err(anyhow::Error::msg)?;
+    let defaults = CardDefaults {
+        set: set_defaults::SetDefaults::load(&args.input)?.unwrap_or_default(),
+        ..CardDefaults::default()
+    };
+    let mut cards = Vec::new();
+    for file in filter_card_files(collect_card_files(&args.input)?, &filter, &defaults) {
+        let card = match read_card(&file, &defaults).await {
+            Ok(card) => card,
+            Err(e) => {
+                eprintln!("Error parsing {:?}: {}", file, e);
+                continue;
+            }
+        };
+        let relative = if args.input.is_file() {
+            Path::new(file.file_name().unwrap_or_default())
+        } else {
+            file.strip_prefix(&args.input)?
+        };
+        let card_path = relative.with_extension(extension);
+        let images: Vec<String> = cockatrice::image_paths(&card, &card_path, &naming)
+            .iter()
+            .map(|path| {
+                let path = path.to_string_lossy().replace('\\', "/");
+                match &args.image_url {
+                    Some(url) => format!("{}/{}", url.trim_end_matches('/'), path),
+                    None => path,
+                }
+            })
+            .collect();
+        cards.push((card, images));
+    }
+
+    let code = args
+        .set_code
+        .clone()
+        .or_else(|| {
+            cards
+                .iter()
+                .find_map(|(card, _)| card.base().set_code.clone())
+        })
+        .or_else(|| {
+            args.input
+                .canonicalize()
+                .ok()
+                .filter(|input| input.is_dir())
+                .and_then(|dir| dir.file_name().map(|n| n.to_string_lossy().into_owned()))
+        })
+        .unwrap_or_else(|| "CUSTOM".to_string());
+    let mut set = cockatrice::CockatriceSet::new(&code, args.set_name.as_deref().unwrap_or(&code));
+    for (card, images) in &cards {
+        set.add(card, images);
+    }
+    tokio::fs::write(&args.output, set.to_xml()).await?;
+    println!("Wrote {} cards to {:?}", set.cards.len(), args.output);
+    Ok(())
+}
+
+async fn render_legend(args: LegendArgs) -> anyhow::Result<()> {
+    let entries = match &args.symbols {
+        Some(codes) => legend::parse_symbols(codes)?,
+        None => legend::supported_symbols(),
     };
+    if entries.is_empty() {
+        anyhow::bail!("No symbols to list");
+    }
+
+    let renderer = Renderer::new().await?;
+    tokio::fs::create_dir_all(&args.output).await?;
+    let output_dir = args.output.canonicalize()?;
+    let cards = legend::legend_cards(entries);
+    for card in &cards {
+        let name = match cards.len() {
+            1 => "legend.png".to_string(),
+            _ => format!("legend_{}.png", card.page),
+        };
+        let path = output_dir.join(name);
+        let outcome = renderer.render_card(card, &path).await?;
+        print_warnings(&path, &outcome.warnings);
+        println!("Rendered {:?}", path);
+    }
+    renderer.close().await
+}
+
+async fn render_test_card(args: TestCardArgs) -> anyhow::Result<()> {
+    let style = load_style(args.style.as_deref())?;
+    let mut renderer = Renderer::new().await?;
+    renderer.set_style(style);
+    let outcome = renderer
+        .render_card(&testcard::test_card(), &args.output)
+        .await?;
+    print_warnings(&args.output, &outcome.warnings);
+    println!("Rendered {:?}", args.output);
+    renderer.close().await
+}
 
-    let output_path = args.output.join(relative_path).with_extension("png");
+async fn serve(args: ServeArgs) -> anyhow::Result<()> {
+    if !args.input.is_dir() {
+        anyhow::bail!("{:?} is not a directory", args.input);
+    }
+    let style = load_style(args.style.as_deref())?;
+    let mut renderer = Renderer::with_max_pages(args.jobs.max(1)).await?;
+    renderer.set_style(style);
+    let listener = tokio::net::TcpListener::bind((args.host.as_str(), args.port)).await?;
+    println!(
+        "Serving {:?} on http://{}",
+        args.input,
+        listener.local_addr()?
+    );
+    serve::serve(listener, args.input, renderer).await
+}
+
+fn print_provenance(args: ProvenanceArgs) -> anyhow::Result<()> {
+    match provenance::Provenance::read(&args.image)? {
+        Some(provenance) => println!("{}", provenance),
+        None => anyhow::bail!(
+            "{:?} has no provenance; render it with --provenance-metadata",
+            args.image
+        ),
+    }
+    Ok(())
+}
+
+#[cfg(feature = "symbols-sync")]
+async fn symbols_sync(args: SymbolsSyncArgs) -> anyhow::Result<()> {
+    use mtg_gen::symbology::sync;
 
-    renderer.render_card(&card, &output_path).await?;
+    let live = sync::fetch_symbology().await?;
+    let report = sync::compare(&live);
+
+    println!("Scryfall lists {} symbols", live.len());
+    for symbol in &report.unsupported {
+        println!("  unsupported: {} ({})", symbol.symbol, symbol.english);
+    }
+    for code in &report.missing_from_snapshot {
+        println!("  missing from snapshot: {}", code);
+    }
+    for code in &report.removed_from_scryfall {
+        println!("  no longer on Scryfall: {}", code);
+    }
+    if report.is_clean() {
+        println!("Symbol coverage is up to date");
+    }
+
+    if let Some(path) = args.emit {
+        tokio::fs::write(&path, sync::generate_table(&live)).await?;
+        println!("Wrote symbology table to {:?}", path);
+    }
 
     Ok(())
 }
@@ -1,9 +1,11 @@
+use crate::color_chips::Color;
 use facet::Facet;
 use std::convert::Infallible;
 use std::fmt;
 
 /// Represents a single mana symbol valid for casting costs
 #[derive(Facet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "fuzz", derive(arbitrary::Arbitrary))]
 #[repr(u8)]
 pub enum CastingManaSymbol {
     // Basic colors
@@ -50,10 +52,210 @@ pub enum CastingManaSymbol {
     PhyrexianBlack,
     PhyrexianRed,
     PhyrexianGreen,
+    PhyrexianColorless,
+
+    // Hybrid Phyrexian (either color or life)
+    PhyrexianWhiteBlue,
+    PhyrexianWhiteBlack,
+    PhyrexianWhiteRed,
+    PhyrexianWhiteGreen,
+    PhyrexianBlueBlack,
+    PhyrexianBlueRed,
+    PhyrexianBlueGreen,
+    PhyrexianBlackRed,
+    PhyrexianBlackGreen,
+    PhyrexianRedGreen,
+
+    // Colorless hybrid (colorless or color)
+    ColorlessWhite,
+    ColorlessBlue,
+    ColorlessBlack,
+    ColorlessRed,
+    ColorlessGreen,
+
+    // Half and infinite mana (Un-sets)
+    HalfWhite,
+    HalfRed,
+    Half,
+    Infinity,
+
+    /// Mana of a custom color, defined in the style file's `colors`
+    Custom(CustomMana),
+}
+
+impl CastingManaSymbol {
+    /// Returns the colors the symbol counts as; hybrid symbols count as both
+    /// of their colors. Custom colors aren't among the five.
+    #[must_use]
+    pub fn colors(self) -> &'static [Color] {
+        use CastingManaSymbol as S;
+        match self {
+            S::White | S::TwoWhite | S::PhyrexianWhite | S::ColorlessWhite | S::HalfWhite => {
+                &[Color::White]
+            }
+            S::Blue | S::TwoBlue | S::PhyrexianBlue | S::ColorlessBlue => &[Color::Blue],
+            S::Black | S::TwoBlack | S::PhyrexianBlack | S::ColorlessBlack => &[Color::Black],
+            S::Red | S::TwoRed | S::PhyrexianRed | S::ColorlessRed | S::HalfRed => &[Color::Red],
+            S::Green | S::TwoGreen | S::PhyrexianGreen | S::ColorlessGreen => &[Color::Green],
+            S::WhiteBlue | S::PhyrexianWhiteBlue => &[Color::White, Color::Blue],
+            S::WhiteBlack | S::PhyrexianWhiteBlack => &[Color::White, Color::Black],
+            S::WhiteRed | S::PhyrexianWhiteRed => &[Color::White, Color::Red],
+            S::WhiteGreen | S::PhyrexianWhiteGreen => &[Color::White, Color::Green],
+            S::BlueBlack | S::PhyrexianBlueBlack => &[Color::Blue, Color::Black],
+            S::BlueRed | S::PhyrexianBlueRed => &[Color::Blue, Color::Red],
+            S::BlueGreen | S::PhyrexianBlueGreen => &[Color::Blue, Color::Green],
+            S::BlackRed | S::PhyrexianBlackRed => &[Color::Black, Color::Red],
+            S::BlackGreen | S::PhyrexianBlackGreen => &[Color::Black, Color::Green],
+            S::RedGreen | S::PhyrexianRedGreen => &[Color::Red, Color::Green],
+            _ => &[],
+        }
+    }
+
+    /// Returns how much the symbol adds to a mana value: the number for
+    /// generic mana, 2 for twobrid, 0 for X, Y and Z, one half for half
+    /// mana, infinity for `{∞}` and 1 for everything else.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mtg_gen::CastingManaSymbol;
+    ///
+    /// assert_eq!(CastingManaSymbol::Generic(3).mana_value_contribution(), 3.0);
+    /// assert_eq!(CastingManaSymbol::TwoRed.mana_value_contribution(), 2.0);
+    /// assert_eq!(CastingManaSymbol::HalfWhite.mana_value_contribution(), 0.5);
+    /// ```
+    #[must_use]
+    pub fn mana_value_contribution(self) -> f64 {
+        use CastingManaSymbol as S;
+        match self {
+            S::Generic(n) => f64::from(n),
+            S::X | S::Y | S::Z => 0.0,
+            S::TwoWhite | S::TwoBlue | S::TwoBlack | S::TwoRed | S::TwoGreen => 2.0,
+            S::HalfWhite | S::HalfRed | S::Half => 0.5,
+            S::Infinity => f64::INFINITY,
+            _ => 1.0,
+        }
+    }
+
+    /// Returns true for symbols payable in more than one way with mana:
+    /// two-color hybrid, twobrid, colorless hybrid and hybrid Phyrexian.
+    #[must_use]
+    pub fn is_hybrid(self) -> bool {
+        use CastingManaSymbol as S;
+        matches!(
+            self,
+            S::TwoWhite
+                | S::TwoBlue
+                | S::TwoBlack
+                | S::TwoRed
+                | S::TwoGreen
+                | S::ColorlessWhite
+                | S::ColorlessBlue
+                | S::ColorlessBlack
+                | S::ColorlessRed
+                | S::ColorlessGreen
+        ) || self.colors().len() > 1
+    }
+
+    /// Returns true for Phyrexian symbols, payable with 2 life.
+    #[must_use]
+    pub fn is_phyrexian(self) -> bool {
+        use CastingManaSymbol as S;
+        matches!(
+            self,
+            S::PhyrexianWhite
+                | S::PhyrexianBlue
+                | S::PhyrexianBlack
+                | S::PhyrexianRed
+                | S::PhyrexianGreen
+                | S::PhyrexianColorless
+                | S::PhyrexianWhiteBlue
+                | S::PhyrexianWhiteBlack
+                | S::PhyrexianWhiteRed
+                | S::PhyrexianWhiteGreen
+                | S::PhyrexianBlueBlack
+                | S::PhyrexianBlueRed
+                | S::PhyrexianBlueGreen
+                | S::PhyrexianBlackRed
+                | S::PhyrexianBlackGreen
+                | S::PhyrexianRedGreen
+        )
+    }
+}
+
+/// The letter of a custom color's mana symbol, e.g. `{O}`. Only letters no
+/// real symbol uses are available, so custom symbols never clash with
+/// Scryfall's.
+#[derive(Facet, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "fuzz", derive(arbitrary::Arbitrary))]
+#[repr(u8)]
+pub enum CustomMana {
+    F,
+    I,
+    J,
+    K,
+    M,
+    N,
+    O,
+    V,
+}
+
+impl CustomMana {
+    /// Every custom mana letter, in alphabetical order
+    pub const ALL: [CustomMana; 8] = [
+        CustomMana::F,
+        CustomMana::I,
+        CustomMana::J,
+        CustomMana::K,
+        CustomMana::M,
+        CustomMana::N,
+        CustomMana::O,
+        CustomMana::V,
+    ];
+
+    /// Parse a custom mana letter, e.g. `O`.
+    #[must_use]
+    pub fn from_letter(s: &str) -> Option<CustomMana> {
+        CustomMana::ALL
+            .into_iter()
+            .find(|custom| s.len() == 1 && s.starts_with(custom.letter()))
+    }
+
+    /// The letter written in the symbol, without braces
+    #[must_use]
+    pub fn letter(self) -> char {
+        match self {
+            CustomMana::F => 'F',
+            CustomMana::I => 'I',
+            CustomMana::J => 'J',
+            CustomMana::K => 'K',
+            CustomMana::M => 'M',
+            CustomMana::N => 'N',
+            CustomMana::O => 'O',
+            CustomMana::V => 'V',
+        }
+    }
+
+    /// The frame color name of cards of only this color, used in CSS
+    /// classes like `frame-custom-o`
+    #[must_use]
+    pub fn frame_color(self) -> &'static str {
+        match self {
+            CustomMana::F => "custom-f",
+            CustomMana::I => "custom-i",
+            CustomMana::J => "custom-j",
+            CustomMana::K => "custom-k",
+            CustomMana::M => "custom-m",
+            CustomMana::N => "custom-n",
+            CustomMana::O => "custom-o",
+            CustomMana::V => "custom-v",
+        }
+    }
 }
 
 /// Represents any symbol that can appear in a cost (casting or action)
 #[derive(Facet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "fuzz", derive(arbitrary::Arbitrary))]
 #[repr(u8)]
 pub enum ManaSymbol {
     Casting(CastingManaSymbol),
@@ -62,6 +264,41 @@ pub enum ManaSymbol {
     Untap,
     Energy,
     Chaos,
+    /// Generic Phyrexian mana `{P}`, used in reminder text
+    Phyrexian,
+    /// Planeswalker symbol `{PW}`
+    Planeswalker,
+    /// Acorn counter `{A}`
+    Acorn,
+    /// Ticket counter `{TK}`
+    Ticket,
+    /// Legendary symbol `{L}`
+    Legendary,
+    /// Land drop symbol `{D}`
+    LandDrop,
+}
+
+impl ManaSymbol {
+    /// Parse any symbol that can appear in rules text or action costs
+    /// (without braces).
+    ///
+    /// Non-mana symbols like `{T}` are tried first, then casting symbols.
+    #[must_use = "parsing returns a Result that should be handled"]
+    pub fn parse_symbol(content: &str) -> Result<ManaSymbol, ManaCostParseError> {
+        match content {
+            "T" => Ok(ManaSymbol::Tap),
+            "Q" => Ok(ManaSymbol::Untap),
+            "E" => Ok(ManaSymbol::Energy),
+            "CHAOS" => Ok(ManaSymbol::Chaos),
+            "P" => Ok(ManaSymbol::Phyrexian),
+            "PW" => Ok(ManaSymbol::Planeswalker),
+            "A" => Ok(ManaSymbol::Acorn),
+            "TK" => Ok(ManaSymbol::Ticket),
+            "L" => Ok(ManaSymbol::Legendary),
+            "D" => Ok(ManaSymbol::LandDrop),
+            s => CastingManaCost::parse_symbol(s).map(ManaSymbol::Casting),
+        }
+    }
 }
 
 impl From<CastingManaSymbol> for ManaSymbol {
@@ -104,6 +341,27 @@ impl fmt::Display for CastingManaSymbol {
             CastingManaSymbol::PhyrexianBlack => write!(f, "{{B/P}}"),
             CastingManaSymbol::PhyrexianRed => write!(f, "{{R/P}}"),
             CastingManaSymbol::PhyrexianGreen => write!(f, "{{G/P}}"),
+            CastingManaSymbol::PhyrexianColorless => write!(f, "{{C/P}}"),
+            CastingManaSymbol::PhyrexianWhiteBlue => write!(f, "{{W/U/P}}"),
+            CastingManaSymbol::PhyrexianWhiteBlack => write!(f, "{{W/B/P}}"),
+            CastingManaSymbol::PhyrexianWhiteRed => write!(f, "{{W/R/P}}"),
+            CastingManaSymbol::PhyrexianWhiteGreen => write!(f, "{{W/G/P}}"),
+            CastingManaSymbol::PhyrexianBlueBlack => write!(f, "{{U/B/P}}"),
+            CastingManaSymbol::PhyrexianBlueRed => write!(f, "{{U/R/P}}"),
+            CastingManaSymbol::PhyrexianBlueGreen => write!(f, "{{U/G/P}}"),
+            CastingManaSymbol::PhyrexianBlackRed => write!(f, "{{B/R/P}}"),
+            CastingManaSymbol::PhyrexianBlackGreen => write!(f, "{{B/G/P}}"),
+            CastingManaSymbol::PhyrexianRedGreen => write!(f, "{{R/G/P}}"),
+            CastingManaSymbol::ColorlessWhite => write!(f, "{{C/W}}"),
+            CastingManaSymbol::ColorlessBlue => write!(f, "{{C/U}}"),
+            CastingManaSymbol::ColorlessBlack => write!(f, "{{C/B}}"),
+            CastingManaSymbol::ColorlessRed => write!(f, "{{C/R}}"),
+            CastingManaSymbol::ColorlessGreen => write!(f, "{{C/G}}"),
+            CastingManaSymbol::HalfWhite => write!(f, "{{HW}}"),
+            CastingManaSymbol::HalfRed => write!(f, "{{HR}}"),
+            CastingManaSymbol::Half => write!(f, "{{½}}"),
+            CastingManaSymbol::Infinity => write!(f, "{{∞}}"),
+            CastingManaSymbol::Custom(custom) => write!(f, "{{{}}}", custom.letter()),
         }
     }
 }
@@ -116,12 +374,19 @@ impl fmt::Display for ManaSymbol {
             ManaSymbol::Untap => write!(f, "{{Q}}"),
             ManaSymbol::Energy => write!(f, "{{E}}"),
             ManaSymbol::Chaos => write!(f, "{{CHAOS}}"),
+            ManaSymbol::Phyrexian => write!(f, "{{P}}"),
+            ManaSymbol::Planeswalker => write!(f, "{{PW}}"),
+            ManaSymbol::Acorn => write!(f, "{{A}}"),
+            ManaSymbol::Ticket => write!(f, "{{TK}}"),
+            ManaSymbol::Legendary => write!(f, "{{L}}"),
+            ManaSymbol::LandDrop => write!(f, "{{D}}"),
         }
     }
 }
 
 /// A mana cost specifically for casting a spell (corner symbols)
 #[derive(Facet, Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "fuzz", derive(arbitrary::Arbitrary))]
 #[facet(proxy = CastingManaCostProxy)]
 pub struct CastingManaCost {
     pub symbols: Vec<CastingManaSymbol>,
@@ -168,6 +433,7 @@ impl TryFrom<&Option<CastingManaCost>> for CastingManaCostProxy {
 
 /// A general cost that can include actions like tap/untap (rules text)
 #[derive(Facet, Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "fuzz", derive(arbitrary::Arbitrary))]
 #[facet(proxy = ActionCostProxy)]
 pub struct ActionCost {
     pub symbols: Vec<ManaSymbol>,
@@ -193,6 +459,7 @@ impl TryFrom<&ActionCost> for ActionCostProxy {
 
 /// Represents a loyalty cost for planeswalker abilities
 #[derive(Facet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "fuzz", derive(arbitrary::Arbitrary))]
 #[repr(u8)]
 #[facet(proxy = LoyaltyCostProxy)]
 pub enum LoyaltyCost {
@@ -268,6 +535,7 @@ impl fmt::Display for LoyaltyCost {
 
 /// Represents a starting loyalty value
 #[derive(Facet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "fuzz", derive(arbitrary::Arbitrary))]
 #[repr(u8)]
 #[facet(proxy = LoyaltyValueProxy)]
 pub enum LoyaltyValue {
@@ -367,37 +635,41 @@ impl fmt::Display for LoyaltyValue {
     }
 }
 
+/// Parse a run of braced symbols like `{2}{U}`, allowing whitespace between
+/// them. Error positions are byte offsets into `input`.
+fn parse_braced_symbols<T>(
+    input: &str,
+    parse_symbol: impl Fn(&str) -> Result<T, ManaCostParseError>,
+) -> Result<Vec<T>, ManaCostParseError> {
+    let mut symbols = Vec::new();
+    let mut position = 0;
+
+    while let Some(c) = input[position..].chars().next() {
+        if c == '{' {
+            let start = position + 1;
+            let end = input[start..]
+                .find('}')
+                .ok_or(ManaCostParseError::UnclosedBrace { position })?;
+            symbols.push(parse_symbol(&input[start..start + end])?);
+            position = start + end + 1;
+        } else if c.is_ascii_whitespace() {
+            position += 1;
+        } else {
+            return Err(ManaCostParseError::UnexpectedCharacter {
+                character: c,
+                position,
+            });
+        }
+    }
+
+    Ok(symbols)
+}
+
 impl CastingManaCost {
     /// Parse a mana cost string like "{2}{U}{U}" into a CastingManaCost
     #[must_use = "parsing returns a Result that should be handled"]
     pub fn parse(input: &str) -> Result<Self, ManaCostParseError> {
-        let mut symbols = Vec::new();
-        let bytes = input.as_bytes();
-        let mut i = 0;
-
-        while i < bytes.len() {
-            if bytes[i] == b'{' {
-                let start = i + 1;
-                let end = bytes[start..]
-                    .iter()
-                    .position(|&b| b == b'}')
-                    .ok_or(ManaCostParseError::UnclosedBrace { position: i })?;
-                let content = std::str::from_utf8(&bytes[start..start + end])
-                    .map_err(|_| ManaCostParseError::InvalidUtf8)?;
-
-                let symbol = Self::parse_symbol(content)?;
-                symbols.push(symbol);
-                i = start + end + 1;
-            } else if bytes[i].is_ascii_whitespace() {
-                i += 1;
-            } else {
-                return Err(ManaCostParseError::UnexpectedCharacter {
-                    character: bytes[i] as char,
-                    position: i,
-                });
-            }
-        }
-
+        let symbols = parse_braced_symbols(input, Self::parse_symbol)?;
         Ok(CastingManaCost { symbols })
     }
 
@@ -438,10 +710,35 @@ impl CastingManaCost {
             "B/P" => Ok(CastingManaSymbol::PhyrexianBlack),
             "R/P" => Ok(CastingManaSymbol::PhyrexianRed),
             "G/P" => Ok(CastingManaSymbol::PhyrexianGreen),
-            // Generic numbers
+            "C/P" => Ok(CastingManaSymbol::PhyrexianColorless),
+            // Hybrid Phyrexian (both orderings for compatibility)
+            "W/U/P" | "U/W/P" => Ok(CastingManaSymbol::PhyrexianWhiteBlue),
+            "W/B/P" | "B/W/P" => Ok(CastingManaSymbol::PhyrexianWhiteBlack),
+            "W/R/P" | "R/W/P" => Ok(CastingManaSymbol::PhyrexianWhiteRed),
+            "W/G/P" | "G/W/P" => Ok(CastingManaSymbol::PhyrexianWhiteGreen),
+            "U/B/P" | "B/U/P" => Ok(CastingManaSymbol::PhyrexianBlueBlack),
+            "U/R/P" | "R/U/P" => Ok(CastingManaSymbol::PhyrexianBlueRed),
+            "U/G/P" | "G/U/P" => Ok(CastingManaSymbol::PhyrexianBlueGreen),
+            "B/R/P" | "R/B/P" => Ok(CastingManaSymbol::PhyrexianBlackRed),
+            "B/G/P" | "G/B/P" => Ok(CastingManaSymbol::PhyrexianBlackGreen),
+            "R/G/P" | "G/R/P" => Ok(CastingManaSymbol::PhyrexianRedGreen),
+            // Colorless hybrid
+            "C/W" => Ok(CastingManaSymbol::ColorlessWhite),
+            "C/U" => Ok(CastingManaSymbol::ColorlessBlue),
+            "C/B" => Ok(CastingManaSymbol::ColorlessBlack),
+            "C/R" => Ok(CastingManaSymbol::ColorlessRed),
+            "C/G" => Ok(CastingManaSymbol::ColorlessGreen),
+            // Half and infinite mana
+            "HW" => Ok(CastingManaSymbol::HalfWhite),
+            "HR" => Ok(CastingManaSymbol::HalfRed),
+            "½" => Ok(CastingManaSymbol::Half),
+            "∞" => Ok(CastingManaSymbol::Infinity),
+            // Generic numbers, then custom colors
             s => {
                 if let Ok(num) = s.parse::<u32>() {
                     Ok(CastingManaSymbol::Generic(num))
+                } else if let Some(custom) = CustomMana::from_letter(s) {
+                    Ok(CastingManaSymbol::Custom(custom))
                 } else {
                     Err(ManaCostParseError::UnknownSymbol {
                         symbol: s.to_string(),
@@ -451,6 +748,16 @@ impl CastingManaCost {
         }
     }
 
+    /// Returns the cost's mana value, see
+    /// [`CastingManaSymbol::mana_value_contribution`].
+    #[must_use]
+    pub fn mana_value(&self) -> f64 {
+        self.symbols
+            .iter()
+            .map(|symbol| symbol.mana_value_contribution())
+            .sum()
+    }
+
     /// Get the total generic mana cost (including all numeric symbols)
     #[must_use]
     pub fn generic_cost(&self) -> u32 {
@@ -460,7 +767,7 @@ impl CastingManaCost {
                 CastingManaSymbol::Generic(n) => Some(*n),
                 _ => None,
             })
-            .sum()
+            .fold(0, u32::saturating_add)
     }
 
     /// Check if this mana cost contains a variable component (X, Y, or Z)
@@ -497,53 +804,12 @@ impl ActionCost {
     /// Parse an action cost string like "{T}{1}{U}" into an ActionCost
     #[must_use = "parsing returns a Result that should be handled"]
     pub fn parse(input: &str) -> Result<Self, ManaCostParseError> {
-        let mut symbols = Vec::new();
-        let bytes = input.as_bytes();
-        let mut i = 0;
-
-        while i < bytes.len() {
-            if bytes[i] == b'{' {
-                let start = i + 1;
-                let end = bytes[start..]
-                    .iter()
-                    .position(|&b| b == b'}')
-                    .ok_or(ManaCostParseError::UnclosedBrace { position: i })?;
-                let content = std::str::from_utf8(&bytes[start..start + end])
-                    .map_err(|_| ManaCostParseError::InvalidUtf8)?;
-
-                let symbol = Self::parse_symbol(content)?;
-                symbols.push(symbol);
-                i = start + end + 1;
-            } else if bytes[i].is_ascii_whitespace() {
-                i += 1;
-            } else {
-                return Err(ManaCostParseError::UnexpectedCharacter {
-                    character: bytes[i] as char,
-                    position: i,
-                });
-            }
-        }
-
+        let symbols = parse_braced_symbols(input, Self::parse_symbol)?;
         Ok(ActionCost { symbols })
     }
 
     fn parse_symbol(content: &str) -> Result<ManaSymbol, ManaCostParseError> {
-        match content {
-            "T" => Ok(ManaSymbol::Tap),
-            "Q" => Ok(ManaSymbol::Untap),
-            "E" => Ok(ManaSymbol::Energy),
-            "CHAOS" => Ok(ManaSymbol::Chaos),
-            // Fallback to mana symbol parsing if it matches
-            s => {
-                if let Ok(mana) = CastingManaCost::parse_symbol(s) {
-                    Ok(ManaSymbol::Casting(mana))
-                } else {
-                    Err(ManaCostParseError::UnknownSymbol {
-                        symbol: s.to_string(),
-                    })
-                }
-            }
-        }
+        ManaSymbol::parse_symbol(content)
     }
 }
 
@@ -605,7 +871,7 @@ impl std::error::Error for ManaCostParseError {}
 // Rules Text Types
 // ============================================================================
 
-/// A segment of rules text - either plain text or a mana/action symbol
+/// A segment of rules text - plain text, a mana/action symbol, or oracle markup
 #[derive(Facet, Debug, Clone, PartialEq, Eq)]
 #[repr(C)]
 pub enum RulesTextSegment {
@@ -613,6 +879,28 @@ pub enum RulesTextSegment {
     Text(String),
     /// A mana or action symbol (tap, untap, energy, etc.)
     Symbol(ManaSymbol),
+    /// Parenthesized reminder text, printed in italics. The parentheses are
+    /// not part of the content.
+    Reminder(Vec<ReminderSegment>),
+    /// An ability word (Landfall, Threshold, ...) leading a paragraph, printed
+    /// in italics. The following " — " is kept in the next text segment.
+    AbilityWord(String),
+    /// Text marked as italic, `*like this*` or `<i>like this</i>`
+    Italic(String),
+    /// Text marked as bold, `**like this**` or `<b>like this</b>`
+    Bold(String),
+    /// A line break between two paragraphs of rules text
+    ParagraphBreak,
+}
+
+/// A segment inside reminder text - either plain text or a symbol
+#[derive(Facet, Debug, Clone, PartialEq, Eq)]
+#[repr(C)]
+pub enum ReminderSegment {
+    /// Plain text content
+    Text(String),
+    /// A mana or action symbol
+    Symbol(ManaSymbol),
 }
 
 /// Parsed rules text that contains a sequence of text and symbol segments.
@@ -644,14 +932,12 @@ impl TryFrom<&RulesText> for RulesTextProxy {
     }
 }
 
+/// An empty string is empty rules text, not missing rules text, so that an
+/// empty text reads back as it was written.
 impl TryFrom<RulesTextProxy> for Option<RulesText> {
     type Error = String;
     fn try_from(proxy: RulesTextProxy) -> Result<Self, Self::Error> {
-        if proxy.0.is_empty() {
-            Ok(None)
-        } else {
-            Ok(Some(RulesText::try_from(proxy)?))
-        }
+        Ok(Some(RulesText::try_from(proxy)?))
     }
 }
 
@@ -670,6 +956,15 @@ impl RulesText {
     ///
     /// Text outside of `{...}` becomes `RulesTextSegment::Text`.
     /// Content inside `{...}` is parsed as a mana/action symbol.
+    /// Text in parentheses on one line, like `(It can't be blocked.)`,
+    /// becomes `RulesTextSegment::Reminder`; an unclosed `(` is plain text.
+    /// Outside reminder text, `*italic*` and `**bold**` text, or the same
+    /// with `<i>` and `<b>` tags, becomes `RulesTextSegment::Italic` and
+    /// `RulesTextSegment::Bold`. Marked text holds no symbols and ends on
+    /// its line; markers without a partner are plain text.
+    ///
+    /// Each newline becomes a `RulesTextSegment::ParagraphBreak`, so every
+    /// line of the text is its own paragraph.
     ///
     /// # Errors
     ///
@@ -677,56 +972,112 @@ impl RulesText {
     #[must_use = "parsing returns a Result that should be handled"]
     pub fn parse(input: &str) -> Result<Self, ManaCostParseError> {
         let mut segments = Vec::new();
-        let mut current_text = String::new();
-        let bytes = input.as_bytes();
-        let mut i = 0;
-
-        while i < bytes.len() {
-            if bytes[i] == b'{' {
-                // Flush any accumulated text
-                if !current_text.is_empty() {
-                    segments.push(RulesTextSegment::Text(std::mem::take(&mut current_text)));
-                }
+        // Where the text not yet parsed starts, and where to look for the
+        // next reminder from
+        let mut start = 0;
+        let mut search = 0;
+
+        while let Some(open) = input[search..].find('(').map(|i| search + i) {
+            // Reminder text ends on the line it starts on
+            let close = input[open..]
+                .find([')', '\n'])
+                .map(|i| open + i)
+                .filter(|&close| input[close..].starts_with(')'));
+            let Some(close) = close else {
+                search = open + 1;
+                continue;
+            };
+
+            segments.extend(markup(Self::parse_symbols(&input[start..open], start)?));
+            let reminder = Self::parse_symbols(&input[open + 1..close], open + 1)?
+                .into_iter()
+                .filter_map(|segment| match segment {
+                    RulesTextSegment::Text(text) => Some(ReminderSegment::Text(text)),
+                    RulesTextSegment::Symbol(symbol) => Some(ReminderSegment::Symbol(symbol)),
+                    _ => None,
+                })
+                .collect();
+            segments.push(RulesTextSegment::Reminder(reminder));
+            start = close + 1;
+            search = start;
+        }
+        segments.extend(markup(Self::parse_symbols(&input[start..], start)?));
 
-                let start = i + 1;
-                let end = bytes[start..]
+        Ok(RulesText {
+            segments: split_paragraphs(segments),
+        })
+    }
+
+    /// Returns the casting symbols in the text, like the `{G}` of
+    /// `{T}: Add {G}.`, leaving out those in reminder text.
+    pub fn mana_symbols(&self) -> impl Iterator<Item = CastingManaSymbol> + '_ {
+        self.segments.iter().filter_map(|segment| match segment {
+            RulesTextSegment::Symbol(ManaSymbol::Casting(symbol)) => Some(*symbol),
+            _ => None,
+        })
+    }
+
+    /// Returns the segments of each paragraph, leaving out blank ones.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mtg_gen::RulesText;
+    ///
+    /// let rules = RulesText::parse("Flying\n\nWhen this enters, draw a card.").unwrap();
+    /// assert_eq!(rules.paragraphs().count(), 2);
+    /// ```
+    pub fn paragraphs(&self) -> impl Iterator<Item = &[RulesTextSegment]> {
+        self.segments
+            .split(|segment| matches!(segment, RulesTextSegment::ParagraphBreak))
+            .filter(|paragraph| {
+                paragraph
                     .iter()
-                    .position(|&b| b == b'}')
-                    .ok_or(ManaCostParseError::UnclosedBrace { position: i })?;
-                let content = std::str::from_utf8(&bytes[start..start + end])
-                    .map_err(|_| ManaCostParseError::InvalidUtf8)?;
-
-                let symbol = Self::parse_symbol(content)?;
-                segments.push(RulesTextSegment::Symbol(symbol));
-                i = start + end + 1;
-            } else {
-                // Regular character - accumulate into current text
-                current_text.push(bytes[i] as char);
-                i += 1;
+                    .any(|segment| !matches!(segment, RulesTextSegment::Text(text) if text.trim().is_empty()))
+            })
+    }
+
+    /// Split `input` into text and symbol segments. `offset` is where
+    /// `input` starts in the whole text, for error positions.
+    fn parse_symbols(
+        input: &str,
+        mut offset: usize,
+    ) -> Result<Vec<RulesTextSegment>, ManaCostParseError> {
+        let mut segments = Vec::new();
+        let mut rest = input;
+
+        while let Some(open) = rest.find('{') {
+            // Flush any text before the symbol
+            if open > 0 {
+                segments.push(RulesTextSegment::Text(rest[..open].to_string()));
             }
+
+            let content_start = open + 1;
+            let close =
+                rest[content_start..]
+                    .find('}')
+                    .ok_or(ManaCostParseError::UnclosedBrace {
+                        position: offset + open,
+                    })?;
+            let symbol = Self::parse_symbol(&rest[content_start..content_start + close])?;
+            segments.push(RulesTextSegment::Symbol(symbol));
+
+            let consumed = content_start + close + 1;
+            offset += consumed;
+            rest = &rest[consumed..];
         }
 
         // Flush any remaining text
-        if !current_text.is_empty() {
-            segments.push(RulesTextSegment::Text(current_text));
+        if !rest.is_empty() {
+            segments.push(RulesTextSegment::Text(rest.to_string()));
         }
 
-        Ok(RulesText { segments })
+        Ok(segments)
     }
 
     /// Parse a single symbol from its string representation (without braces).
     fn parse_symbol(content: &str) -> Result<ManaSymbol, ManaCostParseError> {
-        // First try action-specific symbols
-        match content {
-            "T" => return Ok(ManaSymbol::Tap),
-            "Q" => return Ok(ManaSymbol::Untap),
-            "E" => return Ok(ManaSymbol::Energy),
-            "CHAOS" => return Ok(ManaSymbol::Chaos),
-            _ => {}
-        }
-
-        // Fall back to casting mana symbols
-        CastingManaCost::parse_symbol(content).map(ManaSymbol::Casting)
+        ManaSymbol::parse_symbol(content)
     }
 
     /// Returns true if this rules text is empty (no segments).
@@ -740,6 +1091,267 @@ impl RulesText {
     pub fn len(&self) -> usize {
         self.segments.len()
     }
+
+    /// Returns the number of words, counting a run of symbols and text with
+    /// no space between them (like `{T}:`) as one word.
+    #[must_use]
+    pub fn word_count(&self) -> usize {
+        self.paragraph_words().iter().map(Vec::len).sum()
+    }
+
+    /// Estimate how many lines this text wraps to when a line fits
+    /// `width_chars` characters.
+    ///
+    /// Words wrap greedily, each paragraph starts a new line and a symbol
+    /// takes up [`SYMBOL_WIDTH_CHARS`] characters. Real glyph widths vary, so
+    /// this is a heuristic for comparing texts, not a layout.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mtg_gen::RulesText;
+    ///
+    /// let text = RulesText::parse("{T}: Add {G}.\nFlying").unwrap();
+    /// assert_eq!(text.estimated_lines(40), 2);
+    /// assert_eq!(text.estimated_lines(5), 5);
+    /// ```
+    #[must_use]
+    pub fn estimated_lines(&self, width_chars: usize) -> usize {
+        if self.is_empty() {
+            return 0;
+        }
+        let width = width_chars.max(1);
+        self.paragraph_words()
+            .iter()
+            .map(|words| {
+                let mut lines = 1;
+                let mut line = 0;
+                for &word in words {
+                    if line > 0 && line + 1 + word <= width {
+                        line += 1 + word;
+                        continue;
+                    }
+                    if line > 0 {
+                        lines += 1;
+                    }
+                    // A word longer than a line is broken across lines
+                    lines += (word - 1) / width;
+                    line = (word - 1) % width + 1;
+                }
+                lines
+            })
+            .sum()
+    }
+
+    /// Returns the width in characters of each word, grouped by paragraph.
+    fn paragraph_words(&self) -> Vec<Vec<usize>> {
+        let mut words = WordWidths::default();
+        for segment in &self.segments {
+            match segment {
+                RulesTextSegment::Text(text)
+                | RulesTextSegment::AbilityWord(text)
+                | RulesTextSegment::Italic(text)
+                | RulesTextSegment::Bold(text) => {
+                    words.text(text);
+                }
+                RulesTextSegment::Symbol(_) => words.symbol(),
+                RulesTextSegment::Reminder(reminder) => {
+                    words.text("(");
+                    for part in reminder {
+                        match part {
+                            ReminderSegment::Text(text) => words.text(text),
+                            ReminderSegment::Symbol(_) => words.symbol(),
+                        }
+                    }
+                    words.text(")");
+                }
+                RulesTextSegment::ParagraphBreak => words.paragraph_break(),
+            }
+        }
+        words.finish()
+    }
+}
+
+/// Split the text segments of `segments` at each newline, with a
+/// [`RulesTextSegment::ParagraphBreak`] in place of the newline.
+fn split_paragraphs(segments: Vec<RulesTextSegment>) -> Vec<RulesTextSegment> {
+    let mut out = Vec::new();
+    for segment in segments {
+        let RulesTextSegment::Text(text) = segment else {
+            out.push(segment);
+            continue;
+        };
+        for (i, line) in text.split('\n').enumerate() {
+            if i > 0 {
+                out.push(RulesTextSegment::ParagraphBreak);
+            }
+            if !line.is_empty() {
+                out.push(RulesTextSegment::Text(line.to_string()));
+            }
+        }
+    }
+    out
+}
+
+/// Markers of marked-up text: opening, closing, and whether it's bold
+const MARKUP: [(&str, &str, bool); 4] = [
+    ("<i>", "</i>", false),
+    ("<b>", "</b>", true),
+    ("**", "**", true),
+    ("*", "*", false),
+];
+
+/// Split `text` into plain, italic and bold text segments, marked up as in
+/// rules text. Used for flavor text, which holds no symbols.
+#[must_use]
+pub fn parse_markup(text: &str) -> Vec<RulesTextSegment> {
+    markup(vec![RulesTextSegment::Text(text.to_string())])
+}
+
+/// Returns `text` with its italic and bold markers removed.
+#[must_use]
+pub fn strip_markup(text: &str) -> String {
+    parse_markup(text)
+        .into_iter()
+        .map(|segment| match segment {
+            RulesTextSegment::Text(text)
+            | RulesTextSegment::Italic(text)
+            | RulesTextSegment::Bold(text) => text,
+            _ => String::new(),
+        })
+        .collect()
+}
+
+/// Split the text segments of `segments` into plain, italic and bold text,
+/// see [`RulesText::parse`].
+fn markup(segments: Vec<RulesTextSegment>) -> Vec<RulesTextSegment> {
+    let mut out = Vec::new();
+    for segment in segments {
+        let RulesTextSegment::Text(text) = segment else {
+            out.push(segment);
+            continue;
+        };
+        let mut plain = String::new();
+        let mut rest = text.as_str();
+        while !rest.is_empty() {
+            let marked = MARKUP.iter().find_map(|&(open, close, bold)| {
+                let inner = rest.strip_prefix(open)?;
+                let end = inner.find(close)?;
+                let content = &inner[..end];
+                // `*` only marks text that hugs it, so `2 * 3 * 4` stays plain
+                let hugs = open != "*"
+                    || !(content.starts_with(char::is_whitespace)
+                        || content.ends_with(char::is_whitespace));
+                (!content.is_empty() && !content.contains('\n') && hugs)
+                    .then(|| (content, &inner[end + close.len()..], bold))
+            });
+            match marked {
+                Some((content, after, bold)) => {
+                    if !plain.is_empty() {
+                        out.push(RulesTextSegment::Text(std::mem::take(&mut plain)));
+                    }
+                    out.push(if bold {
+                        RulesTextSegment::Bold(content.to_string())
+                    } else {
+                        RulesTextSegment::Italic(content.to_string())
+                    });
+                    rest = after;
+                }
+                None => {
+                    let mut chars = rest.chars();
+                    plain.extend(chars.next());
+                    rest = chars.as_str();
+                }
+            }
+        }
+        if !plain.is_empty() {
+            out.push(RulesTextSegment::Text(plain));
+        }
+    }
+    out
+}
+
+/// How many characters of text an inline symbol takes up, for
+/// [`RulesText::estimated_lines`]
+pub const SYMBOL_WIDTH_CHARS: usize = 2;
+
+/// Collects word widths for [`RulesText::paragraph_words`]
+struct WordWidths {
+    paragraphs: Vec<Vec<usize>>,
+    word: usize,
+}
+
+impl Default for WordWidths {
+    fn default() -> Self {
+        WordWidths {
+            paragraphs: vec![Vec::new()],
+            word: 0,
+        }
+    }
+}
+
+impl WordWidths {
+    fn text(&mut self, text: &str) {
+        for c in text.chars() {
+            if c == '\n' {
+                self.paragraph_break();
+            } else if c.is_whitespace() {
+                self.end_word();
+            } else {
+                self.word += 1;
+            }
+        }
+    }
+
+    fn symbol(&mut self) {
+        self.word += SYMBOL_WIDTH_CHARS;
+    }
+
+    fn end_word(&mut self) {
+        if self.word > 0 {
+            if let Some(paragraph) = self.paragraphs.last_mut() {
+                paragraph.push(self.word);
+            }
+            self.word = 0;
+        }
+    }
+
+    fn paragraph_break(&mut self) {
+        self.end_word();
+        self.paragraphs.push(Vec::new());
+    }
+
+    fn finish(mut self) -> Vec<Vec<usize>> {
+        self.end_word();
+        self.paragraphs
+    }
+}
+
+/// Generates only text, symbol and paragraph break segments, without
+/// parentheses or markup, the way [`RulesText::parse`] splits them, so parsing a generated text's
+/// `to_string()` gives it back.
+#[cfg(feature = "fuzz")]
+impl<'a> arbitrary::Arbitrary<'a> for RulesText {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let mut segments = Vec::new();
+        for _ in 0..u.int_in_range(0..=16)? {
+            if u.arbitrary()? {
+                segments.push(RulesTextSegment::Symbol(u.arbitrary()?));
+                continue;
+            }
+            let text: String = u
+                .arbitrary::<String>()?
+                .replace(['{', '}', '(', ')', '*', '<'], "");
+            match segments.last_mut() {
+                _ if text.is_empty() => {}
+                Some(RulesTextSegment::Text(previous)) => previous.push_str(&text),
+                _ => segments.push(RulesTextSegment::Text(text)),
+            }
+        }
+        Ok(RulesText {
+            segments: split_paragraphs(segments),
+        })
+    }
 }
 
 impl fmt::Display for RulesText {
@@ -748,6 +1360,27 @@ impl fmt::Display for RulesText {
             match segment {
                 RulesTextSegment::Text(text) => write!(f, "{}", text)?,
                 RulesTextSegment::Symbol(symbol) => write!(f, "{}", symbol)?,
+                RulesTextSegment::Reminder(reminder) => {
+                    write!(f, "(")?;
+                    for part in reminder {
+                        match part {
+                            ReminderSegment::Text(text) => write!(f, "{}", text)?,
+                            ReminderSegment::Symbol(symbol) => write!(f, "{}", symbol)?,
+                        }
+                    }
+                    write!(f, ")")?;
+                }
+                RulesTextSegment::AbilityWord(word) => write!(f, "{}", word)?,
+                // Tags when the text has asterisks of its own
+                RulesTextSegment::Italic(text) if text.contains('*') => {
+                    write!(f, "<i>{}</i>", text)?;
+                }
+                RulesTextSegment::Italic(text) => write!(f, "*{}*", text)?,
+                RulesTextSegment::Bold(text) if text.contains('*') => {
+                    write!(f, "<b>{}</b>", text)?;
+                }
+                RulesTextSegment::Bold(text) => write!(f, "**{}**", text)?,
+                RulesTextSegment::ParagraphBreak => writeln!(f)?,
             }
         }
         Ok(())
@@ -0,0 +1,78 @@
+//! Lossless PNG optimization of rendered images (`--optimize`)
+//!
+//! Screenshots are taken at 4x scale and Chrome encodes them for speed, not
+//! size, so a set of a few hundred cards is far bigger than it needs to be.
+//! Recompressing with oxipng keeps every pixel and typically saves a third
+//! or more.
+
+use anyhow::{Context, Result};
+use std::fmt;
+use std::path::Path;
+
+/// oxipng preset used for `--optimize`; higher presets take much longer for
+/// little extra gain on card images
+const PRESET: u8 = 2;
+
+/// Sizes before and after optimizing a batch of images
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OptimizeStats {
+    pub files: usize,
+    pub bytes_before: u64,
+    pub bytes_after: u64,
+}
+
+impl OptimizeStats {
+    /// Record one optimized file.
+    pub fn add(&mut self, before: u64, after: u64) {
+        self.files += 1;
+        self.bytes_before += before;
+        self.bytes_after += after;
+    }
+
+    /// Returns the bytes saved across all files.
+    #[must_use]
+    pub fn saved(&self) -> u64 {
+        self.bytes_before.saturating_sub(self.bytes_after)
+    }
+}
+
+impl fmt::Display for OptimizeStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let percent = if self.bytes_before == 0 {
+            0.0
+        } else {
+            self.saved() as f64 * 100.0 / self.bytes_before as f64
+        };
+        write!(
+            f,
+            "Optimized {} image(s): {} KB -> {} KB ({:.1}% smaller)",
+            self.files,
+            self.bytes_before / 1024,
+            self.bytes_after / 1024,
+            percent
+        )
+    }
+}
+
+/// Losslessly recompress the PNG at `path` in place, returning its size
+/// before and after. The file is only rewritten if it got smaller.
+///
+/// This is CPU-bound; call it from a blocking task.
+///
+/// # Errors
+///
+/// Returns an error if the file can't be read or written, or isn't a valid
+/// PNG.
+pub fn optimize_png(path: &Path) -> Result<(u64, u64)> {
+    let data = std::fs::read(path).with_context(|| format!("Failed to read {:?}", path))?;
+    let options = oxipng::Options::from_preset(PRESET);
+    let optimized = oxipng::optimize_from_memory(&data, &options)
+        .map_err(|e| anyhow::anyhow!("Failed to optimize {:?}: {}", path, e))?;
+
+    let before = data.len() as u64;
+    if optimized.len() as u64 >= before {
+        return Ok((before, before));
+    }
+    std::fs::write(path, &optimized).with_context(|| format!("Failed to write {:?}", path))?;
+    Ok((before, optimized.len() as u64))
+}
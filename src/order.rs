@@ -0,0 +1,132 @@
+//! Processing order of card files (`--order`)
+//!
+//! Files are always processed in a sorted order, so logs, manifests and
+//! anything else built from a run come out the same on every machine.
+
+use crate::card::Card;
+use crate::collector::ColorGroup;
+use std::cmp::Ordering;
+use std::path::PathBuf;
+
+/// How card files are ordered before rendering
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SortOrder {
+    /// By file path
+    #[default]
+    Path,
+    /// By card name, ignoring case
+    Name,
+    /// By collector number; cards without one come last, by name
+    Collector,
+    /// By color group, then name: the order collector numbers are assigned in
+    Canonical,
+}
+
+impl SortOrder {
+    /// Parse an order name: `path`, `name`, `collector` or `canonical`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error for any other name.
+    #[must_use = "parsing returns a Result that should be handled"]
+    pub fn parse(s: &str) -> Result<Self, String> {
+        match s.trim().to_lowercase().as_str() {
+            "path" => Ok(SortOrder::Path),
+            "name" => Ok(SortOrder::Name),
+            "collector" => Ok(SortOrder::Collector),
+            "canonical" => Ok(SortOrder::Canonical),
+            other => Err(format!(
+                "Unknown order: {} (expected name, collector, canonical or path)",
+                other
+            )),
+        }
+    }
+
+    /// Whether sorting needs the cards' contents, not just their paths
+    #[must_use]
+    pub fn needs_cards(self) -> bool {
+        self != SortOrder::Path
+    }
+}
+
+/// A card file and the fields it sorts by
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SortEntry {
+    pub path: PathBuf,
+    /// The card's name, if the file parsed
+    pub name: Option<String>,
+    /// The card's collector number, if it has one
+    pub collector_number: Option<String>,
+    /// The card's color group, if the file parsed
+    pub color_group: Option<ColorGroup>,
+}
+
+impl SortEntry {
+    /// An entry for `path`, sorting by the fields of `card` when it parsed.
+    #[must_use]
+    pub fn new(path: PathBuf, card: Option<&Card>) -> Self {
+        Self {
+            path,
+            name: card.map(|card| card.base().name.clone()),
+            collector_number: card.and_then(|card| card.base().collector_number.clone()),
+            color_group: card.map(ColorGroup::of),
+        }
+    }
+}
+
+/// Sort `entries` by `order`. Ties, and files that didn't parse, fall back to
+/// path order, so the result never depends on the order they were found in.
+pub fn sort_entries(entries: &mut [SortEntry], order: SortOrder) {
+    entries.sort_by(|a, b| {
+        let by_order = match order {
+            SortOrder::Path => Ordering::Equal,
+            SortOrder::Name => compare_names(a, b),
+            SortOrder::Collector => compare_present(
+                a.collector_number.as_deref(),
+                b.collector_number.as_deref(),
+                compare_collector_numbers,
+            )
+            .then_with(|| compare_names(a, b)),
+            SortOrder::Canonical => compare_present(a.color_group, b.color_group, |a, b| a.cmp(&b))
+                .then_with(|| compare_names(a, b)),
+        };
+        by_order.then_with(|| a.path.cmp(&b.path))
+    });
+}
+
+fn compare_names(a: &SortEntry, b: &SortEntry) -> Ordering {
+    compare_present(a.name.as_deref(), b.name.as_deref(), |a, b| {
+        a.to_lowercase().cmp(&b.to_lowercase())
+    })
+}
+
+/// Compare two optional values, putting missing ones last.
+fn compare_present<T>(
+    a: Option<T>,
+    b: Option<T>,
+    compare: impl FnOnce(T, T) -> Ordering,
+) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => compare(a, b),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+/// Compare collector numbers by their number first, so `9` comes before `10`
+/// and `12` before `12a`.
+#[must_use]
+pub fn compare_collector_numbers(a: &str, b: &str) -> Ordering {
+    fn split(number: &str) -> (Option<u64>, &str) {
+        let digits = number
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(number.len());
+        (number[..digits].parse().ok(), &number[digits..])
+    }
+    let (a_number, a_rest) = split(a.trim());
+    let (b_number, b_rest) = split(b.trim());
+    // Numbered collector numbers come before ones without a number, like `★`
+    compare_present(a_number, b_number, |a, b| a.cmp(&b))
+        .then_with(|| a_rest.to_lowercase().cmp(&b_rest.to_lowercase()))
+}
@@ -0,0 +1,562 @@
+//! Output file naming and the render manifest
+//!
+//! A single card YAML can produce several images: the card itself, the back
+//! of a double-faced card, emblems it creates. [`OutputNaming`] decides where
+//! each image goes, and [`RenderManifest`] records which images came from
+//! which card so exporters can pair fronts with backs.
+//!
+//! Two runs may share an output directory, e.g. a full render and a single
+//! card re-rendered by hand. [`OutputLock`] makes the second wait for the
+//! first, and [`RenderManifest::save`] merges a run's cards into the manifest
+//! already there instead of replacing it.
+
+use crate::card::Card;
+use crate::profile::RenderTimings;
+use crate::provenance::Provenance;
+use anyhow::Context;
+use facet::Facet;
+use std::fs::{File, TryLockError};
+use std::path::{Path, PathBuf};
+
+/// File name of the manifest written to the output directory
+pub const MANIFEST_FILE_NAME: &str = "manifest.json";
+
+/// File name of the lock held by the run writing to an output directory
+pub const LOCK_FILE_NAME: &str = ".mtg-gen.lock";
+
+/// What a rendered image shows
+#[derive(Facet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum OutputKind {
+    /// The whole card, for layouts printed on a single face
+    #[facet(rename = "card")]
+    Card,
+    /// The front face of a double-faced card
+    #[facet(rename = "front")]
+    Front,
+    /// The back face of a double-faced card
+    #[facet(rename = "back")]
+    Back,
+    /// An emblem created by one of the card's abilities
+    #[facet(rename = "emblem")]
+    Emblem,
+    /// The overlay standing in for the card while it's face down
+    #[facet(rename = "face_down")]
+    FaceDown,
+}
+
+impl OutputKind {
+    /// The face name substituted for `{face}` in templates
+    #[must_use]
+    pub fn face_name(self) -> &'static str {
+        match self {
+            OutputKind::Card => "card",
+            OutputKind::Front => "front",
+            OutputKind::Back => "back",
+            OutputKind::Emblem => "emblem",
+            OutputKind::FaceDown => "face_down",
+        }
+    }
+
+    /// The subfolder used for this kind when outputs are split by kind
+    #[must_use]
+    pub fn subfolder(self) -> &'static str {
+        match self {
+            OutputKind::Card => "cards",
+            OutputKind::Front => "fronts",
+            OutputKind::Back => "backs",
+            OutputKind::Emblem => "emblems",
+            OutputKind::FaceDown => "face_down",
+        }
+    }
+}
+
+/// The image format cards are rendered to (`--format`)
+///
+/// PNG is lossless and the default. JPEG and WebP are much smaller, which
+/// suits uploads to Tabletop Simulator and other online play, at the cost
+/// of some detail; how much is set by the quality.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum ImageFormat {
+    #[default]
+    Png,
+    Jpeg,
+    Webp,
+}
+
+impl ImageFormat {
+    /// Quality JPEG and WebP images are encoded at when none is given
+    pub const DEFAULT_QUALITY: u8 = 90;
+
+    /// Parse a format name: `png`, `jpeg` (or `jpg`) or `webp`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error for any other name.
+    #[must_use = "parsing returns a Result that should be handled"]
+    pub fn parse(s: &str) -> Result<Self, String> {
+        match s.trim().to_lowercase().as_str() {
+            "png" => Ok(ImageFormat::Png),
+            "jpeg" | "jpg" => Ok(ImageFormat::Jpeg),
+            "webp" => Ok(ImageFormat::Webp),
+            other => Err(format!(
+                "Unknown image format: {} (expected png, jpeg or webp)",
+                other
+            )),
+        }
+    }
+
+    /// The image file extension, without a leading dot
+    #[must_use]
+    pub fn extension(self) -> &'static str {
+        match self {
+            ImageFormat::Png => "png",
+            ImageFormat::Jpeg => "jpg",
+            ImageFormat::Webp => "webp",
+        }
+    }
+
+    /// Returns the format with the image file extension `ext`.
+    #[must_use]
+    pub fn from_extension(ext: &str) -> Option<Self> {
+        Self::parse(ext).ok()
+    }
+
+    /// The format's MIME type, e.g. `image/png`
+    #[must_use]
+    pub fn content_type(self) -> &'static str {
+        match self {
+            ImageFormat::Png => "image/png",
+            ImageFormat::Jpeg => "image/jpeg",
+            ImageFormat::Webp => "image/webp",
+        }
+    }
+
+    /// Whether the format loses detail, so a quality applies
+    #[must_use]
+    pub fn is_lossy(self) -> bool {
+        self != ImageFormat::Png
+    }
+}
+
+/// How output files are named.
+///
+/// Templates are file stems without an extension and may use:
+///
+/// - `{name}`: the card's file stem (the YAML file name)
+/// - `{face}`: `front`, `back` or `emblem`
+/// - `{index}`: the 1-based number of the image among images of the same kind
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutputNaming {
+    /// Template for the faces of double-faced cards
+    pub face_template: String,
+    /// Template for emblems
+    pub emblem_template: String,
+    /// Put each kind of output in its own subfolder (`cards/`, `backs/`, ...)
+    pub subfolders: bool,
+}
+
+impl Default for OutputNaming {
+    fn default() -> Self {
+        Self {
+            face_template: "{name}_{face}".to_string(),
+            emblem_template: "{name}_emblem".to_string(),
+            subfolders: false,
+        }
+    }
+}
+
+impl OutputNaming {
+    /// Returns the path of an image of `kind` for the card whose output would
+    /// otherwise be `card_path` (e.g. `set/bolt.png`).
+    ///
+    /// `index` is the image's 0-based position among `count` images of the
+    /// same kind. When a card has several images of one kind and the
+    /// template doesn't use `{index}`, `_{index}` is appended so they don't
+    /// overwrite each other.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mtg_gen::{OutputKind, OutputNaming};
+    /// use std::path::Path;
+    ///
+    /// let naming = OutputNaming::default();
+    /// let card = Path::new("out/delver.png");
+    /// assert_eq!(naming.path_for(card, OutputKind::Card, 0, 1), Path::new("out/delver.png"));
+    /// assert_eq!(naming.path_for(card, OutputKind::Back, 0, 1), Path::new("out/delver_back.png"));
+    /// assert_eq!(naming.path_for(card, OutputKind::Emblem, 1, 2), Path::new("out/delver_emblem_2.png"));
+    /// ```
+    #[must_use]
+    pub fn path_for(
+        &self,
+        card_path: &Path,
+        kind: OutputKind,
+        index: usize,
+        count: usize,
+    ) -> PathBuf {
+        let name = card_path
+            .file_stem()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .into_owned();
+        let extension = card_path
+            .extension()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .into_owned();
+
+        let template = match kind {
+            OutputKind::Card => "{name}",
+            OutputKind::Front | OutputKind::Back => self.face_template.as_str(),
+            OutputKind::Emblem => self.emblem_template.as_str(),
+            OutputKind::FaceDown => "{name}_face_down",
+        };
+        let mut stem = template
+            .replace("{name}", &name)
+            .replace("{face}", kind.face_name())
+            .replace("{index}", &(index + 1).to_string());
+        if count > 1 && !template.contains("{index}") {
+            stem = format!("{}_{}", stem, index + 1);
+        }
+
+        let mut dir = card_path.parent().unwrap_or(Path::new("")).to_path_buf();
+        if self.subfolders {
+            dir.push(kind.subfolder());
+        }
+        dir.join(stem).with_extension(extension)
+    }
+}
+
+/// Returns the path standing in for `card_path` when rendering the card in
+/// `language`, so every image of the translation gets a language suffix.
+///
+/// # Examples
+///
+/// ```
+/// use mtg_gen::output::language_card_path;
+/// use std::path::Path;
+///
+/// assert_eq!(language_card_path(Path::new("out/bolt.png"), "de"), Path::new("out/bolt_de.png"));
+/// ```
+#[must_use]
+pub fn language_card_path(card_path: &Path, language: &str) -> PathBuf {
+    let name = card_path.file_stem().unwrap_or_default().to_string_lossy();
+    let mut file_name = format!("{}_{}", name, language);
+    if let Some(extension) = card_path.extension() {
+        file_name.push('.');
+        file_name.push_str(&extension.to_string_lossy());
+    }
+    card_path.with_file_name(file_name)
+}
+
+/// One rendered image
+#[derive(Facet, Debug, Clone, PartialEq, Eq)]
+pub struct ManifestOutput {
+    /// What the image shows
+    pub kind: OutputKind,
+    /// Path of the image, relative to the output directory
+    pub path: String,
+    /// Public URL of the image, once uploaded
+    #[facet(default)]
+    pub url: Option<String>,
+    /// How long each phase of rendering the image took
+    #[facet(default)]
+    pub timings: Option<RenderTimings>,
+    /// Language code of a translated image; `None` for the card's own text
+    #[facet(default)]
+    pub language: Option<String>,
+    /// Problems the image was rendered despite, like art that failed to load
+    #[facet(default)]
+    pub warnings: Vec<String>,
+}
+
+/// All images rendered from one card file
+#[derive(Facet, Debug, Clone, PartialEq, Eq)]
+pub struct ManifestEntry {
+    /// Path of the card YAML, relative to the input
+    pub source: String,
+    /// The card's name
+    pub name: String,
+    /// Every image rendered for the card; a card's `front` and `back`
+    /// outputs are the two sides of the same physical card
+    pub outputs: Vec<ManifestOutput>,
+}
+
+impl ManifestEntry {
+    /// Returns the path of the first output of `kind`, if any.
+    #[must_use]
+    pub fn output(&self, kind: OutputKind) -> Option<&str> {
+        self.outputs
+            .iter()
+            .find(|output| output.kind == kind)
+            .map(|output| output.path.as_str())
+    }
+}
+
+/// A card's data and images, written next to its images with
+/// `--card-json` so a static site can build each card's page from its own
+/// file
+#[derive(Facet, Debug, Clone, PartialEq, Eq)]
+pub struct CardJson {
+    /// Path of the card YAML, relative to the input
+    pub source: String,
+    /// The card's images, with paths relative to the JSON file
+    pub images: Vec<CardJsonImage>,
+    /// Everything in the card's YAML
+    pub card: Card,
+}
+
+/// One of the images in a [`CardJson`]
+#[derive(Facet, Debug, Clone, PartialEq, Eq)]
+pub struct CardJsonImage {
+    pub kind: OutputKind,
+    /// Path of the image, relative to the JSON file
+    pub path: String,
+    /// Public URL of the image, once uploaded
+    #[facet(default)]
+    pub url: Option<String>,
+    /// Language code of a translated image
+    #[facet(default)]
+    pub language: Option<String>,
+}
+
+impl CardJson {
+    /// The data of the card rendered into `entry`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mtg_gen::output::{CardJson, ManifestEntry, ManifestOutput, OutputKind};
+    /// use mtg_gen::Card;
+    ///
+    /// let card: Card = facet_yaml::from_str(
+    ///     "name: Bolt\ntype: normal\ntype_line: Instant\nrarity: common\n",
+    /// )
+    /// .unwrap();
+    /// let entry = ManifestEntry {
+    ///     source: "red/bolt.yaml".to_string(),
+    ///     name: "Bolt".to_string(),
+    ///     outputs: vec![ManifestOutput {
+    ///         kind: OutputKind::Card,
+    ///         path: "red/bolt.png".to_string(),
+    ///         url: None,
+    ///         timings: None,
+    ///         language: None,
+    ///         warnings: Vec::new(),
+    ///     }],
+    /// };
+    /// let json = CardJson::new(&entry, card);
+    /// assert_eq!(CardJson::path(&entry.source), std::path::Path::new("red/bolt.card.json"));
+    /// assert_eq!(json.images[0].path, "bolt.png");
+    /// ```
+    #[must_use]
+    pub fn new(entry: &ManifestEntry, card: Card) -> Self {
+        let path = Self::path(&entry.source);
+        let dir = path.parent().unwrap_or(Path::new(""));
+        let images = entry
+            .outputs
+            .iter()
+            .map(|output| CardJsonImage {
+                kind: output.kind,
+                path: Path::new(&output.path)
+                    .strip_prefix(dir)
+                    .unwrap_or(Path::new(&output.path))
+                    .display()
+                    .to_string(),
+                url: output.url.clone(),
+                language: output.language.clone(),
+            })
+            .collect();
+        Self {
+            source: entry.source.clone(),
+            images,
+            card,
+        }
+    }
+
+    /// Returns where the data of the card from `source` goes, relative to
+    /// the output directory: beside its images, as `name.card.json`, so it
+    /// doesn't clash with a `.json` alt text sidecar.
+    #[must_use]
+    pub fn path(source: &str) -> PathBuf {
+        Path::new(source).with_extension("card.json")
+    }
+
+    /// Serialize the card's data to JSON.
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        facet_json::to_string(self)
+    }
+}
+
+/// A card file that failed to render
+#[derive(Facet, Debug, Clone, PartialEq, Eq)]
+pub struct ManifestFailure {
+    /// Path of the card YAML, relative to the input
+    pub source: String,
+    /// Why it failed
+    pub error: String,
+}
+
+/// Index of every image rendered in one run
+#[derive(Facet, Debug, Clone, Default, PartialEq, Eq)]
+pub struct RenderManifest {
+    /// Where the run's images came from
+    #[facet(default)]
+    pub provenance: Option<Provenance>,
+    /// The seed generated textures were drawn from, with `--seed`, so the
+    /// run's images can be made again
+    #[facet(default)]
+    pub seed: Option<u64>,
+    /// Whether any card is played face down and has a `face_down` overlay,
+    /// so playtest kits know to include overlays
+    #[facet(default)]
+    pub uses_face_down: bool,
+    pub cards: Vec<ManifestEntry>,
+    /// Cards that failed to render, such as one whose page hung past the
+    /// timeout, so a batch can carry on past them and they can be fixed
+    /// afterwards
+    #[facet(default)]
+    pub failed: Vec<ManifestFailure>,
+}
+
+impl RenderManifest {
+    /// Serialize the manifest to JSON.
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        facet_json::to_string(self)
+    }
+
+    /// Parse a manifest previously written by [`RenderManifest::to_json`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the JSON is malformed.
+    #[must_use = "parsing returns a Result that should be handled"]
+    pub fn from_json(json: &str) -> anyhow::Result<Self> {
+        facet_json::from_str(json).map_err(|e| anyhow::anyhow!("{}", e))
+    }
+
+    /// Merge this run's cards into `earlier`, the manifest of an earlier run
+    /// into the same output directory. A card rendered again replaces its
+    /// earlier entry in place, new cards are added at the end, and earlier
+    /// cards whose images are gone from `output_dir` are dropped. Earlier
+    /// failures are kept until the card is rendered, or fails, again.
+    #[must_use]
+    pub fn merged_into(&self, earlier: RenderManifest, output_dir: &Path) -> RenderManifest {
+        let mut cards: Vec<ManifestEntry> = earlier
+            .cards
+            .into_iter()
+            .filter(|entry| {
+                entry
+                    .outputs
+                    .iter()
+                    .all(|output| output_dir.join(&output.path).is_file())
+            })
+            .collect();
+        for entry in &self.cards {
+            match cards.iter_mut().find(|card| card.source == entry.source) {
+                Some(card) => *card = entry.clone(),
+                None => cards.push(entry.clone()),
+            }
+        }
+        let retried = |source: &str| {
+            self.cards.iter().any(|entry| entry.source == source)
+                || self.failed.iter().any(|failure| failure.source == source)
+        };
+        let mut failed: Vec<ManifestFailure> = earlier
+            .failed
+            .into_iter()
+            .filter(|failure| !retried(&failure.source))
+            .collect();
+        failed.extend(self.failed.iter().cloned());
+        RenderManifest {
+            provenance: self.provenance.clone(),
+            seed: self.seed,
+            uses_face_down: cards
+                .iter()
+                .any(|card| card.output(OutputKind::FaceDown).is_some()),
+            cards,
+            failed,
+        }
+    }
+
+    /// Write the manifest to `output_dir`, merged into the one already there
+    /// (see [`RenderManifest::merged_into`]), and return what was written.
+    /// The file is replaced in one step, so readers never see half of it.
+    ///
+    /// Call this while holding the directory's [`OutputLock`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the manifest can't be written. A manifest already
+    /// there that can't be read or parsed is replaced.
+    pub fn save(&self, output_dir: &Path) -> anyhow::Result<RenderManifest> {
+        let path = output_dir.join(MANIFEST_FILE_NAME);
+        let earlier = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|json| Self::from_json(&json).ok())
+            .unwrap_or_default();
+        let merged = self.merged_into(earlier, output_dir);
+
+        let temp = output_dir.join(format!(".{}.tmp", MANIFEST_FILE_NAME));
+        std::fs::write(&temp, merged.to_json())
+            .with_context(|| format!("Failed to write {:?}", temp))?;
+        std::fs::rename(&temp, &path).with_context(|| format!("Failed to write {:?}", path))?;
+        Ok(merged)
+    }
+}
+
+/// Exclusive use of an output directory, released when dropped.
+///
+/// The lock is taken on [`LOCK_FILE_NAME`] in the directory with the
+/// operating system's file locking, so it is released even if mtg-gen is
+/// killed and a leftover lock file never blocks a later run.
+#[derive(Debug)]
+pub struct OutputLock {
+    _file: File,
+}
+
+impl OutputLock {
+    /// Lock `output_dir`, creating it if needed, or return `None` if another
+    /// run holds the lock.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory or lock file can't be created.
+    pub fn try_acquire(output_dir: &Path) -> anyhow::Result<Option<Self>> {
+        let file = Self::open(output_dir)?;
+        match file.try_lock() {
+            Ok(()) => Ok(Some(Self { _file: file })),
+            Err(TryLockError::WouldBlock) => Ok(None),
+            Err(TryLockError::Error(e)) => {
+                Err(e).with_context(|| format!("Failed to lock {:?}", output_dir))
+            }
+        }
+    }
+
+    /// Lock `output_dir`, creating it if needed, waiting for any other run
+    /// holding the lock to finish.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory or lock file can't be created.
+    pub fn acquire(output_dir: &Path) -> anyhow::Result<Self> {
+        let file = Self::open(output_dir)?;
+        file.lock()
+            .with_context(|| format!("Failed to lock {:?}", output_dir))?;
+        Ok(Self { _file: file })
+    }
+
+    fn open(output_dir: &Path) -> anyhow::Result<File> {
+        std::fs::create_dir_all(output_dir)?;
+        let path = output_dir.join(LOCK_FILE_NAME);
+        File::options()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(&path)
+            .with_context(|| format!("Failed to create {:?}", path))
+    }
+}
@@ -0,0 +1,103 @@
+//! Packaging rendered outputs into a single archive
+//!
+//! The archive holds every image listed in the render manifest plus the
+//! manifest itself, with the same relative paths as the output directory.
+
+use crate::output::{MANIFEST_FILE_NAME, RenderManifest};
+use anyhow::Result;
+use std::fs::File;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Archive format for `--package`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PackageFormat {
+    Zip,
+    TarGz,
+}
+
+impl PackageFormat {
+    /// Parse a format name: `zip`, `tar.gz` or `tgz`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error for any other name.
+    #[must_use = "parsing returns a Result that should be handled"]
+    pub fn parse(s: &str) -> Result<Self, String> {
+        match s.trim().to_lowercase().as_str() {
+            "zip" => Ok(PackageFormat::Zip),
+            "tar.gz" | "tgz" => Ok(PackageFormat::TarGz),
+            other => Err(format!(
+                "Unknown package format: {} (expected zip or tar.gz)",
+                other
+            )),
+        }
+    }
+
+    /// The archive file extension, without a leading dot
+    #[must_use]
+    pub fn extension(self) -> &'static str {
+        match self {
+            PackageFormat::Zip => "zip",
+            PackageFormat::TarGz => "tar.gz",
+        }
+    }
+}
+
+/// Returns the archive path for a package called `name` in `output_dir`.
+#[must_use]
+pub fn archive_path(output_dir: &Path, name: &str, format: PackageFormat) -> PathBuf {
+    output_dir.join(format!("{}.{}", name, format.extension()))
+}
+
+/// Bundle the manifest and every image it lists into `archive`.
+///
+/// Paths in the manifest are relative to `output_dir`, and are stored in
+/// the archive unchanged.
+///
+/// # Errors
+///
+/// Returns an error if a listed file can't be read or the archive can't be
+/// written.
+pub fn package_outputs(
+    output_dir: &Path,
+    manifest: &RenderManifest,
+    format: PackageFormat,
+    archive: &Path,
+) -> Result<()> {
+    let mut entries: Vec<&str> = manifest
+        .cards
+        .iter()
+        .flat_map(|card| card.outputs.iter().map(|output| output.path.as_str()))
+        .collect();
+    entries.push(MANIFEST_FILE_NAME);
+
+    let file = File::create(archive)?;
+    match format {
+        PackageFormat::Zip => {
+            let mut zip = zip::ZipWriter::new(file);
+            let options = zip::write::SimpleFileOptions::default()
+                .compression_method(zip::CompressionMethod::Deflated);
+            for entry in entries {
+                zip.start_file(archive_entry_name(entry), options)?;
+                io::copy(&mut File::open(output_dir.join(entry))?, &mut zip)?;
+            }
+            zip.finish()?;
+        }
+        PackageFormat::TarGz => {
+            let encoder = flate2::write::GzEncoder::new(file, flate2::Compression::default());
+            let mut tar = tar::Builder::new(encoder);
+            for entry in entries {
+                tar.append_path_with_name(output_dir.join(entry), archive_entry_name(entry))?;
+            }
+            tar.into_inner()?.finish()?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Archive entries always use `/` separators, whatever the platform.
+fn archive_entry_name(path: &str) -> String {
+    path.replace('\\', "/")
+}
@@ -0,0 +1,191 @@
+//! Text-only playtest cards (`--playtest`)
+//!
+//! Early in a set's design, cards change faster than frames and art are
+//! worth redoing. Playtest cards are plain black-on-white boxes holding the
+//! text of a card and nothing else: no frame images, art or fonts to load,
+//! so they render quickly and stay readable when printed in grayscale.
+//! Symbols are written as in card YAML, e.g. `{2}{R}`.
+
+use crate::card::{Card, CardBase, CardFace, Rarity};
+use crate::mana::RulesText;
+use crate::utils::to_roman_numeral;
+
+/// The text of one face of a playtest card
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PlaytestFace {
+    pub name: String,
+    pub mana_cost: Option<String>,
+    pub type_line: Option<String>,
+    /// Rules text, one entry per paragraph or ability
+    pub rules_text: Vec<String>,
+    /// Power/toughness, loyalty or defense, as printed in the corner
+    pub stats: Option<String>,
+}
+
+/// A card as text only, every face on the one card
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlaytestCard {
+    /// Every face of the card, the main one first
+    pub faces: Vec<PlaytestFace>,
+    pub rarity: Rarity,
+    pub collector_number: Option<String>,
+}
+
+impl PlaytestCard {
+    /// The playtest card for `card`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mtg_gen::{Card, playtest::PlaytestCard};
+    ///
+    /// let yaml = "name: Bear\ntype: normal\nmana_cost: \"{1}{G}\"\ntype_line: Creature\nrules_text: \"Trample\"\npower: 2\ntoughness: 2\nrarity: common\n";
+    /// let card: Card = facet_yaml::from_str(yaml).unwrap();
+    ///
+    /// let playtest = PlaytestCard::from_card(&card);
+    /// let face = &playtest.faces[0];
+    /// assert_eq!(face.mana_cost.as_deref(), Some("{1}{G}"));
+    /// assert_eq!(face.rules_text, vec!["Trample"]);
+    /// assert_eq!(face.stats.as_deref(), Some("2/2"));
+    /// ```
+    #[must_use]
+    pub fn from_card(card: &Card) -> Self {
+        let base = card.base();
+        let faces = match card {
+            Card::Split(card) => card.faces.iter().map(face).collect(),
+            Card::Flip(card) => card.faces.iter().map(face).collect(),
+            Card::Transform(card) => card.faces.iter().map(face).collect(),
+            Card::ModalDfc(card) => card.faces.iter().map(face).collect(),
+            Card::Meld(card) => card.faces.iter().map(face).collect(),
+            _ => Self::single_card_faces(card, base),
+        };
+        Self {
+            faces,
+            rarity: base.rarity,
+            collector_number: base.collector_number.clone(),
+        }
+    }
+
+    /// Returns the faces of a card that isn't printed as several sides: its
+    /// main face, then any extra part like an adventure.
+    fn single_card_faces(card: &Card, base: &CardBase) -> Vec<PlaytestFace> {
+        let mut main = base_face(base);
+        let mut faces = Vec::new();
+
+        match card {
+            Card::Planeswalker(card) => {
+                main.stats = Some(card.loyalty.to_string());
+                for ability in &card.loyalty_abilities {
+                    main.rules_text
+                        .push(format!("{}: {}", ability.cost, one_line(&ability.text)));
+                }
+            }
+            Card::Saga(card) => {
+                for chapter in &card.chapters {
+                    let numbers: Vec<String> = chapter
+                        .chapters
+                        .iter()
+                        .map(|&n| to_roman_numeral(n))
+                        .collect();
+                    main.rules_text.push(format!(
+                        "{} — {}",
+                        numbers.join(", "),
+                        one_line(&chapter.text)
+                    ));
+                }
+            }
+            Card::Class(card) => {
+                for level in &card.levels {
+                    main.rules_text.push(match &level.cost {
+                        Some(cost) => format!(
+                            "{}: Level {} — {}",
+                            cost,
+                            level.level,
+                            one_line(&level.text)
+                        ),
+                        None => format!("Level {} — {}", level.level, one_line(&level.text)),
+                    });
+                }
+            }
+            Card::Leveler(card) => {
+                for range in &card.leveler_ranges {
+                    let mut line = format!("LEVEL {}", range.range);
+                    if let (Some(power), Some(toughness)) = (&range.power, &range.toughness) {
+                        line.push_str(&format!(" [{}/{}]", power, toughness));
+                    }
+                    if let Some(text) = &range.text {
+                        line.push_str(&format!(" {}", one_line(text)));
+                    }
+                    main.rules_text.push(line);
+                }
+            }
+            Card::Adventure(card) => faces.push(PlaytestFace {
+                name: card.adventure.name.clone(),
+                mana_cost: Some(card.adventure.mana_cost.to_string()),
+                type_line: Some(card.adventure.type_line.clone()),
+                rules_text: paragraphs(&card.adventure.rules_text),
+                stats: None,
+            }),
+            Card::Battle(card) => {
+                main.stats = Some(card.defense.to_string());
+                faces.push(PlaytestFace {
+                    name: card.backside_name.clone(),
+                    type_line: Some(card.backside_type_line.clone()),
+                    rules_text: paragraphs(&card.backside_rules_text),
+                    ..PlaytestFace::default()
+                });
+            }
+            Card::Prototype(card) => {
+                let mut prototype = face(&card.prototype);
+                prototype.name = format!("Prototype of {}", main.name);
+                faces.push(prototype);
+            }
+            _ => {}
+        }
+
+        faces.insert(0, main);
+        faces
+    }
+}
+
+fn base_face(base: &CardBase) -> PlaytestFace {
+    PlaytestFace {
+        name: base.name.clone(),
+        mana_cost: base.mana_cost.as_ref().map(ToString::to_string),
+        type_line: Some(base.type_line.clone()),
+        rules_text: base.rules_text.as_ref().map(paragraphs).unwrap_or_default(),
+        stats: power_toughness(&base.power, &base.toughness),
+    }
+}
+
+fn face(face: &CardFace) -> PlaytestFace {
+    PlaytestFace {
+        name: face.name.clone().unwrap_or_default(),
+        mana_cost: face.mana_cost.as_ref().map(ToString::to_string),
+        type_line: face.type_line.clone(),
+        rules_text: face.rules_text.as_ref().map(paragraphs).unwrap_or_default(),
+        stats: power_toughness(&face.power, &face.toughness),
+    }
+}
+
+fn power_toughness(power: &Option<String>, toughness: &Option<String>) -> Option<String> {
+    match (power, toughness) {
+        (Some(power), Some(toughness)) => Some(format!("{}/{}", power, toughness)),
+        _ => None,
+    }
+}
+
+/// Returns each non-blank paragraph of `text`.
+fn paragraphs(text: &RulesText) -> Vec<String> {
+    text.to_string()
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Returns `text` with its paragraphs joined by spaces.
+fn one_line(text: &RulesText) -> String {
+    paragraphs(text).join(" ")
+}
@@ -0,0 +1,136 @@
+//! Printable sheets of card images
+//!
+//! Lays rendered cards out at their real size, 63 × 88 mm for standard
+//! cards, as many to a page as fit (nine standard cards), optionally with
+//! thin cut lines between them. The sheet is HTML, printed to PDF by
+//! [`Renderer::render_pdf`](crate::Renderer::render_pdf).
+
+use crate::card::CardSize;
+use maud::{DOCTYPE, Markup, PreEscaped, html};
+use std::path::PathBuf;
+
+/// Standard cards on each page, in a 3 × 3 grid
+pub const CARDS_PER_PAGE: usize = 9;
+
+/// Blank paper kept around the grid, in millimetres, which most printers
+/// can't print into anyway
+const PAGE_MARGIN_MM: f32 = 5.0;
+/// Width of the cut lines between cards, in millimetres
+const CUT_LINE_MM: f32 = 0.2;
+
+/// Paper the sheet is printed on
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PaperSize {
+    #[default]
+    A4,
+    Letter,
+}
+
+impl PaperSize {
+    /// Parse a paper size name: `a4` or `letter`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error for any other name.
+    #[must_use = "parsing returns a Result that should be handled"]
+    pub fn parse(s: &str) -> Result<Self, String> {
+        match s.trim().to_lowercase().as_str() {
+            "a4" => Ok(PaperSize::A4),
+            "letter" => Ok(PaperSize::Letter),
+            other => Err(format!(
+                "Unknown paper size: {} (expected a4 or letter)",
+                other
+            )),
+        }
+    }
+
+    /// Width and height in millimetres
+    #[must_use]
+    pub fn dimensions_mm(self) -> (f32, f32) {
+        match self {
+            PaperSize::A4 => (210.0, 297.0),
+            PaperSize::Letter => (215.9, 279.4),
+        }
+    }
+}
+
+/// Returns how many columns and rows of cards of `size` fit on a page.
+/// There is always room for at least one card, even one larger than the
+/// paper.
+#[must_use]
+pub fn grid(paper: PaperSize, size: CardSize) -> (usize, usize) {
+    let (paper_width, paper_height) = paper.dimensions_mm();
+    let (card_width, card_height) = size.dimensions_mm();
+    let fit = |space: f32, card: f32| {
+        (((space - 2.0 * PAGE_MARGIN_MM + CUT_LINE_MM) / (card + CUT_LINE_MM)) as usize).max(1)
+    };
+    (fit(paper_width, card_width), fit(paper_height, card_height))
+}
+
+/// Returns how many cards of `size` fit on a page.
+#[must_use]
+pub fn cards_per_page(paper: PaperSize, size: CardSize) -> usize {
+    let (columns, rows) = grid(paper, size);
+    columns * rows
+}
+
+/// Returns the number of pages needed for `cards` cards of `size`.
+#[must_use]
+pub fn page_count(cards: usize, paper: PaperSize, size: CardSize) -> usize {
+    cards.div_ceil(cards_per_page(paper, size))
+}
+
+/// Build a sheet printing each image in `images` once, in order, at `size`.
+/// Repeat an image to print several copies. Without `cut_lines` the cards
+/// are spaced the same, on blank paper.
+#[must_use]
+pub fn sheet_html(images: &[PathBuf], paper: PaperSize, size: CardSize, cut_lines: bool) -> Markup {
+    let (width, height) = paper.dimensions_mm();
+    let (card_width, card_height) = size.dimensions_mm();
+    let (columns, _) = grid(paper, size);
+    let gap_color = if cut_lines { "#bbb" } else { "transparent" };
+    let css = format!(
+        r#"
+        @page {{ size: {width}mm {height}mm; margin: 0; }}
+        html, body {{ margin: 0; padding: 0; }}
+        .page {{
+            width: {width}mm;
+            height: {height}mm;
+            display: flex;
+            align-items: center;
+            justify-content: center;
+            page-break-after: always;
+        }}
+        .page:last-child {{ page-break-after: auto; }}
+        .grid {{
+            display: grid;
+            grid-template-columns: repeat({columns}, {card_width}mm);
+            grid-auto-rows: {card_height}mm;
+            gap: {CUT_LINE_MM}mm;
+            background: {gap_color};
+        }}
+        .grid img {{ width: {card_width}mm; height: {card_height}mm; display: block; }}
+        "#,
+    );
+
+    html! {
+        (DOCTYPE)
+        html {
+            head {
+                meta charset="utf-8";
+                style { (PreEscaped(css)) }
+            }
+            body {
+                @for page in images.chunks(cards_per_page(paper, size)) {
+                    div.page {
+                        div.grid {
+                            @for image in page {
+                                img src=(format!("file://{}", image.display()));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
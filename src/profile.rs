@@ -0,0 +1,166 @@
+//! Render timing and memory metrics
+//!
+//! Every rendered image records how long each phase of the pipeline took,
+//! and the manifest keeps those timings so runs can be compared.
+//! `--profile` prints a summary at the end of a run.
+
+use crate::output::RenderManifest;
+use facet::Facet;
+use std::fmt;
+
+/// Time spent in each phase of rendering one image, in milliseconds
+#[derive(Facet, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RenderTimings {
+    /// Building the card's HTML
+    pub html_ms: u64,
+    /// Opening a page and navigating to the HTML
+    pub navigation_ms: u64,
+    /// Waiting for fonts and images to load, and fitting text
+    pub wait_ms: u64,
+    /// Capturing the screenshot
+    pub screenshot_ms: u64,
+    /// Writing the PNG, including `--optimize` when enabled
+    pub encode_ms: u64,
+    /// The page's JavaScript heap after rendering, when Chrome reports it
+    #[facet(default)]
+    pub js_heap_bytes: Option<u64>,
+}
+
+impl RenderTimings {
+    /// Names of the timed phases, in pipeline order
+    pub const PHASES: [&'static str; 5] = ["html", "navigation", "wait", "screenshot", "encode"];
+
+    /// Returns each phase's time, in the order of [`RenderTimings::PHASES`].
+    #[must_use]
+    pub fn phases(&self) -> [u64; 5] {
+        [
+            self.html_ms,
+            self.navigation_ms,
+            self.wait_ms,
+            self.screenshot_ms,
+            self.encode_ms,
+        ]
+    }
+
+    /// Returns the total time across all phases.
+    #[must_use]
+    pub fn total_ms(&self) -> u64 {
+        self.phases().iter().sum()
+    }
+}
+
+/// Aggregate timings over a run, printed by `--profile`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProfileReport {
+    /// Timings of each image, keyed by its output path
+    pub samples: Vec<(String, RenderTimings)>,
+    /// Peak resident memory of mtg-gen itself, where the OS reports it
+    pub peak_rss_bytes: Option<u64>,
+}
+
+impl ProfileReport {
+    /// How many of the slowest images the report lists
+    const SLOWEST: usize = 5;
+
+    /// Collect the timings recorded in `manifest`.
+    #[must_use]
+    pub fn from_manifest(manifest: &RenderManifest) -> Self {
+        Self {
+            samples: manifest
+                .cards
+                .iter()
+                .flat_map(|card| &card.outputs)
+                .filter_map(|output| output.timings.map(|t| (output.path.clone(), t)))
+                .collect(),
+            peak_rss_bytes: None,
+        }
+    }
+
+    /// Returns the average time of each phase, in milliseconds.
+    #[must_use]
+    pub fn averages(&self) -> [u64; 5] {
+        let mut totals = [0; 5];
+        for (_, timings) in &self.samples {
+            for (total, phase) in totals.iter_mut().zip(timings.phases()) {
+                *total += phase;
+            }
+        }
+        let count = self.samples.len().max(1) as u64;
+        totals.map(|total| total / count)
+    }
+
+    /// Returns the slowest images, slowest first.
+    #[must_use]
+    pub fn slowest(&self) -> Vec<&(String, RenderTimings)> {
+        let mut samples: Vec<_> = self.samples.iter().collect();
+        samples.sort_by_key(|(_, timings)| std::cmp::Reverse(timings.total_ms()));
+        samples.truncate(Self::SLOWEST);
+        samples
+    }
+}
+
+impl fmt::Display for ProfileReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Profile of {} image(s)", self.samples.len())?;
+        writeln!(
+            f,
+            "  {:<12} {:>8} {:>8} {:>10}",
+            "phase", "avg ms", "max ms", "total ms"
+        )?;
+        let averages = self.averages();
+        for (i, phase) in RenderTimings::PHASES.iter().enumerate() {
+            let max = self.samples.iter().map(|(_, t)| t.phases()[i]).max();
+            let total: u64 = self.samples.iter().map(|(_, t)| t.phases()[i]).sum();
+            writeln!(
+                f,
+                "  {:<12} {:>8} {:>8} {:>10}",
+                phase,
+                averages[i],
+                max.unwrap_or(0),
+                total
+            )?;
+        }
+
+        if let Some(heap) = self
+            .samples
+            .iter()
+            .filter_map(|(_, t)| t.js_heap_bytes)
+            .max()
+        {
+            writeln!(f, "  peak page JS heap: {} KB", heap / 1024)?;
+        }
+        if let Some(rss) = self.peak_rss_bytes {
+            writeln!(f, "  peak mtg-gen memory: {} MB", rss / (1024 * 1024))?;
+        }
+
+        writeln!(f, "Slowest images:")?;
+        for (path, timings) in self.slowest() {
+            writeln!(f, "  {:>8} ms  {}", timings.total_ms(), path)?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the sample with the median total time, or `None` when there are
+/// none. With an even number of samples, the faster of the middle two.
+#[must_use]
+pub fn median_timings(samples: &[RenderTimings]) -> Option<RenderTimings> {
+    let mut samples = samples.to_vec();
+    samples.sort_by_key(RenderTimings::total_ms);
+    samples.get(samples.len().saturating_sub(1) / 2).copied()
+}
+
+/// Returns the peak resident memory of this process in bytes, on Linux.
+#[must_use]
+pub fn peak_rss_bytes() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    let kb = status
+        .lines()
+        .find_map(|line| line.strip_prefix("VmHWM:"))?
+        .trim()
+        .strip_suffix("kB")?
+        .trim()
+        .parse::<u64>()
+        .ok()?;
+    Some(kb * 1024)
+}
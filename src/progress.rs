@@ -0,0 +1,96 @@
+//! Resumable progress for long runs (`--resume`)
+//!
+//! Each card is appended to a journal in the output directory as soon as it
+//! has been rendered. If a run is interrupted, `--resume` reads the journal
+//! back and skips the cards it lists; a run that completes removes it.
+
+use crate::output::ManifestEntry;
+use anyhow::{Context, Result};
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// File name of the journal, in the output directory
+pub const JOURNAL_FILE_NAME: &str = ".mtg-gen-progress.jsonl";
+
+/// Append-only record of the cards rendered so far
+pub struct ProgressJournal {
+    path: PathBuf,
+    file: File,
+}
+
+impl ProgressJournal {
+    /// Open the journal in `output_dir`.
+    ///
+    /// With `resume`, returns the entries of an earlier, interrupted run whose
+    /// images all still exist, and keeps appending to the journal. Without it,
+    /// any earlier journal is discarded.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the journal can't be read or created.
+    pub fn open(output_dir: &Path, resume: bool) -> Result<(Self, Vec<ManifestEntry>)> {
+        std::fs::create_dir_all(output_dir)?;
+        let path = output_dir.join(JOURNAL_FILE_NAME);
+
+        let done = if resume && path.exists() {
+            let journal = std::fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read {:?}", path))?;
+            completed_entries(&journal, output_dir)
+        } else {
+            Vec::new()
+        };
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .open(&path)
+            .with_context(|| format!("Failed to create {:?}", path))?;
+        // Rewrite the entries being kept, dropping any half-written line
+        for entry in &done {
+            writeln!(file, "{}", facet_json::to_string(entry))?;
+        }
+
+        Ok((Self { path, file }, done))
+    }
+
+    /// Record a rendered card.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the journal can't be written.
+    pub fn record(&mut self, entry: &ManifestEntry) -> Result<()> {
+        writeln!(self.file, "{}", facet_json::to_string(entry))?;
+        self.file.flush()?;
+        Ok(())
+    }
+
+    /// Remove the journal after a completed run.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the journal can't be removed.
+    pub fn finish(self) -> Result<()> {
+        drop(self.file);
+        std::fs::remove_file(&self.path)?;
+        Ok(())
+    }
+}
+
+/// Parse a journal, keeping the entries whose images all exist under
+/// `output_dir`. Lines that don't parse, like one cut short by a crash, are
+/// skipped.
+#[must_use]
+pub fn completed_entries(journal: &str, output_dir: &Path) -> Vec<ManifestEntry> {
+    journal
+        .lines()
+        .filter_map(|line| facet_json::from_str::<ManifestEntry>(line).ok())
+        .filter(|entry| {
+            entry
+                .outputs
+                .iter()
+                .all(|output| output_dir.join(&output.path).is_file())
+        })
+        .collect()
+}
@@ -0,0 +1,204 @@
+//! Render provenance: which set revision and mtg-gen version made an image
+//!
+//! Every run records its [`Provenance`] in the manifest. With
+//! `--provenance-metadata` it is also written into each PNG as a `tEXt`
+//! chunk, so a single image shared on its own can be traced back to the set
+//! revision it came from with `mtg-gen provenance <image>`.
+
+use anyhow::{Context, Result};
+use facet::Facet;
+use std::fmt;
+use std::path::Path;
+use std::process::Command;
+
+/// Keyword of the PNG `tEXt` chunk holding an image's provenance
+pub const PNG_KEYWORD: &str = "mtg-gen:provenance";
+
+/// The eight bytes every PNG starts with
+const PNG_SIGNATURE: &[u8; 8] = b"\x89PNG\r\n\x1a\n";
+
+/// Where a run's images came from
+#[derive(Facet, Debug, Clone, Default, PartialEq, Eq)]
+pub struct Provenance {
+    /// Version of mtg-gen that rendered the images
+    pub version: String,
+    /// Commit checked out in the input's git repository, if it is in one
+    #[facet(default)]
+    pub commit: Option<String>,
+    /// Whether the input had uncommitted changes when it was rendered
+    #[facet(default)]
+    pub dirty: Option<bool>,
+}
+
+impl Provenance {
+    /// Describe a run rendering `input`, a card file or directory. The
+    /// commit and dirty state are left out when `input` isn't in a git
+    /// repository or git isn't installed.
+    #[must_use]
+    pub fn detect(input: &Path) -> Self {
+        let dir = if input.is_dir() {
+            input
+        } else {
+            input
+                .parent()
+                .filter(|dir| !dir.as_os_str().is_empty())
+                .unwrap_or(Path::new("."))
+        };
+        let commit = git(dir, &["rev-parse", "HEAD"]);
+        // Only changes to the input itself make the render dirty
+        let dirty = commit
+            .as_ref()
+            .and_then(|_| git(dir, &["status", "--porcelain", "--", "."]))
+            .map(|status| !status.is_empty());
+        Provenance {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            commit,
+            dirty,
+        }
+    }
+
+    /// Serialize the provenance to JSON.
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        facet_json::to_string(self)
+    }
+
+    /// Parse provenance previously written by [`Provenance::to_json`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the JSON is malformed.
+    #[must_use = "parsing returns a Result that should be handled"]
+    pub fn from_json(json: &str) -> Result<Self> {
+        facet_json::from_str(json).map_err(|e| anyhow::anyhow!("{}", e))
+    }
+
+    /// Write this provenance into the PNG at `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can't be read or written, or isn't a PNG.
+    pub fn embed(&self, path: &Path) -> Result<()> {
+        let png = std::fs::read(path).with_context(|| format!("Failed to read {:?}", path))?;
+        let png = with_text_chunk(&png, PNG_KEYWORD, &self.to_json())
+            .with_context(|| format!("{:?} is not a PNG", path))?;
+        std::fs::write(path, png).with_context(|| format!("Failed to write {:?}", path))
+    }
+
+    /// Read the provenance written into the PNG at `path`, or `None` if it
+    /// has none.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can't be read, or its provenance is
+    /// malformed.
+    pub fn read(path: &Path) -> Result<Option<Self>> {
+        let png = std::fs::read(path).with_context(|| format!("Failed to read {:?}", path))?;
+        read_text_chunk(&png, PNG_KEYWORD)
+            .map(|json| Self::from_json(&json))
+            .transpose()
+    }
+}
+
+impl fmt::Display for Provenance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "mtg-gen {}", self.version)?;
+        match (&self.commit, self.dirty) {
+            (Some(commit), Some(true)) => write!(f, "commit {} (uncommitted changes)", commit),
+            (Some(commit), _) => write!(f, "commit {}", commit),
+            (None, _) => write!(f, "not in a git repository"),
+        }
+    }
+}
+
+/// Run git in `dir`, returning its trimmed output if it succeeds.
+fn git(dir: &Path, args: &[&str]) -> Option<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Returns `png` with a `tEXt` chunk of `keyword` and `text` added after
+/// its header, replacing any chunk with the same keyword. `None` if `png`
+/// isn't a PNG.
+#[must_use]
+pub fn with_text_chunk(png: &[u8], keyword: &str, text: &str) -> Option<Vec<u8>> {
+    let chunks = chunks(png)?;
+    let mut data = keyword.as_bytes().to_vec();
+    data.push(0);
+    data.extend_from_slice(text.as_bytes());
+
+    let mut out = PNG_SIGNATURE.to_vec();
+    for (i, chunk) in chunks.iter().enumerate() {
+        if text_chunk_keyword(chunk) == Some(keyword.as_bytes()) {
+            continue;
+        }
+        out.extend_from_slice(chunk.raw);
+        // IHDR always comes first
+        if i == 0 {
+            write_chunk(&mut out, b"tEXt", &data);
+        }
+    }
+    Some(out)
+}
+
+/// Returns the text of the `tEXt` chunk with `keyword` in `png`, if any.
+#[must_use]
+pub fn read_text_chunk(png: &[u8], keyword: &str) -> Option<String> {
+    chunks(png)?.iter().find_map(|chunk| {
+        (text_chunk_keyword(chunk) == Some(keyword.as_bytes()))
+            .then(|| String::from_utf8_lossy(&chunk.data[keyword.len() + 1..]).into_owned())
+    })
+}
+
+/// One chunk of a PNG
+struct Chunk<'a> {
+    kind: &'a [u8],
+    data: &'a [u8],
+    /// The whole chunk: length, type, data and CRC
+    raw: &'a [u8],
+}
+
+/// Split a PNG into its chunks, or `None` if it isn't one.
+fn chunks(png: &[u8]) -> Option<Vec<Chunk<'_>>> {
+    let mut rest = png.strip_prefix(PNG_SIGNATURE)?;
+    let mut chunks = Vec::new();
+    while !rest.is_empty() {
+        let length = u32::from_be_bytes(rest.get(..4)?.try_into().ok()?) as usize;
+        let end = length.checked_add(12)?;
+        let raw = rest.get(..end)?;
+        chunks.push(Chunk {
+            kind: &raw[4..8],
+            data: &raw[8..8 + length],
+            raw,
+        });
+        rest = &rest[end..];
+    }
+    Some(chunks)
+}
+
+/// Returns the keyword of a `tEXt` chunk, or `None` for other chunks.
+fn text_chunk_keyword<'a>(chunk: &Chunk<'a>) -> Option<&'a [u8]> {
+    if chunk.kind != b"tEXt" {
+        return None;
+    }
+    let end = chunk.data.iter().position(|&b| b == 0)?;
+    Some(&chunk.data[..end])
+}
+
+fn write_chunk(out: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    out.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    out.extend_from_slice(kind);
+    out.extend_from_slice(data);
+    let mut crc = flate2::Crc::new();
+    crc.update(kind);
+    crc.update(data);
+    out.extend_from_slice(&crc.sum().to_be_bytes());
+}
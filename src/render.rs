@@ -1,6 +1,8 @@
 //! Card rendering module
 //!
 //! This module handles rendering MTG cards to HTML and capturing them as PNG images.
+//! Capturing needs headless Chrome and is built with the `browser` feature
+//! (on by default); the HTML alone needs no browser.
 //!
 //! # Asset Repository
 //!
@@ -17,27 +19,47 @@
 //! See AGENTS.md for complete asset documentation and usage guidelines.
 
 use crate::card::{
-    AdventureCard, BattleCard, Card, CardBase, CardFace, ClassCard, FlipCard, LevelerCard,
-    MeldCard, ModalDfcCard, NormalCard, PlaneswalkerCard, PrototypeCard, Rarity, SagaCard,
-    SplitCard, TransformCard,
+    AdventureCard, ArtFit, BattleCard, Card, CardBase, CardFace, CardSize, ClassCard, DfcIcons,
+    FlipCard, FrameEffect, LevelerCard, MeldCard, ModalDfcCard, NormalCard, PlaneswalkerCard,
+    PrototypeCard, Rarity, SagaCard, SplitCard, TokenCard, TransformCard,
 };
+use crate::color_chips::{Color, card_colors, indicator_colors, named_colors};
+use crate::extract::{Emblem, FaceDown};
+use crate::legend::SymbolLegend;
 use crate::mana::{
-    CastingManaCost, CastingManaSymbol, LoyaltyCost, LoyaltyValue, ManaSymbol, RulesText,
-    RulesTextSegment,
+    CastingManaCost, CastingManaSymbol, CustomMana, LoyaltyCost, LoyaltyValue, ManaSymbol,
+    ReminderSegment, RulesText, RulesTextSegment, parse_markup,
 };
-use anyhow::Result;
-use chromiumoxide::browser::{Browser, BrowserConfig};
-use chromiumoxide::page::ScreenshotParams;
-use chromiumoxide_cdp::cdp::browser_protocol::emulation::SetDeviceMetricsOverrideParams;
-use chromiumoxide_cdp::cdp::browser_protocol::page::CaptureScreenshotFormat;
-use futures::StreamExt;
+use crate::playtest::PlaytestCard;
+use crate::style::StyleConfig;
+use crate::symbology;
+use crate::utils::to_roman_numeral;
 use maud::{Markup, html};
-use std::path::Path;
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+
+#[cfg(feature = "browser")]
+mod browser;
+mod html;
+#[cfg(feature = "browser")]
+pub use browser::{BrowserPreset, ElementBox, RenderOutcome, RenderedImage, Renderer};
+pub use html::CardHtml;
 
 // ============================================================================
 // Rendering Helpers
 // ============================================================================
 
+/// Returns the absolute path of the mtgrender asset directory.
+///
+/// Assets are resolved against the current directory, so the CLI has to be
+/// run from the repository root.
+#[must_use]
+pub fn assets_base() -> PathBuf {
+    std::env::current_dir()
+        .unwrap_or_default()
+        .join("mtgrender/client/src/assets")
+}
+
 /// CSS class names for frame colors
 pub struct FrameClasses {
     pub bg: String,
@@ -49,7 +71,18 @@ pub struct FrameClasses {
 impl FrameClasses {
     #[must_use]
     pub fn from_mana_cost(mana_cost: &Option<CastingManaCost>) -> Self {
-        let frame_color = derive_frame_color(mana_cost);
+        Self::for_color(derive_frame_color(mana_cost))
+    }
+
+    /// Classes for a card's frame, see [`card_frame_color`]
+    #[must_use]
+    pub fn for_card(base: &CardBase) -> Self {
+        Self::for_color(card_frame_color(base))
+    }
+
+    /// Classes for a frame color name like `blue` or `colorless`
+    #[must_use]
+    pub fn for_color(frame_color: &str) -> Self {
         Self {
             bg: format!("bg-{}", frame_color),
             frame: format!("frame-{}", frame_color),
@@ -59,6 +92,59 @@ impl FrameClasses {
     }
 }
 
+/// Returns the two colors of a hybrid cost, one whose colored symbols are
+/// all hybrid symbols of the same two colors, like `{1}{R/G}{R/G}`, or
+/// `None` for any other cost.
+///
+/// # Examples
+///
+/// ```
+/// use mtg_gen::CastingManaCost;
+/// use mtg_gen::color_chips::Color;
+/// use mtg_gen::render::hybrid_colors;
+///
+/// let cost = CastingManaCost::parse("{1}{R/G}{R/G}").ok();
+/// assert_eq!(hybrid_colors(&cost), Some([Color::Red, Color::Green]));
+/// assert_eq!(hybrid_colors(&CastingManaCost::parse("{R}{G}").ok()), None);
+/// ```
+#[must_use]
+pub fn hybrid_colors(mana_cost: &Option<CastingManaCost>) -> Option<[Color; 2]> {
+    let mut colored = mana_cost
+        .iter()
+        .flat_map(|cost| cost.symbols.iter())
+        .map(|symbol| symbol.colors())
+        .filter(|colors| !colors.is_empty());
+    let pair = colored.next()?;
+    match pair {
+        &[first, second] if colored.all(|colors| colors == pair) => Some([first, second]),
+        _ => None,
+    }
+}
+
+/// Render a box with the class `class` holding `content`, backed by the
+/// text box of its own cost's color rather than the card's, for regions
+/// like an adventure's bookmark or a class level. A hybrid cost's box
+/// blends from one of its colors into the other, see [`hybrid_colors`].
+#[must_use]
+pub fn render_cost_box(
+    class: &str,
+    mana_cost: &Option<CastingManaCost>,
+    content: Markup,
+) -> Markup {
+    html! {
+        @if let Some([first, second]) = hybrid_colors(mana_cost) {
+            div class=(format!("{} hybrid-box text-box-bg-{}", class, first.name())) {
+                div class=(format!("hybrid-blend text-box-bg-{}", second.name())) {}
+                (content)
+            }
+        } @else {
+            div class=(format!("{} {}", class, FrameClasses::from_mana_cost(mana_cost).text_box_bg)) {
+                (content)
+            }
+        }
+    }
+}
+
 /// Convert rarity to CSS class name
 #[must_use]
 pub fn rarity_class(rarity: Rarity) -> &'static str {
@@ -67,6 +153,149 @@ pub fn rarity_class(rarity: Rarity) -> &'static str {
         Rarity::Uncommon => "rarity-uncommon",
         Rarity::Rare => "rarity-rare",
         Rarity::Mythic => "rarity-mythic",
+        Rarity::Special => "rarity-special",
+        Rarity::Bonus => "rarity-bonus",
+        Rarity::Land => "rarity-land",
+    }
+}
+
+/// Render a card's set symbol, filled with its rarity's gradient, or a
+/// rarity dot when it has no `set_symbol`. It goes at the end of a type
+/// line.
+///
+/// The symbol is an SVG: the image at `set_symbol` masks a radial gradient
+/// whose stops come from the rarity's `--rarity-center`, `--rarity-middle`
+/// and `--rarity-edge` CSS variables, which the style file can override
+/// (see [`StyleConfig::rarity_colors`](crate::style::StyleConfig::rarity_colors)).
+///
+/// `set_symbol` should be absolute, see [`Card::resolve_art_paths`].
+#[must_use]
+pub fn render_set_symbol(base: &CardBase) -> Markup {
+    let rarity = rarity_class(base.rarity);
+    html! {
+        @if let Some(ref path) = base.set_symbol {
+            svg class=(format!("rarity-indicator set-symbol-image {}", rarity)) viewBox="0 0 40 30" {
+                @if let Some(ref code) = base.set_code {
+                    title { (code) }
+                }
+                defs {
+                    radialGradient id="set-symbol-gradient" r="60%" {
+                        stop offset="0" style="stop-color: var(--rarity-center);" {}
+                        stop offset="0.5" style="stop-color: var(--rarity-middle);" {}
+                        stop offset="1" style="stop-color: var(--rarity-edge);" {}
+                    }
+                    // Only the image's shape is used
+                    mask id="set-symbol-mask" style="mask-type: alpha;" {
+                        image href=(format!("file://{}", path)) width="40" height="30" preserveAspectRatio="xMidYMid meet" {}
+                    }
+                }
+                rect width="40" height="30" fill="url(#set-symbol-gradient)" mask="url(#set-symbol-mask)" {}
+            }
+        } @else {
+            div class=(format!("rarity-indicator {}", rarity)) title=[base.set_code.as_deref()] {}
+        }
+    }
+}
+
+/// Render the collector line: the card's collector number and rarity letter,
+/// then its artist credit, or nothing for cards with neither
+#[must_use]
+pub fn render_collector_line(base: &CardBase) -> Markup {
+    let artist = base.artist.as_deref().filter(|artist| !artist.is_empty());
+    html! {
+        @if base.collector_number.is_some() || artist.is_some() {
+            div.collector-line {
+                @if let Some(ref number) = base.collector_number {
+                    (number) " " (base.rarity.letter())
+                    @if artist.is_some() { " • " }
+                }
+                @if let Some(artist) = artist {
+                    span.artist-credit { "Illus. " (artist) }
+                }
+            }
+        }
+    }
+}
+
+/// Render the artist credit of part of a card, "Illus. <artist>", in a
+/// `div` of `class`, or nothing without an artist
+#[must_use]
+pub fn render_artist_credit(class: &str, artist: Option<&str>) -> Markup {
+    html! {
+        @if let Some(artist) = artist.filter(|artist| !artist.is_empty()) {
+            div class=(class) { "Illus. " (artist) }
+        }
+    }
+}
+
+/// Show a card's rarity only as the letter on its collector line, for
+/// printing without the set symbol. The rarity indicator is hidden, and a
+/// card without a collector number gets the letter before its artist
+/// credit, or a collector line with just the letter.
+#[must_use]
+pub fn with_rarity_letter(html: &str, rarity: Rarity) -> String {
+    let mut extra = String::from("<style>.rarity-indicator { display: none; }</style>");
+    let mut html = html.to_string();
+    // A line with only the artist credit gets the letter in front of it
+    let artist_only = "<div class=\"collector-line\"><span class=\"artist-credit\">";
+    if let Some(start) = html.find(artist_only) {
+        let at = start + "<div class=\"collector-line\">".len();
+        html.insert_str(at, &format!("{} • ", rarity.letter()));
+    } else if !html.contains("class=\"collector-line\"") {
+        extra.push_str(&html! { div.collector-line { (rarity.letter()) } }.into_string());
+    }
+    match html.rfind("</body>") {
+        Some(end) => format!("{}{}{}", &html[..end], extra, &html[end..]),
+        None => format!("{}{}", html, extra),
+    }
+}
+
+/// Width of a card with print bleed, in CSS pixels: MakePlayingCards'
+/// 816 × 1110 template
+pub const BLEED_WIDTH: u32 = 816;
+/// Height of a card with print bleed, in CSS pixels
+pub const BLEED_HEIGHT: u32 = 1110;
+
+/// Add print bleed around a card, for printers like MakePlayingCards that
+/// trim into the border: the page grows to [`BLEED_WIDTH`] ×
+/// [`BLEED_HEIGHT`], the card sits in the middle with square corners, and
+/// the black border extends out to the edges.
+#[must_use]
+pub fn with_bleed(html: &str) -> String {
+    // Everything positioned over the card is positioned within the body,
+    // so it moves with the card
+    let css = format!(
+        "<style>html {{ width: {}px; height: {}px; background: #000; }} \
+         body {{ position: relative; width: 744px; height: 1040px; margin: {}px {}px; }} \
+         .card {{ border-radius: 0; }}</style>",
+        BLEED_WIDTH,
+        BLEED_HEIGHT,
+        (BLEED_HEIGHT - 1040) / 2,
+        (BLEED_WIDTH - 744) / 2
+    );
+    match html.rfind("</head>") {
+        Some(end) => format!("{}{}{}", &html[..end], css, &html[end..]),
+        None => format!("{}{}", css, html),
+    }
+}
+
+/// Render an art box with the class `class`: the art at `path`, scaled by
+/// `fit`, or the "[Art]" placeholder when there's no art.
+///
+/// `path` should be absolute, see [`Card::resolve_art_paths`].
+#[must_use]
+pub fn render_art(class: &str, path: Option<&str>, fit: Option<ArtFit>) -> Markup {
+    html! {
+        div class=(class) {
+            @if let Some(path) = path {
+                img.art-image
+                    src=(format!("file://{}", path))
+                    style=(format!("object-fit: {};", fit.unwrap_or_default().as_str()))
+                    alt="";
+            } @else {
+                "[Art]"
+            }
+        }
     }
 }
 
@@ -77,78 +306,107 @@ pub fn derive_frame_color(mana_cost: &Option<CastingManaCost>) -> &'static str {
         return "land"; // No mana cost = land
     };
 
-    let mut has_white = false;
-    let mut has_blue = false;
-    let mut has_black = false;
-    let mut has_red = false;
-    let mut has_green = false;
-    let mut has_colorless = false;
-
-    for symbol in &cost.symbols {
-        match symbol {
-            CastingManaSymbol::White
-            | CastingManaSymbol::WhiteBlue
-            | CastingManaSymbol::WhiteBlack
-            | CastingManaSymbol::WhiteRed
-            | CastingManaSymbol::WhiteGreen
-            | CastingManaSymbol::TwoWhite
-            | CastingManaSymbol::PhyrexianWhite => has_white = true,
-            CastingManaSymbol::Blue
-            | CastingManaSymbol::BlueBlack
-            | CastingManaSymbol::BlueRed
-            | CastingManaSymbol::BlueGreen
-            | CastingManaSymbol::TwoBlue
-            | CastingManaSymbol::PhyrexianBlue => has_blue = true,
-            CastingManaSymbol::Black
-            | CastingManaSymbol::BlackRed
-            | CastingManaSymbol::BlackGreen
-            | CastingManaSymbol::TwoBlack
-            | CastingManaSymbol::PhyrexianBlack => has_black = true,
-            CastingManaSymbol::Red
-            | CastingManaSymbol::RedGreen
-            | CastingManaSymbol::TwoRed
-            | CastingManaSymbol::PhyrexianRed => has_red = true,
-            CastingManaSymbol::Green
-            | CastingManaSymbol::TwoGreen
-            | CastingManaSymbol::PhyrexianGreen => has_green = true,
-            CastingManaSymbol::Colorless => has_colorless = true,
-            _ => {}
-        }
-    }
-
-    let color_count = [has_white, has_blue, has_black, has_red, has_green]
+    let mut colors: Vec<Color> = cost
+        .symbols
+        .iter()
+        .flat_map(|symbol| symbol.colors().iter().copied())
+        .collect();
+    colors.sort();
+    colors.dedup();
+    let has_colorless = cost.symbols.iter().any(|symbol| {
+        matches!(
+            symbol,
+            CastingManaSymbol::Colorless | CastingManaSymbol::PhyrexianColorless
+        )
+    });
+
+    let mut customs: Vec<CustomMana> = cost
+        .symbols
         .iter()
-        .filter(|&&x| x)
-        .count();
+        .filter_map(|symbol| match symbol {
+            CastingManaSymbol::Custom(custom) => Some(*custom),
+            _ => None,
+        })
+        .collect();
+    customs.sort();
+    customs.dedup();
+
+    match customs.as_slice() {
+        [] => {}
+        [custom] if colors.is_empty() => return custom.frame_color(),
+        _ => return "gold", // Custom colors mixed with others are multicolor
+    }
 
-    match color_count {
-        0 => {
-            if has_colorless {
-                "colorless"
-            } else {
-                "artifact" // Generic mana only
-            }
-        }
-        1 => {
-            if has_white {
-                "white"
-            } else if has_blue {
-                "blue"
-            } else if has_black {
-                "black"
-            } else if has_red {
-                "red"
-            } else {
-                "green"
-            }
-        }
+    match colors.as_slice() {
+        [] if has_colorless => "colorless",
+        [] => "artifact", // Generic mana only
+        [Color::White] => "white",
+        [Color::Blue] => "blue",
+        [Color::Black] => "black",
+        [Color::Red] => "red",
+        [Color::Green] => "green",
         _ => "gold", // Multicolor
     }
 }
 
+/// Returns a card's frame color. It follows the mana cost, as
+/// [`derive_frame_color`], unless the card sets `frame_colors: identity`:
+/// then the colors of mana symbols in its rules text count too, so a land
+/// that adds `{U}{U}` gets a blue frame.
+#[must_use]
+pub fn card_frame_color(base: &CardBase) -> &'static str {
+    base.frame_from_identity()
+        .then(|| identity_frame_color(base.mana_cost.as_ref(), base.rules_text.as_ref(), &[]))
+        .flatten()
+        .unwrap_or_else(|| derive_frame_color(&base.mana_cost))
+}
+
+/// Returns the frame color for the colors of `cost`, the mana symbols of
+/// `rules` and `indicator` together, or `None` when they have no color.
+fn identity_frame_color(
+    cost: Option<&CastingManaCost>,
+    rules: Option<&RulesText>,
+    indicator: &[Color],
+) -> Option<&'static str> {
+    let symbols = cost
+        .into_iter()
+        .flat_map(|cost| cost.symbols.iter().copied())
+        .chain(rules.into_iter().flat_map(RulesText::mana_symbols));
+    let mut colors = indicator.to_vec();
+    colors.extend(symbols.flat_map(|symbol| symbol.colors().iter().copied()));
+    colors.sort();
+    colors.dedup();
+    match colors.as_slice() {
+        [] => None,
+        [color] => Some(color.name()),
+        _ => Some("gold"),
+    }
+}
+
 /// Render a single casting mana symbol
 #[must_use]
 pub fn render_casting_symbol(symbol: CastingManaSymbol) -> Markup {
+    match symbol {
+        CastingManaSymbol::Generic(n) => html! { span.mana-generic { (n) } },
+        CastingManaSymbol::Custom(custom) => html! {
+            span class=(format!("mana-symbol mana-custom mana-{}", custom.frame_color()))
+                title=(symbol) {
+                (custom.letter())
+            }
+        },
+        _ => match casting_symbol_file(symbol) {
+            Some((directory, name)) => html! {
+                img.mana-symbol src=(symbol_url(directory, name)) alt=(name);
+            },
+            None => html! { span.mana-symbol-missing { (symbol.to_string()) } },
+        },
+    }
+}
+
+/// Returns the SVG of a casting symbol, as its directory under the assets'
+/// `img/` and its file stem, or `None` for generic and custom mana, which
+/// are drawn without one.
+fn casting_symbol_file(symbol: CastingManaSymbol) -> Option<(&'static str, &'static str)> {
     let (symbol_name, use_symbols_dir) = match symbol {
         CastingManaSymbol::White => ("W", false),
         CastingManaSymbol::Blue => ("U", false),
@@ -156,7 +414,7 @@ pub fn render_casting_symbol(symbol: CastingManaSymbol) -> Markup {
         CastingManaSymbol::Red => ("R", false),
         CastingManaSymbol::Green => ("G", false),
         CastingManaSymbol::Colorless => ("C", true),
-        CastingManaSymbol::Generic(n) => return html! { span.mana-generic { (n) } },
+        CastingManaSymbol::Generic(_) | CastingManaSymbol::Custom(_) => return None,
         CastingManaSymbol::X => ("X", true),
         CastingManaSymbol::Y => ("Y", true),
         CastingManaSymbol::Z => ("Z", true),
@@ -181,64 +439,126 @@ pub fn render_casting_symbol(symbol: CastingManaSymbol) -> Markup {
         CastingManaSymbol::PhyrexianBlack => ("BP", true),
         CastingManaSymbol::PhyrexianRed => ("RP", true),
         CastingManaSymbol::PhyrexianGreen => ("GP", true),
+        CastingManaSymbol::PhyrexianColorless => ("CP", true),
+        CastingManaSymbol::PhyrexianWhiteBlue => ("WUP", true),
+        CastingManaSymbol::PhyrexianWhiteBlack => ("WBP", true),
+        CastingManaSymbol::PhyrexianWhiteRed => ("RWP", true),
+        CastingManaSymbol::PhyrexianWhiteGreen => ("GWP", true),
+        CastingManaSymbol::PhyrexianBlueBlack => ("UBP", true),
+        CastingManaSymbol::PhyrexianBlueRed => ("URP", true),
+        CastingManaSymbol::PhyrexianBlueGreen => ("GUP", true),
+        CastingManaSymbol::PhyrexianBlackRed => ("BRP", true),
+        CastingManaSymbol::PhyrexianBlackGreen => ("BGP", true),
+        CastingManaSymbol::PhyrexianRedGreen => ("RGP", true),
+        CastingManaSymbol::ColorlessWhite => ("CW", true),
+        CastingManaSymbol::ColorlessBlue => ("CU", true),
+        CastingManaSymbol::ColorlessBlack => ("CB", true),
+        CastingManaSymbol::ColorlessRed => ("CR", true),
+        CastingManaSymbol::ColorlessGreen => ("CG", true),
+        CastingManaSymbol::HalfWhite => ("HW", true),
+        CastingManaSymbol::HalfRed => ("HR", true),
+        CastingManaSymbol::Half => ("HALF", true),
+        CastingManaSymbol::Infinity => ("INFINITY", true),
     };
 
-    // Get absolute path to mtgrender assets
-    let assets_base = std::env::current_dir()
-        .unwrap_or_default()
-        .join("mtgrender/client/src/assets");
-
     let directory = if use_symbols_dir {
         "symbols"
     } else {
         "archives_symbols"
     };
-    let url = format!(
-        "file://{}/{}.svg",
-        assets_base.join("img").join(directory).display(),
-        symbol_name
-    );
+    Some((directory, symbol_name))
+}
 
-    html! {
-        img.mana-symbol src=(url) alt=(symbol_name);
-    }
+/// Returns the URL of the SVG `name` in the assets' `img/<directory>/`.
+fn symbol_url(directory: &str, name: &str) -> String {
+    format!(
+        "file://{}/{}.svg",
+        assets_base().join("img").join(directory).display(),
+        name
+    )
 }
 
 /// Render any mana symbol (including tap, untap, energy, chaos)
+///
+/// Non-casting symbols are looked up in the Scryfall symbology snapshot to
+/// find their SVG file.
 #[must_use]
 pub fn render_mana_symbol(symbol: ManaSymbol) -> Markup {
-    match symbol {
-        ManaSymbol::Casting(s) => render_casting_symbol(s),
-        ManaSymbol::Tap => {
-            // Get absolute path to mtgrender assets
-            let assets_base = std::env::current_dir()
-                .unwrap_or_default()
-                .join("mtgrender/client/src/assets");
-            let url = format!("file://{}/img/symbols/T.svg", assets_base.display());
-            html! { img.mana-symbol src=(url) alt="T"; }
-        }
-        ManaSymbol::Untap => {
-            let assets_base = std::env::current_dir()
-                .unwrap_or_default()
-                .join("mtgrender/client/src/assets");
-            let url = format!("file://{}/img/symbols/Q.svg", assets_base.display());
-            html! { img.mana-symbol src=(url) alt="Q"; }
-        }
-        ManaSymbol::Energy => {
-            let assets_base = std::env::current_dir()
-                .unwrap_or_default()
-                .join("mtgrender/client/src/assets");
-            let url = format!("file://{}/img/symbols/E.svg", assets_base.display());
-            html! { img.mana-symbol src=(url) alt="E"; }
+    let ManaSymbol::Casting(casting) = symbol else {
+        let Some(entry) = symbology::lookup(symbol) else {
+            return html! { span.mana-symbol-missing { (symbol.to_string()) } };
+        };
+        return html! {
+            img.mana-symbol src=(symbol_url("symbols", entry.svg)) alt=(entry.svg) title=(entry.english);
+        };
+    };
+    render_casting_symbol(casting)
+}
+
+/// Prefix of the ids of the symbol definitions rules text refers to
+const SYMBOL_ID_PREFIX: &str = "symbol-";
+
+/// Render a mana symbol in rules text as a reference to the symbol's
+/// definition, which [`with_symbol_defs`] adds to the document once, however
+/// often the symbol appears. Symbols without an SVG render as in
+/// [`render_mana_symbol`].
+#[must_use]
+pub fn render_symbol_use(symbol: ManaSymbol) -> Markup {
+    let (file, title) = match symbol {
+        ManaSymbol::Casting(casting) => (casting_symbol_file(casting), None),
+        _ => match symbology::lookup(symbol) {
+            Some(entry) => (Some(("symbols", entry.svg)), Some(entry.english)),
+            None => (None, None),
+        },
+    };
+    let Some((directory, name)) = file else {
+        return render_mana_symbol(symbol);
+    };
+    html! {
+        svg.mana-symbol viewBox="0 0 100 100" role="img" aria-label=(name) {
+            @if let Some(title) = title {
+                title { (title) }
+            }
+            use href=(format!("#{}{}--{}", SYMBOL_ID_PREFIX, directory, name)) {}
         }
-        ManaSymbol::Chaos => {
-            let assets_base = std::env::current_dir()
-                .unwrap_or_default()
-                .join("mtgrender/client/src/assets");
-            let url = format!("file://{}/img/symbols/CHAOS.svg", assets_base.display());
-            html! { img.mana-symbol src=(url) alt="CHAOS"; }
+    }
+}
+
+/// Returns a rendered card document with a definition of each symbol its
+/// rules text refers to, see [`render_symbol_use`], added at the end of its
+/// body. Each symbol's SVG is then named, and loaded, once per card.
+#[must_use]
+pub fn with_symbol_defs(html: &str) -> String {
+    let reference = format!("href=\"#{}", SYMBOL_ID_PREFIX);
+    let used: BTreeSet<&str> = html
+        .match_indices(&reference)
+        .filter_map(|(start, _)| {
+            let id = &html[start + reference.len()..];
+            id.find('"').map(|end| &id[..end])
+        })
+        .collect();
+    if used.is_empty() {
+        return html.to_string();
+    }
+
+    let defs = html! {
+        svg.symbol-defs width="0" height="0" style="position: absolute" aria-hidden="true" {
+            defs {
+                @for id in &used {
+                    @if let Some((directory, name)) = id.split_once("--") {
+                        symbol id=(format!("{}{}", SYMBOL_ID_PREFIX, id)) viewBox="0 0 100 100" {
+                            image href=(symbol_url(directory, name)) width="100" height="100" {}
+                        }
+                    }
+                }
+            }
         }
     }
+    .into_string();
+    match html.rfind("</body>") {
+        Some(end) => format!("{}{}{}", &html[..end], defs, &html[end..]),
+        None => format!("{}{}", html, defs),
+    }
 }
 
 /// Render a mana cost (sequence of symbols)
@@ -253,31 +573,121 @@ pub fn render_mana_cost(cost: &CastingManaCost) -> Markup {
     }
 }
 
+/// Render flavor text, with its italic and bold markup. Flavor text is
+/// already italic, so italic markup sets words in roman, as printed cards
+/// do with names.
+#[must_use]
+pub fn render_flavor_text(flavor: &str) -> Markup {
+    html! {
+        @for segment in parse_markup(flavor) {
+            @match segment {
+                RulesTextSegment::Italic(text) => em { (text) },
+                RulesTextSegment::Bold(text) => strong { (text) },
+                RulesTextSegment::Text(text) => (text),
+                _ => {}
+            }
+        }
+    }
+}
+
 /// Render rules text with inline mana symbols
 ///
 /// This function takes a pre-parsed `RulesText` and renders each segment
 /// appropriately - text segments as plain text, symbol segments as images.
+/// Each paragraph is its own `<p>`. An activation cost made of symbols
+/// alone, like `{E}{E}, {T}:`, is kept together on one line.
 #[must_use]
 pub fn render_rules_text(rules: &RulesText) -> Markup {
     html! {
         div.rules-text-inner {
-            @for segment in &rules.segments {
-                @match segment {
-                    RulesTextSegment::Text(text) => (text),
-                    RulesTextSegment::Symbol(symbol) => (render_mana_symbol(*symbol)),
+            @for paragraph in rules.paragraphs() {
+                p.rules-paragraph {
+                    @if let Some((cost, colon, after, rest)) = split_symbol_cost(paragraph) {
+                        span.symbol-cost {
+                            @for segment in cost {
+                                (render_rules_segment(segment))
+                            }
+                            (colon)
+                        }
+                        (after)
+                        @for segment in rest {
+                            (render_rules_segment(segment))
+                        }
+                    } @else {
+                        @for segment in paragraph {
+                            (render_rules_segment(segment))
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn render_rules_segment(segment: &RulesTextSegment) -> Markup {
+    html! {
+        @match segment {
+            RulesTextSegment::Text(text) => (text),
+            RulesTextSegment::Symbol(symbol) => (render_symbol_use(*symbol)),
+            RulesTextSegment::Reminder(reminder) => {
+                span.reminder-text {
+                    "("
+                    @for part in reminder {
+                        @match part {
+                            ReminderSegment::Text(text) => (text),
+                            ReminderSegment::Symbol(symbol) => (render_symbol_use(*symbol)),
+                        }
+                    }
+                    ")"
+                }
+            }
+            RulesTextSegment::AbilityWord(word) => span.ability-word { (word) },
+            RulesTextSegment::Italic(text) => em { (text) },
+            RulesTextSegment::Bold(text) => strong { (text) },
+            RulesTextSegment::ParagraphBreak => {}
+        }
+    }
+}
+
+/// Splits an activation cost off the start of `paragraph` when the cost is
+/// only symbols and commas, at least one symbol not being mana, like
+/// `{E}{E}, {T}:`. Returns the cost's segments, the text after them up to
+/// and including the colon, the rest of that text, and the segments after.
+fn split_symbol_cost(
+    paragraph: &[RulesTextSegment],
+) -> Option<(&[RulesTextSegment], &str, &str, &[RulesTextSegment])> {
+    let mut has_action = false;
+    for (i, segment) in paragraph.iter().enumerate() {
+        match segment {
+            RulesTextSegment::Symbol(symbol) => {
+                has_action |= !matches!(symbol, ManaSymbol::Casting(_));
+            }
+            RulesTextSegment::Text(text) => {
+                let end = text.find(':').unwrap_or(text.len());
+                if !text[..end].chars().all(|c| c == ',' || c.is_whitespace()) {
+                    return None;
+                }
+                if end < text.len() {
+                    return has_action.then(|| {
+                        (
+                            &paragraph[..i],
+                            &text[..=end],
+                            &text[end + 1..],
+                            &paragraph[i + 1..],
+                        )
+                    });
                 }
             }
+            _ => return None,
         }
     }
+    None
 }
 
 /// Generate CSS for card styling with real MTG assets
 #[must_use]
 pub fn generate_css() -> Markup {
-    // Get absolute path to mtgrender assets
-    let assets_base = std::env::current_dir()
-        .unwrap_or_default()
-        .join("mtgrender/client/src/assets");
+    let assets_base = assets_base();
 
     html! {
         style {
@@ -339,7 +749,9 @@ pub fn generate_css() -> Markup {
                 position: relative;
             }
 
-            /* Frame backgrounds using real assets - use bg/ for ornate textured borders */
+            /* Frame backgrounds using real assets - use bg/ for ornate textured borders.
+               Custom colors borrow the artifact assets until the style file
+               gives them their own */
             .bg-white { background-image: url('file://"# (assets_base.join("img/bg/W.png").display()) r#"'); }
             .bg-blue { background-image: url('file://"# (assets_base.join("img/bg/U.png").display()) r#"'); }
             .bg-black { background-image: url('file://"# (assets_base.join("img/bg/B.png").display()) r#"'); }
@@ -349,6 +761,7 @@ pub fn generate_css() -> Markup {
             .bg-artifact { background-image: url('file://"# (assets_base.join("img/bg/Artifact.png").display()) r#"'); }
             .bg-colorless { background-image: url('file://"# (assets_base.join("img/bg/Colourless.png").display()) r#"'); }
             .bg-land { background-image: url('file://"# (assets_base.join("img/bg/Land.png").display()) r#"'); }
+            [class*="bg-custom-"] { background-image: url('file://"# (assets_base.join("img/bg/Artifact.png").display()) r#"'); }
 
             /* Main Frame overlays (Borders, Name/Type boxes) */
             .frame-white { background-image: url('file://"# (assets_base.join("img/frames/W.png").display()) r#"'); }
@@ -360,6 +773,7 @@ pub fn generate_css() -> Markup {
             .frame-artifact { background-image: url('file://"# (assets_base.join("img/frames/Artifact.png").display()) r#"'); }
             .frame-colorless { background-image: url('file://"# (assets_base.join("img/frames/Colourless.png").display()) r#"'); }
             .frame-land { background-image: url('file://"# (assets_base.join("img/frames/Land.png").display()) r#"'); }
+            [class*="frame-custom-"] { background-image: url('file://"# (assets_base.join("img/frames/Artifact.png").display()) r#"'); }
 
             /* Text box backgrounds (parchment) */
             .text-box-bg-white { background-image: url('file://"# (assets_base.join("img/boxes/W.png").display()) r#"'); }
@@ -371,6 +785,7 @@ pub fn generate_css() -> Markup {
             .text-box-bg-artifact { background-image: url('file://"# (assets_base.join("img/boxes/Artifact.png").display()) r#"'); }
             .text-box-bg-colorless { background-image: url('file://"# (assets_base.join("img/boxes/Colourless.png").display()) r#"'); }
             .text-box-bg-land { background-image: url('file://"# (assets_base.join("img/boxes/Land.png").display()) r#"'); }
+            [class*="text-box-bg-custom-"] { background-image: url('file://"# (assets_base.join("img/boxes/Artifact.png").display()) r#"'); }
 
             /* P/T box backgrounds */
             .pt-box-white { background-image: url('file://"# (assets_base.join("img/pt_boxes/W.png").display()) r#"'); }
@@ -382,6 +797,7 @@ pub fn generate_css() -> Markup {
             .pt-box-artifact { background-image: url('file://"# (assets_base.join("img/pt_boxes/Artifact.png").display()) r#"'); }
             .pt-box-colorless { background-image: url('file://"# (assets_base.join("img/pt_boxes/Colourless.png").display()) r#"'); }
             .pt-box-land { background-image: url('file://"# (assets_base.join("img/pt_boxes/Land.png").display()) r#"'); }
+            [class*="pt-box-custom-"] { background-image: url('file://"# (assets_base.join("img/pt_boxes/Artifact.png").display()) r#"'); }
 
             /* Header section */
             .card-header {
@@ -434,6 +850,17 @@ pub fn generate_css() -> Markup {
                 font-size: 14px;
             }
 
+            /* Custom mana without a symbol image from the style file */
+            .mana-custom {
+                display: inline-flex;
+                align-items: center;
+                justify-content: center;
+                background: #b9a7c9 center / contain no-repeat;
+                color: #000;
+                font-weight: bold;
+                font-size: 15px;
+            }
+
             /* Art box */
             .art-box {
                 position: absolute;
@@ -450,6 +877,16 @@ pub fn generate_css() -> Markup {
                 z-index: 1;
             }
 
+            .art-box, .split-art {
+                overflow: hidden;
+            }
+
+            .art-image {
+                width: 100%;
+                height: 100%;
+                display: block;
+            }
+
             /* Type line */
             .type-line {
                 position: absolute;
@@ -516,8 +953,19 @@ pub fn generate_css() -> Markup {
                 margin-bottom: 12px;
             }
 
-            .rules-text-inner {
-                display: inline;
+            .rules-paragraph {
+                margin: 0;
+            }
+
+            .rules-paragraph + .rules-paragraph {
+                margin-top: 0.3em;
+            }
+
+            /* An activation cost of symbols, like {E}{E}, {T}:, set
+               tightly on one line */
+            .symbol-cost {
+                white-space: nowrap;
+                word-spacing: -0.15em;
             }
 
             .rules-text .mana-symbol {
@@ -535,6 +983,20 @@ pub fn generate_css() -> Markup {
                 margin-top: 8px;
             }
 
+            .flavor-text em {
+                font-style: normal;
+            }
+
+            /* The thin bar between rules text and flavor text, fading out
+               at both ends */
+            .rules-text + .flavor-text::before {
+                content: "";
+                display: block;
+                height: 2px;
+                margin: 0 24px 14px;
+                background: linear-gradient(to right, transparent, rgba(0, 0, 0, 0.55) 12%, rgba(0, 0, 0, 0.55) 88%, transparent);
+            }
+
             /* Power/Toughness box */
             .pt-box {
                 position: absolute;
@@ -559,21 +1021,108 @@ pub fn generate_css() -> Markup {
                 padding-left: 6px;
             }
 
-            /* Rarity indicator */
-            .rarity-indicator {
+            /* Prototype inset (colored by the prototype cost) */
+            .prototype-inset {
                 position: absolute;
-                bottom: 32px;
-                left: 50%;
-                transform: translateX(-50%);
-                width: 20px;
-                height: 20px;
+                top: 606px;
+                left: 56px;
+                width: 150px;
+                height: 130px;
+                padding: 10px;
+                display: flex;
+                flex-direction: column;
+                align-items: center;
+                justify-content: space-between;
+                background-size: cover;
+                border: 3px solid rgba(0, 0, 0, 0.6);
+                border-radius: 8px;
+                z-index: 22;
+            }
+
+            .prototype-cost .mana-symbol {
+                width: 24px;
+                height: 24px;
+            }
+
+            .prototype-pt {
+                width: 76px;
+                height: 48px;
+                background-size: 100% 100%;
+                background-repeat: no-repeat;
+                display: flex;
+                align-items: center;
+                justify-content: center;
+            }
+
+            .prototype-pt-text {
+                font-size: 26px;
+                font-weight: bold;
+                color: #000;
+                font-family: 'Matrix', serif;
+                padding-top: 4px;
+                padding-left: 4px;
+            }
+
+            .text-box-with-prototype {
+                padding-left: 190px;
+            }
+
+            /* Token: art up to the top of the card, under a curved title
+               plate with the name centered */
+            .token .art-box {
+                z-index: 15;
+                border-radius: 24px 24px 0 0;
+            }
+
+            .token .card-header {
+                justify-content: center;
+                left: 142px;
+                width: 448px;
+                height: 44px;
+                background: linear-gradient(to bottom, #f4efe4, #d8d0bf);
+                border: 3px solid rgba(0, 0, 0, 0.7);
+                border-bottom: none;
+                border-radius: 50% 50% 0 0 / 100% 100% 0 0;
+            }
+
+            /* Set symbol at the end of the type line, tinted by rarity. Cards
+               without a set symbol get a dot in its place */
+            .rarity-indicator {
+                flex-shrink: 0;
+                margin-left: auto;
+                margin-right: 8px;
+                width: 22px;
+                height: 22px;
                 border-radius: 50%;
+                background: radial-gradient(circle, var(--rarity-center), var(--rarity-middle), var(--rarity-edge));
             }
 
-            .rarity-common { background: #1a1a1a; }
-            .rarity-uncommon { background: #707070; }
-            .rarity-rare { background: #a58e4a; }
-            .rarity-mythic { background: #bf4427; }
+            .set-symbol-image {
+                width: 40px;
+                height: 30px;
+                border-radius: 0;
+                background: none;
+            }
+
+            .collector-line {
+                position: absolute;
+                bottom: 14px;
+                left: 28px;
+                font-size: 14px;
+                font-family: 'Beleren Small Caps', serif;
+                color: #fff;
+                z-index: 25;
+            }
+
+            /* Each rarity's gradient, from the middle of the symbol out */
+            .rarity-common { --rarity-center: #1a1a1a; --rarity-middle: #1a1a1a; --rarity-edge: #1a1a1a; }
+            .rarity-uncommon { --rarity-center: #e0e8eb; --rarity-middle: #a7b2b7; --rarity-edge: #6e7a80; }
+            .rarity-rare { --rarity-center: #f2d98b; --rarity-middle: #c9a54a; --rarity-edge: #8a6d2b; }
+            .rarity-mythic { --rarity-center: #f7a33c; --rarity-middle: #e2591d; --rarity-edge: #b23a12; }
+            .rarity-special { --rarity-center: #b98ac6; --rarity-middle: #8a4d9c; --rarity-edge: #652978; }
+            .rarity-bonus { --rarity-center: #b06bb3; --rarity-middle: #766ca9; --rarity-edge: #3c6e9e; }
+            .rarity-land { --rarity-center: #1a1a1a; --rarity-middle: #1a1a1a; --rarity-edge: #1a1a1a; }
+            .rarity-land:not(.set-symbol-image) { border: 2px solid #c8c8c8; }
 
             /* Planeswalker styles */
             .planeswalker-text-box {
@@ -584,49 +1133,71 @@ pub fn generate_css() -> Markup {
                 height: 420px;
                 display: flex;
                 flex-direction: column;
-                gap: 8px;
-                padding: 16px 24px;
                 z-index: 5;
             }
 
+            /* Each ability is a bar across the whole text box, alternating
+               light and dark like the printed frame */
             .loyalty-ability {
+                position: relative;
+                flex: 1;
                 display: flex;
-                gap: 12px;
-                padding: 8px 12px;
-                background: rgba(255, 255, 255, 0.85);
-                border-radius: 6px;
-                border: 1px solid rgba(0, 0, 0, 0.2);
-                align-items: flex-start;
+                align-items: center;
+                padding: 8px 24px 8px 68px;
+            }
+
+            .loyalty-ability:nth-child(odd) {
+                background: rgba(242, 237, 228, 0.88);
+            }
+
+            .loyalty-ability:nth-child(even) {
+                background: rgba(205, 197, 184, 0.88);
+            }
+
+            /* Keep the last ability's text clear of the loyalty shield */
+            .loyalty-ability:last-child {
+                padding-right: 112px;
+            }
+
+            /* The badge hangs over the frame's left edge; the outline is a
+               shadow on this wrapper, since the clipped badge can't have a
+               border */
+            .loyalty-badge {
+                position: absolute;
+                left: -18px;
+                top: 50%;
+                transform: translateY(-50%);
+                filter: drop-shadow(0 0 1.5px #000) drop-shadow(0 2px 3px rgba(0, 0, 0, 0.5));
             }
 
             .loyalty-cost {
-                flex-shrink: 0;
-                width: 48px;
-                height: 48px;
+                width: 68px;
+                height: 56px;
+                box-sizing: border-box;
                 display: flex;
                 align-items: center;
                 justify-content: center;
                 font-size: 28px;
                 font-weight: bold;
                 font-family: 'Beleren', serif;
-                border-radius: 50%;
                 color: #fff;
-                text-shadow: 1px 1px 2px rgba(0, 0, 0, 0.8);
+                background: linear-gradient(180deg, #5a5a5a 0%, #262626 100%);
             }
 
+            /* Official badge shapes: pointing up for +N, down for -N, and a
+               flat hexagon for 0 */
             .loyalty-cost-plus {
-                background: linear-gradient(135deg, #4a90e2 0%, #357abd 100%);
-                box-shadow: 0 2px 4px rgba(0, 0, 0, 0.3);
+                clip-path: polygon(50% 0, 100% 26%, 100% 100%, 0 100%, 0 26%);
+                padding-top: 10px;
             }
 
             .loyalty-cost-minus {
-                background: linear-gradient(135deg, #e24a4a 0%, #bd3535 100%);
-                box-shadow: 0 2px 4px rgba(0, 0, 0, 0.3);
+                clip-path: polygon(0 0, 100% 0, 100% 74%, 50% 100%, 0 74%);
+                padding-bottom: 10px;
             }
 
             .loyalty-cost-zero {
-                background: linear-gradient(135deg, #888 0%, #666 100%);
-                box-shadow: 0 2px 4px rgba(0, 0, 0, 0.3);
+                clip-path: polygon(14% 0, 86% 0, 100% 50%, 86% 100%, 14% 100%, 0 50%);
             }
 
             .loyalty-ability-text {
@@ -635,7 +1206,6 @@ pub fn generate_css() -> Markup {
                 line-height: 1.3;
                 color: #000;
                 font-family: 'MPlantin', serif;
-                padding-top: 4px;
             }
 
             .loyalty-ability-text .mana-symbol {
@@ -645,72 +1215,104 @@ pub fn generate_css() -> Markup {
 
             .loyalty-counter {
                 position: absolute;
-                bottom: 32px;
-                right: 36px;
-                width: 80px;
-                height: 80px;
-                background: linear-gradient(135deg, #f4f4f4 0%, #d4d4d4 100%);
-                border: 4px solid #000;
-                border-radius: 50%;
+                bottom: 28px;
+                right: 30px;
+                width: 92px;
+                height: 84px;
+                filter: drop-shadow(0 0 2px #000) drop-shadow(0 3px 4px rgba(0, 0, 0, 0.5));
+                z-index: 20;
+            }
+
+            /* The loyalty shield: flat top, rounded point at the bottom */
+            .loyalty-shield {
+                width: 100%;
+                height: 100%;
                 display: flex;
                 align-items: center;
                 justify-content: center;
-                font-size: 48px;
+                padding-bottom: 10px;
+                box-sizing: border-box;
+                clip-path: polygon(0 0, 100% 0, 100% 58%, 86% 82%, 50% 100%, 14% 82%, 0 58%);
+                background: linear-gradient(180deg, #4a4a4a 0%, #1c1c1c 100%);
+                font-size: 44px;
                 font-weight: bold;
                 font-family: 'Beleren', serif;
-                color: #000;
-                box-shadow: 0 4px 8px rgba(0, 0, 0, 0.4);
-                z-index: 20;
+                color: #fff;
+            }
+
+            /* Saga styles: chapters down the left half, art down the right */
+            .saga-art {
+                top: 75px;
+                left: 372px;
+                width: 336px;
+                height: 815px;
+            }
+
+            .saga-type-line {
+                top: 900px;
             }
 
-            /* Saga styles */
             .saga-text-box {
                 position: absolute;
-                top: 480px;
+                top: 75px;
                 left: 36px;
-                width: 672px;
-                height: 420px;
+                width: 336px;
+                height: 815px;
+                box-sizing: border-box;
                 display: flex;
                 flex-direction: column;
-                gap: 12px;
-                padding: 20px 28px;
+                padding: 14px 12px 0 0;
+                background: rgba(255, 255, 255, 0.85);
                 z-index: 5;
             }
 
+            .saga-reminder {
+                font-size: 18px;
+                font-style: italic;
+                line-height: 1.2;
+                color: #000;
+                font-family: 'MPlantin', serif;
+                padding: 0 0 12px 14px;
+            }
+
             .saga-chapter {
+                flex: 1;
+                min-height: 0;
                 display: flex;
-                gap: 16px;
-                padding: 10px 14px;
-                background: rgba(255, 255, 255, 0.85);
-                border-radius: 6px;
-                border-left: 4px solid rgba(0, 0, 0, 0.3);
-                align-items: flex-start;
+                align-items: center;
+                gap: 10px;
+                border-top: 2px solid rgba(0, 0, 0, 0.35);
             }
 
-            .saga-chapter-number {
+            /* Combined chapters stack their numerals, I above II */
+            .saga-chapter-marker {
                 flex-shrink: 0;
-                width: 40px;
+                display: flex;
+                flex-direction: column;
+                gap: 4px;
+                margin-left: -14px;
+            }
+
+            .saga-chapter-numeral {
+                width: 46px;
                 height: 40px;
                 display: flex;
                 align-items: center;
                 justify-content: center;
-                font-size: 24px;
+                clip-path: polygon(25% 0, 75% 0, 100% 50%, 75% 100%, 25% 100%, 0 50%);
+                font-size: 22px;
                 font-weight: bold;
                 font-family: 'Beleren', serif;
                 color: #fff;
                 background: linear-gradient(135deg, #2a2a2a 0%, #1a1a1a 100%);
-                border-radius: 50%;
-                border: 2px solid #000;
-                box-shadow: 0 2px 4px rgba(0, 0, 0, 0.3);
             }
 
             .saga-chapter-text {
                 flex: 1;
-                font-size: 22px;
-                line-height: 1.3;
+                font-size: 21px;
+                line-height: 1.25;
                 color: #000;
                 font-family: 'MPlantin', serif;
-                padding-top: 6px;
             }
 
             .saga-chapter-text .mana-symbol {
@@ -718,6 +1320,97 @@ pub fn generate_css() -> Markup {
                 height: 20px;
             }
 
+            /* Leveler styles: a strip per level range, each with its own
+               power/toughness */
+            .leveler-text-box {
+                padding: 0;
+                gap: 0;
+            }
+
+            .leveler-strip {
+                flex: 1;
+                min-height: 0;
+                display: flex;
+                align-items: center;
+                gap: 14px;
+                padding: 6px 14px 6px 20px;
+                border-top: 2px solid rgba(0, 0, 0, 0.35);
+            }
+
+            .leveler-base {
+                border-top: none;
+            }
+
+            .leveler-strip:nth-child(even) {
+                background: rgba(0, 0, 0, 0.08);
+            }
+
+            .leveler-badge {
+                flex-shrink: 0;
+                width: 82px;
+                height: 58px;
+                display: flex;
+                flex-direction: column;
+                align-items: center;
+                justify-content: center;
+                clip-path: polygon(0 0, 82% 0, 100% 50%, 82% 100%, 0 100%);
+                background: linear-gradient(180deg, #4a4a4a 0%, #1c1c1c 100%);
+                color: #fff;
+                font-family: 'Beleren', serif;
+                font-weight: bold;
+                padding-right: 10px;
+                box-sizing: border-box;
+            }
+
+            .leveler-badge-label {
+                font-size: 13px;
+                letter-spacing: 1px;
+            }
+
+            .leveler-badge-range {
+                font-size: 24px;
+                line-height: 1;
+            }
+
+            .leveler-text {
+                flex: 1;
+                min-width: 0;
+            }
+
+            .leveler-text .rules-text {
+                font-size: 22px;
+            }
+
+            .leveler-pt {
+                flex-shrink: 0;
+                width: 90px;
+                height: 64px;
+                background-size: contain;
+                background-repeat: no-repeat;
+                display: flex;
+                align-items: center;
+                justify-content: center;
+            }
+
+            /* Boxes of a hybrid cost, blending from one color's box into
+               the other's */
+            .hybrid-box {
+                position: relative;
+            }
+
+            .hybrid-box > * {
+                position: relative;
+            }
+
+            .hybrid-box > .hybrid-blend {
+                position: absolute;
+                inset: 0;
+                background-size: 100% 100%;
+                -webkit-mask-image: linear-gradient(to right, transparent 35%, #000 65%);
+                mask-image: linear-gradient(to right, transparent 35%, #000 65%);
+                pointer-events: none;
+            }
+
             /* Class card styles */
             .class-text-box {
                 flex: 1;
@@ -733,6 +1426,7 @@ pub fn generate_css() -> Markup {
             .class-level {
                 padding: 12px 16px;
                 border-bottom: 2px solid rgba(0, 0, 0, 0.2);
+                background-size: 100% 100%;
             }
 
             .class-level:last-child {
@@ -750,9 +1444,6 @@ pub fn generate_css() -> Markup {
                 font-size: 14px;
                 font-weight: bold;
                 color: #333;
-                background: rgba(0, 0, 0, 0.1);
-                padding: 4px 10px;
-                border-radius: 4px;
             }
 
             .class-level-cost {
@@ -774,10 +1465,6 @@ pub fn generate_css() -> Markup {
                 color: #000;
             }
 
-            .class-level-text .rules-text-inner {
-                display: inline;
-            }
-
             .class-level-text .mana-symbol {
                 width: 14px;
                 height: 14px;
@@ -864,6 +1551,30 @@ pub fn generate_css() -> Markup {
                 color: #000;
             }
 
+            .split-text-box .flavor-text {
+                font-size: 19px;
+            }
+
+            .split-rules + .flavor-text::before {
+                content: "";
+                display: block;
+                height: 2px;
+                margin: 0 16px 10px;
+                background: linear-gradient(to right, transparent, rgba(0, 0, 0, 0.55) 12%, rgba(0, 0, 0, 0.55) 88%, transparent);
+            }
+
+            /* Each half credits its own artist, in the border below its
+               text box */
+            .split-artist {
+                position: absolute;
+                bottom: 12px;
+                left: 48px;
+                font-size: 12px;
+                font-family: 'Beleren Small Caps', serif;
+                color: #fff;
+                z-index: 25;
+            }
+
             /* Battle card styles */
             .defense-counter {
                 position: absolute;
@@ -885,6 +1596,237 @@ pub fn generate_css() -> Markup {
                 clip-path: polygon(50% 0%, 100% 25%, 100% 75%, 50% 100%, 0% 75%, 0% 25%);
             }
 
+            .reminder-text,
+            .ability-word {
+                font-style: italic;
+            }
+
+            /* Emblems */
+            .emblem-source {
+                font-size: 20px;
+                font-style: italic;
+                text-align: right;
+                color: #333;
+                margin-top: 12px;
+            }
+
+            /* Symbol legend */
+            .legend-box {
+                position: absolute;
+                top: 75px;
+                left: 44px;
+                width: 656px;
+                height: 870px;
+                box-sizing: border-box;
+                display: grid;
+                grid-template-columns: 1fr 1fr;
+                /* legend::SYMBOLS_PER_CARD, down two columns */
+                grid-template-rows: repeat(12, 1fr);
+                grid-auto-flow: column;
+                column-gap: 20px;
+                padding: 16px 20px;
+                background: rgba(255, 255, 255, 0.9);
+                border-radius: 8px;
+                z-index: 5;
+            }
+
+            .legend-entry {
+                display: flex;
+                align-items: center;
+                gap: 12px;
+                min-height: 0;
+            }
+
+            .legend-entry .mana-symbol,
+            .legend-entry .mana-generic {
+                width: 36px;
+                height: 36px;
+                flex-shrink: 0;
+            }
+
+            .legend-meaning {
+                font-size: 19px;
+                line-height: 1.15;
+                color: #000;
+                font-family: 'MPlantin', serif;
+            }
+
+            /* Back face hint bar (name and type of the transformed face) */
+            .back-face-hint {
+                position: absolute;
+                bottom: 40px;
+                left: 60px;
+                width: 500px;
+                height: 30px;
+                display: flex;
+                align-items: center;
+                justify-content: space-between;
+                gap: 12px;
+                padding: 0 12px;
+                background: rgba(0, 0, 0, 0.75);
+                border-radius: 4px;
+                color: #fff;
+                font-family: 'Beleren Small Caps', serif;
+                font-size: 16px;
+                z-index: 20;
+            }
+
+            .back-face-hint-type {
+                font-size: 14px;
+                opacity: 0.8;
+            }
+
+            /* Flip cards: the top face, the shared art, then the flipped
+               face turned upside down */
+            .flip-half {
+                position: absolute;
+                top: 0;
+                left: 0;
+                width: 744px;
+                height: 520px;
+            }
+
+            .flip-bottom {
+                top: 520px;
+                transform: rotate(180deg);
+            }
+
+            .flip-half .text-box-bg {
+                top: 72px;
+                height: 160px;
+            }
+
+            .flip-text-box {
+                position: absolute;
+                top: 72px;
+                left: 44px;
+                width: 656px;
+                height: 160px;
+                box-sizing: border-box;
+                padding: 12px 28px;
+                z-index: 20;
+                font-family: 'MPlantin', serif;
+                display: flex;
+                flex-direction: column;
+                justify-content: center;
+                gap: 6px;
+            }
+
+            .flip-text-box .rules-text {
+                font-size: 22px;
+            }
+
+            .flip-text-box .flavor-text {
+                font-size: 20px;
+            }
+
+            .flip-type-line {
+                top: 238px;
+            }
+
+            .flip-pt-box {
+                top: 226px;
+                bottom: auto;
+            }
+
+            .flip-art {
+                top: 282px;
+                height: 476px;
+            }
+
+            /* Keep the set symbol clear of the top half's P/T box */
+            .flip-card .rarity-indicator {
+                margin-right: 120px;
+            }
+
+            /* Double-faced card icons, in the frame's top-left corner */
+            .dfc-icon {
+                position: absolute;
+                top: 31px;
+                left: 8px;
+                width: 30px;
+                height: 30px;
+                border-radius: 50%;
+                border: 2px solid #d8d8d8;
+                background-color: #111;
+                z-index: 25;
+            }
+
+            .dfc-icon-sun {
+                background-image: radial-gradient(circle, #f4e3a1 38%, transparent 41%);
+            }
+
+            .dfc-icon-moon {
+                background-image:
+                    radial-gradient(circle at 66% 38%, #111 30%, transparent 32%),
+                    radial-gradient(circle, #d8d8d8 40%, transparent 43%);
+            }
+
+            .dfc-icon-full-moon {
+                background-image: radial-gradient(circle, #d8d8d8 40%, transparent 43%);
+            }
+
+            .dfc-icon-meld::after,
+            .dfc-icon-modal-front::after,
+            .dfc-icon-modal-back::after,
+            .dfc-icon-compass::after,
+            .dfc-icon-land::after,
+            .dfc-icon-spark::after,
+            .dfc-icon-planeswalker::after,
+            .dfc-icon-eldrazi::after {
+                content: '';
+                position: absolute;
+                inset: 6px;
+                background: #d8d8d8;
+            }
+
+            .dfc-icon-meld::after {
+                clip-path: polygon(50% 0, 100% 50%, 50% 100%, 0 50%);
+            }
+
+            .dfc-icon-modal-front::after {
+                clip-path: polygon(25% 10%, 90% 50%, 25% 90%);
+            }
+
+            .dfc-icon-modal-back::after {
+                clip-path: polygon(75% 10%, 10% 50%, 75% 90%);
+            }
+
+            /* A four-pointed compass star */
+            .dfc-icon-compass::after {
+                clip-path: polygon(50% 0, 60% 40%, 100% 50%, 60% 60%, 50% 100%, 40% 60%, 0 50%, 40% 40%);
+            }
+
+            /* Two mountain peaks */
+            .dfc-icon-land::after {
+                clip-path: polygon(0 85%, 35% 20%, 55% 55%, 70% 35%, 100% 85%);
+            }
+
+            /* A narrow four-pointed spark */
+            .dfc-icon-spark::after {
+                clip-path: polygon(50% 0, 56% 44%, 90% 50%, 56% 56%, 50% 100%, 44% 56%, 10% 50%, 44% 44%);
+            }
+
+            /* The planeswalker symbol's three prongs */
+            .dfc-icon-planeswalker::after {
+                clip-path: polygon(10% 10%, 25% 10%, 35% 60%, 42% 0, 58% 0, 65% 60%, 75% 10%, 90% 10%, 75% 80%, 50% 100%, 25% 80%);
+            }
+
+            /* Eldrazi tendrils hanging from a crescent */
+            .dfc-icon-eldrazi::after {
+                clip-path: polygon(0 15%, 100% 15%, 85% 40%, 80% 95%, 68% 45%, 58% 100%, 50% 45%, 42% 100%, 32% 45%, 20% 95%, 15% 40%);
+            }
+
+            /* Color indicator of faces without a mana cost */
+            .color-indicator {
+                flex-shrink: 0;
+                width: 22px;
+                height: 22px;
+                margin-right: 8px;
+                border-radius: 50%;
+                border: 1px solid #000;
+            }
+
             /* Adventure card styles */
             .adventure-card {
                 display: flex;
@@ -898,7 +1840,8 @@ pub fn generate_css() -> Markup {
                 flex-direction: column;
                 padding: 20px 16px;
                 border-right: 2px solid rgba(0, 0, 0, 0.3);
-                background: rgba(0, 0, 0, 0.05);
+                background-color: rgba(0, 0, 0, 0.05);
+                background-size: 100% 100%;
             }
 
             .adventure-name {
@@ -945,14 +1888,39 @@ pub fn generate_css() -> Markup {
                 flex: 2;
             }
 
-            .adventure-right {
-                flex: 1;
-                display: flex;
-                flex-direction: column;
-                position: relative;
+            .adventure-flavor {
+                font-size: 13px;
+                font-style: italic;
+                line-height: 1.2;
+                font-family: 'MPlantin', serif;
+                color: #000;
+                writing-mode: vertical-rl;
+                text-orientation: mixed;
+                transform: rotate(180deg);
+                margin-top: 8px;
             }
-            "#
-        }
+
+            .adventure-flavor em {
+                font-style: normal;
+            }
+
+            .adventure-artist {
+                font-size: 11px;
+                font-family: 'Beleren Small Caps', serif;
+                color: #000;
+                writing-mode: vertical-rl;
+                transform: rotate(180deg);
+                margin-top: 8px;
+            }
+
+            .adventure-right {
+                flex: 1;
+                display: flex;
+                flex-direction: column;
+                position: relative;
+            }
+            "#
+        }
     }
 }
 
@@ -964,6 +1932,49 @@ pub fn generate_css() -> Markup {
 pub trait RenderableCard {
     /// Render the card to HTML markup
     fn render_html(&self) -> Markup;
+
+    /// The card's colors, shown as chips when color chips are turned on.
+    /// None by default.
+    fn colors(&self) -> Vec<Color> {
+        Vec::new()
+    }
+
+    /// The card's rarity, printed as a letter when rarity letters are
+    /// turned on. None by default.
+    fn rarity(&self) -> Option<Rarity> {
+        None
+    }
+
+    /// The card's set code, filled into its legal line, see
+    /// [`crate::legal`]. None by default.
+    fn set_code(&self) -> Option<&str> {
+        None
+    }
+
+    /// The size the card asks to be rendered at, instead of the renderer's.
+    /// None by default.
+    fn size(&self) -> Option<CardSize> {
+        None
+    }
+
+    /// The effects laid over the card's frame, see [`crate::frame_effects`].
+    /// None by default.
+    fn frame_effects(&self) -> Vec<FrameEffect> {
+        Vec::new()
+    }
+
+    /// The card's name, which its generated textures are drawn from in a
+    /// seeded run, see [`crate::frame_effects::card_seed`]. Empty by
+    /// default.
+    fn card_name(&self) -> &str {
+        ""
+    }
+
+    /// The CSS `style` adds to this card's page. Layouts with their own
+    /// style settings add them to [`StyleConfig::css`].
+    fn style_css(&self, style: &StyleConfig) -> String {
+        style.css()
+    }
 }
 
 // ============================================================================
@@ -972,8 +1983,7 @@ pub trait RenderableCard {
 
 impl RenderableCard for NormalCard {
     fn render_html(&self) -> Markup {
-        let classes = FrameClasses::from_mana_cost(&self.base.mana_cost);
-        let rarity = rarity_class(self.base.rarity);
+        let classes = FrameClasses::for_card(&self.base);
 
         html! {
             html {
@@ -983,7 +1993,7 @@ impl RenderableCard for NormalCard {
                 }
                 body {
                     div class=(format!("card {}", classes.bg)) {
-                        div.art-box { "[Art]" }
+                        (render_art("art-box", self.base.art.as_deref(), self.base.art_fit))
                         div class=(format!("card-frame {}", classes.frame)) {}
                         div.card-inner {
                             div.card-header {
@@ -994,6 +2004,7 @@ impl RenderableCard for NormalCard {
                             }
                             div.type-line {
                                 div.type-text { (&self.base.type_line) }
+                                (render_set_symbol(&self.base))
                             }
                             div class=(format!("text-box-bg {}", classes.text_box_bg)) {}
                             div.text-box {
@@ -1001,7 +2012,7 @@ impl RenderableCard for NormalCard {
                                     div.rules-text { (render_rules_text(rules)) }
                                 }
                                 @if let Some(ref flavor) = self.base.flavor_text {
-                                    div.flavor-text { (flavor) }
+                                    div.flavor-text { (render_flavor_text(flavor)) }
                                 }
                             }
                             @if let (Some(power), Some(toughness)) = (&self.base.power, &self.base.toughness) {
@@ -1009,7 +2020,7 @@ impl RenderableCard for NormalCard {
                                     div.pt-text { (power) "/" (toughness) }
                                 }
                             }
-                            div.rarity-indicator class=(rarity) {}
+                            (render_collector_line(&self.base))
                         }
                     }
                 }
@@ -1020,8 +2031,7 @@ impl RenderableCard for NormalCard {
 
 impl RenderableCard for PlaneswalkerCard {
     fn render_html(&self) -> Markup {
-        let classes = FrameClasses::from_mana_cost(&self.base.mana_cost);
-        let rarity = rarity_class(self.base.rarity);
+        let classes = FrameClasses::for_card(&self.base);
         let loyalty_text = match self.loyalty {
             LoyaltyValue::Numeric(n) => n.to_string(),
             LoyaltyValue::X => "X".to_string(),
@@ -1042,9 +2052,10 @@ impl RenderableCard for PlaneswalkerCard {
                                     (render_mana_cost(cost))
                                 }
                             }
-                            div.art-box { "[Art]" }
+                            (render_art("art-box", self.base.art.as_deref(), self.base.art_fit))
                             div.type-line {
                                 div.type-text { (&self.base.type_line) }
+                                (render_set_symbol(&self.base))
                             }
                             div.planeswalker-text-box {
                                 @for ability in &self.loyalty_abilities {
@@ -1056,13 +2067,17 @@ impl RenderableCard for PlaneswalkerCard {
                                             LoyaltyCost::PlusX => ("loyalty-cost-plus", "+X".to_string()),
                                             LoyaltyCost::MinusX => ("loyalty-cost-minus", "-X".to_string()),
                                         };
-                                        div class=(format!("loyalty-cost {}", cost_class)) { (cost_text) }
+                                        div.loyalty-badge {
+                                            div class=(format!("loyalty-cost {}", cost_class)) { (cost_text) }
+                                        }
                                         div.loyalty-ability-text { (render_rules_text(&ability.text)) }
                                     }
                                 }
                             }
-                            div.loyalty-counter { (loyalty_text) }
-                            div.rarity-indicator class=(rarity) {}
+                            div.loyalty-counter {
+                                div.loyalty-shield { (loyalty_text) }
+                            }
+                            (render_collector_line(&self.base))
                         }
                     }
                 }
@@ -1071,10 +2086,24 @@ impl RenderableCard for PlaneswalkerCard {
     }
 }
 
+/// Build the reminder line printed above a saga's chapter abilities
+fn saga_reminder_text(saga: &SagaCard) -> String {
+    if saga.is_read_ahead() {
+        format!(
+            "Read ahead (Choose a chapter and start with that many lore counters. Add one after your draw step. Skipped chapters don't trigger. Sacrifice after {}.)",
+            to_roman_numeral(saga.final_chapter())
+        )
+    } else {
+        format!(
+            "(As this Saga enters and after your draw step, add a lore counter. Sacrifice after {}.)",
+            to_roman_numeral(saga.final_chapter())
+        )
+    }
+}
+
 impl RenderableCard for SagaCard {
     fn render_html(&self) -> Markup {
-        let classes = FrameClasses::from_mana_cost(&self.base.mana_cost);
-        let rarity = rarity_class(self.base.rarity);
+        let classes = FrameClasses::for_card(&self.base);
 
         html! {
             html {
@@ -1091,27 +2120,25 @@ impl RenderableCard for SagaCard {
                                     (render_mana_cost(cost))
                                 }
                             }
-                            div.art-box { "[Art]" }
-                            div.type-line {
+                            (render_art("art-box saga-art", self.base.art.as_deref(), self.base.art_fit))
+                            div.type-line.saga-type-line {
                                 div.type-text { (&self.base.type_line) }
+                                (render_set_symbol(&self.base))
                             }
                             div.saga-text-box {
+                                div.saga-reminder { (saga_reminder_text(self)) }
                                 @for chapter in &self.chapters {
                                     div.saga-chapter {
-                                        div.saga-chapter-number {
-                                            @if chapter.chapters.len() == 1 {
-                                                (format!("{}", chapter.chapters[0]))
-                                            } @else {
-                                                (format!("{}-{}",
-                                                    chapter.chapters.first().unwrap_or(&1),
-                                                    chapter.chapters.last().unwrap_or(&1)))
+                                        div.saga-chapter-marker {
+                                            @for &number in &chapter.chapters {
+                                                div.saga-chapter-numeral { (to_roman_numeral(number)) }
                                             }
                                         }
                                         div.saga-chapter-text { (render_rules_text(&chapter.text)) }
                                     }
                                 }
                             }
-                            div.rarity-indicator class=(rarity) {}
+                            (render_collector_line(&self.base))
                         }
                     }
                 }
@@ -1122,8 +2149,7 @@ impl RenderableCard for SagaCard {
 
 impl RenderableCard for ClassCard {
     fn render_html(&self) -> Markup {
-        let classes = FrameClasses::from_mana_cost(&self.base.mana_cost);
-        let rarity = rarity_class(self.base.rarity);
+        let classes = FrameClasses::for_card(&self.base);
 
         html! {
             html {
@@ -1140,28 +2166,34 @@ impl RenderableCard for ClassCard {
                                     (render_mana_cost(cost))
                                 }
                             }
-                            div.art-box { "[Art]" }
+                            (render_art("art-box", self.base.art.as_deref(), self.base.art_fit))
                             div.type-line {
                                 div.type-text { (&self.base.type_line) }
+                                (render_set_symbol(&self.base))
                             }
                             div.class-text-box {
+                                // Each level is colored by its own cost; the
+                                // first, which has none, by the card's
                                 @for level in &self.levels {
-                                    div.class-level {
-                                        div.class-level-header {
-                                            @if level.level == 1 {
-                                                span.class-level-indicator { "(Level 1)" }
-                                            } @else {
-                                                span.class-level-indicator { (format!("Level {}", level.level)) }
-                                                @if let Some(ref cost) = level.cost {
-                                                    div.class-level-cost { (render_mana_cost(cost)) }
+                                    @let content = html! {
+                                        @if let Some(ref cost) = level.cost {
+                                            div.class-level-header {
+                                                div.class-level-cost {
+                                                    (render_mana_cost(cost))
+                                                    span.class-level-indicator { (format!(": Level {}", level.level)) }
                                                 }
                                             }
                                         }
                                         div.class-level-text { (render_rules_text(&level.text)) }
+                                    };
+                                    @if level.cost.is_some() {
+                                        (render_cost_box("class-level", &level.cost, content))
+                                    } @else {
+                                        div class=(format!("class-level {}", classes.text_box_bg)) { (content) }
                                     }
                                 }
                             }
-                            div.rarity-indicator class=(rarity) {}
+                            (render_collector_line(&self.base))
                         }
                     }
                 }
@@ -1172,8 +2204,7 @@ impl RenderableCard for ClassCard {
 
 impl RenderableCard for AdventureCard {
     fn render_html(&self) -> Markup {
-        let classes = FrameClasses::from_mana_cost(&self.base.mana_cost);
-        let rarity = rarity_class(self.base.rarity);
+        let classes = FrameClasses::for_card(&self.base);
 
         html! {
             html {
@@ -1184,12 +2215,17 @@ impl RenderableCard for AdventureCard {
                 body {
                     div class=(format!("card {}", classes.frame)) {
                         div.adventure-card {
-                            div.adventure-left {
+                            // The bookmark is colored by the adventure's cost
+                            (render_cost_box("adventure-left", &Some(self.adventure.mana_cost.clone()), html! {
                                 div.adventure-cost { (render_mana_cost(&self.adventure.mana_cost)) }
                                 div.adventure-name { (&self.adventure.name) }
                                 div.adventure-type { (&self.adventure.type_line) }
                                 div.adventure-text { (render_rules_text(&self.adventure.rules_text)) }
-                            }
+                                @if let Some(ref flavor) = self.adventure.flavor_text {
+                                    div.adventure-flavor { (render_flavor_text(flavor)) }
+                                }
+                                (render_artist_credit("adventure-artist", self.adventure.artist.as_deref()))
+                            }))
                             div.adventure-right {
                                 div.card-header {
                                     div.card-name { (&self.base.name) }
@@ -1197,9 +2233,10 @@ impl RenderableCard for AdventureCard {
                                         (render_mana_cost(cost))
                                     }
                                 }
-                                div.art-box { "[Art]" }
+                                (render_art("art-box", self.base.art.as_deref(), self.base.art_fit))
                                 div.type-line {
                                     div.type-text { (&self.base.type_line) }
+                                    (render_set_symbol(&self.base))
                                 }
                                 div class=(format!("text-box-bg {}", classes.text_box_bg)) {}
                                 div.text-box {
@@ -1207,7 +2244,7 @@ impl RenderableCard for AdventureCard {
                                         div.rules-text { (render_rules_text(rules)) }
                                     }
                                     @if let Some(ref flavor) = self.base.flavor_text {
-                                        div.flavor-text { (flavor) }
+                                        div.flavor-text { (render_flavor_text(flavor)) }
                                     }
                                 }
                                 @if let (Some(power), Some(toughness)) = (&self.base.power, &self.base.toughness) {
@@ -1215,7 +2252,7 @@ impl RenderableCard for AdventureCard {
                                         div.pt-text { (power) "/" (toughness) }
                                     }
                                 }
-                                div.rarity-indicator class=(rarity) {}
+                                (render_collector_line(&self.base))
                             }
                         }
                     }
@@ -1223,12 +2260,14 @@ impl RenderableCard for AdventureCard {
             }
         }
     }
+
+    fn style_css(&self, style: &StyleConfig) -> String {
+        style.css() + &style.adventure_css(self)
+    }
 }
 
 impl RenderableCard for SplitCard {
     fn render_html(&self) -> Markup {
-        let rarity = rarity_class(self.base.rarity);
-
         html! {
             html {
                 head {
@@ -1249,20 +2288,25 @@ impl RenderableCard for SplitCard {
                                             (render_mana_cost(cost))
                                         }
                                     }
-                                    div.split-art { "[Art]" }
+                                    (render_art("split-art", face.art.as_deref().or(self.base.art.as_deref()), self.base.art_fit))
                                     div.split-type {
                                         div.split-type-text {
                                             @if let Some(ref type_line) = face.type_line { (type_line) }
                                         }
+                                        (render_set_symbol(&self.base))
                                     }
-                                    div class=(format!("split-text-box {}", face_classes.text_box_bg)) {
+                                    (render_cost_box("split-text-box", &face.mana_cost, html! {
                                         @if let Some(ref rules) = face.rules_text {
                                             div.split-rules { (render_rules_text(rules)) }
                                         }
-                                    }
+                                        @if let Some(ref flavor) = face.flavor_text {
+                                            div.flavor-text { (render_flavor_text(flavor)) }
+                                        }
+                                    }))
+                                    (render_artist_credit("split-artist", face.artist.as_deref()))
                                 }
                             }
-                            div.rarity-indicator class=(rarity) style="position: absolute; bottom: 32px; left: 50%; transform: translateX(-50%);" {}
+                            (render_collector_line(&self.base))
                         }
                     }
                 }
@@ -1271,14 +2315,188 @@ impl RenderableCard for SplitCard {
     }
 }
 
-/// Helper function to render a DFC-style card (front face only)
-fn render_dfc_front_face(base: &CardBase, faces: &[CardFace]) -> Markup {
-    let Some(front_face) = faces.first() else {
-        return html! { html { body { "Error: No faces found" } } };
+/// How a double-faced card marks its two faces
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DfcKind {
+    /// A pair of icons like a sun on the front and a moon on the back
+    Transform(DfcIcons),
+    /// A triangle on each face, and a hint bar naming the other face
+    Modal,
+    /// A sun on the front and the meld icon on the back
+    Meld,
+}
+
+impl DfcKind {
+    /// The card layout, by its YAML `type`
+    #[must_use]
+    pub fn layout_name(self) -> &'static str {
+        match self {
+            DfcKind::Transform(_) => "transform",
+            DfcKind::Modal => "modal_dfc",
+            DfcKind::Meld => "meld",
+        }
+    }
+
+    fn icon_class(self, back: bool) -> &'static str {
+        match (self, back) {
+            (DfcKind::Transform(DfcIcons::SunMoon) | DfcKind::Meld, false) => "dfc-icon-sun",
+            (DfcKind::Transform(DfcIcons::SunMoon), true) => "dfc-icon-moon",
+            (DfcKind::Transform(DfcIcons::CompassLand), false) => "dfc-icon-compass",
+            (DfcKind::Transform(DfcIcons::CompassLand), true) => "dfc-icon-land",
+            (DfcKind::Transform(DfcIcons::Spark), false) => "dfc-icon-spark",
+            (DfcKind::Transform(DfcIcons::Spark), true) => "dfc-icon-planeswalker",
+            (DfcKind::Transform(DfcIcons::MoonEldrazi), false) => "dfc-icon-full-moon",
+            (DfcKind::Transform(DfcIcons::MoonEldrazi), true) => "dfc-icon-eldrazi",
+            (DfcKind::Meld, true) => "dfc-icon-meld",
+            (DfcKind::Modal, false) => "dfc-icon-modal-front",
+            (DfcKind::Modal, true) => "dfc-icon-modal-back",
+        }
+    }
+}
+
+/// The back face of a transform, modal or meld card, rendered as its own
+/// image
+#[derive(Debug, Clone, Copy)]
+pub struct BackFace<'a> {
+    pub base: &'a CardBase,
+    pub front: &'a CardFace,
+    pub back: &'a CardFace,
+    pub kind: DfcKind,
+}
+
+/// Returns the back face of a transform, modal or meld card, or `None` for
+/// other layouts and for cards with only one face.
+#[must_use]
+pub fn back_face(card: &Card) -> Option<BackFace<'_>> {
+    let (base, faces, kind) = match card {
+        Card::Transform(card) => (&card.base, &card.faces, DfcKind::Transform(card.icons())),
+        Card::ModalDfc(card) => (&card.base, &card.faces, DfcKind::Modal),
+        Card::Meld(card) => (&card.base, &card.faces, DfcKind::Meld),
+        _ => return None,
     };
+    match faces.as_slice() {
+        [front, back, ..] => Some(BackFace {
+            base,
+            front,
+            back,
+            kind,
+        }),
+        _ => None,
+    }
+}
 
-    let classes = FrameClasses::from_mana_cost(&front_face.mana_cost);
-    let rarity = rarity_class(base.rarity);
+impl RenderableCard for BackFace<'_> {
+    fn render_html(&self) -> Markup {
+        let side = DfcSide {
+            kind: self.kind,
+            back: true,
+            other: Some(self.front),
+        };
+        render_dfc_face(self.base, self.back, self.back.art.as_deref(), side)
+    }
+
+    fn colors(&self) -> Vec<Color> {
+        face_colors(self.back)
+    }
+
+    fn rarity(&self) -> Option<Rarity> {
+        Some(self.base.rarity)
+    }
+
+    fn set_code(&self) -> Option<&str> {
+        self.base.set_code.as_deref()
+    }
+
+    fn size(&self) -> Option<CardSize> {
+        self.base.size
+    }
+
+    fn frame_effects(&self) -> Vec<FrameEffect> {
+        self.base.frame_effects.clone()
+    }
+
+    fn card_name(&self) -> &str {
+        &self.base.name
+    }
+
+    fn style_css(&self, style: &StyleConfig) -> String {
+        style.css() + &style.art_css(self.kind.layout_name(), self.back.art.is_some())
+    }
+}
+
+/// Returns a face's frame color: from its color indicator when it has one,
+/// as back faces without a mana cost do, and otherwise from its mana cost.
+#[must_use]
+pub fn face_frame_color(face: &CardFace) -> &'static str {
+    match indicator_colors(face).as_slice() {
+        [] => derive_frame_color(&face.mana_cost),
+        [color] => color.name(),
+        _ => "gold",
+    }
+}
+
+/// The colors of a face, from its color indicator or its mana cost
+fn face_colors(face: &CardFace) -> Vec<Color> {
+    let colors = indicator_colors(face);
+    if !colors.is_empty() {
+        return colors;
+    }
+    let mut colors: Vec<Color> = face
+        .mana_cost
+        .iter()
+        .flat_map(|cost| &cost.symbols)
+        .flat_map(|symbol| symbol.colors().iter().copied())
+        .collect();
+    colors.sort();
+    colors.dedup();
+    colors
+}
+
+/// The color indicator dot's background: an equal slice for each color
+fn indicator_background(colors: &[Color]) -> String {
+    let step = 100.0 / colors.len() as f64;
+    let stops: Vec<String> = colors
+        .iter()
+        .enumerate()
+        .map(|(i, color)| {
+            let hex = match color {
+                Color::White => "#f8f6d8",
+                Color::Blue => "#0e68ab",
+                Color::Black => "#150b00",
+                Color::Red => "#d3202a",
+                Color::Green => "#00733e",
+            };
+            format!("{} {}% {}%", hex, step * i as f64, step * (i + 1) as f64)
+        })
+        .collect();
+    format!("background: conic-gradient({});", stops.join(", "))
+}
+
+/// Which face of a double-faced card is rendered
+struct DfcSide<'a> {
+    kind: DfcKind,
+    back: bool,
+    /// The face on the other side, named on modal cards' hint bar
+    other: Option<&'a CardFace>,
+}
+
+/// Render one face of a double-faced card on the standard frame, with the
+/// icon marking which face it is.
+fn render_dfc_face(base: &CardBase, face: &CardFace, art: Option<&str>, side: DfcSide) -> Markup {
+    let frame_color = base
+        .frame_from_identity()
+        .then(|| {
+            identity_frame_color(
+                face.mana_cost.as_ref(),
+                face.rules_text.as_ref(),
+                &indicator_colors(face),
+            )
+        })
+        .flatten()
+        .unwrap_or_else(|| face_frame_color(face));
+    let classes = FrameClasses::for_color(frame_color);
+    let indicator = indicator_colors(face);
+    let hint = side.other.filter(|_| side.kind == DfcKind::Modal);
 
     html! {
         html {
@@ -1289,35 +2507,50 @@ fn render_dfc_front_face(base: &CardBase, faces: &[CardFace]) -> Markup {
             body {
                 div class=(format!("card {}", classes.frame)) {
                     div.card-inner {
+                        div class=(format!("dfc-icon {}", side.kind.icon_class(side.back))) {}
                         div.card-header {
                             div.card-name {
-                                @if let Some(ref name) = front_face.name { (name) }
+                                @if let Some(ref name) = face.name { (name) }
                             }
-                            @if let Some(ref cost) = front_face.mana_cost {
+                            @if let Some(ref cost) = face.mana_cost {
                                 (render_mana_cost(cost))
                             }
                         }
-                        div.art-box { "[Art]" }
+                        (render_art("art-box", art, base.art_fit))
                         div.type-line {
+                            @if !indicator.is_empty() {
+                                span.color-indicator style=(indicator_background(&indicator)) {}
+                            }
                             div.type-text {
-                                @if let Some(ref type_line) = front_face.type_line { (type_line) }
+                                @if let Some(ref type_line) = face.type_line { (type_line) }
                             }
+                            (render_set_symbol(base))
                         }
                         div class=(format!("text-box-bg {}", classes.text_box_bg)) {}
                         div.text-box {
-                            @if let Some(ref rules) = front_face.rules_text {
+                            @if let Some(ref rules) = face.rules_text {
                                 div.rules-text { (render_rules_text(rules)) }
                             }
-                            @if let Some(ref flavor) = front_face.flavor_text {
-                                div.flavor-text { (flavor) }
+                            @if let Some(ref flavor) = face.flavor_text {
+                                div.flavor-text { (render_flavor_text(flavor)) }
                             }
                         }
-                        @if let (Some(power), Some(toughness)) = (&front_face.power, &front_face.toughness) {
+                        @if let (Some(power), Some(toughness)) = (&face.power, &face.toughness) {
                             div class=(format!("pt-box {}", classes.pt_box)) {
                                 div.pt-text { (power) "/" (toughness) }
                             }
                         }
-                        div.rarity-indicator class=(rarity) {}
+                        @if let Some(other) = hint {
+                            div.back-face-hint {
+                                span.back-face-hint-name {
+                                    @if let Some(ref name) = other.name { (name) }
+                                }
+                                span.back-face-hint-type {
+                                    @if let Some(ref type_line) = other.type_line { (type_line) }
+                                }
+                            }
+                        }
+                        (render_collector_line(base))
                     }
                 }
             }
@@ -1325,34 +2558,111 @@ fn render_dfc_front_face(base: &CardBase, faces: &[CardFace]) -> Markup {
     }
 }
 
+/// Render the front face of a double-faced card, or an error page if it
+/// has no faces.
+fn render_front_face(base: &CardBase, faces: &[CardFace], kind: DfcKind) -> Markup {
+    let Some(front) = faces.first() else {
+        return html! { html { body { "Error: No faces found" } } };
+    };
+    let side = DfcSide {
+        kind,
+        back: false,
+        other: faces.get(1),
+    };
+    render_dfc_face(
+        base,
+        front,
+        front.art.as_deref().or(base.art.as_deref()),
+        side,
+    )
+}
+
 impl RenderableCard for FlipCard {
     fn render_html(&self) -> Markup {
-        render_dfc_front_face(&self.base, &self.faces)
+        let Some(top) = self.faces.first() else {
+            return html! { html { body { "Error: No faces found" } } };
+        };
+        let classes = FrameClasses::for_color(face_frame_color(top));
+        let art = top.art.as_deref().or(self.base.art.as_deref());
+
+        html! {
+            html {
+                head {
+                    meta charset="utf-8";
+                    (generate_css())
+                }
+                body {
+                    div class=(format!("card flip-card {}", classes.frame)) {
+                        div.card-inner {
+                            (render_art("art-box flip-art", art, self.base.art_fit))
+                            // The flipped face is the same half, turned upside down
+                            @for (i, face) in self.faces.iter().take(2).enumerate() {
+                                div class=(if i == 0 { "flip-half" } else { "flip-half flip-bottom" }) {
+                                    div.card-header {
+                                        div.card-name {
+                                            @if let Some(ref name) = face.name { (name) }
+                                        }
+                                        @if let Some(ref cost) = face.mana_cost {
+                                            (render_mana_cost(cost))
+                                        }
+                                    }
+                                    div class=(format!("text-box-bg {}", classes.text_box_bg)) {}
+                                    div.flip-text-box {
+                                        @if let Some(ref rules) = face.rules_text {
+                                            div.rules-text { (render_rules_text(rules)) }
+                                        }
+                                        @if let Some(ref flavor) = face.flavor_text {
+                                            div.flavor-text { (render_flavor_text(flavor)) }
+                                        }
+                                    }
+                                    div.type-line.flip-type-line {
+                                        div.type-text {
+                                            @if let Some(ref type_line) = face.type_line { (type_line) }
+                                        }
+                                        @if i == 0 {
+                                            (render_set_symbol(&self.base))
+                                        }
+                                    }
+                                    @if let (Some(power), Some(toughness)) = (&face.power, &face.toughness) {
+                                        div class=(format!("pt-box flip-pt-box {}", classes.pt_box)) {
+                                            div.pt-text { (power) "/" (toughness) }
+                                        }
+                                    }
+                                }
+                            }
+                            (render_collector_line(&self.base))
+                        }
+                    }
+                }
+            }
+        }
     }
 }
 
 impl RenderableCard for TransformCard {
     fn render_html(&self) -> Markup {
-        render_dfc_front_face(&self.base, &self.faces)
+        render_front_face(&self.base, &self.faces, DfcKind::Transform(self.icons()))
     }
 }
 
 impl RenderableCard for ModalDfcCard {
     fn render_html(&self) -> Markup {
-        render_dfc_front_face(&self.base, &self.faces)
+        render_front_face(&self.base, &self.faces, DfcKind::Modal)
     }
 }
 
 impl RenderableCard for MeldCard {
     fn render_html(&self) -> Markup {
-        render_dfc_front_face(&self.base, &self.faces)
+        render_front_face(&self.base, &self.faces, DfcKind::Meld)
     }
 }
 
+/// Reminder text for the Siege battle subtype
+const SIEGE_REMINDER_TEXT: &str = "(As a Siege enters, choose an opponent to protect it. You and others can attack it. When it's defeated, exile it, then cast it transformed.)";
+
 impl RenderableCard for BattleCard {
     fn render_html(&self) -> Markup {
-        let classes = FrameClasses::from_mana_cost(&self.base.mana_cost);
-        let rarity = rarity_class(self.base.rarity);
+        let classes = FrameClasses::for_card(&self.base);
 
         html! {
             html {
@@ -1369,21 +2679,29 @@ impl RenderableCard for BattleCard {
                                     (render_mana_cost(cost))
                                 }
                             }
-                            div.art-box { "[Art]" }
+                            (render_art("art-box", self.base.art.as_deref(), self.base.art_fit))
                             div.type-line {
                                 div.type-text { (&self.base.type_line) }
+                                (render_set_symbol(&self.base))
                             }
                             div class=(format!("text-box-bg {}", classes.text_box_bg)) {}
                             div.text-box {
+                                @if self.is_siege() && self.base.auto_reminder_text() {
+                                    div.rules-text.reminder-text { (SIEGE_REMINDER_TEXT) }
+                                }
                                 @if let Some(ref rules) = self.base.rules_text {
                                     div.rules-text { (render_rules_text(rules)) }
                                 }
                                 @if let Some(ref flavor) = self.base.flavor_text {
-                                    div.flavor-text { (flavor) }
+                                    div.flavor-text { (render_flavor_text(flavor)) }
                                 }
                             }
+                            div.back-face-hint {
+                                span.back-face-hint-name { (&self.backside_name) }
+                                span.back-face-hint-type { (&self.backside_type_line) }
+                            }
                             div.defense-counter { (self.defense) }
-                            div.rarity-indicator class=(rarity) {}
+                            (render_collector_line(&self.base))
                         }
                     }
                 }
@@ -1394,128 +2712,524 @@ impl RenderableCard for BattleCard {
 
 impl RenderableCard for LevelerCard {
     fn render_html(&self) -> Markup {
-        // TODO: Implement proper leveler layout
-        NormalCard {
-            base: self.base.clone(),
+        let classes = FrameClasses::for_card(&self.base);
+        // A range from level 0 is the creature before it levels up, printed
+        // in the top strip with the level up ability
+        let (first, ranges) = match self.leveler_ranges.split_first() {
+            Some((first, rest)) if first.range.min == 0 => (Some(first), rest),
+            _ => (None, self.leveler_ranges.as_slice()),
+        };
+        let (power, toughness) = match first {
+            Some(first) => (&first.power, &first.toughness),
+            None => (&self.base.power, &self.base.toughness),
+        };
+
+        html! {
+            html {
+                head {
+                    meta charset="utf-8";
+                    (generate_css())
+                }
+                body {
+                    div class=(format!("card {}", classes.bg)) {
+                        (render_art("art-box", self.base.art.as_deref(), self.base.art_fit))
+                        div class=(format!("card-frame {}", classes.frame)) {}
+                        div.card-inner {
+                            div.card-header {
+                                div.card-name { (&self.base.name) }
+                                @if let Some(ref cost) = self.base.mana_cost {
+                                    (render_mana_cost(cost))
+                                }
+                            }
+                            div.type-line {
+                                div.type-text { (&self.base.type_line) }
+                                (render_set_symbol(&self.base))
+                            }
+                            div class=(format!("text-box-bg {}", classes.text_box_bg)) {}
+                            div.text-box.leveler-text-box {
+                                div.leveler-strip.leveler-base {
+                                    div.leveler-text {
+                                        @if let Some(ref rules) = self.base.rules_text {
+                                            div.rules-text { (render_rules_text(rules)) }
+                                        }
+                                        @if let Some(rules) = first.and_then(|first| first.text.as_ref()) {
+                                            div.rules-text { (render_rules_text(rules)) }
+                                        }
+                                    }
+                                    (render_leveler_pt(&classes, power, toughness))
+                                }
+                                @for range in ranges {
+                                    div.leveler-strip {
+                                        div.leveler-badge {
+                                            span.leveler-badge-label { "LEVEL" }
+                                            span.leveler-badge-range { (range.range) }
+                                        }
+                                        div.leveler-text {
+                                            @if let Some(ref rules) = range.text {
+                                                div.rules-text { (render_rules_text(rules)) }
+                                            }
+                                        }
+                                        (render_leveler_pt(&classes, &range.power, &range.toughness))
+                                    }
+                                }
+                            }
+                            (render_collector_line(&self.base))
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Render the power/toughness box at the right of a leveler strip, or
+/// nothing when the strip doesn't change them.
+fn render_leveler_pt(
+    classes: &FrameClasses,
+    power: &Option<String>,
+    toughness: &Option<String>,
+) -> Markup {
+    html! {
+        @if let (Some(power), Some(toughness)) = (power, toughness) {
+            div class=(format!("leveler-pt {}", classes.pt_box)) {
+                div.pt-text { (power) "/" (toughness) }
+            }
         }
-        .render_html()
     }
 }
 
 impl RenderableCard for PrototypeCard {
     fn render_html(&self) -> Markup {
-        // TODO: Implement proper prototype layout
-        NormalCard {
-            base: self.base.clone(),
+        let classes = FrameClasses::for_card(&self.base);
+        // The prototype inset is colored by the prototype cost, not the main cost
+        let prototype_classes = FrameClasses::from_mana_cost(&self.prototype.mana_cost);
+
+        html! {
+            html {
+                head {
+                    meta charset="utf-8";
+                    (generate_css())
+                }
+                body {
+                    div class=(format!("card {}", classes.bg)) {
+                        (render_art("art-box", self.base.art.as_deref(), self.base.art_fit))
+                        div class=(format!("card-frame {}", classes.frame)) {}
+                        div.card-inner {
+                            div.card-header {
+                                div.card-name { (&self.base.name) }
+                                @if let Some(ref cost) = self.base.mana_cost {
+                                    (render_mana_cost(cost))
+                                }
+                            }
+                            div.type-line {
+                                div.type-text { (&self.base.type_line) }
+                                (render_set_symbol(&self.base))
+                            }
+                            div class=(format!("text-box-bg {}", classes.text_box_bg)) {}
+                            div class=(format!("prototype-inset {}", prototype_classes.bg)) {
+                                @if let Some(ref cost) = self.prototype.mana_cost {
+                                    div.prototype-cost { (render_mana_cost(cost)) }
+                                }
+                                @if let (Some(power), Some(toughness)) = (&self.prototype.power, &self.prototype.toughness) {
+                                    div class=(format!("prototype-pt {}", prototype_classes.pt_box)) {
+                                        div.prototype-pt-text { (power) "/" (toughness) }
+                                    }
+                                }
+                            }
+                            div.text-box.text-box-with-prototype {
+                                @if let Some(ref rules) = self.base.rules_text {
+                                    div.rules-text { (render_rules_text(rules)) }
+                                }
+                                @if let Some(ref flavor) = self.base.flavor_text {
+                                    div.flavor-text { (render_flavor_text(flavor)) }
+                                }
+                            }
+                            @if let (Some(power), Some(toughness)) = (&self.base.power, &self.base.toughness) {
+                                div class=(format!("pt-box {}", classes.pt_box)) {
+                                    div.pt-text { (power) "/" (toughness) }
+                                }
+                            }
+                            (render_collector_line(&self.base))
+                        }
+                    }
+                }
+            }
         }
-        .render_html()
     }
 }
 
-// Implement RenderableCard for the Card enum by delegating to the inner type
-impl RenderableCard for Card {
+/// Returns a token's frame color, from its colors. Colorless artifact tokens
+/// get the artifact frame.
+#[must_use]
+pub fn token_frame_color(token: &TokenCard) -> &'static str {
+    match named_colors(&token.colors).as_slice() {
+        [] if token.base.type_line.contains("Artifact") => "artifact",
+        [] => "colorless",
+        [color] => color.name(),
+        _ => "gold",
+    }
+}
+
+impl RenderableCard for TokenCard {
     fn render_html(&self) -> Markup {
-        match self {
-            Card::Normal(card) => card.render_html(),
-            Card::Planeswalker(card) => card.render_html(),
-            Card::Saga(card) => card.render_html(),
-            Card::Class(card) => card.render_html(),
-            Card::Adventure(card) => card.render_html(),
-            Card::Split(card) => card.render_html(),
-            Card::Flip(card) => card.render_html(),
-            Card::Transform(card) => card.render_html(),
-            Card::ModalDfc(card) => card.render_html(),
-            Card::Battle(card) => card.render_html(),
-            Card::Meld(card) => card.render_html(),
-            Card::Leveler(card) => card.render_html(),
-            Card::Prototype(card) => card.render_html(),
+        let classes = FrameClasses::for_color(token_frame_color(self));
+
+        html! {
+            html {
+                head {
+                    meta charset="utf-8";
+                    (generate_css())
+                }
+                body {
+                    div class=(format!("card token {}", classes.bg)) {
+                        (render_art("art-box", self.base.art.as_deref(), self.base.art_fit))
+                        div class=(format!("card-frame {}", classes.frame)) {}
+                        div.card-inner {
+                            div.card-header {
+                                div.card-name { (&self.base.name) }
+                            }
+                            div.type-line {
+                                div.type-text { (&self.base.type_line) }
+                                (render_set_symbol(&self.base))
+                            }
+                            div class=(format!("text-box-bg {}", classes.text_box_bg)) {}
+                            div.text-box {
+                                @if let Some(ref rules) = self.base.rules_text {
+                                    div.rules-text { (render_rules_text(rules)) }
+                                }
+                                @if let Some(ref flavor) = self.base.flavor_text {
+                                    div.flavor-text { (render_flavor_text(flavor)) }
+                                }
+                            }
+                            @if let (Some(power), Some(toughness)) = (&self.base.power, &self.base.toughness) {
+                                div class=(format!("pt-box {}", classes.pt_box)) {
+                                    div.pt-text { (power) "/" (toughness) }
+                                }
+                            }
+                            (render_collector_line(&self.base))
+                        }
+                    }
+                }
+            }
         }
     }
 }
 
-// ============================================================================
-// Renderer (Browser automation)
-// ============================================================================
+impl RenderableCard for Emblem {
+    fn render_html(&self) -> Markup {
+        let classes = FrameClasses::for_color("colorless");
+
+        html! {
+            html {
+                head {
+                    meta charset="utf-8";
+                    (generate_css())
+                }
+                body {
+                    div class=(format!("card emblem {}", classes.bg)) {
+                        (render_art("art-box", None, None))
+                        div class=(format!("card-frame {}", classes.frame)) {}
+                        div.card-inner {
+                            div.card-header {
+                                div.card-name { "Emblem" }
+                            }
+                            div.type-line {
+                                div.type-text { (self.type_line()) }
+                            }
+                            div class=(format!("text-box-bg {}", classes.text_box_bg)) {}
+                            div.text-box {
+                                div.rules-text { (render_rules_text(&self.rules_text)) }
+                                div.emblem-source { (&self.source_name) }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
 
-pub struct Renderer {
-    browser: Browser,
+/// Returns the HTML document a card is rendered from, as Chrome loads it.
+#[must_use]
+pub fn card_to_html(card: &impl RenderableCard) -> String {
+    with_symbol_defs(&card.render_html().into_string())
 }
 
-impl Renderer {
-    pub async fn new() -> Result<Self> {
-        let mut config = BrowserConfig::builder()
-            .no_sandbox()
-            .arg("--disable-web-security")
-            .arg("--allow-file-access-from-files")
-            .arg("--disable-features=IsolateOrigins,site-per-process")
-            .arg("--disable-blink-features=AutomationControlled");
-
-        if let Ok(path) = std::env::var("CHROME_PATH") {
-            config = config.chrome_executable(path);
+impl RenderableCard for FaceDown {
+    fn render_html(&self) -> Markup {
+        let classes = FrameClasses::for_color("colorless");
+
+        html! {
+            html {
+                head {
+                    meta charset="utf-8";
+                    (generate_css())
+                }
+                body {
+                    div class=(format!("card face-down {}", classes.bg)) {
+                        (render_art("art-box", None, None))
+                        div class=(format!("card-frame {}", classes.frame)) {}
+                        div.card-inner {
+                            div.card-header {
+                                div.card-name { (self.title()) }
+                            }
+                            div.type-line {
+                                div.type-text { "Creature" }
+                            }
+                            div class=(format!("text-box-bg {}", classes.text_box_bg)) {}
+                            div.text-box {
+                                div.rules-text { (render_rules_text(&self.rules_text())) }
+                            }
+                            div class=(format!("pt-box {}", classes.pt_box)) {
+                                div.pt-text { "2/2" }
+                            }
+                        }
+                    }
+                }
+            }
         }
+    }
+}
 
-        let (browser, mut handler) =
-            Browser::launch(config.build().map_err(anyhow::Error::msg)?).await?;
+impl RenderableCard for SymbolLegend {
+    fn render_html(&self) -> Markup {
+        let classes = FrameClasses::for_color("colorless");
 
-        tokio::spawn(async move {
-            while let Some(h) = handler.next().await {
-                if let Err(e) = h {
-                    eprintln!("Browser handler error: {}", e);
-                    break;
+        html! {
+            html {
+                head {
+                    meta charset="utf-8";
+                    (generate_css())
+                }
+                body {
+                    div class=(format!("card legend {}", classes.bg)) {
+                        div class=(format!("card-frame {}", classes.frame)) {}
+                        div.card-inner {
+                            div.card-header {
+                                div.card-name { "Symbol Reference" }
+                            }
+                            div.legend-box {
+                                @for entry in &self.entries {
+                                    div.legend-entry {
+                                        (render_mana_symbol(entry.symbol))
+                                        div.legend-meaning { (&entry.meaning) }
+                                    }
+                                }
+                            }
+                            @if let Some(label) = self.page_label() {
+                                div.collector-line { (label) }
+                            }
+                        }
+                    }
                 }
             }
-        });
+        }
+    }
+}
 
-        Ok(Self { browser })
+impl RenderableCard for PlaytestCard {
+    fn render_html(&self) -> Markup {
+        html! {
+            html {
+                head {
+                    meta charset="utf-8";
+                    (playtest_css())
+                }
+                body {
+                    div.playtest-card {
+                        @for face in &self.faces {
+                            div.playtest-face {
+                                div.playtest-header {
+                                    div.playtest-name { (&face.name) }
+                                    @if let Some(ref cost) = face.mana_cost {
+                                        div.playtest-cost { (cost) }
+                                    }
+                                }
+                                @if let Some(ref type_line) = face.type_line {
+                                    div.playtest-type { (type_line) }
+                                }
+                                div.playtest-text {
+                                    @for paragraph in &face.rules_text {
+                                        p { (paragraph) }
+                                    }
+                                }
+                                @if let Some(ref stats) = face.stats {
+                                    div.playtest-stats { (stats) }
+                                }
+                            }
+                        }
+                        div.playtest-footer {
+                            @if let Some(ref number) = self.collector_number {
+                                span { (number) " " }
+                            }
+                            span { (self.rarity.letter()) }
+                            span.playtest-mark { "PLAYTEST" }
+                        }
+                    }
+                }
+            }
+        }
     }
 
-    /// Render any card that implements RenderableCard to a PNG file
-    pub async fn render_card(&self, card: &impl RenderableCard, output_path: &Path) -> Result<()> {
-        let html = card.render_html();
+    /// Style overrides are for the full frames, so playtest cards ignore
+    /// them.
+    fn style_css(&self, _style: &StyleConfig) -> String {
+        String::new()
+    }
+}
 
-        // Create a new page
-        let page = self.browser.new_page("about:blank").await?;
+/// The stylesheet of playtest cards: system fonts and black on white, so
+/// nothing is loaded from the assets directory
+fn playtest_css() -> Markup {
+    html! {
+        style {
+            r#"
+            * {
+                margin: 0;
+                padding: 0;
+                box-sizing: border-box;
+            }
 
-        // Set device metrics for proper card dimensions (744x1040 at 4x scale = 300 DPI)
-        let metrics = SetDeviceMetricsOverrideParams::builder()
-            .width(744)
-            .height(1040)
-            .device_scale_factor(4.0)
-            .mobile(false)
-            .build()
-            .map_err(|e| anyhow::anyhow!("Failed to build device metrics: {}", e))?;
+            body {
+                font-family: sans-serif;
+                background: #fff;
+                color: #000;
+            }
 
-        page.execute(metrics).await?;
+            .playtest-card {
+                width: 744px;
+                height: 1040px;
+                display: flex;
+                flex-direction: column;
+                gap: 16px;
+                padding: 32px;
+                border: 6px solid #000;
+                border-radius: 37px;
+                background: #fff;
+            }
 
-        // Save HTML to temporary file and navigate to it
-        let html_string = html.into_string();
-        let temp_html = std::env::temp_dir().join(format!("mtg_card_{}.html", std::process::id()));
-        std::fs::write(&temp_html, &html_string)?;
-        eprintln!("Debug: HTML saved to {}", temp_html.display());
+            .playtest-face {
+                flex: 1;
+                min-height: 0;
+                display: flex;
+                flex-direction: column;
+                gap: 12px;
+            }
 
-        let file_url = format!("file://{}", temp_html.display());
-        page.goto(&file_url).await?;
+            .playtest-face + .playtest-face {
+                border-top: 3px dashed #000;
+                padding-top: 16px;
+            }
 
-        // Wait for page to fully load including external resources
-        page.wait_for_navigation().await?;
+            .playtest-header,
+            .playtest-type {
+                display: flex;
+                justify-content: space-between;
+                gap: 16px;
+                padding-bottom: 8px;
+                border-bottom: 2px solid #000;
+            }
 
-        // Additional wait to ensure SVGs are rendered
-        tokio::time::sleep(tokio::time::Duration::from_millis(3000)).await;
+            .playtest-name {
+                font-size: 40px;
+                font-weight: bold;
+            }
 
-        // Ensure output directory exists
-        if let Some(parent) = output_path.parent() {
-            std::fs::create_dir_all(parent)?;
+            .playtest-cost {
+                font-size: 34px;
+                font-family: monospace;
+                white-space: nowrap;
+            }
+
+            .playtest-type {
+                font-size: 30px;
+            }
+
+            .playtest-text {
+                flex: 1;
+                min-height: 0;
+                overflow: hidden;
+                font-size: 30px;
+                line-height: 1.3;
+            }
+
+            .playtest-text p + p {
+                margin-top: 12px;
+            }
+
+            .playtest-stats {
+                align-self: flex-end;
+                font-size: 40px;
+                font-weight: bold;
+                padding: 4px 16px;
+                border: 3px solid #000;
+            }
+
+            .playtest-footer {
+                display: flex;
+                gap: 8px;
+                font-size: 20px;
+            }
+
+            .playtest-mark {
+                margin-left: auto;
+                letter-spacing: 2px;
+            }
+            "#
         }
+    }
+}
 
-        // Take screenshot with high DPI
-        let screenshot_params = ScreenshotParams::builder()
-            .format(CaptureScreenshotFormat::Png)
-            .full_page(false)
-            .omit_background(false)
-            .build();
+// Implement RenderableCard for the Card enum by delegating to the inner type
+impl RenderableCard for Card {
+    fn render_html(&self) -> Markup {
+        match self {
+            Card::Normal(card) => card.render_html(),
+            Card::Planeswalker(card) => card.render_html(),
+            Card::Saga(card) => card.render_html(),
+            Card::Class(card) => card.render_html(),
+            Card::Adventure(card) => card.render_html(),
+            Card::Split(card) => card.render_html(),
+            Card::Flip(card) => card.render_html(),
+            Card::Transform(card) => card.render_html(),
+            Card::ModalDfc(card) => card.render_html(),
+            Card::Battle(card) => card.render_html(),
+            Card::Meld(card) => card.render_html(),
+            Card::Leveler(card) => card.render_html(),
+            Card::Prototype(card) => card.render_html(),
+            Card::Token(card) => card.render_html(),
+        }
+    }
+
+    fn colors(&self) -> Vec<Color> {
+        card_colors(self)
+    }
+
+    fn rarity(&self) -> Option<Rarity> {
+        Some(Card::rarity(self))
+    }
+
+    fn set_code(&self) -> Option<&str> {
+        self.base().set_code.as_deref()
+    }
+
+    fn size(&self) -> Option<CardSize> {
+        self.base().size
+    }
 
-        page.save_screenshot(screenshot_params, output_path).await?;
+    fn frame_effects(&self) -> Vec<FrameEffect> {
+        self.base().frame_effects.clone()
+    }
 
-        Ok(())
+    fn card_name(&self) -> &str {
+        self.name()
+    }
+
+    fn style_css(&self, style: &StyleConfig) -> String {
+        let css = match self {
+            Card::Adventure(card) => card.style_css(style),
+            _ => style.css(),
+        };
+        css + &style.art_css(self.layout_name(), self.art_paths().next().is_some())
     }
 }
@@ -0,0 +1,1044 @@
+//! Rendering cards in headless Chrome
+//!
+//! Built with the `browser` feature. [`Renderer`] writes each card's HTML
+//! to a temporary file, loads it at card size, shrinks any text that
+//! overflows its text box and screenshots it.
+//!
+//! Chrome is driven over a connection that needs Tokio. A renderer created
+//! inside a Tokio runtime uses it; created anywhere else, it starts a small
+//! runtime of its own, so its async methods work under any executor and the
+//! `_blocking` methods work with none.
+//!
+//! A renderer is cheap to clone, and clones share one Chrome instance and
+//! a pool of its pages, so servers and parallel jobs don't each need a
+//! browser of their own.
+//!
+//! Each render has a deadline (see [`Renderer::set_timeout`]). A card whose
+//! page hangs, say on a script stuck in a loop, fails once it passes; its
+//! page is killed and the next card gets a fresh one, so one bad card can't
+//! stall a batch.
+
+use super::{BLEED_HEIGHT, BLEED_WIDTH, CardHtml, RenderableCard};
+use crate::card::CardSize;
+use crate::output::ImageFormat;
+use crate::profile::RenderTimings;
+use crate::style::StyleConfig;
+use anyhow::Result;
+use chromiumoxide::browser::{Browser, BrowserConfig};
+use chromiumoxide::page::{Page, ScreenshotParams};
+use chromiumoxide_cdp::cdp::browser_protocol::emulation::SetDeviceMetricsOverrideParams;
+use chromiumoxide_cdp::cdp::browser_protocol::network::{
+    EventLoadingFailed, EventRequestWillBeSent, EventResponseReceived,
+};
+use chromiumoxide_cdp::cdp::browser_protocol::page::{CaptureScreenshotFormat, PrintToPdfParams};
+use chromiumoxide_cdp::cdp::browser_protocol::target::CloseTargetParams;
+use chromiumoxide_cdp::cdp::js_protocol::runtime::EvaluateParams;
+use futures::StreamExt;
+use maud::Markup;
+use std::collections::HashMap;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+use tokio::runtime::{Handle, Runtime};
+use tokio::sync::{RwLock, Semaphore};
+
+mod presets;
+#[cfg(feature = "megapage")]
+mod tiles;
+
+pub use presets::BrowserPreset;
+
+/// Renders cards in headless Chrome
+///
+/// Cloning a renderer is cheap: clones share one Chrome instance and its
+/// pages, so a web server or several jobs can render through it at once,
+/// each clone with settings of its own. Chrome closes when the last clone
+/// is closed.
+#[derive(Clone)]
+pub struct Renderer {
+    chrome: Arc<Chrome>,
+    /// How cards' HTML is built
+    html: CardHtml,
+    /// Size of cards that don't set their own
+    size: CardSize,
+    /// Whether a card with images or fonts that fail to load fails instead
+    /// of rendering with a warning
+    strict: bool,
+    /// Format card images are written in
+    format: ImageFormat,
+    /// Quality JPEG and WebP images are encoded at, from 0 to 100
+    quality: u8,
+    /// How long one render may take before it's abandoned
+    timeout: Duration,
+    /// Cards waiting to share a page, when cards are tiled
+    #[cfg(feature = "megapage")]
+    tiler: Option<Arc<tiles::Tiler>>,
+}
+
+/// What rendering a card produced
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RenderOutcome {
+    /// The files written
+    pub output_paths: Vec<PathBuf>,
+    /// Problems the card was rendered despite, like an image or font that
+    /// failed to load (without [`Renderer::set_strict`])
+    pub warnings: Vec<String>,
+    /// How long each phase took
+    pub timings: RenderTimings,
+}
+
+/// A card rendered to an image in memory
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RenderedImage {
+    /// The encoded image
+    pub bytes: Vec<u8>,
+    /// Problems the card was rendered despite, as in [`RenderOutcome`]
+    pub warnings: Vec<String>,
+    /// How long each phase took
+    pub timings: RenderTimings,
+}
+
+/// The error a Chrome connection stopped on, once it has
+type HandlerError = Arc<Mutex<Option<String>>>;
+
+/// The Chrome instance the clones of a renderer share
+struct Chrome {
+    /// Replaced when the renderer is recycled. Renders hold a read lock
+    /// for as long as they use a page, so recycling waits for them.
+    browser: RwLock<Browser>,
+    /// Where the current instance's connection records the error it
+    /// stopped on, replaced with it
+    handler_error: Mutex<HandlerError>,
+    /// Chromium flags added to the ones every launch has, kept for
+    /// relaunching when the renderer is recycled
+    args: Vec<String>,
+    /// The Tokio runtime Chrome's connection runs on
+    handle: Handle,
+    /// The runtime the renderer started itself, when it wasn't created
+    /// inside one
+    runtime: Option<Arc<Runtime>>,
+    /// Limits how many pages are in use at once
+    pages: Semaphore,
+    /// Pages of finished renders, kept open for the next ones
+    idle: Mutex<Vec<Page>>,
+    /// Numbers temporary HTML files so concurrent renders don't clash
+    next_page: AtomicUsize,
+}
+
+impl Chrome {
+    /// Take an idle page, or open a new one.
+    async fn page(&self, browser: &Browser) -> Result<Page> {
+        let idle = self.idle.lock().unwrap().pop();
+        match idle {
+            Some(page) => Ok(page),
+            None => Ok(browser.new_page("about:blank").await?),
+        }
+    }
+
+    /// Keep `page` for the next render when `result` is a success. A page
+    /// whose render failed may be left in any state, hung even, so it's
+    /// killed: closed from the browser's side, which doesn't wait on the
+    /// page itself.
+    async fn release<T>(&self, browser: &Browser, page: Page, result: &Result<T>) {
+        if result.is_ok() {
+            self.idle.lock().unwrap().push(page);
+        } else {
+            let close = browser.execute(CloseTargetParams::new(page.target_id().clone()));
+            let _ = tokio::time::timeout(CLOSE_TIMEOUT, close).await;
+        }
+    }
+}
+
+/// How long killing a failed page may take before it's left to Chrome
+const CLOSE_TIMEOUT: Duration = Duration::from_secs(5);
+
+impl Renderer {
+    /// How long one render may take by default, see
+    /// [`Renderer::set_timeout`]
+    pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+
+    pub async fn new() -> Result<Self> {
+        Self::with_max_pages(1).await
+    }
+
+    /// Start Chrome, allowing up to `max_pages` cards to render at once.
+    pub async fn with_max_pages(max_pages: usize) -> Result<Self> {
+        Self::with_browser_args(max_pages, Vec::new()).await
+    }
+
+    /// Start Chrome like [`Renderer::with_max_pages`], launched with the
+    /// Chromium flags `args` on top of the ones rendering needs, e.g. a
+    /// [`BrowserPreset`]'s.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use mtg_gen::render::{BrowserPreset, Renderer};
+    ///
+    /// # async fn example() -> anyhow::Result<()> {
+    /// let args = BrowserPreset::Docker.args().iter().map(|arg| arg.to_string()).collect();
+    /// let renderer = Renderer::with_browser_args(4, args).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn with_browser_args(max_pages: usize, args: Vec<String>) -> Result<Self> {
+        let (handle, runtime) = match Handle::try_current() {
+            Ok(handle) => (handle, None),
+            Err(_) => {
+                let runtime = Arc::new(background_runtime()?);
+                (runtime.handle().clone(), Some(runtime))
+            }
+        };
+        Self::launch(max_pages, args, handle, runtime).await
+    }
+
+    /// Start Chrome like [`Renderer::new`], for callers without an async
+    /// runtime, e.g. GUI apps and scripts.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called from within an async runtime; use
+    /// [`Renderer::new`] there.
+    pub fn new_blocking() -> Result<Self> {
+        Self::with_max_pages_blocking(1)
+    }
+
+    /// Start Chrome like [`Renderer::with_max_pages`], for callers without
+    /// an async runtime.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called from within an async runtime.
+    pub fn with_max_pages_blocking(max_pages: usize) -> Result<Self> {
+        let runtime = Arc::new(background_runtime()?);
+        let handle = runtime.handle().clone();
+        runtime.block_on(Self::launch(
+            max_pages,
+            Vec::new(),
+            handle,
+            Some(runtime.clone()),
+        ))
+    }
+
+    async fn launch(
+        max_pages: usize,
+        args: Vec<String>,
+        handle: Handle,
+        runtime: Option<Arc<Runtime>>,
+    ) -> Result<Self> {
+        let (browser, handler_error) = in_runtime(&handle, launch_browser(&handle, &args)).await?;
+        Ok(Self {
+            chrome: Arc::new(Chrome {
+                browser: RwLock::new(browser),
+                handler_error: Mutex::new(handler_error),
+                args,
+                handle,
+                runtime,
+                pages: Semaphore::new(max_pages.max(1)),
+                idle: Mutex::new(Vec::new()),
+                next_page: AtomicUsize::new(0),
+            }),
+            html: CardHtml::new(),
+            size: CardSize::default(),
+            strict: false,
+            format: ImageFormat::default(),
+            quality: ImageFormat::DEFAULT_QUALITY,
+            timeout: Self::DEFAULT_TIMEOUT,
+            #[cfg(feature = "megapage")]
+            tiler: None,
+        })
+    }
+
+    /// Add a lettered chip for each of a card's colors below its title bar,
+    /// see [`crate::color_chips`].
+    pub fn set_color_chips(&mut self, enabled: bool) {
+        self.html.set_color_chips(enabled);
+    }
+
+    /// Show rarity as a letter on the collector line instead of the rarity
+    /// indicator, see [`with_rarity_letter`](super::with_rarity_letter).
+    pub fn set_rarity_letter(&mut self, enabled: bool) {
+        self.html.set_rarity_letter(enabled);
+    }
+
+    /// Apply `style` on top of the built-in stylesheet, see [`crate::style`].
+    pub fn set_style(&mut self, style: StyleConfig) {
+        self.html.set_style(style);
+    }
+
+    /// Render cards that don't set their own size at `size`. Larger sizes
+    /// are rendered with more pixels, so they print as sharply.
+    pub fn set_card_size(&mut self, size: CardSize) {
+        self.size = size;
+    }
+
+    /// Fail cards whose images or fonts fail to load, such as a Scryfall
+    /// symbol that 404s or a missing art file, instead of rendering them
+    /// with a warning.
+    pub fn set_strict(&mut self, enabled: bool) {
+        self.strict = enabled;
+    }
+
+    /// Render cards with print bleed, at MakePlayingCards' 816 × 1110
+    /// instead of 744 × 1040, see [`with_bleed`](super::with_bleed).
+    pub fn set_bleed(&mut self, enabled: bool) {
+        self.html.set_bleed(enabled);
+    }
+
+    /// Mark every card `NOT FOR SALE` on its legal line, for proxies, see
+    /// [`crate::legal`].
+    pub fn set_not_for_sale(&mut self, enabled: bool) {
+        self.html.set_not_for_sale(enabled);
+    }
+
+    /// Draw each card's generated textures from `seed`, see
+    /// [`CardHtml::set_seed`].
+    pub fn set_seed(&mut self, seed: Option<u64>) {
+        self.html.set_seed(seed);
+    }
+
+    /// Write card images as `format`, encoding JPEG and WebP at `quality`
+    /// (0 to 100, clamped). PNG ignores the quality.
+    pub fn set_image_format(&mut self, format: ImageFormat, quality: u8) {
+        self.format = format;
+        self.quality = quality.min(100);
+    }
+
+    /// Fail any render that takes longer than `timeout`, killing its page
+    /// so the next render starts on a fresh one. Defaults to
+    /// [`Renderer::DEFAULT_TIMEOUT`].
+    pub fn set_timeout(&mut self, timeout: Duration) {
+        self.timeout = timeout;
+    }
+
+    /// Lay out up to `cards_per_page` cards rendered at about the same time
+    /// on one Chrome page, capture them in a single screenshot and crop
+    /// them apart, instead of giving each a page load and screenshot of
+    /// its own. Cards only share a page when they're rendered concurrently,
+    /// by tasks or clones of this renderer. 0 or 1 renders each card on its
+    /// own page, the default.
+    ///
+    /// WebP images are then encoded losslessly, whatever their quality.
+    #[cfg(feature = "megapage")]
+    pub fn set_cards_per_page(&mut self, cards_per_page: usize) {
+        self.tiler = (cards_per_page > 1).then(|| Arc::new(tiles::Tiler::new(cards_per_page)));
+    }
+
+    /// Close Chrome and start a fresh instance, releasing the memory it
+    /// builds up over a long run. Waits for renders in progress, in this
+    /// renderer and its clones, to finish first.
+    pub async fn recycle(&self) -> Result<()> {
+        let handle = &self.chrome.handle;
+        in_runtime(handle, async {
+            let mut browser = self.chrome.browser.write().await;
+            // The idle pages belong to the old instance
+            self.chrome.idle.lock().unwrap().clear();
+            browser.close().await?;
+            let _ = browser.wait().await;
+            let (relaunched, handler_error) = launch_browser(handle, &self.chrome.args).await?;
+            *browser = relaunched;
+            *self.chrome.handler_error.lock().unwrap() = handler_error;
+            Ok(())
+        })
+        .await
+    }
+
+    /// Close Chrome and wait for it to exit. While other clones of the
+    /// renderer are open, Chrome stays open for them.
+    pub async fn close(self) -> Result<()> {
+        let (result, runtime) = self.shut_down().await;
+        // Shut down the renderer's own runtime only once nothing runs on it
+        drop(runtime);
+        result
+    }
+
+    /// Close Chrome like [`Renderer::close`], for callers without an async
+    /// runtime.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called from within an async runtime.
+    pub fn close_blocking(self) -> Result<()> {
+        let handle = self.chrome.handle.clone();
+        let (result, runtime) = handle.block_on(self.shut_down());
+        drop(runtime);
+        result
+    }
+
+    /// Close Chrome if no other clone shares it, handing back the runtime
+    /// the renderer started so the caller can shut it down.
+    async fn shut_down(self) -> (Result<()>, Option<Arc<Runtime>>) {
+        let Some(chrome) = Arc::into_inner(self.chrome) else {
+            return (Ok(()), None);
+        };
+        let Chrome {
+            browser,
+            handle,
+            runtime,
+            ..
+        } = chrome;
+        let mut browser = browser.into_inner();
+        let result = in_runtime(&handle, async move {
+            browser.close().await?;
+            let _ = browser.wait().await;
+            Ok(())
+        })
+        .await;
+        (result, runtime)
+    }
+
+    /// Render any card that implements RenderableCard to an image file, in
+    /// the format set with [`Renderer::set_image_format`], returning the
+    /// files written, any warnings and how long each phase took.
+    pub async fn render_card(
+        &self,
+        card: &impl RenderableCard,
+        output_path: &Path,
+    ) -> Result<RenderOutcome> {
+        self.render_card_as(card, output_path, self.format).await
+    }
+
+    /// Render a card like [`Renderer::render_card`], returning the image
+    /// in the renderer's format instead of writing it to a file. Only the
+    /// temporary HTML page Chrome loads the card from touches the disk.
+    pub async fn render_card_to_bytes(&self, card: &impl RenderableCard) -> Result<RenderedImage> {
+        self.capture_card(card, self.format).await
+    }
+
+    /// Render a card like [`Renderer::render_card_to_bytes`], as a PNG
+    /// whatever the renderer's format.
+    pub async fn render_card_png(&self, card: &impl RenderableCard) -> Result<RenderedImage> {
+        self.capture_card(card, ImageFormat::Png).await
+    }
+
+    /// Returns the HTML document Chrome renders a card from, with this
+    /// renderer's settings applied, for debugging a card or rendering it
+    /// elsewhere.
+    #[must_use]
+    pub fn render_card_html(&self, card: &impl RenderableCard) -> String {
+        self.html.to_html(card)
+    }
+
+    /// Returns how this renderer builds cards' HTML, e.g. to build the same
+    /// documents where there's no Chrome.
+    #[must_use]
+    pub fn card_html(&self) -> &CardHtml {
+        &self.html
+    }
+
+    /// Render a card to PNG bytes like [`Renderer::render_card_png`], for
+    /// callers without an async runtime.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called from within an async runtime.
+    pub fn render_card_png_blocking(&self, card: &impl RenderableCard) -> Result<RenderedImage> {
+        self.chrome.handle.block_on(self.render_card_png(card))
+    }
+
+    /// Render a card like [`Renderer::render_card`], for callers without an
+    /// async runtime.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called from within an async runtime.
+    pub fn render_card_blocking(
+        &self,
+        card: &impl RenderableCard,
+        output_path: &Path,
+    ) -> Result<RenderOutcome> {
+        self.chrome
+            .handle
+            .block_on(self.render_card(card, output_path))
+    }
+
+    /// Render a card like [`Renderer::render_card`], in `format`.
+    async fn render_card_as(
+        &self,
+        card: &impl RenderableCard,
+        output_path: &Path,
+        format: ImageFormat,
+    ) -> Result<RenderOutcome> {
+        let RenderedImage {
+            bytes,
+            mut timings,
+            warnings,
+        } = self.capture_card(card, format).await?;
+        let start = Instant::now();
+        if let Some(parent) = output_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(output_path, bytes)?;
+        timings.encode_ms = start.elapsed().as_millis() as u64;
+        Ok(RenderOutcome {
+            output_paths: vec![output_path.to_path_buf()],
+            warnings,
+            timings,
+        })
+    }
+
+    /// Render a card to an image in `format`, returning it, how long each
+    /// phase took and any warnings.
+    async fn capture_card(
+        &self,
+        card: &impl RenderableCard,
+        format: ImageFormat,
+    ) -> Result<RenderedImage> {
+        in_runtime(&self.chrome.handle, async {
+            let mut timings = RenderTimings::default();
+            let mut phase = Instant::now();
+            let html = self.render_card_html(card);
+            timings.html_ms = lap(&mut phase);
+            let options = CaptureOptions {
+                scale: self.scale(card),
+                bleed: self.html.bleed(),
+                format,
+                quality: self.quality,
+                strict: self.strict,
+            };
+
+            #[cfg(feature = "megapage")]
+            if let Some(tiler) = &self.tiler {
+                return tiler.capture(self, html, options, timings.html_ms).await;
+            }
+
+            let _permit = self.chrome.pages.acquire().await?;
+            let browser = self.chrome.browser.read().await;
+            // Save HTML to a temporary file, unique to this page
+            let temp_html = self.temp_html("mtg_card", &html)?;
+
+            let page = self.chrome.page(&browser).await?;
+            let mut warnings = Vec::new();
+            let result = self
+                .watchdog(capture(
+                    &page,
+                    &temp_html.0,
+                    options,
+                    &mut timings,
+                    &mut phase,
+                    &mut warnings,
+                ))
+                .await;
+
+            self.chrome.release(&browser, page, &result).await;
+            result.map(|bytes| RenderedImage {
+                bytes,
+                timings,
+                warnings,
+            })
+        })
+        .await
+    }
+
+    /// Print an HTML document, like a sheet from [`crate::print`], to a PDF.
+    /// Page size and margins come from the document's `@page` rule.
+    pub async fn render_pdf(&self, html: Markup, output_path: &Path) -> Result<()> {
+        in_runtime(&self.chrome.handle, async {
+            let _permit = self.chrome.pages.acquire().await?;
+            let browser = self.chrome.browser.read().await;
+            let temp_html = self.temp_html("mtg_sheet", &html.into_string())?;
+
+            let page = self.chrome.page(&browser).await?;
+            let result = self
+                .watchdog(print_pdf(&page, &temp_html.0, output_path))
+                .await;
+            self.chrome.release(&browser, page, &result).await;
+            result
+        })
+        .await
+    }
+
+    /// Lay out a card as it would be rendered and measure the first element
+    /// matching each of `selectors`, or `None` where nothing matches.
+    /// Positions are in CSS pixels from the card's top-left corner.
+    pub async fn element_boxes(
+        &self,
+        card: &impl RenderableCard,
+        selectors: &[&str],
+    ) -> Result<Vec<Option<ElementBox>>> {
+        in_runtime(&self.chrome.handle, async {
+            let _permit = self.chrome.pages.acquire().await?;
+            let browser = self.chrome.browser.read().await;
+            let temp_html = self.temp_html("mtg_layout", &self.html.card_page(card))?;
+
+            let page = self.chrome.page(&browser).await?;
+            let result = self
+                .watchdog(measure(&page, &temp_html.0, self.scale(card), selectors))
+                .await;
+            self.chrome.release(&browser, page, &result).await;
+            result
+        })
+        .await
+    }
+
+    /// Run `work` on a page, failing it once it takes longer than the
+    /// renderer's timeout. A failure after Chrome's connection stopped
+    /// says why it did.
+    async fn watchdog<T>(&self, work: impl Future<Output = Result<T>>) -> Result<T> {
+        let result = match tokio::time::timeout(self.timeout, work).await {
+            Ok(result) => result,
+            Err(_) => Err(anyhow::anyhow!(
+                "Page didn't finish within {}s, so it was killed",
+                self.timeout.as_secs_f32()
+            )),
+        };
+        let handler_error = self.chrome.handler_error.lock().unwrap().clone();
+        let stopped = handler_error.lock().unwrap().clone();
+        match stopped {
+            Some(e) => {
+                result.map_err(|error| error.context(format!("Chrome's connection stopped: {}", e)))
+            }
+            None => result,
+        }
+    }
+
+    /// Returns how many times larger than standard `card` is rendered.
+    fn scale(&self, card: &impl RenderableCard) -> f32 {
+        card.size().unwrap_or(self.size).scale()
+    }
+
+    /// Write `html` to a temporary file, unique to this page.
+    fn temp_html(&self, prefix: &str, html: &str) -> Result<TempFile> {
+        let file = self.temp_file(prefix, "html");
+        std::fs::write(&file.0, html)?;
+        Ok(file)
+    }
+
+    /// Returns a temporary file path, unique to this page.
+    fn temp_file(&self, prefix: &str, extension: &str) -> TempFile {
+        TempFile(std::env::temp_dir().join(format!(
+            "{}_{}_{}.{}",
+            prefix,
+            std::process::id(),
+            self.chrome.next_page.fetch_add(1, Ordering::Relaxed),
+            extension
+        )))
+    }
+}
+
+/// Where an element was laid out, in CSS pixels
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ElementBox {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+    /// Vertical position of the element's first text baseline
+    pub baseline: f64,
+}
+
+impl ElementBox {
+    #[must_use]
+    pub fn right(&self) -> f64 {
+        self.x + self.width
+    }
+
+    #[must_use]
+    pub fn bottom(&self) -> f64 {
+        self.y + self.height
+    }
+}
+
+/// Measures each selector's element; the baseline is found by appending an
+/// empty inline-block, which sits on it.
+const MEASURE_JS: &str = r#"
+    SELECTORS.map((selector) => {
+        const element = document.querySelector(selector);
+        if (!element) return null;
+        const rect = element.getBoundingClientRect();
+        const probe = document.createElement("span");
+        probe.style.cssText = "display: inline-block; width: 0; height: 0;";
+        element.appendChild(probe);
+        const baseline = probe.getBoundingClientRect().top;
+        probe.remove();
+        return [rect.x, rect.y, rect.width, rect.height, baseline];
+    })
+"#;
+
+async fn measure(
+    page: &Page,
+    html_file: &Path,
+    scale: f32,
+    selectors: &[&str],
+) -> Result<Vec<Option<ElementBox>>> {
+    load_card(page, html_file, scale, false).await?;
+    settle(page).await?;
+    fit_text(page).await?;
+
+    let selectors: Vec<String> = selectors.iter().map(|s| s.to_string()).collect();
+    let script = MEASURE_JS.replace("SELECTORS", &facet_json::to_string(&selectors));
+    let boxes: Vec<Option<Vec<f64>>> = page.evaluate(script).await?.into_value()?;
+    Ok(boxes
+        .into_iter()
+        .map(|b| match b.as_deref() {
+            Some(&[x, y, width, height, baseline]) => Some(ElementBox {
+                x,
+                y,
+                width,
+                height,
+                baseline,
+            }),
+            _ => None,
+        })
+        .collect())
+}
+
+/// Shrinks the text of every text box whose content overflows it, a step
+/// at a time, until it fits or reaches [`MIN_TEXT_SCALE`] of its size.
+/// Font sizes and line heights are scaled together, so the text keeps its
+/// spacing.
+const FIT_TEXT_JS: &str = r#"
+    (() => {
+        const boxes = ".text-box, .planeswalker-text-box, .saga-text-box, .class-text-box, "
+            + ".split-text-box, .flip-text-box, .adventure-text";
+        document.querySelectorAll(boxes).forEach((box) => {
+            const overflows = () => box.scrollHeight > box.clientHeight + 1;
+            if (!overflows()) return;
+            const elements = [box, ...box.querySelectorAll("*")].map((element) => {
+                const style = getComputedStyle(element);
+                const lineHeight = parseFloat(style.lineHeight);
+                return [element, parseFloat(style.fontSize), lineHeight];
+            });
+            for (let scale = 1 - STEP; scale >= MIN_SCALE - 1e-9 && overflows(); scale -= STEP) {
+                for (const [element, fontSize, lineHeight] of elements) {
+                    element.style.fontSize = `${fontSize * scale}px`;
+                    if (!Number.isNaN(lineHeight)) {
+                        element.style.lineHeight = `${lineHeight * scale}px`;
+                    }
+                }
+            }
+        });
+    })()
+"#;
+
+/// Smallest scale overflowing text is shrunk to
+const MIN_TEXT_SCALE: f64 = 0.6;
+/// How much the scale drops at each step
+const TEXT_SCALE_STEP: f64 = 0.04;
+
+/// Shrink text that overflows its text box, see [`FIT_TEXT_JS`]. Runs once
+/// fonts have loaded, since they decide how the text wraps.
+async fn fit_text(page: &Page) -> Result<()> {
+    page.evaluate(fit_text_script()).await?;
+    Ok(())
+}
+
+/// Returns [`FIT_TEXT_JS`] with its limits filled in.
+fn fit_text_script() -> String {
+    FIT_TEXT_JS
+        .replace("MIN_SCALE", &MIN_TEXT_SCALE.to_string())
+        .replace("STEP", &TEXT_SCALE_STEP.to_string())
+}
+
+/// Collects the URLs a page fails to load while it's watched: requests
+/// that error out, like a missing file, and responses with an error
+/// status, like a 404 from Scryfall.
+struct FailedLoads {
+    urls: Arc<Mutex<Vec<String>>>,
+    task: tokio::task::JoinHandle<()>,
+}
+
+impl FailedLoads {
+    /// Start watching `page`, before it navigates.
+    async fn watch(page: &Page) -> Result<Self> {
+        let mut requests = page.event_listener::<EventRequestWillBeSent>().await?;
+        let mut failures = page.event_listener::<EventLoadingFailed>().await?;
+        let mut responses = page.event_listener::<EventResponseReceived>().await?;
+        let urls = Arc::new(Mutex::new(Vec::new()));
+
+        let found = urls.clone();
+        let task = tokio::spawn(async move {
+            // Failures only name their request, so remember each one's URL
+            let mut requested: HashMap<String, String> = HashMap::new();
+            let mut push = |url: String| {
+                let mut found = found.lock().unwrap();
+                if !found.contains(&url) {
+                    found.push(url);
+                }
+            };
+            loop {
+                tokio::select! {
+                    Some(event) = requests.next() => {
+                        requested.insert(
+                            event.request_id.as_ref().to_string(),
+                            event.request.url.clone(),
+                        );
+                    }
+                    Some(event) = failures.next() => {
+                        let url = requested.remove(event.request_id.as_ref());
+                        if let Some(url) = url.filter(|_| event.canceled != Some(true)) {
+                            push(url);
+                        }
+                    }
+                    Some(event) = responses.next() => {
+                        if event.response.status >= 400 {
+                            push(event.response.url.clone());
+                        }
+                    }
+                    else => break,
+                }
+            }
+        });
+        Ok(Self { urls, task })
+    }
+
+    /// Returns the URLs that failed so far, each once.
+    fn urls(&self) -> Vec<String> {
+        self.urls.lock().unwrap().clone()
+    }
+}
+
+impl Drop for FailedLoads {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// A file removed when dropped, including when a render is aborted partway
+struct TempFile(PathBuf);
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
+
+/// The runtime a renderer starts when it isn't created inside one
+fn background_runtime() -> Result<Runtime> {
+    Ok(tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(2)
+        .thread_name("mtg-gen-renderer")
+        .enable_all()
+        .build()?)
+}
+
+/// Run `future` with `handle`'s runtime entered, so the Tokio timers and I/O
+/// it uses work whichever executor polls it.
+fn in_runtime<'a, F: Future + 'a>(
+    handle: &'a Handle,
+    future: F,
+) -> impl Future<Output = F::Output> + 'a {
+    InRuntime {
+        handle,
+        future: Box::pin(future),
+    }
+}
+
+struct InRuntime<'a, F> {
+    handle: &'a Handle,
+    future: Pin<Box<F>>,
+}
+
+impl<F: Future> Future for InRuntime<'_, F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<F::Output> {
+        let this = self.get_mut();
+        let _guard = this.handle.enter();
+        this.future.as_mut().poll(cx)
+    }
+}
+
+/// Launch Chrome with the flags rendering needs, then `args`. Returns it
+/// with where its connection records the error it stops on.
+async fn launch_browser(handle: &Handle, args: &[String]) -> Result<(Browser, HandlerError)> {
+    let mut config = BrowserConfig::builder()
+        .no_sandbox()
+        .arg("--disable-web-security")
+        .arg("--allow-file-access-from-files")
+        .arg("--disable-features=IsolateOrigins,site-per-process")
+        .arg("--disable-blink-features=AutomationControlled")
+        .args(args);
+
+    if let Ok(path) = std::env::var("CHROME_PATH") {
+        config = config.chrome_executable(path);
+    }
+
+    let (browser, mut handler) =
+        Browser::launch(config.build().map_err(anyhow::Error::msg)?).await?;
+
+    let handler_error = HandlerError::default();
+    let stopped = handler_error.clone();
+    handle.spawn(async move {
+        while let Some(h) = handler.next().await {
+            if let Err(e) = h {
+                *stopped.lock().unwrap() = Some(e.to_string());
+                break;
+            }
+        }
+    });
+
+    Ok((browser, handler_error))
+}
+
+/// Load `html_file` in `page` and print it to a PDF at `output_path`.
+async fn print_pdf(page: &Page, html_file: &Path, output_path: &Path) -> Result<()> {
+    page.goto(format!("file://{}", html_file.display())).await?;
+    page.wait_for_navigation().await?;
+
+    let params = PrintToPdfParams::builder()
+        .print_background(true)
+        .prefer_css_page_size(true)
+        .build();
+    let pdf = page.pdf(params).await?;
+
+    if let Some(parent) = output_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(output_path, pdf)?;
+    Ok(())
+}
+
+/// Load a card's `html_file` in `page` at card size, `scale` times the pixels
+/// of a standard card.
+async fn load_card(page: &Page, html_file: &Path, scale: f32, bleed: bool) -> Result<()> {
+    // Set device metrics for proper card dimensions (744x1040 at 4x scale = 300 DPI).
+    // Every size keeps the same layout, only with more or fewer pixels
+    let (width, height) = if bleed {
+        (BLEED_WIDTH, BLEED_HEIGHT)
+    } else {
+        (744, 1040)
+    };
+    load_page(page, html_file, width, height, 4.0 * f64::from(scale)).await
+}
+
+/// Load `html_file` in `page` with a viewport of `width` × `height` CSS
+/// pixels, each `pixel_ratio` device pixels wide.
+async fn load_page(
+    page: &Page,
+    html_file: &Path,
+    width: u32,
+    height: u32,
+    pixel_ratio: f64,
+) -> Result<()> {
+    let metrics = SetDeviceMetricsOverrideParams::builder()
+        .width(width)
+        .height(height)
+        .device_scale_factor(pixel_ratio)
+        .mobile(false)
+        .build()
+        .map_err(|e| anyhow::anyhow!("Failed to build device metrics: {}", e))?;
+
+    page.execute(metrics).await?;
+
+    let file_url = format!("file://{}", html_file.display());
+    page.goto(&file_url).await?;
+
+    // Wait for page to fully load including external resources
+    page.wait_for_navigation().await?;
+    Ok(())
+}
+
+/// Resolves once the fonts and images of the page, and of any frames in
+/// it, have loaded and decoded. Images that fail to load resolve too; they
+/// are reported by [`FailedLoads`].
+const SETTLE_JS: &str = r#"
+    (() => {
+        const documents = [document];
+        document.querySelectorAll("iframe").forEach((frame) => {
+            if (frame.contentDocument) documents.push(frame.contentDocument);
+        });
+        const waits = documents.flatMap((doc) => [
+            doc.fonts.ready,
+            ...[...doc.images].map((img) =>
+                img.complete
+                    ? img.decode().catch(() => {})
+                    : new Promise((resolve) => {
+                          img.addEventListener("load", resolve, { once: true });
+                          img.addEventListener("error", resolve, { once: true });
+                      }).then(() => img.decode().catch(() => {}))
+            ),
+        ]);
+        return Promise.all(waits).then(() => true);
+    })()
+"#;
+
+/// Wait until the page's fonts and images are ready, see [`SETTLE_JS`].
+/// The renderer's timeout bounds the wait, since it runs under
+/// [`Renderer::watchdog`].
+async fn settle(page: &Page) -> Result<()> {
+    page.evaluate_expression(
+        EvaluateParams::builder()
+            .expression(SETTLE_JS)
+            .await_promise(true)
+            .build()
+            .map_err(|e| anyhow::anyhow!("Failed to build settle script: {}", e))?,
+    )
+    .await?;
+    Ok(())
+}
+
+/// How [`capture`] renders a card
+#[derive(Debug, Clone, Copy)]
+struct CaptureOptions {
+    /// How many times larger than standard the card is rendered
+    scale: f32,
+    bleed: bool,
+    format: ImageFormat,
+    /// Quality of JPEG and WebP images
+    quality: u8,
+    /// Whether images or fonts failing to load fail the capture
+    strict: bool,
+}
+
+async fn capture(
+    page: &Page,
+    html_file: &Path,
+    options: CaptureOptions,
+    timings: &mut RenderTimings,
+    phase: &mut Instant,
+    warnings: &mut Vec<String>,
+) -> Result<Vec<u8>> {
+    let failed_loads = FailedLoads::watch(page).await?;
+    load_card(page, html_file, options.scale, options.bleed).await?;
+    timings.navigation_ms = lap(phase);
+
+    settle(page).await?;
+    fit_text(page).await?;
+    timings.wait_ms = lap(phase);
+
+    let missing = failed_loads.urls();
+    if !missing.is_empty() {
+        if options.strict {
+            anyhow::bail!("Failed to load {}", missing.join(", "));
+        }
+        for url in &missing {
+            warnings.push(format!("failed to load {}", url));
+        }
+    }
+
+    // Take screenshot with high DPI
+    let mut screenshot_params = ScreenshotParams::builder()
+        .format(match options.format {
+            ImageFormat::Png => CaptureScreenshotFormat::Png,
+            ImageFormat::Jpeg => CaptureScreenshotFormat::Jpeg,
+            ImageFormat::Webp => CaptureScreenshotFormat::Webp,
+        })
+        .full_page(false)
+        .omit_background(false);
+    if options.format.is_lossy() {
+        screenshot_params = screenshot_params.quality(i64::from(options.quality));
+    }
+
+    let image = page.screenshot(screenshot_params.build()).await?;
+    timings.screenshot_ms = lap(phase);
+
+    // Non-standard, Chrome-only; missing elsewhere, which is fine
+    timings.js_heap_bytes = match page.evaluate("performance.memory.usedJSHeapSize").await {
+        Ok(result) => result.into_value::<u64>().ok(),
+        Err(_) => None,
+    };
+
+    Ok(image)
+}
+
+/// Returns the milliseconds since `start` and restarts it.
+fn lap(start: &mut Instant) -> u64 {
+    let elapsed = start.elapsed().as_millis() as u64;
+    *start = Instant::now();
+    elapsed
+}
@@ -0,0 +1,67 @@
+//! Bundles of Chromium flags for environments the default launch fails in
+//!
+//! Chrome is always launched with the flags rendering needs: no sandbox,
+//! and file access for the card pages. Some machines need more, like a
+//! container whose `/dev/shm` is too small for Chrome's shared memory, or a
+//! WSL install without a working GPU. A [`BrowserPreset`] names the flags
+//! for one of them, passed to [`Renderer::with_browser_args`](super::Renderer::with_browser_args)
+//! along with any others.
+
+/// A bundle of Chromium flags for one environment
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BrowserPreset {
+    /// Containers: a small `/dev/shm` and no GPU
+    Docker,
+    /// Windows Subsystem for Linux, where the GPU process tends to crash
+    Wsl,
+    /// macOS, where Chrome would otherwise ask for keychain access
+    Macos,
+    /// Small machines and CI runners: one Chrome process, kept small
+    LowMemory,
+}
+
+impl BrowserPreset {
+    /// Parse a preset name: `docker`, `wsl`, `macos` or `low-memory`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mtg_gen::render::BrowserPreset;
+    ///
+    /// assert_eq!(BrowserPreset::parse("Docker"), Ok(BrowserPreset::Docker));
+    /// assert!(BrowserPreset::parse("docker").unwrap().args().contains(&"--disable-dev-shm-usage"));
+    /// assert!(BrowserPreset::parse("windows").is_err());
+    /// ```
+    pub fn parse(s: &str) -> Result<Self, String> {
+        match s.trim().to_lowercase().as_str() {
+            "docker" => Ok(BrowserPreset::Docker),
+            "wsl" => Ok(BrowserPreset::Wsl),
+            "macos" => Ok(BrowserPreset::Macos),
+            "low-memory" => Ok(BrowserPreset::LowMemory),
+            other => Err(format!(
+                "Unknown browser preset: {} (expected docker, wsl, macos or low-memory)",
+                other
+            )),
+        }
+    }
+
+    /// The Chromium flags the preset adds
+    #[must_use]
+    pub fn args(self) -> &'static [&'static str] {
+        match self {
+            // Docker gives containers 64 MB of /dev/shm, which Chrome
+            // outgrows; --disable-dev-shm-usage moves it to /tmp
+            BrowserPreset::Docker => &["--disable-dev-shm-usage", "--disable-gpu", "--no-zygote"],
+            BrowserPreset::Wsl => &["--disable-gpu", "--disable-software-rasterizer"],
+            BrowserPreset::Macos => &["--use-mock-keychain", "--password-store=basic"],
+            BrowserPreset::LowMemory => &[
+                "--single-process",
+                "--renderer-process-limit=1",
+                "--disable-dev-shm-usage",
+                "--disable-gpu",
+                "--disable-extensions",
+                "--js-flags=--max-old-space-size=256",
+            ],
+        }
+    }
+}
@@ -0,0 +1,361 @@
+//! Rendering several cards on one Chrome page (the `megapage` feature)
+//!
+//! Each card is normally a page load and a screenshot of its own, several
+//! round trips to Chrome that can dominate a batch on some machines. With
+//! tiling (see [`Renderer::set_cards_per_page`]), cards rendered at about
+//! the same time are laid out side by side, each in an iframe, on one
+//! tall page. The page is captured in a single screenshot and cropped back
+//! into cards with the `image` crate.
+//!
+//! Whether that's faster depends on the machine, so it's opt-in; compare
+//! the totals of a `--profile` run with and without it.
+
+use super::{
+    BLEED_HEIGHT, BLEED_WIDTH, CaptureOptions, FailedLoads, RenderedImage, Renderer,
+    fit_text_script, lap, load_page, settle,
+};
+use crate::output::ImageFormat;
+use crate::profile::RenderTimings;
+use anyhow::Result;
+use chromiumoxide::page::{Page, ScreenshotParams};
+use chromiumoxide_cdp::cdp::browser_protocol::page::CaptureScreenshotFormat;
+use image::codecs::jpeg::JpegEncoder;
+use image::codecs::png::PngEncoder;
+use image::codecs::webp::WebPEncoder;
+use image::{DynamicImage, ImageEncoder};
+use maud::{PreEscaped, html};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tokio::sync::oneshot;
+
+/// How long the first card of a page waits for others to join it
+const GATHER_WINDOW: Duration = Duration::from_millis(100);
+
+/// Largest screenshot Chrome takes reliably, in device pixels per side
+const MAX_PAGE_PIXELS: f64 = 16_384.0;
+
+/// Cards waiting to be laid out on a shared page
+pub(super) struct Tiler {
+    /// Most cards on one page
+    cards_per_page: usize,
+    queue: Mutex<Vec<Job>>,
+}
+
+/// A card waiting for a page
+struct Job {
+    html: String,
+    options: CaptureOptions,
+    html_ms: u64,
+    reply: oneshot::Sender<Result<RenderedImage>>,
+}
+
+impl Job {
+    /// Cards can share a page when they're laid out at the same size and
+    /// fail the same way.
+    fn fits_with(&self, other: &Job) -> bool {
+        self.options.scale == other.options.scale
+            && self.options.bleed == other.options.bleed
+            && self.options.strict == other.options.strict
+    }
+}
+
+impl Tiler {
+    pub(super) fn new(cards_per_page: usize) -> Self {
+        Self {
+            cards_per_page,
+            queue: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Render the card document `html` on a page shared with the cards
+    /// rendered at about the same time, returning its image, how long each
+    /// phase took and any warnings. The first card to arrive waits briefly
+    /// for others, then renders pages until no card is left waiting.
+    pub(super) async fn capture(
+        &self,
+        renderer: &Renderer,
+        html: String,
+        options: CaptureOptions,
+        html_ms: u64,
+    ) -> Result<RenderedImage> {
+        let (reply, result) = oneshot::channel();
+        let leads = {
+            let mut queue = self.queue.lock().unwrap();
+            queue.push(Job {
+                html,
+                options,
+                html_ms,
+                reply,
+            });
+            queue.len() == 1
+        };
+
+        if leads {
+            tokio::time::sleep(GATHER_WINDOW).await;
+            loop {
+                let jobs = self.take_page();
+                if jobs.is_empty() {
+                    break;
+                }
+                render_page(renderer, jobs).await;
+            }
+        }
+        result
+            .await
+            .map_err(|_| anyhow::anyhow!("The page the card was laid out on was dropped"))?
+    }
+
+    /// Take the waiting cards that fit on one page with the first of them.
+    fn take_page(&self) -> Vec<Job> {
+        let mut queue = self.queue.lock().unwrap();
+        if queue.is_empty() {
+            return Vec::new();
+        }
+        let mut page = vec![queue.remove(0)];
+        let limit = self
+            .cards_per_page
+            .min(Layout::new(page[0].options, usize::MAX).max_cards());
+        let mut i = 0;
+        while i < queue.len() && page.len() < limit {
+            if queue[i].fits_with(&page[0]) {
+                page.push(queue.remove(i));
+            } else {
+                i += 1;
+            }
+        }
+        page
+    }
+}
+
+/// Where the cards of a page go
+#[derive(Debug, Clone, Copy)]
+struct Layout {
+    /// One card's size, in CSS pixels
+    width: u32,
+    height: u32,
+    /// Device pixels per CSS pixel
+    pixel_ratio: f64,
+    columns: usize,
+    rows: usize,
+}
+
+impl Layout {
+    /// Returns the layout of `cards` cards captured with `options`, as
+    /// close to square as Chrome's screenshot limit allows.
+    fn new(options: CaptureOptions, cards: usize) -> Self {
+        let (width, height) = if options.bleed {
+            (BLEED_WIDTH, BLEED_HEIGHT)
+        } else {
+            (744, 1040)
+        };
+        let pixel_ratio = 4.0 * f64::from(options.scale);
+        let mut layout = Self {
+            width,
+            height,
+            pixel_ratio,
+            columns: 1,
+            rows: 1,
+        };
+        let cards = cards.clamp(1, layout.max_cards());
+        layout.columns = (cards as f64).sqrt().ceil() as usize;
+        layout.columns = layout.columns.min(layout.max_columns());
+        layout.rows = cards.div_ceil(layout.columns);
+        layout
+    }
+
+    fn max_columns(&self) -> usize {
+        ((MAX_PAGE_PIXELS / (f64::from(self.width) * self.pixel_ratio)) as usize).max(1)
+    }
+
+    fn max_rows(&self) -> usize {
+        ((MAX_PAGE_PIXELS / (f64::from(self.height) * self.pixel_ratio)) as usize).max(1)
+    }
+
+    /// Most cards that fit in one screenshot
+    fn max_cards(&self) -> usize {
+        self.max_columns() * self.max_rows()
+    }
+
+    /// Returns the device pixel rectangle of the `i`th card.
+    fn cell(&self, i: usize) -> (u32, u32, u32, u32) {
+        let to_pixels = |css: f64| (css * self.pixel_ratio).round() as u32;
+        let (column, row) = (i % self.columns, i / self.columns);
+        (
+            to_pixels((column as u32 * self.width).into()),
+            to_pixels((row as u32 * self.height).into()),
+            to_pixels(self.width.into()),
+            to_pixels(self.height.into()),
+        )
+    }
+}
+
+/// Render `jobs` on one page and answer each of them. Runs in the
+/// renderer's runtime, see [`in_runtime`](super::in_runtime).
+async fn render_page(renderer: &Renderer, jobs: Vec<Job>) {
+    let documents: Vec<&str> = jobs.iter().map(|job| job.html.as_str()).collect();
+    let options = jobs[0].options;
+    let formats: Vec<(ImageFormat, u8)> = jobs
+        .iter()
+        .map(|job| (job.options.format, job.options.quality))
+        .collect();
+
+    let result = async {
+        let _permit = renderer.chrome.pages.acquire().await?;
+        let browser = renderer.chrome.browser.read().await;
+        let files = documents
+            .iter()
+            .map(|html| renderer.temp_html("mtg_card", html))
+            .collect::<Result<Vec<_>>>()?;
+        let layout = Layout::new(options, files.len());
+        let sheet = renderer.temp_html(
+            "mtg_tiles",
+            &page_html(
+                &files.iter().map(|file| file.0.clone()).collect::<Vec<_>>(),
+                layout,
+            ),
+        )?;
+
+        let page = renderer.chrome.page(&browser).await?;
+        let result = renderer
+            .watchdog(capture_page(
+                &page,
+                &sheet.0,
+                layout,
+                &formats,
+                options.strict,
+            ))
+            .await;
+        renderer.chrome.release(&browser, page, &result).await;
+        result
+    }
+    .await;
+
+    match result {
+        Ok(captures) => {
+            for (job, mut capture) in jobs.into_iter().zip(captures) {
+                capture.timings.html_ms = job.html_ms;
+                let _ = job.reply.send(Ok(capture));
+            }
+        }
+        Err(e) => {
+            for job in jobs {
+                let _ = job.reply.send(Err(anyhow::anyhow!("{}", e)));
+            }
+        }
+    }
+}
+
+/// Returns the page laying out the card documents in `files` as a grid.
+fn page_html(files: &[PathBuf], layout: Layout) -> String {
+    let css = format!(
+        "body {{ margin: 0; display: grid; grid-template-columns: repeat({}, {}px); grid-auto-rows: {}px; }} \
+         iframe {{ display: block; width: {}px; height: {}px; border: 0; }}",
+        layout.columns, layout.width, layout.height, layout.width, layout.height
+    );
+    html! {
+        html {
+            head {
+                meta charset="utf-8";
+                style { (PreEscaped(css)) }
+            }
+            body {
+                @for file in files {
+                    iframe scrolling="no" src=(format!("file://{}", file.display())) {}
+                }
+            }
+        }
+    }
+    .into_string()
+}
+
+/// Load the page of cards, screenshot it and crop out each card, in its
+/// own format.
+async fn capture_page(
+    page: &Page,
+    html_file: &std::path::Path,
+    layout: Layout,
+    formats: &[(ImageFormat, u8)],
+    strict: bool,
+) -> Result<Vec<RenderedImage>> {
+    let mut timings = RenderTimings::default();
+    let mut phase = Instant::now();
+
+    let failed_loads = FailedLoads::watch(page).await?;
+    load_page(
+        page,
+        html_file,
+        layout.width * layout.columns as u32,
+        layout.height * layout.rows as u32,
+        layout.pixel_ratio,
+    )
+    .await?;
+    timings.navigation_ms = lap(&mut phase);
+
+    settle(page).await?;
+    // Each card is a document of its own, so its text is fitted in its frame
+    let script = format!(
+        "document.querySelectorAll('iframe').forEach((frame) => frame.contentWindow.eval({}))",
+        facet_json::to_string(&fit_text_script())
+    );
+    page.evaluate(script).await?;
+    timings.wait_ms = lap(&mut phase);
+
+    // The page's loads can't be told apart by card, so every card on it
+    // fails or is warned
+    let missing = failed_loads.urls();
+    if strict && !missing.is_empty() {
+        anyhow::bail!(
+            "Failed to load {} on a page shared by {} cards",
+            missing.join(", "),
+            formats.len()
+        );
+    }
+    let warnings: Vec<String> = missing
+        .iter()
+        .map(|url| format!("failed to load {} on a page shared with other cards", url))
+        .collect();
+
+    let params = ScreenshotParams::builder()
+        .format(CaptureScreenshotFormat::Png)
+        .full_page(false)
+        .omit_background(false)
+        .build();
+    let screenshot = image::load_from_memory(&page.screenshot(params).await?)?;
+    timings.screenshot_ms = lap(&mut phase);
+
+    let mut captures = Vec::with_capacity(formats.len());
+    for (i, &(format, quality)) in formats.iter().enumerate() {
+        let (x, y, width, height) = layout.cell(i);
+        let card = screenshot.crop_imm(x, y, width, height);
+        let image = encode(&card, format, quality)?;
+        let mut card_timings = timings;
+        card_timings.encode_ms = lap(&mut phase);
+        captures.push(RenderedImage {
+            bytes: image,
+            timings: card_timings,
+            warnings: warnings.clone(),
+        });
+    }
+    Ok(captures)
+}
+
+/// Encode a cropped card in `format`. WebP is encoded losslessly, whatever
+/// the quality; the `image` crate has no lossy WebP encoder.
+fn encode(card: &DynamicImage, format: ImageFormat, quality: u8) -> Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    match format {
+        ImageFormat::Png => card.write_with_encoder(PngEncoder::new(&mut bytes))?,
+        ImageFormat::Jpeg => {
+            let rgb = card.to_rgb8();
+            JpegEncoder::new_with_quality(&mut bytes, quality).write_image(
+                &rgb,
+                rgb.width(),
+                rgb.height(),
+                image::ExtendedColorType::Rgb8,
+            )?;
+        }
+        ImageFormat::Webp => card.write_with_encoder(WebPEncoder::new_lossless(&mut bytes))?,
+    }
+    Ok(bytes)
+}
@@ -0,0 +1,137 @@
+//! Building the documents cards are rendered from, without a browser
+//!
+//! [`CardHtml`] turns a card into the complete HTML document Chrome
+//! screenshots: the card's markup with its style file, frame effects, color
+//! chips, legal line and bleed applied. It needs no browser, so static site
+//! generators and preview servers can reuse the markup; only
+//! [`Renderer`](super::Renderer)'s screenshot step needs Chrome.
+
+use super::{RenderableCard, card_to_html, with_bleed, with_rarity_letter};
+use crate::color_chips::with_color_chips;
+use crate::frame_effects::{card_seed, with_frame_effects};
+use crate::legal::{current_year, legal_line, with_legal_line};
+use crate::style::{StyleConfig, with_css};
+
+/// Settings for the HTML documents cards are rendered from
+///
+/// # Examples
+///
+/// ```
+/// use mtg_gen::render::CardHtml;
+/// use mtg_gen::Card;
+///
+/// let card: Card = facet_yaml::from_str(
+///     "name: Bolt\ntype: normal\ntype_line: Instant\nrarity: common\n",
+/// )
+/// .unwrap();
+/// let mut template = CardHtml::new();
+/// template.set_color_chips(true);
+/// assert!(template.to_html(&card).contains("Bolt"));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CardHtml {
+    /// Whether cards get color identity chips
+    color_chips: bool,
+    /// Whether rarity is shown as a letter instead of the rarity indicator
+    rarity_letter: bool,
+    /// Overrides applied on top of the built-in stylesheet
+    style: StyleConfig,
+    /// Whether cards are laid out with print bleed around them
+    bleed: bool,
+    /// Whether cards are marked as proxies on their legal line
+    not_for_sale: bool,
+    /// The run's seed generated textures are drawn from, if any
+    seed: Option<u64>,
+}
+
+impl CardHtml {
+    /// Settings for plain cards: the built-in stylesheet, no color chips,
+    /// no bleed.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a lettered chip for each of a card's colors below its title bar,
+    /// see [`crate::color_chips`].
+    pub fn set_color_chips(&mut self, enabled: bool) {
+        self.color_chips = enabled;
+    }
+
+    /// Show rarity as a letter on the collector line instead of the rarity
+    /// indicator, see [`with_rarity_letter`].
+    pub fn set_rarity_letter(&mut self, enabled: bool) {
+        self.rarity_letter = enabled;
+    }
+
+    /// Apply `style` on top of the built-in stylesheet, see [`crate::style`].
+    pub fn set_style(&mut self, style: StyleConfig) {
+        self.style = style;
+    }
+
+    /// Lay cards out with print bleed, at MakePlayingCards' 816 × 1110
+    /// instead of 744 × 1040, see [`with_bleed`].
+    pub fn set_bleed(&mut self, enabled: bool) {
+        self.bleed = enabled;
+    }
+
+    /// Mark every card `NOT FOR SALE` on its legal line, for proxies, see
+    /// [`crate::legal`].
+    pub fn set_not_for_sale(&mut self, enabled: bool) {
+        self.not_for_sale = enabled;
+    }
+
+    /// Draw each card's generated textures, like its frame effects, from
+    /// `seed` and the card's name instead of using the built-in ones, see
+    /// [`card_seed`].
+    pub fn set_seed(&mut self, seed: Option<u64>) {
+        self.seed = seed;
+    }
+
+    /// Returns the style applied on top of the built-in stylesheet.
+    #[must_use]
+    pub fn style(&self) -> &StyleConfig {
+        &self.style
+    }
+
+    /// Returns whether cards are laid out with print bleed.
+    #[must_use]
+    pub fn bleed(&self) -> bool {
+        self.bleed
+    }
+
+    /// Returns the HTML document `card` is rendered from, with these
+    /// settings applied.
+    #[must_use]
+    pub fn to_html(&self, card: &impl RenderableCard) -> String {
+        let html = self.card_page(card);
+        if self.bleed { with_bleed(&html) } else { html }
+    }
+
+    /// Returns the document like [`CardHtml::to_html`], but always at card
+    /// size, without bleed.
+    pub(crate) fn card_page(&self, card: &impl RenderableCard) -> String {
+        let mut html = with_css(&card_to_html(card), &card.style_css(&self.style));
+        let effects = card.frame_effects();
+        if !effects.is_empty() {
+            let seed = self.seed.map(|seed| card_seed(seed, card.card_name()));
+            html = with_frame_effects(&html, &effects, &self.style, seed);
+        }
+        if self.color_chips {
+            html = with_color_chips(&html, &card.colors());
+        }
+        if let Some(rarity) = card.rarity().filter(|_| self.rarity_letter) {
+            html = with_rarity_letter(&html, rarity);
+        }
+        let legal = legal_line(
+            self.style.legal.as_ref(),
+            card.set_code(),
+            self.not_for_sale,
+            current_year(),
+        );
+        if let Some(text) = legal {
+            html = with_legal_line(&html, &text);
+        }
+        html
+    }
+}
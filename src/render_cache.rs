@@ -0,0 +1,129 @@
+//! Skipping unchanged cards (`--skip-existing`)
+//!
+//! Re-rendering a whole set to change one card is slow. With
+//! `--skip-existing`, every rendered card is recorded in a sidecar file in
+//! the output directory with a hash of everything its images are made from:
+//! its YAML and the card it renders as, with what `set.yaml` and
+//! `--auto-number` fill in, the art and set symbol files it references, the
+//! mtg-gen version and the run's render settings. The next run skips a card whose
+//! hash matches and whose images all still exist, reusing its earlier
+//! manifest entry.
+
+use crate::card::Card;
+use crate::output::ManifestEntry;
+use crate::yaml::card_to_yaml;
+use anyhow::{Context, Result};
+use facet::Facet;
+use sha2::{Digest, Sha256};
+use std::fmt::Write;
+use std::path::Path;
+
+/// File name of the cache, in the output directory
+pub const CACHE_FILE_NAME: &str = ".mtg-gen-cache.json";
+
+/// A card rendered by an earlier run
+#[derive(Facet, Debug, Clone, PartialEq, Eq)]
+pub struct CachedCard {
+    /// Hash of everything the card's images were made from, see
+    /// [`content_hash`]
+    pub hash: String,
+    /// The card's manifest entry
+    pub entry: ManifestEntry,
+}
+
+/// Hashes of the cards rendered into an output directory
+#[derive(Facet, Debug, Clone, Default, PartialEq, Eq)]
+pub struct RenderCache {
+    pub cards: Vec<CachedCard>,
+}
+
+impl RenderCache {
+    /// Read the cache in `output_dir`. A cache that's missing, or can't be
+    /// read or parsed, is empty, so every card is rendered.
+    #[must_use]
+    pub fn load(output_dir: &Path) -> Self {
+        std::fs::read_to_string(output_dir.join(CACHE_FILE_NAME))
+            .ok()
+            .and_then(|json| facet_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
+
+    /// Returns the earlier entry of the card file `source` when it was
+    /// rendered from content hashing to `hash` and its images all still
+    /// exist in `output_dir`.
+    #[must_use]
+    pub fn fresh(&self, source: &str, hash: &str, output_dir: &Path) -> Option<&ManifestEntry> {
+        self.cards
+            .iter()
+            .find(|card| card.entry.source == source && card.hash == hash)
+            .map(|card| &card.entry)
+            .filter(|entry| {
+                entry
+                    .outputs
+                    .iter()
+                    .all(|output| output_dir.join(&output.path).is_file())
+            })
+    }
+
+    /// Record a rendered card, replacing any earlier record of it.
+    pub fn insert(&mut self, hash: String, entry: ManifestEntry) {
+        match self
+            .cards
+            .iter_mut()
+            .find(|card| card.entry.source == entry.source)
+        {
+            Some(card) => *card = CachedCard { hash, entry },
+            None => self.cards.push(CachedCard { hash, entry }),
+        }
+    }
+
+    /// Write the cache to `output_dir`, replacing it in one step.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cache can't be written.
+    pub fn save(&self, output_dir: &Path) -> Result<()> {
+        let path = output_dir.join(CACHE_FILE_NAME);
+        let temp = output_dir.join(format!("{}.tmp", CACHE_FILE_NAME));
+        std::fs::write(&temp, facet_json::to_string(self))
+            .with_context(|| format!("Failed to write {:?}", temp))?;
+        std::fs::rename(&temp, &path).with_context(|| format!("Failed to write {:?}", path))?;
+        Ok(())
+    }
+}
+
+/// Returns a hash of everything `card`'s images are made from: `yaml`, the
+/// YAML it was parsed from, the card itself, which may hold more, like a
+/// collector number assigned for the run, the contents of its art and set
+/// symbol files, the mtg-gen version and `settings`, the run's render
+/// settings written out in any stable form.
+///
+/// The card's art paths should be resolved, see
+/// [`Card::resolve_art_paths`]. A file that can't be read hashes as empty,
+/// so it counts as changed once it appears.
+#[must_use]
+pub fn content_hash(yaml: &str, card: &Card, settings: &str) -> String {
+    let mut hasher = Sha256::new();
+    // Each part is length-prefixed, so moving bytes between them changes
+    // the hash
+    let mut part = |bytes: &[u8]| {
+        hasher.update((bytes.len() as u64).to_le_bytes());
+        hasher.update(bytes);
+    };
+    part(env!("CARGO_PKG_VERSION").as_bytes());
+    part(settings.as_bytes());
+    part(yaml.as_bytes());
+    part(card_to_yaml(card).as_bytes());
+    let files = card.art_paths().chain(card.base().set_symbol.as_deref());
+    for file in files {
+        let contents = std::fs::read(file).unwrap_or_default();
+        part(file.as_bytes());
+        part(&contents);
+    }
+
+    let mut hash = String::with_capacity(64);
+    for byte in hasher.finalize() {
+        let _ = write!(hash, "{:02x}", byte);
+    }
+    hash
+}
@@ -0,0 +1,607 @@
+//! JSON Schema for card YAML files
+//!
+//! Used by `mtg-gen schema` so YAML editors (e.g. VS Code with the YAML
+//! extension) can offer autocomplete and validation for card files. The
+//! field tables below mirror the card structs in [`crate::card`]; the test
+//! suite checks them against the Facet shapes by parsing every layout's
+//! example with each required field removed, and by checking that every
+//! field the examples use is listed here.
+
+use std::fmt::Write;
+
+const MANA_COST_PATTERN: &str = r"^(\{[^{}]+\})*$";
+const LOYALTY_COST_PATTERN: &str = r"^([+-]?[0-9]+|[+-][xX])$";
+const LOYALTY_PATTERN: &str = r"^([0-9]+|[xX])$";
+const LEVEL_RANGE_PATTERN: &str = r"^[0-9]+(-[0-9]+|\+)?$";
+
+/// The kind of value a field holds
+#[derive(Debug, Clone, Copy)]
+enum Kind {
+    String,
+    /// A string or a bare number, like power and toughness
+    Stat,
+    ManaCost,
+    RulesText,
+    Bool,
+    Integer {
+        minimum: u32,
+    },
+    Enum(&'static [&'static str]),
+    Pattern(&'static str),
+    Strings,
+    Integers {
+        minimum: u32,
+    },
+    Object(&'static [Field]),
+    List(&'static [Field]),
+    /// An object whose keys are free-form, each holding the given fields
+    Map(&'static [Field]),
+}
+
+/// One field of a card or nested object
+#[derive(Debug, Clone, Copy)]
+struct Field {
+    name: &'static str,
+    kind: Kind,
+    required: bool,
+    description: &'static str,
+}
+
+const fn required(name: &'static str, kind: Kind, description: &'static str) -> Field {
+    Field {
+        name,
+        kind,
+        required: true,
+        description,
+    }
+}
+
+const fn optional(name: &'static str, kind: Kind, description: &'static str) -> Field {
+    Field {
+        name,
+        kind,
+        required: false,
+        description,
+    }
+}
+
+const RARITIES: &[&str] = &[
+    "common", "uncommon", "rare", "mythic", "special", "bonus", "land",
+];
+
+/// Fields of [`CardBase`](crate::CardBase), shared by every layout
+const BASE: &[Field] = &[
+    required("name", Kind::String, "Card name"),
+    optional(
+        "mana_cost",
+        Kind::ManaCost,
+        "Mana cost in Scryfall notation, e.g. {2}{U}{U}",
+    ),
+    required(
+        "type_line",
+        Kind::String,
+        "Full type line, e.g. Creature — Elf Druid",
+    ),
+    optional(
+        "rules_text",
+        Kind::RulesText,
+        "Rules text; symbols like {T} render as icons",
+    ),
+    optional("flavor_text", Kind::String, "Flavor text"),
+    optional("power", Kind::Stat, "Power (for creatures)"),
+    optional("toughness", Kind::Stat, "Toughness (for creatures)"),
+    required("rarity", Kind::Enum(RARITIES), "Card rarity"),
+    optional(
+        "collector_number",
+        Kind::String,
+        "Collector number within the set, e.g. 12 or 12a",
+    ),
+    optional(
+        "reminder_text",
+        Kind::Enum(&["auto", "none"]),
+        "Whether to add automatic reminder text (defaults to none)",
+    ),
+    optional(
+        "translations",
+        Kind::Map(TRANSLATION),
+        "Translations keyed by language code, e.g. de or jp",
+    ),
+    optional(
+        "art",
+        Kind::String,
+        "Path to the card's art, relative to this file",
+    ),
+    optional(
+        "art_fit",
+        Kind::Enum(&["cover", "contain", "fill"]),
+        "How the art fills its art box (defaults to cover)",
+    ),
+    optional(
+        "artist",
+        Kind::String,
+        "Who illustrated the card's art, credited on its collector line",
+    ),
+    optional(
+        "size",
+        Kind::Enum(&["standard", "mini", "jumbo"]),
+        "Size to render and print the card at (defaults to the run's --size)",
+    ),
+    optional(
+        "frame_effects",
+        Kind::Strings,
+        "Aging effects laid over the frame: worn, grunge",
+    ),
+    optional(
+        "set_code",
+        Kind::String,
+        "Expansion code of the card's set, e.g. DOM",
+    ),
+    optional(
+        "set_symbol",
+        Kind::String,
+        "Path to the set symbol (SVG or PNG), relative to this file",
+    ),
+    optional(
+        "frame_colors",
+        Kind::Enum(&["cost", "identity"]),
+        "Where the frame color comes from; identity adds rules text mana symbols",
+    ),
+];
+
+/// Fields of [`Translation`](crate::Translation)
+const TRANSLATION: &[Field] = &[
+    optional("name", Kind::String, "Translated card name"),
+    optional("type_line", Kind::String, "Translated type line"),
+    optional("rules_text", Kind::RulesText, "Translated rules text"),
+    optional("flavor_text", Kind::String, "Translated flavor text"),
+];
+
+/// Fields of [`CardFace`](crate::CardFace)
+const FACE: &[Field] = &[
+    optional("name", Kind::String, "Face name"),
+    optional("mana_cost", Kind::ManaCost, "Mana cost"),
+    optional("type_line", Kind::String, "Type line"),
+    optional("rules_text", Kind::RulesText, "Rules text"),
+    optional("flavor_text", Kind::String, "Flavor text"),
+    optional("power", Kind::Stat, "Power (for creatures)"),
+    optional("toughness", Kind::Stat, "Toughness (for creatures)"),
+    optional(
+        "color_indicator",
+        Kind::Strings,
+        "Color indicator (for faces without a mana cost)",
+    ),
+    optional(
+        "art",
+        Kind::String,
+        "Path to the face's art, relative to this file",
+    ),
+    optional(
+        "artist",
+        Kind::String,
+        "Artist of the face's art, credited on split card halves",
+    ),
+];
+
+const FACES: Field = required("faces", Kind::List(FACE), "The card's faces, in order");
+
+const LOYALTY_ABILITY: &[Field] = &[
+    required(
+        "cost",
+        Kind::Pattern(LOYALTY_COST_PATTERN),
+        "Cost (e.g., \"+2\", \"-1\", \"0\")",
+    ),
+    required("text", Kind::RulesText, "Ability text"),
+];
+
+const SAGA_CHAPTER: &[Field] = &[
+    required(
+        "chapters",
+        Kind::Integers { minimum: 1 },
+        "Chapter numbers this ability applies to",
+    ),
+    required("text", Kind::RulesText, "The chapter text"),
+];
+
+const CLASS_LEVEL: &[Field] = &[
+    required(
+        "level",
+        Kind::Integer { minimum: 1 },
+        "The level number (1, 2, 3)",
+    ),
+    optional(
+        "cost",
+        Kind::ManaCost,
+        "Cost to level up to this level (only for level 2+)",
+    ),
+    required("text", Kind::RulesText, "Ability text for this level"),
+];
+
+const ADVENTURE_SPELL: &[Field] = &[
+    required("name", Kind::String, "The adventure spell name"),
+    required(
+        "mana_cost",
+        Kind::ManaCost,
+        "The adventure spell's mana cost",
+    ),
+    required("type_line", Kind::String, "The adventure spell's type"),
+    required(
+        "rules_text",
+        Kind::RulesText,
+        "The adventure spell's rules text",
+    ),
+    optional(
+        "flavor_text",
+        Kind::String,
+        "The adventure spell's flavor text",
+    ),
+    optional(
+        "artist",
+        Kind::String,
+        "Artist credited under the adventure spell's text",
+    ),
+];
+
+const LEVELER_RANGE: &[Field] = &[
+    required(
+        "range",
+        Kind::Pattern(LEVEL_RANGE_PATTERN),
+        "Level range (e.g., \"0-3\" or \"8+\")",
+    ),
+    optional("power", Kind::Stat, "Power for this range"),
+    optional("toughness", Kind::Stat, "Toughness for this range"),
+    optional("text", Kind::RulesText, "Ability text for this range"),
+];
+
+/// Layout-specific fields, keyed by the `type` value
+const LAYOUTS: &[(&str, &str, &[Field])] = &[
+    ("normal", "A standard card", &[]),
+    (
+        "planeswalker",
+        "A planeswalker card with loyalty abilities",
+        &[
+            required(
+                "loyalty",
+                Kind::Pattern(LOYALTY_PATTERN),
+                "Starting loyalty: a number or X",
+            ),
+            required(
+                "loyalty_abilities",
+                Kind::List(LOYALTY_ABILITY),
+                "Loyalty abilities, top to bottom",
+            ),
+        ],
+    ),
+    (
+        "saga",
+        "A saga enchantment with chapter abilities",
+        &[
+            required("chapters", Kind::List(SAGA_CHAPTER), "Chapter abilities"),
+            optional(
+                "read_ahead",
+                Kind::Bool,
+                "Read ahead sagas let the controller choose which chapter to start on",
+            ),
+        ],
+    ),
+    (
+        "class",
+        "A class enchantment with level-up abilities",
+        &[required(
+            "levels",
+            Kind::List(CLASS_LEVEL),
+            "Levels, starting at 1",
+        )],
+    ),
+    (
+        "adventure",
+        "An adventure card (creature with an adventure spell)",
+        &[required(
+            "adventure",
+            Kind::Object(ADVENTURE_SPELL),
+            "The adventure spell",
+        )],
+    ),
+    (
+        "split",
+        "A split card (two spells side-by-side, like Fire // Ice)",
+        &[
+            FACES,
+            optional(
+                "fuse",
+                Kind::Bool,
+                "Whether the halves can be cast together",
+            ),
+            optional("aftermath", Kind::Bool, "Whether this is an aftermath card"),
+        ],
+    ),
+    (
+        "flip",
+        "A flip card (Kamigawa-style, rotated bottom half)",
+        &[FACES],
+    ),
+    (
+        "transform",
+        "A transform double-faced card (like Delver of Secrets)",
+        &[
+            FACES,
+            optional(
+                "dfc_icons",
+                Kind::Enum(&["sun_moon", "compass_land", "spark", "moon_eldrazi"]),
+                "Icons marking the faces (chosen from the back face's type line when not set)",
+            ),
+        ],
+    ),
+    (
+        "modal_dfc",
+        "A modal double-faced card (either side playable)",
+        &[FACES],
+    ),
+    (
+        "battle",
+        "A battle card with defense counter",
+        &[
+            required(
+                "defense",
+                Kind::Integer { minimum: 0 },
+                "Starting defense counters",
+            ),
+            required("backside_name", Kind::String, "The back face's name"),
+            required(
+                "backside_type_line",
+                Kind::String,
+                "The back face's type line",
+            ),
+            required(
+                "backside_rules_text",
+                Kind::RulesText,
+                "The back face's rules text",
+            ),
+        ],
+    ),
+    (
+        "meld",
+        "A meld card (two cards that combine into one)",
+        &[FACES],
+    ),
+    (
+        "leveler",
+        "A leveler creature (Rise of the Eldrazi style)",
+        &[required(
+            "leveler_ranges",
+            Kind::List(LEVELER_RANGE),
+            "Level ranges in ascending order",
+        )],
+    ),
+    (
+        "prototype",
+        "A prototype card (two casting costs/stats)",
+        &[required(
+            "prototype",
+            Kind::Object(FACE),
+            "Cost and stats of the prototype",
+        )],
+    ),
+    (
+        "token",
+        "A token, made by another card (no mana cost)",
+        &[optional(
+            "colors",
+            Kind::Strings,
+            "The token's colors; colorless when left out",
+        )],
+    ),
+];
+
+/// Returns the layout names that have a schema.
+pub fn layouts() -> impl Iterator<Item = &'static str> {
+    LAYOUTS.iter().map(|(name, _, _)| *name)
+}
+
+/// A top-level field of a card layout
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FieldInfo {
+    pub name: &'static str,
+    pub required: bool,
+    /// Whether the value is a list or object rather than a single value
+    pub nested: bool,
+    /// Whether the value is written unquoted (booleans, numbers, enum values)
+    pub plain: bool,
+    pub description: &'static str,
+}
+
+/// Returns the top-level fields of a layout, including `type` and the
+/// common fields, in the order they are documented.
+#[must_use]
+pub fn layout_fields(layout: &str) -> Option<Vec<FieldInfo>> {
+    let &(_, _, fields) = LAYOUTS.iter().find(|(name, _, _)| *name == layout)?;
+    let mut infos = vec![FieldInfo {
+        name: "type",
+        required: true,
+        nested: false,
+        plain: true,
+        description: "Card layout",
+    }];
+    infos.extend(layout_field_list(fields).map(|f| FieldInfo {
+        name: f.name,
+        required: f.required,
+        nested: matches!(
+            f.kind,
+            Kind::Strings | Kind::Integers { .. } | Kind::Object(_) | Kind::List(_) | Kind::Map(_)
+        ),
+        plain: matches!(f.kind, Kind::Bool | Kind::Integer { .. } | Kind::Enum(_)),
+        description: f.description,
+    }));
+    Some(infos)
+}
+
+fn layout_field_list(fields: &'static [Field]) -> impl Iterator<Item = &'static Field> {
+    BASE.iter().chain(fields.iter())
+}
+
+/// Returns a JSON Schema covering every card layout, switching on `type`.
+#[must_use]
+pub fn card_schema() -> String {
+    let mut out = String::new();
+    out.push_str("{\n");
+    out.push_str("  \"$schema\": \"http://json-schema.org/draft-07/schema#\",\n");
+    out.push_str("  \"title\": \"MTG card\",\n");
+    out.push_str("  \"type\": \"object\",\n");
+    out.push_str("  \"required\": [\"type\"],\n");
+    let _ = writeln!(
+        out,
+        "  \"properties\": {{ \"type\": {{ \"description\": \"Card layout\", \"enum\": [{}] }} }},",
+        string_list(layouts())
+    );
+
+    out.push_str("  \"allOf\": [\n");
+    for (i, &(name, _, _)) in LAYOUTS.iter().enumerate() {
+        let _ = write!(
+            out,
+            "    {{ \"if\": {{ \"properties\": {{ \"type\": {{ \"const\": {} }} }} }}, \"then\": {{ \"$ref\": \"#/definitions/{}\" }} }}",
+            json_string(name),
+            name
+        );
+        out.push_str(if i + 1 < LAYOUTS.len() { ",\n" } else { "\n" });
+    }
+    out.push_str("  ],\n");
+
+    out.push_str("  \"definitions\": {\n");
+    for (i, &(name, description, fields)) in LAYOUTS.iter().enumerate() {
+        let _ = write!(out, "    {}: ", json_string(name));
+        write_layout(&mut out, "", name, description, fields);
+        out.push_str(if i + 1 < LAYOUTS.len() { ",\n" } else { "\n" });
+    }
+    out.push_str("  }\n");
+    out.push_str("}\n");
+    out
+}
+
+/// Returns a JSON Schema for a single layout, or `None` for an unknown
+/// layout name.
+#[must_use]
+pub fn layout_schema(layout: &str) -> Option<String> {
+    let &(name, description, fields) = LAYOUTS.iter().find(|(name, _, _)| *name == layout)?;
+    let mut out = String::new();
+    let header = format!(
+        "\"$schema\": \"http://json-schema.org/draft-07/schema#\", \"title\": {}, ",
+        json_string(&format!("MTG {} card", name))
+    );
+    write_layout(&mut out, &header, name, description, fields);
+    out.push('\n');
+    Some(out)
+}
+
+/// Write a layout's object schema; `header` is spliced in as extra members.
+fn write_layout(
+    out: &mut String,
+    header: &str,
+    name: &str,
+    description: &str,
+    fields: &'static [Field],
+) {
+    let fields: Vec<&Field> = layout_field_list(fields).collect();
+    let _ = write!(
+        out,
+        "{{ {}\"description\": {}, \"type\": \"object\", \"required\": [\"type\", {}], \"additionalProperties\": false, \"properties\": {{ \"type\": {{ \"const\": {} }}",
+        header,
+        json_string(description),
+        string_list(fields.iter().filter(|f| f.required).map(|f| f.name)),
+        json_string(name)
+    );
+    for field in fields {
+        let _ = write!(out, ", {}: ", json_string(field.name));
+        write_field(out, field);
+    }
+    out.push_str(" } }");
+}
+
+fn write_object(out: &mut String, fields: &[Field]) {
+    let _ = write!(
+        out,
+        "\"type\": \"object\", \"required\": [{}], \"additionalProperties\": false, \"properties\": {{ ",
+        string_list(fields.iter().filter(|f| f.required).map(|f| f.name))
+    );
+    for (i, field) in fields.iter().enumerate() {
+        if i > 0 {
+            out.push_str(", ");
+        }
+        let _ = write!(out, "{}: ", json_string(field.name));
+        write_field(out, field);
+    }
+    out.push_str(" }");
+}
+
+fn write_field(out: &mut String, field: &Field) {
+    let _ = write!(
+        out,
+        "{{ \"description\": {}, ",
+        json_string(field.description)
+    );
+    match field.kind {
+        Kind::String | Kind::RulesText => out.push_str("\"type\": \"string\""),
+        Kind::Stat => out.push_str("\"type\": [\"string\", \"integer\"]"),
+        Kind::ManaCost => {
+            let _ = write!(
+                out,
+                "\"type\": \"string\", \"pattern\": {}",
+                json_string(MANA_COST_PATTERN)
+            );
+        }
+        Kind::Bool => out.push_str("\"type\": \"boolean\""),
+        Kind::Integer { minimum } => {
+            let _ = write!(out, "\"type\": \"integer\", \"minimum\": {}", minimum);
+        }
+        Kind::Enum(values) => {
+            let _ = write!(out, "\"enum\": [{}]", string_list(values.iter().copied()));
+        }
+        Kind::Pattern(pattern) => {
+            let _ = write!(
+                out,
+                "\"type\": [\"string\", \"integer\"], \"pattern\": {}",
+                json_string(pattern)
+            );
+        }
+        Kind::Strings => out.push_str("\"type\": \"array\", \"items\": { \"type\": \"string\" }"),
+        Kind::Integers { minimum } => {
+            let _ = write!(
+                out,
+                "\"type\": \"array\", \"items\": {{ \"type\": \"integer\", \"minimum\": {} }}",
+                minimum
+            );
+        }
+        Kind::Object(fields) => write_object(out, fields),
+        Kind::List(fields) => {
+            out.push_str("\"type\": \"array\", \"items\": { ");
+            write_object(out, fields);
+            out.push_str(" }");
+        }
+        Kind::Map(fields) => {
+            out.push_str("\"type\": \"object\", \"additionalProperties\": { ");
+            write_object(out, fields);
+            out.push_str(" }");
+        }
+    }
+    out.push_str(" }");
+}
+
+fn string_list<'a>(items: impl Iterator<Item = &'a str>) -> String {
+    items.map(json_string).collect::<Vec<_>>().join(", ")
+}
+
+fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
@@ -0,0 +1,665 @@
+//! Conversion of Scryfall card objects into the crate's card model
+//!
+//! Scryfall's `oracle_text` is a flat string: paragraphs are separated by
+//! newlines, reminder text is wrapped in parentheses, and ability words are
+//! only recognizable by the " — " that follows them. [`oracle_text_to_rules_text`]
+//! turns that markup into structured [`RulesText`] segments so the renderer
+//! can italicize reminder text and ability words and break paragraphs.
+
+use crate::card::{
+    AdventureCard, AdventureSpell, BattleCard, Card, CardBase, CardFace, DfcIcons, FlipCard,
+    LoyaltyAbility, ModalDfcCard, NormalCard, PlaneswalkerCard, Rarity, SagaCard, SagaChapter,
+    SplitCard, TransformCard,
+};
+use crate::mana::{
+    CastingManaCost, LoyaltyCost, LoyaltyValue, ManaCostParseError, RulesText, RulesTextSegment,
+};
+use facet::Facet;
+use std::collections::BTreeMap;
+
+#[cfg(feature = "proxy")]
+pub mod fetch;
+
+/// Ability words printed in italics at the start of a paragraph.
+///
+/// Ability words have no rules meaning, so oracle text doesn't mark them;
+/// the prefix before " — " is only treated as one if it's on this list.
+pub const ABILITY_WORDS: &[&str] = &[
+    "adamant",
+    "addendum",
+    "alliance",
+    "battalion",
+    "bloodrush",
+    "celebration",
+    "channel",
+    "chroma",
+    "cohort",
+    "constellation",
+    "converge",
+    "corrupted",
+    "council's dilemma",
+    "coven",
+    "delirium",
+    "descend 4",
+    "descend 8",
+    "domain",
+    "eerie",
+    "eminence",
+    "enrage",
+    "fateful hour",
+    "fathomless descent",
+    "ferocious",
+    "flurry",
+    "formidable",
+    "grandeur",
+    "hellbent",
+    "heroic",
+    "imprint",
+    "inspired",
+    "join forces",
+    "kinship",
+    "landfall",
+    "lieutenant",
+    "magecraft",
+    "metalcraft",
+    "morbid",
+    "pack tactics",
+    "paradox",
+    "parley",
+    "radiance",
+    "raid",
+    "rally",
+    "revolt",
+    "spell mastery",
+    "strive",
+    "survival",
+    "sweep",
+    "tempting offer",
+    "threshold",
+    "undergrowth",
+    "valiant",
+    "will of the council",
+];
+
+/// Problems converting a Scryfall card into the crate's card model
+#[derive(Debug, thiserror::Error)]
+pub enum ImportError {
+    #[error("Scryfall card is missing {0}")]
+    MissingField(&'static str),
+    #[error("unknown rarity: {0}")]
+    UnknownRarity(String),
+    #[error("invalid mana cost {cost:?}: {source}")]
+    InvalidManaCost {
+        cost: String,
+        source: ManaCostParseError,
+    },
+    #[error("invalid oracle text: {0}")]
+    InvalidOracleText(#[from] ManaCostParseError),
+    #[error("invalid {field}: {message}")]
+    InvalidValue {
+        field: &'static str,
+        message: String,
+    },
+    #[error("layout {layout:?} expects {expected} card faces, found {found}")]
+    FaceCount {
+        layout: String,
+        expected: usize,
+        found: usize,
+    },
+}
+
+/// One face of a multi-face Scryfall card
+#[derive(Facet, Debug, Clone)]
+pub struct ScryfallFace {
+    pub name: String,
+    #[facet(default)]
+    pub mana_cost: Option<String>,
+    #[facet(default)]
+    pub type_line: Option<String>,
+    #[facet(default)]
+    pub oracle_text: Option<String>,
+    #[facet(default)]
+    pub flavor_text: Option<String>,
+    #[facet(default)]
+    pub power: Option<String>,
+    #[facet(default)]
+    pub toughness: Option<String>,
+    #[facet(default)]
+    pub loyalty: Option<String>,
+    #[facet(default)]
+    pub defense: Option<String>,
+    /// Single-letter color codes (`W`, `U`, `B`, `R`, `G`)
+    #[facet(default)]
+    pub color_indicator: Option<Vec<String>>,
+    #[facet(default)]
+    pub artist: Option<String>,
+}
+
+/// The subset of a Scryfall card object the importer uses
+#[derive(Facet, Debug, Clone)]
+pub struct ScryfallCard {
+    pub name: String,
+    pub layout: String,
+    pub rarity: String,
+    #[facet(default)]
+    pub mana_cost: Option<String>,
+    #[facet(default)]
+    pub type_line: Option<String>,
+    #[facet(default)]
+    pub oracle_text: Option<String>,
+    #[facet(default)]
+    pub flavor_text: Option<String>,
+    #[facet(default)]
+    pub power: Option<String>,
+    #[facet(default)]
+    pub toughness: Option<String>,
+    #[facet(default)]
+    pub loyalty: Option<String>,
+    #[facet(default)]
+    pub defense: Option<String>,
+    #[facet(default)]
+    pub collector_number: Option<String>,
+    /// Set code, lowercase, e.g. `dom`
+    #[facet(default)]
+    pub set: Option<String>,
+    #[facet(default)]
+    pub card_faces: Option<Vec<ScryfallFace>>,
+    /// Frame effects, e.g. `compasslanddfc` for the icons of a transform card
+    #[facet(default)]
+    pub frame_effects: Vec<String>,
+    /// The illustrator, for cards with one face; faces credit their own
+    #[facet(default)]
+    pub artist: Option<String>,
+}
+
+impl ScryfallCard {
+    /// Parse a Scryfall card object from its JSON representation.
+    ///
+    /// Fields the importer doesn't use are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the JSON is malformed or lacks required fields.
+    #[must_use = "parsing returns a Result that should be handled"]
+    pub fn from_json(json: &str) -> anyhow::Result<Self> {
+        facet_json::from_str(json).map_err(|e| anyhow::anyhow!("{}", e))
+    }
+
+    /// The set code as printed on cards, e.g. `DOM`
+    fn set_code(&self) -> Option<String> {
+        self.set.as_ref().map(|set| set.to_uppercase())
+    }
+
+    /// The transform icons named by the card's frame effects
+    fn dfc_icons(&self) -> Option<DfcIcons> {
+        self.frame_effects
+            .iter()
+            .find_map(|effect| match effect.as_str() {
+                "sunmoondfc" => Some(DfcIcons::SunMoon),
+                "compasslanddfc" => Some(DfcIcons::CompassLand),
+                "originpwdfc" => Some(DfcIcons::Spark),
+                "mooneldrazidfc" => Some(DfcIcons::MoonEldrazi),
+                _ => None,
+            })
+    }
+
+    fn faces(&self) -> &[ScryfallFace] {
+        self.card_faces.as_deref().unwrap_or_default()
+    }
+
+    fn expect_faces(&self, expected: usize) -> Result<&[ScryfallFace], ImportError> {
+        let faces = self.faces();
+        if faces.len() == expected {
+            Ok(faces)
+        } else {
+            Err(ImportError::FaceCount {
+                layout: self.layout.clone(),
+                expected,
+                found: faces.len(),
+            })
+        }
+    }
+
+    /// Convert this Scryfall card into a [`Card`].
+    ///
+    /// Layouts without a dedicated conversion (class, leveler, prototype,
+    /// meld, ...) are imported as normal cards carrying the full oracle text.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a cost, oracle text or stat can't be parsed, or
+    /// if the number of faces doesn't match the layout.
+    pub fn to_card(&self) -> Result<Card, ImportError> {
+        match self.layout.as_str() {
+            "transform" => Ok(Card::Transform(TransformCard {
+                base: self.multi_face_base()?,
+                faces: self.card_faces_of(2)?,
+                dfc_icons: self.dfc_icons(),
+            })),
+            "modal_dfc" => Ok(Card::ModalDfc(ModalDfcCard {
+                base: self.multi_face_base()?,
+                faces: self.card_faces_of(2)?,
+            })),
+            "split" => Ok(Card::Split(SplitCard {
+                base: self.multi_face_base()?,
+                faces: self.card_faces_of(2)?,
+                fuse: None,
+                aftermath: None,
+            })),
+            "flip" => Ok(Card::Flip(FlipCard {
+                base: self.multi_face_base()?,
+                faces: self.card_faces_of(2)?,
+            })),
+            "adventure" => self.to_adventure(),
+            "saga" => self.to_saga(),
+            "battle" => self.to_battle(),
+            _ if self
+                .type_line
+                .as_deref()
+                .is_some_and(|t| t.contains("Planeswalker")) =>
+            {
+                self.to_planeswalker()
+            }
+            _ => Ok(Card::Normal(NormalCard { base: self.base()? })),
+        }
+    }
+
+    fn rarity(&self) -> Result<Rarity, ImportError> {
+        match self.rarity.as_str() {
+            "common" => Ok(Rarity::Common),
+            "uncommon" => Ok(Rarity::Uncommon),
+            "rare" => Ok(Rarity::Rare),
+            "mythic" => Ok(Rarity::Mythic),
+            "special" => Ok(Rarity::Special),
+            "bonus" => Ok(Rarity::Bonus),
+            other => Err(ImportError::UnknownRarity(other.to_string())),
+        }
+    }
+
+    /// Card base built from the top-level fields.
+    fn base(&self) -> Result<CardBase, ImportError> {
+        Ok(CardBase {
+            name: self.name.clone(),
+            mana_cost: parse_mana_cost(self.mana_cost.as_deref())?,
+            type_line: self
+                .type_line
+                .clone()
+                .ok_or(ImportError::MissingField("type_line"))?,
+            rules_text: parse_oracle_text(self.oracle_text.as_deref())?,
+            flavor_text: self.flavor_text.clone(),
+            power: self.power.clone(),
+            toughness: self.toughness.clone(),
+            rarity: self.rarity()?,
+            collector_number: self.collector_number.clone(),
+            reminder_text: None,
+            translations: BTreeMap::new(),
+            art: None,
+            art_fit: None,
+            artist: self.artist.clone(),
+            size: None,
+            frame_effects: Vec::new(),
+            set_code: self.set_code(),
+            set_symbol: None,
+            frame_colors: None,
+        })
+    }
+
+    /// Card base for layouts whose text lives on the faces.
+    fn multi_face_base(&self) -> Result<CardBase, ImportError> {
+        let front = self
+            .faces()
+            .first()
+            .ok_or(ImportError::MissingField("card_faces"))?;
+        Ok(CardBase {
+            name: self.name.clone(),
+            mana_cost: None,
+            type_line: front
+                .type_line
+                .clone()
+                .or_else(|| self.type_line.clone())
+                .ok_or(ImportError::MissingField("type_line"))?,
+            rules_text: None,
+            flavor_text: None,
+            power: None,
+            toughness: None,
+            rarity: self.rarity()?,
+            collector_number: self.collector_number.clone(),
+            reminder_text: None,
+            translations: BTreeMap::new(),
+            art: None,
+            art_fit: None,
+            artist: self.artist.clone(),
+            size: None,
+            frame_effects: Vec::new(),
+            set_code: self.set_code(),
+            set_symbol: None,
+            frame_colors: None,
+        })
+    }
+
+    fn card_faces_of(&self, expected: usize) -> Result<Vec<CardFace>, ImportError> {
+        self.expect_faces(expected)?
+            .iter()
+            .map(ScryfallFace::to_card_face)
+            .collect()
+    }
+
+    fn to_adventure(&self) -> Result<Card, ImportError> {
+        let [creature, adventure] = self.expect_faces(2)? else {
+            unreachable!("expect_faces checked the face count");
+        };
+        let base = CardBase {
+            name: creature.name.clone(),
+            mana_cost: parse_mana_cost(creature.mana_cost.as_deref())?,
+            type_line: creature
+                .type_line
+                .clone()
+                .ok_or(ImportError::MissingField("type_line"))?,
+            rules_text: parse_oracle_text(creature.oracle_text.as_deref())?,
+            flavor_text: creature
+                .flavor_text
+                .clone()
+                .or_else(|| self.flavor_text.clone()),
+            power: creature.power.clone().or_else(|| self.power.clone()),
+            toughness: creature
+                .toughness
+                .clone()
+                .or_else(|| self.toughness.clone()),
+            rarity: self.rarity()?,
+            collector_number: self.collector_number.clone(),
+            reminder_text: None,
+            translations: BTreeMap::new(),
+            art: None,
+            art_fit: None,
+            artist: self.artist.clone(),
+            size: None,
+            frame_effects: Vec::new(),
+            set_code: self.set_code(),
+            set_symbol: None,
+            frame_colors: None,
+        };
+        Ok(Card::Adventure(AdventureCard {
+            base,
+            adventure: AdventureSpell {
+                name: adventure.name.clone(),
+                mana_cost: parse_mana_cost(adventure.mana_cost.as_deref())?
+                    .ok_or(ImportError::MissingField("adventure mana_cost"))?,
+                type_line: adventure
+                    .type_line
+                    .clone()
+                    .ok_or(ImportError::MissingField("adventure type_line"))?,
+                rules_text: parse_oracle_text(adventure.oracle_text.as_deref())?
+                    .unwrap_or_default(),
+                flavor_text: adventure.flavor_text.clone(),
+                artist: adventure.artist.clone(),
+            },
+        }))
+    }
+
+    fn to_planeswalker(&self) -> Result<Card, ImportError> {
+        let mut base = self.base()?;
+        let loyalty = self
+            .loyalty
+            .as_deref()
+            .ok_or(ImportError::MissingField("loyalty"))?;
+        let loyalty =
+            LoyaltyValue::parse(loyalty).map_err(|message| ImportError::InvalidValue {
+                field: "loyalty",
+                message,
+            })?;
+
+        // Loyalty abilities are their own paragraphs; anything else is static text
+        let mut static_text = Vec::new();
+        let mut loyalty_abilities = Vec::new();
+        for paragraph in self.oracle_text.as_deref().unwrap_or_default().lines() {
+            match split_loyalty_ability(paragraph) {
+                Some((cost, text)) => loyalty_abilities.push(LoyaltyAbility {
+                    cost,
+                    text: oracle_text_to_rules_text(text)?,
+                }),
+                None => static_text.push(paragraph),
+            }
+        }
+        base.rules_text = parse_oracle_text(Some(&static_text.join("\n")))?;
+
+        Ok(Card::Planeswalker(PlaneswalkerCard {
+            base,
+            loyalty,
+            loyalty_abilities,
+        }))
+    }
+
+    fn to_saga(&self) -> Result<Card, ImportError> {
+        let mut base = self.base()?;
+        let mut chapters = Vec::new();
+        let mut read_ahead = false;
+        let mut other_text = Vec::new();
+
+        for paragraph in self.oracle_text.as_deref().unwrap_or_default().lines() {
+            if let Some((numbers, text)) = split_saga_chapter(paragraph) {
+                chapters.push(SagaChapter {
+                    chapters: numbers,
+                    text: oracle_text_to_rules_text(text)?,
+                });
+            } else if paragraph.starts_with("(As this Saga enters") {
+                // The renderer generates the standard saga reminder itself
+            } else if paragraph.starts_with("Read ahead") {
+                read_ahead = true;
+            } else {
+                other_text.push(paragraph);
+            }
+        }
+        base.rules_text = parse_oracle_text(Some(&other_text.join("\n")))?;
+
+        Ok(Card::Saga(SagaCard {
+            base,
+            chapters,
+            read_ahead: read_ahead.then_some(true),
+        }))
+    }
+
+    fn to_battle(&self) -> Result<Card, ImportError> {
+        let [front, back] = self.expect_faces(2)? else {
+            unreachable!("expect_faces checked the face count");
+        };
+        let defense = front
+            .defense
+            .as_deref()
+            .or(self.defense.as_deref())
+            .ok_or(ImportError::MissingField("defense"))?;
+        let defense = defense
+            .parse::<u32>()
+            .map_err(|e| ImportError::InvalidValue {
+                field: "defense",
+                message: e.to_string(),
+            })?;
+
+        let base = CardBase {
+            name: front.name.clone(),
+            mana_cost: parse_mana_cost(front.mana_cost.as_deref())?,
+            type_line: front
+                .type_line
+                .clone()
+                .ok_or(ImportError::MissingField("type_line"))?,
+            rules_text: parse_oracle_text(front.oracle_text.as_deref())?,
+            flavor_text: front.flavor_text.clone(),
+            power: None,
+            toughness: None,
+            rarity: self.rarity()?,
+            collector_number: self.collector_number.clone(),
+            reminder_text: None,
+            translations: BTreeMap::new(),
+            art: None,
+            art_fit: None,
+            artist: self.artist.clone(),
+            size: None,
+            frame_effects: Vec::new(),
+            set_code: self.set_code(),
+            set_symbol: None,
+            frame_colors: None,
+        };
+
+        Ok(Card::Battle(BattleCard {
+            base,
+            defense,
+            backside_name: back.name.clone(),
+            backside_type_line: back
+                .type_line
+                .clone()
+                .ok_or(ImportError::MissingField("backside type_line"))?,
+            backside_rules_text: parse_oracle_text(back.oracle_text.as_deref())?
+                .unwrap_or_default(),
+        }))
+    }
+}
+
+impl ScryfallFace {
+    fn to_card_face(&self) -> Result<CardFace, ImportError> {
+        Ok(CardFace {
+            name: Some(self.name.clone()),
+            mana_cost: parse_mana_cost(self.mana_cost.as_deref())?,
+            type_line: self.type_line.clone(),
+            rules_text: parse_oracle_text(self.oracle_text.as_deref())?,
+            flavor_text: self.flavor_text.clone(),
+            power: self.power.clone(),
+            toughness: self.toughness.clone(),
+            color_indicator: self
+                .color_indicator
+                .as_ref()
+                .map(|colors| colors.iter().map(|c| color_name(c).to_string()).collect()),
+            art: None,
+            artist: self.artist.clone(),
+        })
+    }
+}
+
+/// Map a Scryfall color letter to the color name used in card YAML.
+fn color_name(letter: &str) -> &str {
+    match letter {
+        "W" => "white",
+        "U" => "blue",
+        "B" => "black",
+        "R" => "red",
+        "G" => "green",
+        other => other,
+    }
+}
+
+fn parse_mana_cost(cost: Option<&str>) -> Result<Option<CastingManaCost>, ImportError> {
+    match cost {
+        None | Some("") => Ok(None),
+        Some(cost) => {
+            CastingManaCost::parse(cost)
+                .map(Some)
+                .map_err(|source| ImportError::InvalidManaCost {
+                    cost: cost.to_string(),
+                    source,
+                })
+        }
+    }
+}
+
+fn parse_oracle_text(text: Option<&str>) -> Result<Option<RulesText>, ImportError> {
+    match text {
+        None | Some("") => Ok(None),
+        Some(text) => Ok(Some(oracle_text_to_rules_text(text)?)),
+    }
+}
+
+/// Split a planeswalker paragraph like "+1: Draw a card." into its cost and text.
+///
+/// Scryfall writes minus costs with U+2212 MINUS SIGN, which is normalized
+/// to an ASCII hyphen before parsing.
+fn split_loyalty_ability(paragraph: &str) -> Option<(LoyaltyCost, &str)> {
+    let (cost, text) = paragraph.split_once(": ")?;
+    let cost = cost.replace('\u{2212}', "-");
+    let is_cost = !cost.is_empty()
+        && cost
+            .chars()
+            .all(|c| c.is_ascii_digit() || matches!(c, '+' | '-' | 'X'));
+    if !is_cost {
+        return None;
+    }
+    LoyaltyCost::parse(&cost).ok().map(|cost| (cost, text))
+}
+
+/// Split a saga paragraph like "II, III — Draw a card." into chapter numbers and text.
+fn split_saga_chapter(paragraph: &str) -> Option<(Vec<u32>, &str)> {
+    let (numerals, text) = paragraph.split_once(" — ")?;
+    let chapters = numerals
+        .split(", ")
+        .map(from_roman_numeral)
+        .collect::<Option<Vec<_>>>()?;
+    Some((chapters, text))
+}
+
+fn from_roman_numeral(numeral: &str) -> Option<u32> {
+    let values = numeral
+        .chars()
+        .map(|c| match c {
+            'I' => Some(1),
+            'V' => Some(5),
+            'X' => Some(10),
+            _ => None,
+        })
+        .collect::<Option<Vec<i64>>>()?;
+
+    // A numeral smaller than the one after it is subtracted (IV, IX)
+    let total: i64 = values
+        .iter()
+        .enumerate()
+        .map(|(i, &v)| match values.get(i + 1) {
+            Some(&next) if next > v => -v,
+            _ => v,
+        })
+        .sum();
+    u32::try_from(total).ok().filter(|&n| n > 0)
+}
+
+/// Convert Scryfall oracle text into structured rules text.
+///
+/// - Newlines become [`RulesTextSegment::ParagraphBreak`]
+/// - Parenthesized text becomes [`RulesTextSegment::Reminder`]
+/// - A known ability word followed by " — " at the start of a paragraph
+///   becomes [`RulesTextSegment::AbilityWord`]
+/// - Symbols like `{T}` and `{2}{G}` are parsed as usual
+///
+/// # Examples
+///
+/// ```
+/// use mtg_gen::{RulesTextSegment, scryfall::oracle_text_to_rules_text};
+///
+/// let rules = oracle_text_to_rules_text(
+///     "Flying\nLandfall — Whenever a land you control enters, scry 1. (Look at the top card of your library.)",
+/// )
+/// .unwrap();
+/// assert!(matches!(rules.segments[1], RulesTextSegment::ParagraphBreak));
+/// assert!(matches!(&rules.segments[2], RulesTextSegment::AbilityWord(w) if w == "Landfall"));
+/// assert!(matches!(rules.segments.last(), Some(RulesTextSegment::Reminder(_))));
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if a symbol inside braces cannot be parsed.
+#[must_use = "parsing returns a Result that should be handled"]
+pub fn oracle_text_to_rules_text(oracle: &str) -> Result<RulesText, ManaCostParseError> {
+    let mut segments = Vec::new();
+
+    for (i, paragraph) in oracle.lines().enumerate() {
+        if i > 0 {
+            segments.push(RulesTextSegment::ParagraphBreak);
+        }
+
+        let mut rest = paragraph;
+        if let Some((word, _)) = paragraph.split_once(" — ")
+            && ABILITY_WORDS.contains(&word.to_lowercase().as_str())
+        {
+            segments.push(RulesTextSegment::AbilityWord(word.to_string()));
+            rest = &paragraph[word.len()..];
+        }
+
+        segments.extend(RulesText::parse(rest)?.segments);
+    }
+
+    Ok(RulesText { segments })
+}
@@ -0,0 +1,59 @@
+//! Fetch card data from the Scryfall API
+//!
+//! Used by `mtg-gen proxy` to look up the cards of a decklist. Only card
+//! data is fetched, never images: the cards are rendered in this crate's
+//! frames.
+
+use super::ScryfallCard;
+use crate::decklist::DecklistEntry;
+use std::time::{Duration, Instant};
+
+/// Scryfall asks clients to wait 50-100 ms between requests
+const REQUEST_INTERVAL: Duration = Duration::from_millis(100);
+
+/// A Scryfall API client that spaces out its requests
+pub struct ScryfallClient {
+    client: reqwest::Client,
+    last_request: Option<Instant>,
+}
+
+impl Default for ScryfallClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ScryfallClient {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            last_request: None,
+        }
+    }
+
+    /// Fetch the card a decklist line names.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails, Scryfall doesn't know the card,
+    /// or the response can't be parsed.
+    pub async fn fetch(&mut self, entry: &DecklistEntry) -> anyhow::Result<ScryfallCard> {
+        if let Some(last) = self.last_request {
+            tokio::time::sleep(REQUEST_INTERVAL.saturating_sub(last.elapsed())).await;
+        }
+        self.last_request = Some(Instant::now());
+
+        let body = self
+            .client
+            .get(entry.scryfall_url())
+            .header("User-Agent", concat!("mtg-gen/", env!("CARGO_PKG_VERSION")))
+            .header("Accept", "application/json")
+            .send()
+            .await?
+            .error_for_status()?
+            .text()
+            .await?;
+        ScryfallCard::from_json(&body)
+    }
+}
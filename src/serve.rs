@@ -0,0 +1,350 @@
+//! A preview server for reviewing a set in a browser (`mtg-gen serve`)
+//!
+//! The server renders cards on demand: `GET /card/<path>.png` renders the
+//! card file `<path>.yaml` under the set directory and answers with the
+//! PNG, so collaborators only need a browser, not Chrome. `GET /` is a
+//! gallery of every card in the set. It polls `GET /version`, which changes
+//! whenever a card file is added, removed or edited, and reloads itself, so
+//! the gallery stays live while the set is being worked on.
+//!
+//! The server speaks just enough HTTP/1.1 for browsers: `GET` requests,
+//! answered one per connection.
+
+use crate::card::Card;
+use crate::render::Renderer;
+use crate::set_defaults::SetDefaults;
+use crate::yaml::find_card_files;
+use anyhow::{Context, Result};
+use maud::{DOCTYPE, Markup, PreEscaped, html};
+use std::path::{Component, Path, PathBuf};
+use std::time::UNIX_EPOCH;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use walkdir::WalkDir;
+
+/// What a request asks for
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Route {
+    /// The gallery page
+    Gallery,
+    /// The set's current version, see [`set_version`]
+    Version,
+    /// A card image, by the path of its YAML file relative to the set
+    /// without the extension
+    Card(PathBuf),
+    /// Anything else
+    NotFound,
+}
+
+impl Route {
+    /// Returns what a request for `target` with `method` asks for. Card
+    /// paths are percent-decoded, and paths that would leave the set
+    /// directory aren't found.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mtg_gen::serve::Route;
+    /// use std::path::PathBuf;
+    ///
+    /// assert_eq!(Route::parse("GET", "/"), Route::Gallery);
+    /// assert_eq!(
+    ///     Route::parse("GET", "/card/white/day%20of%20judgment.png?v=3"),
+    ///     Route::Card(PathBuf::from("white/day of judgment"))
+    /// );
+    /// assert_eq!(Route::parse("GET", "/card/../secrets.png"), Route::NotFound);
+    /// ```
+    #[must_use]
+    pub fn parse(method: &str, target: &str) -> Self {
+        if method != "GET" {
+            return Route::NotFound;
+        }
+        let path = target.split(['?', '#']).next().unwrap_or_default();
+        match path {
+            "/" | "/index.html" => Route::Gallery,
+            "/version" => Route::Version,
+            _ => path
+                .strip_prefix("/card/")
+                .and_then(|card| card.strip_suffix(".png"))
+                .and_then(percent_decode)
+                .map(PathBuf::from)
+                .filter(|card| {
+                    card.components()
+                        .all(|component| matches!(component, Component::Normal(_)))
+                })
+                .map_or(Route::NotFound, Route::Card),
+        }
+    }
+}
+
+/// Decode `%XX` escapes, or `None` when that isn't valid UTF-8.
+fn percent_decode(s: &str) -> Option<String> {
+    let bytes = s.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = (bytes[i] == b'%')
+            .then(|| s.get(i + 1..i + 3))
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8(decoded).ok()
+}
+
+/// Percent-encode a relative card path for a URL, keeping its slashes.
+fn percent_encode(path: &str) -> String {
+    let mut encoded = String::with_capacity(path.len());
+    for byte in path.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b'/' => {
+                encoded.push(byte as char);
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+/// Returns the card files in `dir`, relative to it and without their
+/// extension, in the order the gallery shows them. YAML files that aren't
+/// cards, like `set.yaml` and style files, are left out, see
+/// [`find_card_files`].
+///
+/// # Errors
+///
+/// Returns an error if the directory can't be read.
+pub fn card_paths(dir: &Path) -> Result<Vec<PathBuf>> {
+    find_card_files(dir)?
+        .cards
+        .into_iter()
+        .map(|file| Ok(file.strip_prefix(dir)?.with_extension("")))
+        .collect()
+}
+
+/// Returns a version of the set in `dir` that changes whenever a card file
+/// is added, removed or edited.
+#[must_use]
+pub fn set_version(dir: &Path) -> String {
+    let mut count = 0u64;
+    let mut latest = 0u128;
+    for entry in WalkDir::new(dir).into_iter().filter_map(Result::ok) {
+        let modified = entry
+            .metadata()
+            .ok()
+            .and_then(|metadata| metadata.modified().ok())
+            .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+            .map_or(0, |time| time.as_nanos());
+        count += 1;
+        latest = latest.max(modified);
+    }
+    format!("{}-{}", count, latest)
+}
+
+/// Returns the card file for `path`, a route's card path, with whichever
+/// of the YAML extensions it has.
+fn card_file(dir: &Path, path: &Path) -> Option<PathBuf> {
+    ["yaml", "yml"]
+        .into_iter()
+        .map(|ext| dir.join(path).with_extension(ext))
+        .find(|file| file.is_file())
+}
+
+/// Read, validate and resolve the art of the card in `file`, filling in
+/// what it leaves out from the set's `set.yaml`. The set file is read for
+/// every card, so edits to it show up on the next reload.
+fn load_card(dir: &Path, file: &Path) -> Result<Card> {
+    let yaml =
+        std::fs::read_to_string(file).with_context(|| format!("Failed to read {:?}", file))?;
+    let mut card: Card = facet_yaml::from_str(&yaml).map_err(|e| anyhow::anyhow!("{}", e))?;
+    if let Some(set) = SetDefaults::load(dir)? {
+        set.apply(&mut card);
+    }
+    card.validate()?;
+    let card_dir = file
+        .parent()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    card.resolve_art_paths(&card_dir.canonicalize()?);
+    Ok(card)
+}
+
+/// One card in the gallery: its path as in [`Route::Card`], and its name,
+/// or why it couldn't be read
+pub struct GalleryCard {
+    pub path: PathBuf,
+    pub name: Result<String, String>,
+}
+
+/// Render the gallery page. Each card's image URL carries `version`, so the
+/// browser fetches a fresh render after every change, and the page reloads
+/// itself once `/version` no longer returns it.
+#[must_use]
+pub fn gallery_html(title: &str, cards: &[GalleryCard], version: &str) -> Markup {
+    html! {
+        (DOCTYPE)
+        html {
+            head {
+                meta charset="utf-8";
+                title { (title) }
+                style { (PreEscaped(GALLERY_CSS)) }
+            }
+            body {
+                h1 { (title) " " span.count { "(" (cards.len()) " cards)" } }
+                div.gallery {
+                    @for card in cards {
+                        @let path = card.path.display().to_string();
+                        figure {
+                            @match &card.name {
+                                Ok(name) => {
+                                    img loading="lazy" alt=(name)
+                                        src=(format!("/card/{}.png?v={}", percent_encode(&path), version));
+                                    figcaption { (name) }
+                                }
+                                Err(error) => {
+                                    div.error { (error) }
+                                    figcaption { (path) }
+                                }
+                            }
+                        }
+                    }
+                }
+                script {
+                    (PreEscaped(format!(
+                        "const version = {:?};\n{}",
+                        version, RELOAD_SCRIPT
+                    )))
+                }
+            }
+        }
+    }
+}
+
+/// Reload the page when the set's version changes
+const RELOAD_SCRIPT: &str = r#"
+setInterval(async () => {
+    try {
+        const current = await (await fetch("/version", { cache: "no-store" })).text();
+        if (current !== version) location.reload();
+    } catch (e) {}
+}, 1000);
+"#;
+
+const GALLERY_CSS: &str = r#"
+    body { margin: 24px; background: #222; color: #eee; font-family: sans-serif; }
+    h1 { font-weight: normal; }
+    .count { color: #999; font-size: 0.6em; }
+    .gallery { display: grid; grid-template-columns: repeat(auto-fill, minmax(250px, 1fr)); gap: 20px; }
+    figure { margin: 0; }
+    img { width: 100%; aspect-ratio: 744 / 1040; border-radius: 4.7%; background: #333; }
+    .error { aspect-ratio: 744 / 1040; padding: 12px; box-sizing: border-box; background: #4a1c1c; color: #fbb; font-family: monospace; white-space: pre-wrap; overflow: auto; }
+    figcaption { margin-top: 6px; text-align: center; color: #bbb; }
+"#;
+
+/// Serve previews of the set in `dir` on `listener` until the process is
+/// stopped, rendering with `renderer`. Each connection is handled in a task
+/// of its own, so several cards render at once, up to the renderer's
+/// page limit.
+///
+/// # Errors
+///
+/// Returns an error if accepting a connection fails.
+pub async fn serve(listener: TcpListener, dir: PathBuf, renderer: Renderer) -> Result<()> {
+    loop {
+        let (stream, _) = listener.accept().await?;
+        let dir = dir.clone();
+        let renderer = renderer.clone();
+        tokio::spawn(async move {
+            if let Err(e) = handle(stream, &dir, &renderer).await {
+                eprintln!("Error serving a request: {}", e);
+            }
+        });
+    }
+}
+
+/// Answer one request on `stream`.
+async fn handle(stream: TcpStream, dir: &Path, renderer: &Renderer) -> Result<()> {
+    let mut stream = BufReader::new(stream);
+    let mut request_line = String::new();
+    stream.read_line(&mut request_line).await?;
+    // The headers don't change the answer, so they're skipped
+    let mut header = String::new();
+    while stream.read_line(&mut header).await? > 2 {
+        header.clear();
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let route = Route::parse(
+        parts.next().unwrap_or_default(),
+        parts.next().unwrap_or_default(),
+    );
+    let (status, content_type, body) = match route {
+        Route::Gallery => {
+            let cards = card_paths(dir)?
+                .into_iter()
+                .map(|path| {
+                    let name = card_file(dir, &path)
+                        .ok_or_else(|| "Card file is gone".to_string())
+                        .and_then(|file| load_card(dir, &file).map_err(|e| e.to_string()))
+                        .map(|card| card.name().to_string());
+                    GalleryCard { path, name }
+                })
+                .collect::<Vec<_>>();
+            let title = dir
+                .canonicalize()
+                .ok()
+                .and_then(|dir| {
+                    dir.file_name()
+                        .map(|name| name.to_string_lossy().into_owned())
+                })
+                .unwrap_or_else(|| "Cards".to_string());
+            let html = gallery_html(&title, &cards, &set_version(dir)).into_string();
+            ("200 OK", "text/html; charset=utf-8", html.into_bytes())
+        }
+        Route::Version => ("200 OK", "text/plain", set_version(dir).into_bytes()),
+        Route::Card(path) => match card_file(dir, &path) {
+            Some(file) => {
+                let png = async { renderer.render_card_png(&load_card(dir, &file)?).await }.await;
+                match png {
+                    Ok(png) => {
+                        for warning in &png.warnings {
+                            eprintln!("Warning: {:?}: {}", file, warning);
+                        }
+                        ("200 OK", "image/png", png.bytes)
+                    }
+                    Err(e) => {
+                        eprintln!("Error rendering {:?}: {}", file, e);
+                        (
+                            "500 Internal Server Error",
+                            "text/plain",
+                            e.to_string().into_bytes(),
+                        )
+                    }
+                }
+            }
+            None => ("404 Not Found", "text/plain", b"No such card".to_vec()),
+        },
+        Route::NotFound => ("404 Not Found", "text/plain", b"Not found".to_vec()),
+    };
+
+    let head = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nCache-Control: no-store\r\nConnection: close\r\n\r\n",
+        status,
+        content_type,
+        body.len()
+    );
+    let stream = stream.get_mut();
+    stream.write_all(head.as_bytes()).await?;
+    stream.write_all(&body).await?;
+    stream.shutdown().await?;
+    Ok(())
+}
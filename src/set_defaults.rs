@@ -0,0 +1,172 @@
+//! Shared defaults for a set's cards (`set.yaml`)
+//!
+//! A `set.yaml` (or `set.yml`) at the root of the input directory holds
+//! what every card of a set would otherwise repeat. Each card inherits the
+//! fields it leaves out and keeps the ones it gives, so a card can still
+//! override its set.
+//!
+//! ```yaml
+//! set_code: DSK
+//! set_symbol: symbols/dsk.svg
+//! artist: Cramt
+//! copyright: "™ & © {year} Cramt Games"
+//! frame_effects: [worn]
+//! frame_colors: identity
+//! numbering: canonical
+//! ```
+//!
+//! `copyright` is a legal line template, see [`crate::legal`], used when the
+//! style file has no `legal.template` of its own. `numbering: canonical`
+//! numbers the set's cards in canonical order on every render, as
+//! `--assign-collector-numbers` does, and `numbering: auto` as
+//! `--auto-number` does.
+
+use crate::card::{Card, FrameColors, FrameEffect};
+use crate::style::StyleConfig;
+use facet::Facet;
+use std::path::{Path, PathBuf};
+
+/// Names the set file may have, in the order they're looked for
+pub const FILE_NAMES: [&str; 2] = ["set.yaml", "set.yml"];
+
+/// Returns the directory the set file of `input` is looked for in: the
+/// input directory, or the directory of an input file.
+#[must_use]
+pub fn set_dir(input: &Path) -> &Path {
+    if input.is_file() {
+        input
+            .parent()
+            .filter(|dir| !dir.as_os_str().is_empty())
+            .unwrap_or(Path::new("."))
+    } else {
+        input
+    }
+}
+
+/// How a set's collector numbers are managed
+#[derive(Facet, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Numbering {
+    /// Each card's `collector_number` is written by hand
+    #[default]
+    #[facet(rename = "manual")]
+    Manual,
+    /// Cards are numbered in canonical order, see [`crate::collector`]
+    #[facet(rename = "canonical")]
+    Canonical,
+    /// Cards are numbered in canonical order on every render without
+    /// writing the numbers down, see [`crate::collector::auto_numbers`]
+    #[facet(rename = "auto")]
+    Auto,
+}
+
+/// The fields a set's cards inherit
+#[derive(Facet, Debug, Clone, Default, PartialEq)]
+pub struct SetDefaults {
+    #[facet(default)]
+    pub set_code: Option<String>,
+    /// Image of the set symbol, relative to the set file
+    #[facet(default)]
+    pub set_symbol: Option<String>,
+    /// Who illustrated the cards, for cards that don't credit anyone
+    #[facet(default)]
+    pub artist: Option<String>,
+    /// Legal line template, e.g. `™ & © {year} {studio}`
+    #[facet(default)]
+    pub copyright: Option<String>,
+    /// Aging effects of cards without any of their own
+    #[facet(default)]
+    pub frame_effects: Vec<FrameEffect>,
+    #[facet(default)]
+    pub frame_colors: Option<FrameColors>,
+    #[facet(default)]
+    pub numbering: Numbering,
+}
+
+impl SetDefaults {
+    /// Returns the set file in `dir`, if there is one.
+    #[must_use]
+    pub fn find(dir: &Path) -> Option<PathBuf> {
+        FILE_NAMES
+            .iter()
+            .map(|name| dir.join(name))
+            .find(|path| path.is_file())
+    }
+
+    /// Read the set file of `input`, a card file or directory, with its set
+    /// symbol resolved, or `None` if there isn't one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the set file can't be read or isn't valid.
+    pub fn load(input: &Path) -> anyhow::Result<Option<SetDefaults>> {
+        let dir = set_dir(input);
+        let Some(path) = Self::find(dir) else {
+            return Ok(None);
+        };
+        let content = std::fs::read_to_string(&path)
+            .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", path.display(), e))?;
+        let mut set: SetDefaults = facet_yaml::from_str(&content)
+            .map_err(|e| anyhow::anyhow!("Invalid {}: {}", path.display(), e))?;
+        // The set symbol is relative to the set file
+        set.resolve_paths(&dir.canonicalize()?);
+        Ok(Some(set))
+    }
+
+    /// Resolve a relative set symbol against `dir`, the set file's
+    /// directory.
+    pub fn resolve_paths(&mut self, dir: &Path) {
+        if let Some(symbol) = &mut self.set_symbol
+            && Path::new(symbol).is_relative()
+        {
+            *symbol = dir.join(&*symbol).display().to_string();
+        }
+    }
+
+    /// Fill in the fields `card` leaves out.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mtg_gen::set_defaults::SetDefaults;
+    /// use mtg_gen::Card;
+    ///
+    /// let defaults: SetDefaults = facet_yaml::from_str("set_code: DSK\nartist: Cramt\n").unwrap();
+    /// let mut card: Card = facet_yaml::from_str(
+    ///     "name: Bolt\ntype: normal\ntype_line: Instant\nrarity: common\nset_code: M10\n",
+    /// )
+    /// .unwrap();
+    /// defaults.apply(&mut card);
+    /// assert_eq!(card.base().set_code.as_deref(), Some("M10"));
+    /// assert_eq!(card.base().artist.as_deref(), Some("Cramt"));
+    /// ```
+    pub fn apply(&self, card: &mut Card) {
+        let base = card.base_mut();
+        if base.set_code.is_none() {
+            base.set_code.clone_from(&self.set_code);
+        }
+        if base.set_symbol.is_none() {
+            base.set_symbol.clone_from(&self.set_symbol);
+        }
+        if base.artist.is_none() {
+            base.artist.clone_from(&self.artist);
+        }
+        if base.frame_effects.is_empty() {
+            base.frame_effects.clone_from(&self.frame_effects);
+        }
+        if base.frame_colors.is_none() {
+            base.frame_colors = self.frame_colors;
+        }
+    }
+
+    /// Use `copyright` as the legal line template of `style`, unless the
+    /// style file gives one.
+    pub fn apply_to_style(&self, style: &mut StyleConfig) {
+        if let Some(copyright) = &self.copyright {
+            let legal = style.legal.get_or_insert_default();
+            if legal.template.is_none() {
+                legal.template = Some(copyright.clone());
+            }
+        }
+    }
+}
@@ -0,0 +1,248 @@
+//! Statistics over a set of cards
+
+use crate::card::{Card, Rarity};
+use crate::color_chips::{Color, card_colors};
+use std::fmt;
+
+/// Number of cards at each rarity.
+///
+/// Special, bonus and land-slot cards are counted in their own buckets
+/// rather than folded into the main rarities, so a set's main sheet
+/// distribution isn't skewed by masterpieces or basic lands.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RarityCounts {
+    counts: Vec<(Rarity, usize)>,
+}
+
+impl Default for RarityCounts {
+    fn default() -> Self {
+        Self {
+            counts: Rarity::ALL.iter().map(|&rarity| (rarity, 0)).collect(),
+        }
+    }
+}
+
+impl RarityCounts {
+    /// Count the rarities of `cards`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mtg_gen::{Card, Rarity, stats::RarityCounts};
+    ///
+    /// let cards: Vec<Card> = ["common", "common", "bonus"]
+    ///     .iter()
+    ///     .map(|rarity| {
+    ///         let yaml = format!("name: Bear\ntype: normal\ntype_line: Creature\nrarity: {rarity}\n");
+    ///         facet_yaml::from_str(&yaml).unwrap()
+    ///     })
+    ///     .collect();
+    ///
+    /// let counts = RarityCounts::from_cards(&cards);
+    /// assert_eq!(counts.get(Rarity::Common), 2);
+    /// assert_eq!(counts.get(Rarity::Bonus), 1);
+    /// assert_eq!(counts.main_sheet_total(), 2);
+    /// ```
+    #[must_use]
+    pub fn from_cards<'a>(cards: impl IntoIterator<Item = &'a Card>) -> Self {
+        let mut counts = Self::default();
+        for card in cards {
+            counts.add(card.rarity());
+        }
+        counts
+    }
+
+    /// Count one more card of `rarity`.
+    pub fn add(&mut self, rarity: Rarity) {
+        if let Some((_, count)) = self.counts.iter_mut().find(|(r, _)| *r == rarity) {
+            *count += 1;
+        }
+    }
+
+    /// Returns the number of cards of `rarity`.
+    #[must_use]
+    pub fn get(&self, rarity: Rarity) -> usize {
+        self.counts
+            .iter()
+            .find(|(r, _)| *r == rarity)
+            .map_or(0, |(_, count)| *count)
+    }
+
+    /// Returns the total number of cards counted.
+    #[must_use]
+    pub fn total(&self) -> usize {
+        self.counts.iter().map(|(_, count)| count).sum()
+    }
+
+    /// Returns the number of common, uncommon, rare and mythic cards.
+    #[must_use]
+    pub fn main_sheet_total(&self) -> usize {
+        self.counts
+            .iter()
+            .filter(|(rarity, _)| rarity.is_main_sheet())
+            .map(|(_, count)| count)
+            .sum()
+    }
+
+    /// Iterate over every rarity and its count, from most to least common.
+    pub fn iter(&self) -> impl Iterator<Item = (Rarity, usize)> + '_ {
+        self.counts.iter().copied()
+    }
+}
+
+impl fmt::Display for RarityCounts {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Cards: {}", self.total())?;
+        for (rarity, count) in self.iter().filter(|(_, count)| *count > 0) {
+            writeln!(f, "  {}: {}", rarity, count)?;
+        }
+        Ok(())
+    }
+}
+
+/// The ten two-color pairs, in the order set files usually list them:
+/// allied pairs around the color wheel, then enemy pairs
+pub const COLOR_PAIRS: [[Color; 2]; 10] = [
+    [Color::White, Color::Blue],
+    [Color::Blue, Color::Black],
+    [Color::Black, Color::Red],
+    [Color::Red, Color::Green],
+    [Color::Green, Color::White],
+    [Color::White, Color::Black],
+    [Color::Blue, Color::Red],
+    [Color::Black, Color::Green],
+    [Color::Red, Color::White],
+    [Color::Green, Color::Blue],
+];
+
+/// The cards of one two-color draft archetype
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Archetype {
+    pub colors: [Color; 2],
+    /// Uncommons of exactly these two colors
+    pub signposts: Vec<String>,
+    /// Commons whose colors are all in the pair, mono-colored or gold
+    pub commons: Vec<String>,
+}
+
+impl Archetype {
+    /// Returns the pair's letters, e.g. `WU`.
+    #[must_use]
+    pub fn label(&self) -> String {
+        self.colors.iter().map(|color| color.letter()).collect()
+    }
+}
+
+/// The signpost uncommons and supporting commons of each two-color pair.
+///
+/// Limited sets usually have one gold uncommon per pair pointing at what the
+/// pair's deck does, so a pair without one, or with several, is worth a look
+/// in a design review.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchetypeGrid {
+    /// One archetype per pair, in [`COLOR_PAIRS`] order
+    pub archetypes: Vec<Archetype>,
+    /// Uncommons of three or more colors, which belong to no single pair
+    pub other_multicolor_uncommons: Vec<String>,
+}
+
+impl ArchetypeGrid {
+    /// Sort the multicolor uncommons and the commons of `cards` into pairs,
+    /// by the colors of their casting costs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mtg_gen::{Card, stats::ArchetypeGrid};
+    ///
+    /// let cards: Vec<Card> = [("Azorius Sage", "{W}{U}", "uncommon"), ("Gull", "{U}", "common")]
+    ///     .iter()
+    ///     .map(|(name, cost, rarity)| {
+    ///         let yaml = format!(
+    ///             "name: {name}\ntype: normal\nmana_cost: \"{cost}\"\ntype_line: Creature\nrarity: {rarity}\n"
+    ///         );
+    ///         facet_yaml::from_str(&yaml).unwrap()
+    ///     })
+    ///     .collect();
+    ///
+    /// let grid = ArchetypeGrid::from_cards(&cards);
+    /// let wu = &grid.archetypes[0];
+    /// assert_eq!(wu.label(), "WU");
+    /// assert_eq!(wu.signposts, vec!["Azorius Sage"]);
+    /// assert_eq!(wu.commons, vec!["Gull"]);
+    /// // The blue common supports every pair with blue in it
+    /// assert_eq!(grid.archetypes[1].commons, vec!["Gull"]);
+    /// assert!(grid.archetypes[2].commons.is_empty());
+    /// ```
+    #[must_use]
+    pub fn from_cards<'a>(cards: impl IntoIterator<Item = &'a Card>) -> Self {
+        let mut grid = Self {
+            archetypes: COLOR_PAIRS
+                .iter()
+                .map(|&colors| Archetype {
+                    colors,
+                    signposts: Vec::new(),
+                    commons: Vec::new(),
+                })
+                .collect(),
+            other_multicolor_uncommons: Vec::new(),
+        };
+
+        for card in cards {
+            let colors = card_colors(card);
+            match card.rarity() {
+                Rarity::Uncommon if colors.len() > 2 => {
+                    grid.other_multicolor_uncommons
+                        .push(card.name().to_string());
+                }
+                Rarity::Uncommon if colors.len() == 2 => {
+                    if let Some(archetype) = grid.archetypes.iter_mut().find(|archetype| {
+                        colors.iter().all(|color| archetype.colors.contains(color))
+                    }) {
+                        archetype.signposts.push(card.name().to_string());
+                    }
+                }
+                Rarity::Common if !colors.is_empty() => {
+                    for archetype in &mut grid.archetypes {
+                        if colors.iter().all(|color| archetype.colors.contains(color)) {
+                            archetype.commons.push(card.name().to_string());
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        grid
+    }
+}
+
+impl fmt::Display for ArchetypeGrid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Archetypes:")?;
+        for archetype in &self.archetypes {
+            writeln!(
+                f,
+                "  {}: {} signpost(s), {} common(s)",
+                archetype.label(),
+                archetype.signposts.len(),
+                archetype.commons.len()
+            )?;
+            if archetype.signposts.is_empty() {
+                writeln!(f, "    Signposts: none")?;
+            } else {
+                writeln!(f, "    Signposts: {}", archetype.signposts.join(", "))?;
+            }
+            if !archetype.commons.is_empty() {
+                writeln!(f, "    Commons: {}", archetype.commons.join(", "))?;
+            }
+        }
+        if !self.other_multicolor_uncommons.is_empty() {
+            writeln!(
+                f,
+                "  Other multicolor uncommons: {}",
+                self.other_multicolor_uncommons.join(", ")
+            )?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,641 @@
+//! Style configuration
+//!
+//! `--style <file>` reads a YAML file of layout tuning that is applied on
+//! top of the built-in stylesheet. Every setting is optional; anything left
+//! out keeps the built-in look.
+//!
+//! ```yaml
+//! rules_symbols:
+//!   size: 24
+//!   baseline: -3
+//!   spacing: 1
+//! adventure_symbols:
+//!   size: 12
+//! adventure_text:
+//!   scale: 0.9
+//!   auto_shrink: true
+//! art_regions:
+//!   class: { top: 75, left: 36, width: 336, height: 815 }
+//! frame_effects:
+//!   worn: { opacity: 0.5 }
+//! colors:
+//!   purple: { symbol: O, like: blue, mana_symbol: purple.svg }
+//! rarity_colors:
+//!   rare: ["#fff2c0", "#d4a93c", "#7a5a1c"]
+//! legal:
+//!   template: "™ & © {year} {studio}"
+//!   studio: Cramt Games
+//! large_print:
+//!   scale: 1.4
+//!   drop_flavor_text: true
+//! frame_text:
+//!   black: { color: white, outline: black }
+//! ```
+
+use crate::card::{AdventureCard, CardBase, FrameEffect};
+use crate::mana::CustomMana;
+use crate::render::assets_base;
+use facet::Facet;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+/// Built-in font size of an adventure's rules text, in pixels
+const ADVENTURE_TEXT_SIZE: f64 = 14.0;
+/// Built-in font size of rules text in the main text box, in pixels
+const RULES_TEXT_SIZE: f64 = 25.0;
+/// Built-in font size of flavor text in the main text box, in pixels
+const FLAVOR_TEXT_SIZE: f64 = 23.0;
+
+/// Characters per line of an adventure's text column at the built-in size
+const ADVENTURE_TEXT_WIDTH_CHARS: usize = 60;
+/// Lines of text an adventure's text column fits at the built-in size
+const ADVENTURE_TEXT_LINES: usize = 9;
+
+/// How much larger large print makes text unless `scale` says otherwise
+const DEFAULT_LARGE_PRINT_SCALE: f64 = 1.3;
+
+/// Smallest scale auto-shrink goes down to unless `min_scale` says otherwise
+const DEFAULT_MIN_SCALE: f64 = 0.6;
+/// How much auto-shrink lowers the scale at each try
+const SHRINK_STEP: f64 = 0.05;
+
+/// Layout tuning applied on top of the built-in stylesheet
+#[derive(Facet, Debug, Clone, Default, PartialEq)]
+pub struct StyleConfig {
+    /// Inline symbols in rules text, e.g. the `{T}` and `{G}` of a mana ability
+    #[facet(default)]
+    pub rules_symbols: Option<SymbolStyle>,
+    /// Inline symbols in reminder text, which is smaller and italic
+    #[facet(default)]
+    pub reminder_symbols: Option<SymbolStyle>,
+    /// Inline symbols in an adventure's rules text
+    #[facet(default)]
+    pub adventure_symbols: Option<SymbolStyle>,
+    /// Text size of the adventure half of an adventure card
+    #[facet(default)]
+    pub adventure_text: Option<TextScale>,
+    /// Text size of the creature half of an adventure card
+    #[facet(default)]
+    pub adventure_creature_text: Option<TextScale>,
+    /// Where art goes, keyed by layout name (`class`, `battle`, ...), for
+    /// layouts that shouldn't use [`ArtRegion::for_layout`]
+    #[facet(default)]
+    pub art_regions: BTreeMap<String, ArtRegion>,
+    /// How strongly each frame effect shows, keyed by effect name (`worn`,
+    /// `grunge`), for cards that list it in `frame_effects`
+    #[facet(default)]
+    pub frame_effects: BTreeMap<String, FrameEffectStyle>,
+    /// Custom colors beyond WUBRG, keyed by name (`purple`, ...)
+    #[facet(default)]
+    pub colors: BTreeMap<String, CustomColor>,
+    /// Gradients the set symbol is filled with, keyed by rarity (`rare`,
+    /// `mythic`, ...): colors from the middle of the symbol out. One color
+    /// is a flat tint; with two, the middle of the gradient is a blend of
+    /// them; with more, the first is the middle of the symbol, the last its
+    /// edge and the one halfway along the list in between.
+    #[facet(default)]
+    pub rarity_colors: BTreeMap<String, Vec<String>>,
+    /// The legal line printed along the bottom of every card, see
+    /// [`crate::legal`]
+    #[facet(default)]
+    pub legal: Option<LegalStyle>,
+    /// Larger rules and flavor text on every card, for players who need it
+    #[facet(default)]
+    pub large_print: Option<LargePrint>,
+    /// Text color and outline on each frame, keyed by frame color (`black`,
+    /// `gold`, a custom color's name, ...), for text that has to stay
+    /// readable over dark frames and art
+    #[facet(default)]
+    pub frame_text: BTreeMap<String, FrameTextStyle>,
+}
+
+/// Size and placement of inline symbols in one kind of text, in CSS pixels
+#[derive(Facet, Debug, Clone, Copy, Default, PartialEq)]
+pub struct SymbolStyle {
+    /// Width and height of each symbol
+    #[facet(default)]
+    pub size: Option<f64>,
+    /// How far the bottom of a symbol sits above the text baseline;
+    /// negative values drop it below
+    #[facet(default)]
+    pub baseline: Option<f64>,
+    /// Extra space on each side of a symbol, so runs like `{G}{G}` don't
+    /// touch
+    #[facet(default)]
+    pub spacing: Option<f64>,
+}
+
+/// Font size of one text region, relative to its built-in size
+#[derive(Facet, Debug, Clone, Copy, Default, PartialEq)]
+pub struct TextScale {
+    /// Font size as a multiple of the built-in size, e.g. 0.9 (defaults to 1)
+    #[facet(default)]
+    pub scale: Option<f64>,
+    /// Shrink the text further, card by card, when it's estimated not to
+    /// fit its region
+    #[facet(default)]
+    pub auto_shrink: bool,
+    /// Smallest scale auto-shrink goes down to (defaults to 0.6)
+    #[facet(default)]
+    pub min_scale: Option<f64>,
+}
+
+/// Large print: every card's rules and flavor text enlarged, in every
+/// layout, inline symbols and all. Text that no longer fits its box is
+/// shrunk back until it does, like any other overflowing text.
+#[derive(Facet, Debug, Clone, Copy, Default, PartialEq)]
+pub struct LargePrint {
+    /// Text size as a multiple of the built-in size (defaults to 1.3)
+    #[facet(default)]
+    pub scale: Option<f64>,
+    /// Leave flavor text off, giving rules text the whole text box
+    #[facet(default)]
+    pub drop_flavor_text: bool,
+}
+
+/// How the text on one frame is drawn
+#[derive(Facet, Debug, Clone, Default, PartialEq)]
+pub struct FrameTextStyle {
+    /// Text color, any CSS color
+    #[facet(default)]
+    pub color: Option<String>,
+    /// Color of an outline drawn around each letter
+    #[facet(default)]
+    pub outline: Option<String>,
+    /// Width of the outline, in pixels (defaults to 2)
+    #[facet(default)]
+    pub outline_width: Option<f64>,
+    /// A CSS `text-shadow` drawn under the text, e.g. `0 2px 6px black`
+    #[facet(default)]
+    pub shadow: Option<String>,
+}
+
+/// How a frame effect is laid over the card
+#[derive(Facet, Debug, Clone, Copy, Default, PartialEq)]
+pub struct FrameEffectStyle {
+    /// Opacity of the effect's layer, from 0 (invisible) to 1
+    #[facet(default)]
+    pub opacity: Option<f64>,
+}
+
+/// The legal line's templates and the values filled into them. Templates
+/// may use `{year}`, `{studio}` and `{set}`, the card's set code.
+#[derive(Facet, Debug, Clone, Default, PartialEq)]
+pub struct LegalStyle {
+    /// Template for cards whose set has none of its own, e.g.
+    /// `™ & © {year} {studio}`
+    #[facet(default)]
+    pub template: Option<String>,
+    /// Templates for the cards of particular sets, keyed by set code
+    #[facet(default)]
+    pub sets: BTreeMap<String, String>,
+    /// The year filled in for `{year}` (defaults to the current year)
+    #[facet(default)]
+    pub year: Option<u32>,
+    /// The name filled in for `{studio}`
+    #[facet(default)]
+    pub studio: Option<String>,
+}
+
+/// A custom color: its mana symbol and the frame of cards of only that
+/// color. Cards mixing it with any other color use the gold frame.
+#[derive(Facet, Debug, Clone, PartialEq)]
+pub struct CustomColor {
+    /// The letter of its mana symbol, e.g. `O` for `{O}`
+    pub symbol: CustomMana,
+    /// A built-in frame color (`blue`, `gold`, ...) whose assets fill in
+    /// for any not given here (defaults to the artifact frame)
+    #[facet(default)]
+    pub like: Option<String>,
+    /// Image of the mana symbol, in place of a plain lettered circle
+    #[facet(default)]
+    pub mana_symbol: Option<String>,
+    /// Frame background, as in `img/bg`
+    #[facet(default)]
+    pub background: Option<String>,
+    /// Frame overlay with the name and type boxes, as in `img/frames`
+    #[facet(default)]
+    pub frame: Option<String>,
+    /// Text box background, as in `img/boxes`
+    #[facet(default)]
+    pub text_box: Option<String>,
+    /// Power/toughness box, as in `img/pt_boxes`
+    #[facet(default)]
+    pub pt_box: Option<String>,
+}
+
+/// Where a layout's art goes, in CSS pixels from the card's top-left
+/// corner. Supplied art is cropped to the region's aspect ratio.
+#[derive(Facet, Debug, Clone, Copy, PartialEq)]
+pub struct ArtRegion {
+    /// Distance from the card's top edge
+    pub top: f64,
+    /// Distance from the card's left edge
+    pub left: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl ArtRegion {
+    /// The art box between the title bar and the type line
+    pub const STANDARD: ArtRegion = ArtRegion {
+        top: 75.0,
+        left: 36.0,
+        width: 672.0,
+        height: 460.0,
+    };
+
+    /// Art reaching down behind the loyalty abilities
+    pub const PLANESWALKER: ArtRegion = ArtRegion {
+        top: 75.0,
+        left: 36.0,
+        width: 672.0,
+        height: 825.0,
+    };
+
+    /// Art down the right half, beside a saga's chapters
+    pub const SAGA: ArtRegion = ArtRegion {
+        top: 75.0,
+        left: 372.0,
+        width: 336.0,
+        height: 815.0,
+    };
+
+    /// Art between the two halves of a flip card
+    pub const FLIP: ArtRegion = ArtRegion {
+        top: 282.0,
+        left: 36.0,
+        width: 672.0,
+        height: 476.0,
+    };
+
+    /// Art covering the whole card, showing through the frame
+    pub const FULL_BLEED: ArtRegion = ArtRegion {
+        top: 0.0,
+        left: 0.0,
+        width: 744.0,
+        height: 1040.0,
+    };
+
+    /// Art reaching up behind a token's title plate
+    pub const TOKEN: ArtRegion = ArtRegion {
+        top: 36.0,
+        left: 36.0,
+        width: 672.0,
+        height: 499.0,
+    };
+
+    /// Returns the built-in art region of a layout, by its YAML `type`.
+    #[must_use]
+    pub fn for_layout(layout: &str) -> ArtRegion {
+        match layout {
+            "planeswalker" => ArtRegion::PLANESWALKER,
+            "saga" => ArtRegion::SAGA,
+            "flip" => ArtRegion::FLIP,
+            "battle" => ArtRegion::FULL_BLEED,
+            "token" => ArtRegion::TOKEN,
+            _ => ArtRegion::STANDARD,
+        }
+    }
+
+    /// Returns width over height, the aspect ratio art is cropped to.
+    #[must_use]
+    pub fn aspect_ratio(&self) -> f64 {
+        self.width / self.height
+    }
+}
+
+impl TextScale {
+    /// Returns the scale to print a region's text at. `width_chars` and
+    /// `max_lines` are what the region fits at the built-in size, and
+    /// `estimate_lines` estimates the lines the text wraps to at a width.
+    #[must_use]
+    pub fn fit(
+        &self,
+        width_chars: usize,
+        max_lines: usize,
+        estimate_lines: impl Fn(usize) -> usize,
+    ) -> f64 {
+        let mut scale = self.scale.unwrap_or(1.0);
+        if !self.auto_shrink {
+            return scale;
+        }
+        // Smaller text fits more characters on a line and more lines in
+        // the region
+        let fits = |scale: f64| {
+            let width = (width_chars as f64 / scale) as usize;
+            estimate_lines(width) as f64 <= max_lines as f64 / scale
+        };
+        let min_scale = self.min_scale.unwrap_or(DEFAULT_MIN_SCALE).min(scale);
+        while scale > min_scale && !fits(scale) {
+            scale = (scale - SHRINK_STEP).max(min_scale);
+        }
+        scale
+    }
+}
+
+impl StyleConfig {
+    /// Returns the CSS overriding the built-in stylesheet, empty when
+    /// nothing is set.
+    #[must_use]
+    pub fn css(&self) -> String {
+        let mut css = String::new();
+        let contexts = [
+            (".rules-text", &self.rules_symbols),
+            (".rules-text .reminder-text", &self.reminder_symbols),
+            (".adventure-text", &self.adventure_symbols),
+        ];
+        for (selector, style) in contexts {
+            if let Some(style) = style {
+                style.write_css(&mut css, selector);
+            }
+        }
+        for color in self.colors.values() {
+            color.write_css(&mut css);
+        }
+        for (rarity, stops) in &self.rarity_colors {
+            write_rarity_css(&mut css, rarity, stops);
+        }
+        if let Some(large_print) = &self.large_print {
+            large_print.write_css(&mut css);
+        }
+        for (frame, text) in &self.frame_text {
+            text.write_css(&mut css, frame);
+        }
+        css
+    }
+
+    /// Resolve relative image paths of custom colors against `dir`, the
+    /// directory of the style file.
+    pub fn resolve_paths(&mut self, dir: &Path) {
+        for color in self.colors.values_mut() {
+            for path in [
+                &mut color.mana_symbol,
+                &mut color.background,
+                &mut color.frame,
+                &mut color.text_box,
+                &mut color.pt_box,
+            ]
+            .into_iter()
+            .flatten()
+            {
+                if Path::new(path).is_relative() {
+                    *path = dir.join(&*path).display().to_string();
+                }
+            }
+        }
+    }
+
+    /// Returns the opacity of a frame effect's layer: the one in
+    /// `frame_effects`, kept between 0 and 1, or the effect's built-in one.
+    #[must_use]
+    pub fn frame_effect_opacity(&self, effect: FrameEffect) -> f64 {
+        self.frame_effects
+            .get(effect.as_str())
+            .and_then(|style| style.opacity)
+            .map_or_else(
+                || effect.default_opacity(),
+                |opacity| opacity.clamp(0.0, 1.0),
+            )
+    }
+
+    /// Returns the art region of a layout, by its YAML `type`: the one in
+    /// `art_regions`, or the built-in one.
+    #[must_use]
+    pub fn art_region(&self, layout: &str) -> ArtRegion {
+        self.art_regions
+            .get(layout)
+            .copied()
+            .unwrap_or_else(|| ArtRegion::for_layout(layout))
+    }
+
+    /// Returns the CSS moving the art box of a layout to its region. Layouts
+    /// in `art_regions` always move; a layout whose built-in region isn't
+    /// [`ArtRegion::STANDARD`] only moves when the card has art, so the
+    /// placeholder stays where the frame expects it.
+    #[must_use]
+    pub fn art_css(&self, layout: &str, has_art: bool) -> String {
+        let region = match self.art_regions.get(layout) {
+            Some(region) => *region,
+            None if has_art => ArtRegion::for_layout(layout),
+            None => return String::new(),
+        };
+        if region == ArtRegion::STANDARD {
+            return String::new();
+        }
+        format!(
+            ".art-box {{ top: {}px; left: {}px; width: {}px; height: {}px; }}\n",
+            region.top, region.left, region.width, region.height
+        )
+    }
+
+    /// Returns the text sizes for an adventure card's two halves, shrunk
+    /// to fit where auto-shrink is on.
+    #[must_use]
+    pub fn adventure_css(&self, card: &AdventureCard) -> String {
+        let mut css = String::new();
+        if let Some(text) = &self.adventure_text {
+            let scale = text.fit(ADVENTURE_TEXT_WIDTH_CHARS, ADVENTURE_TEXT_LINES, |width| {
+                card.adventure.rules_text.estimated_lines(width)
+            });
+            let _ = writeln!(
+                css,
+                ".adventure-text {{ font-size: {}px; }}",
+                px(ADVENTURE_TEXT_SIZE * scale)
+            );
+        }
+        if let Some(text) = &self.adventure_creature_text {
+            let scale = text.fit(
+                CardBase::TEXT_BOX_WIDTH_CHARS,
+                CardBase::TEXT_BOX_LINES,
+                |width| card.base.estimated_text_lines(width),
+            );
+            let _ = writeln!(
+                css,
+                ".adventure-right .rules-text {{ font-size: {}px; }}",
+                px(RULES_TEXT_SIZE * scale)
+            );
+            let _ = writeln!(
+                css,
+                ".adventure-right .flavor-text {{ font-size: {}px; }}",
+                px(FLAVOR_TEXT_SIZE * scale)
+            );
+        }
+        css
+    }
+}
+
+/// Write the CSS variables of a rarity's set symbol gradient, see
+/// [`StyleConfig::rarity_colors`].
+fn write_rarity_css(css: &mut String, rarity: &str, stops: &[String]) {
+    let (center, middle, edge) = match stops {
+        [] => return,
+        [color] => (color.clone(), color.clone(), color.clone()),
+        [center, edge] => (
+            center.clone(),
+            format!("color-mix(in srgb, {center}, {edge})"),
+            edge.clone(),
+        ),
+        [center, .., edge] => (center.clone(), stops[stops.len() / 2].clone(), edge.clone()),
+    };
+    let _ = writeln!(
+        css,
+        ".rarity-{} {{ --rarity-center: {center}; --rarity-middle: {middle}; --rarity-edge: {edge}; }}",
+        rarity.to_lowercase()
+    );
+}
+
+impl SymbolStyle {
+    fn write_css(&self, css: &mut String, selector: &str) {
+        let mut rules = String::new();
+        if let Some(size) = self.size {
+            let _ = write!(rules, "width: {size}px; height: {size}px; ");
+        }
+        if let Some(baseline) = self.baseline {
+            let _ = write!(rules, "vertical-align: {baseline}px; ");
+        }
+        if let Some(spacing) = self.spacing {
+            let _ = write!(rules, "margin: 0 {spacing}px; ");
+        }
+        if !rules.is_empty() {
+            let _ = writeln!(css, "{selector} .mana-symbol {{ {}}}", rules);
+        }
+    }
+}
+
+/// The text a frame's text style applies to, in every layout
+const FRAME_TEXT_SELECTORS: &str = ".card-name, .split-name, .type-text, .split-type-text, \
+    .rules-text, .flavor-text, .loyalty-ability-text, .saga-chapter-text, .class-level-text, \
+    .leveler-text, .adventure-text, .adventure-flavor";
+
+/// Default width of a frame text outline, in pixels
+const DEFAULT_OUTLINE_WIDTH: f64 = 2.0;
+
+impl FrameTextStyle {
+    fn write_css(&self, css: &mut String, frame: &str) {
+        let mut rules = String::new();
+        if let Some(color) = &self.color {
+            let _ = write!(rules, "color: {color}; ");
+        }
+        // An outline is eight hard shadows around each letter; unlike a
+        // stroke it's drawn outside the glyph, so thin type stays legible
+        let mut shadows = Vec::new();
+        if let Some(outline) = &self.outline {
+            let width = px(self.outline_width.unwrap_or(DEFAULT_OUTLINE_WIDTH));
+            for (x, y) in [
+                (-1, -1),
+                (0, -1),
+                (1, -1),
+                (-1, 0),
+                (1, 0),
+                (-1, 1),
+                (0, 1),
+                (1, 1),
+            ] {
+                shadows.push(format!(
+                    "{}px {}px 0 {outline}",
+                    f64::from(x) * width,
+                    f64::from(y) * width
+                ));
+            }
+        }
+        if let Some(shadow) = &self.shadow {
+            shadows.push(shadow.clone());
+        }
+        if !shadows.is_empty() {
+            let _ = write!(rules, "text-shadow: {}; ", shadows.join(", "));
+        }
+        if !rules.is_empty() {
+            // The card carries its background class; a split half carries
+            // the frame class of its own color
+            let _ = writeln!(
+                css,
+                ":is(.bg-{frame}, .frame-{frame}) :is({FRAME_TEXT_SELECTORS}) {{ {rules}}}"
+            );
+        }
+    }
+}
+
+impl LargePrint {
+    fn write_css(&self, css: &mut String) {
+        // Zoom scales text whatever size each layout sets it at, and its
+        // symbols and spacing with it
+        let scale = self.scale.unwrap_or(DEFAULT_LARGE_PRINT_SCALE);
+        let _ = writeln!(
+            css,
+            ".rules-text-inner, .flavor-text, .adventure-flavor {{ zoom: {}; }}",
+            scale
+        );
+        if self.drop_flavor_text {
+            let _ = writeln!(css, ".flavor-text, .adventure-flavor {{ display: none; }}");
+        }
+    }
+}
+
+impl CustomColor {
+    fn write_css(&self, css: &mut String) {
+        let color = self.symbol.frame_color();
+        let like = self.like.as_deref().and_then(frame_asset_name);
+        let parts = [
+            ("bg", "bg", &self.background),
+            ("frame", "frames", &self.frame),
+            ("text-box-bg", "boxes", &self.text_box),
+            ("pt-box", "pt_boxes", &self.pt_box),
+        ];
+        for (class, dir, path) in parts {
+            let image = match (path, like) {
+                (Some(path), _) => PathBuf::from(path),
+                (None, Some(name)) => assets_base().join(format!("img/{dir}/{name}.png")),
+                (None, None) => continue,
+            };
+            let _ = writeln!(
+                css,
+                ".{class}-{color} {{ background-image: url('file://{}'); }}",
+                image.display()
+            );
+        }
+        if let Some(symbol) = &self.mana_symbol {
+            let _ = writeln!(
+                css,
+                ".mana-{color} {{ background-image: url('file://{symbol}'); color: transparent; }}"
+            );
+        }
+    }
+}
+
+/// Returns the asset file name of a built-in frame color, e.g. `U` for
+/// `blue`.
+fn frame_asset_name(color: &str) -> Option<&'static str> {
+    Some(match color {
+        "white" => "W",
+        "blue" => "U",
+        "black" => "B",
+        "red" => "R",
+        "green" => "G",
+        "gold" => "Gold",
+        "artifact" => "Artifact",
+        "colorless" => "Colourless",
+        "land" => "Land",
+        _ => return None,
+    })
+}
+
+/// Add style overrides to a card's HTML document, after the built-in
+/// stylesheet.
+#[must_use]
+pub fn with_css(html: &str, css: &str) -> String {
+    if css.is_empty() {
+        return html.to_string();
+    }
+    let style = format!("<style>{}</style>", css);
+    match html.find("</head>") {
+        Some(end) => format!("{}{}{}", &html[..end], style, &html[end..]),
+        None => format!("{}{}", style, html),
+    }
+}
+
+/// Round a pixel size to hundredths, so it prints without float noise
+fn px(size: f64) -> f64 {
+    (size * 100.0).round() / 100.0
+}
@@ -0,0 +1,63 @@
+//! Symbol coverage data derived from Scryfall's symbology list
+//!
+//! The table in `symbology_table.rs` is a checked-in snapshot of
+//! <https://api.scryfall.com/symbology>. Rendering uses it to find the SVG
+//! file and description for each symbol, and tests use it to make sure every
+//! listed symbol can be parsed.
+
+use crate::mana::{ManaCostParseError, ManaSymbol};
+
+#[cfg(feature = "symbols-sync")]
+pub mod sync;
+
+/// One entry of the Scryfall symbology list
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SymbologyEntry {
+    /// The symbol as written in card text, including braces (e.g. `{W/U/P}`)
+    pub code: &'static str,
+    /// File stem of the symbol's SVG (e.g. `WUP` for `WUP.svg`)
+    pub svg: &'static str,
+    /// Scryfall's English description of the symbol
+    pub english: &'static str,
+    /// Whether the symbol can appear in a mana cost
+    pub appears_in_mana_costs: bool,
+}
+
+impl SymbologyEntry {
+    /// Parse this entry's code into the crate's symbol type.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the crate doesn't model this symbol yet.
+    pub fn parse(&self) -> Result<ManaSymbol, ManaCostParseError> {
+        let content = self
+            .code
+            .strip_prefix('{')
+            .and_then(|rest| rest.strip_suffix('}'))
+            .unwrap_or(self.code);
+        ManaSymbol::parse_symbol(content)
+    }
+}
+
+include!("symbology_table.rs");
+
+/// Find the symbology entry for a parsed symbol.
+///
+/// Hybrid symbols are matched regardless of color order, so `{W/R}` finds
+/// Scryfall's `{R/W}` entry.
+#[must_use]
+pub fn lookup(symbol: ManaSymbol) -> Option<&'static SymbologyEntry> {
+    SYMBOLOGY
+        .iter()
+        .find(|entry| entry.parse().is_ok_and(|parsed| parsed == symbol))
+}
+
+/// Returns the codes of snapshot entries the crate can't parse.
+#[must_use]
+pub fn unsupported_symbols() -> Vec<&'static str> {
+    SYMBOLOGY
+        .iter()
+        .filter(|entry| entry.parse().is_err())
+        .map(|entry| entry.code)
+        .collect()
+}
@@ -0,0 +1,141 @@
+//! Compare the crate's symbol coverage against Scryfall's live symbology list
+//!
+//! Used by `mtg-gen symbols sync` to report symbols the crate's enums don't
+//! cover yet, and to regenerate the checked-in `symbology_table.rs` snapshot.
+
+use super::SYMBOLOGY;
+use crate::mana::ManaSymbol;
+use facet::Facet;
+use std::fmt::Write;
+
+const SYMBOLOGY_URL: &str = "https://api.scryfall.com/symbology";
+
+/// A symbol as returned by Scryfall's /symbology endpoint
+#[derive(Facet, Debug, Clone)]
+pub struct ScryfallSymbol {
+    /// The symbol as written in card text, including braces
+    pub symbol: String,
+    /// URL of the symbol's SVG image
+    #[facet(default)]
+    pub svg_uri: Option<String>,
+    /// English description of the symbol
+    pub english: String,
+    /// Whether the symbol can appear in a mana cost
+    pub appears_in_mana_costs: bool,
+}
+
+impl ScryfallSymbol {
+    /// Returns the SVG file stem from `svg_uri` (e.g. `WUP` for `.../WUP.svg`).
+    #[must_use]
+    pub fn svg_stem(&self) -> &str {
+        self.svg_uri
+            .as_deref()
+            .and_then(|uri| uri.rsplit('/').next())
+            .and_then(|file| file.strip_suffix(".svg"))
+            .unwrap_or_default()
+    }
+
+    /// Returns true if the crate can parse this symbol.
+    #[must_use]
+    pub fn is_supported(&self) -> bool {
+        let content = self
+            .symbol
+            .strip_prefix('{')
+            .and_then(|rest| rest.strip_suffix('}'))
+            .unwrap_or(&self.symbol);
+        ManaSymbol::parse_symbol(content).is_ok()
+    }
+}
+
+#[derive(Facet, Debug)]
+struct SymbologyList {
+    data: Vec<ScryfallSymbol>,
+}
+
+/// Differences between Scryfall's live list and the crate
+#[derive(Debug, Default)]
+pub struct SyncReport {
+    /// Live symbols the crate's enums can't parse
+    pub unsupported: Vec<ScryfallSymbol>,
+    /// Live symbols missing from the checked-in snapshot
+    pub missing_from_snapshot: Vec<String>,
+    /// Snapshot symbols Scryfall no longer lists
+    pub removed_from_scryfall: Vec<&'static str>,
+}
+
+impl SyncReport {
+    /// Returns true if the crate and snapshot fully match Scryfall.
+    #[must_use]
+    pub fn is_clean(&self) -> bool {
+        self.unsupported.is_empty()
+            && self.missing_from_snapshot.is_empty()
+            && self.removed_from_scryfall.is_empty()
+    }
+}
+
+/// Fetch the current symbology list from Scryfall.
+///
+/// # Errors
+///
+/// Returns an error if the request fails or the response can't be parsed.
+pub async fn fetch_symbology() -> anyhow::Result<Vec<ScryfallSymbol>> {
+    let body = reqwest::Client::new()
+        .get(SYMBOLOGY_URL)
+        .header("User-Agent", concat!("mtg-gen/", env!("CARGO_PKG_VERSION")))
+        .header("Accept", "application/json")
+        .send()
+        .await?
+        .error_for_status()?
+        .text()
+        .await?;
+
+    let list: SymbologyList = facet_json::from_str(&body)
+        .map_err(|e| anyhow::anyhow!("Invalid symbology JSON: {}", e))?;
+    Ok(list.data)
+}
+
+/// Compare a live symbology list against the crate and the snapshot.
+#[must_use]
+pub fn compare(live: &[ScryfallSymbol]) -> SyncReport {
+    let unsupported = live.iter().filter(|s| !s.is_supported()).cloned().collect();
+
+    let missing_from_snapshot = live
+        .iter()
+        .filter(|s| !SYMBOLOGY.iter().any(|entry| entry.code == s.symbol))
+        .map(|s| s.symbol.clone())
+        .collect();
+
+    let removed_from_scryfall = SYMBOLOGY
+        .iter()
+        .filter(|entry| !live.iter().any(|s| s.symbol == entry.code))
+        .map(|entry| entry.code)
+        .collect();
+
+    SyncReport {
+        unsupported,
+        missing_from_snapshot,
+        removed_from_scryfall,
+    }
+}
+
+/// Generate the contents of `symbology_table.rs` from a live list.
+#[must_use]
+pub fn generate_table(live: &[ScryfallSymbol]) -> String {
+    let mut out = String::new();
+    out.push_str("/// Snapshot of Scryfall's /symbology endpoint, in Scryfall's order.\n");
+    out.push_str("pub static SYMBOLOGY: &[SymbologyEntry] = &[\n");
+    for symbol in live {
+        let _ = writeln!(out, "    SymbologyEntry {{");
+        let _ = writeln!(out, "        code: {:?},", symbol.symbol);
+        let _ = writeln!(out, "        svg: {:?},", symbol.svg_stem());
+        let _ = writeln!(out, "        english: {:?},", symbol.english);
+        let _ = writeln!(
+            out,
+            "        appears_in_mana_costs: {},",
+            symbol.appears_in_mana_costs
+        );
+        let _ = writeln!(out, "    }},");
+    }
+    out.push_str("];\n");
+    out
+}
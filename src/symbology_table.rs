@@ -0,0 +1,501 @@
+/// Snapshot of Scryfall's /symbology endpoint, in Scryfall's order.
+pub static SYMBOLOGY: &[SymbologyEntry] = &[
+    SymbologyEntry {
+        code: "{T}",
+        svg: "T",
+        english: "tap this permanent",
+        appears_in_mana_costs: false,
+    },
+    SymbologyEntry {
+        code: "{Q}",
+        svg: "Q",
+        english: "untap this permanent",
+        appears_in_mana_costs: false,
+    },
+    SymbologyEntry {
+        code: "{E}",
+        svg: "E",
+        english: "an energy counter",
+        appears_in_mana_costs: false,
+    },
+    SymbologyEntry {
+        code: "{P}",
+        svg: "P",
+        english: "one colored Phyrexian mana",
+        appears_in_mana_costs: false,
+    },
+    SymbologyEntry {
+        code: "{PW}",
+        svg: "PW",
+        english: "planeswalker",
+        appears_in_mana_costs: false,
+    },
+    SymbologyEntry {
+        code: "{CHAOS}",
+        svg: "CHAOS",
+        english: "chaos",
+        appears_in_mana_costs: false,
+    },
+    SymbologyEntry {
+        code: "{A}",
+        svg: "A",
+        english: "an acorn counter",
+        appears_in_mana_costs: false,
+    },
+    SymbologyEntry {
+        code: "{TK}",
+        svg: "TK",
+        english: "a ticket counter",
+        appears_in_mana_costs: false,
+    },
+    SymbologyEntry {
+        code: "{X}",
+        svg: "X",
+        english: "X generic mana",
+        appears_in_mana_costs: true,
+    },
+    SymbologyEntry {
+        code: "{Y}",
+        svg: "Y",
+        english: "Y generic mana",
+        appears_in_mana_costs: true,
+    },
+    SymbologyEntry {
+        code: "{Z}",
+        svg: "Z",
+        english: "Z generic mana",
+        appears_in_mana_costs: true,
+    },
+    SymbologyEntry {
+        code: "{0}",
+        svg: "0",
+        english: "zero mana",
+        appears_in_mana_costs: true,
+    },
+    SymbologyEntry {
+        code: "{½}",
+        svg: "HALF",
+        english: "one-half generic mana",
+        appears_in_mana_costs: true,
+    },
+    SymbologyEntry {
+        code: "{1}",
+        svg: "1",
+        english: "1 generic mana",
+        appears_in_mana_costs: true,
+    },
+    SymbologyEntry {
+        code: "{2}",
+        svg: "2",
+        english: "2 generic mana",
+        appears_in_mana_costs: true,
+    },
+    SymbologyEntry {
+        code: "{3}",
+        svg: "3",
+        english: "3 generic mana",
+        appears_in_mana_costs: true,
+    },
+    SymbologyEntry {
+        code: "{4}",
+        svg: "4",
+        english: "4 generic mana",
+        appears_in_mana_costs: true,
+    },
+    SymbologyEntry {
+        code: "{5}",
+        svg: "5",
+        english: "5 generic mana",
+        appears_in_mana_costs: true,
+    },
+    SymbologyEntry {
+        code: "{6}",
+        svg: "6",
+        english: "6 generic mana",
+        appears_in_mana_costs: true,
+    },
+    SymbologyEntry {
+        code: "{7}",
+        svg: "7",
+        english: "7 generic mana",
+        appears_in_mana_costs: true,
+    },
+    SymbologyEntry {
+        code: "{8}",
+        svg: "8",
+        english: "8 generic mana",
+        appears_in_mana_costs: true,
+    },
+    SymbologyEntry {
+        code: "{9}",
+        svg: "9",
+        english: "9 generic mana",
+        appears_in_mana_costs: true,
+    },
+    SymbologyEntry {
+        code: "{10}",
+        svg: "10",
+        english: "10 generic mana",
+        appears_in_mana_costs: true,
+    },
+    SymbologyEntry {
+        code: "{11}",
+        svg: "11",
+        english: "11 generic mana",
+        appears_in_mana_costs: true,
+    },
+    SymbologyEntry {
+        code: "{12}",
+        svg: "12",
+        english: "12 generic mana",
+        appears_in_mana_costs: true,
+    },
+    SymbologyEntry {
+        code: "{13}",
+        svg: "13",
+        english: "13 generic mana",
+        appears_in_mana_costs: true,
+    },
+    SymbologyEntry {
+        code: "{14}",
+        svg: "14",
+        english: "14 generic mana",
+        appears_in_mana_costs: true,
+    },
+    SymbologyEntry {
+        code: "{15}",
+        svg: "15",
+        english: "15 generic mana",
+        appears_in_mana_costs: true,
+    },
+    SymbologyEntry {
+        code: "{16}",
+        svg: "16",
+        english: "16 generic mana",
+        appears_in_mana_costs: true,
+    },
+    SymbologyEntry {
+        code: "{17}",
+        svg: "17",
+        english: "17 generic mana",
+        appears_in_mana_costs: true,
+    },
+    SymbologyEntry {
+        code: "{18}",
+        svg: "18",
+        english: "18 generic mana",
+        appears_in_mana_costs: true,
+    },
+    SymbologyEntry {
+        code: "{19}",
+        svg: "19",
+        english: "19 generic mana",
+        appears_in_mana_costs: true,
+    },
+    SymbologyEntry {
+        code: "{20}",
+        svg: "20",
+        english: "20 generic mana",
+        appears_in_mana_costs: true,
+    },
+    SymbologyEntry {
+        code: "{100}",
+        svg: "100",
+        english: "one hundred generic mana",
+        appears_in_mana_costs: true,
+    },
+    SymbologyEntry {
+        code: "{1000000}",
+        svg: "1000000",
+        english: "one million generic mana",
+        appears_in_mana_costs: true,
+    },
+    SymbologyEntry {
+        code: "{∞}",
+        svg: "INFINITY",
+        english: "infinite generic mana",
+        appears_in_mana_costs: true,
+    },
+    SymbologyEntry {
+        code: "{W/U}",
+        svg: "WU",
+        english: "one white or blue mana",
+        appears_in_mana_costs: true,
+    },
+    SymbologyEntry {
+        code: "{W/B}",
+        svg: "WB",
+        english: "one white or black mana",
+        appears_in_mana_costs: true,
+    },
+    SymbologyEntry {
+        code: "{B/R}",
+        svg: "BR",
+        english: "one black or red mana",
+        appears_in_mana_costs: true,
+    },
+    SymbologyEntry {
+        code: "{B/G}",
+        svg: "BG",
+        english: "one black or green mana",
+        appears_in_mana_costs: true,
+    },
+    SymbologyEntry {
+        code: "{U/B}",
+        svg: "UB",
+        english: "one blue or black mana",
+        appears_in_mana_costs: true,
+    },
+    SymbologyEntry {
+        code: "{U/R}",
+        svg: "UR",
+        english: "one blue or red mana",
+        appears_in_mana_costs: true,
+    },
+    SymbologyEntry {
+        code: "{R/G}",
+        svg: "RG",
+        english: "one red or green mana",
+        appears_in_mana_costs: true,
+    },
+    SymbologyEntry {
+        code: "{R/W}",
+        svg: "RW",
+        english: "one red or white mana",
+        appears_in_mana_costs: true,
+    },
+    SymbologyEntry {
+        code: "{G/W}",
+        svg: "GW",
+        english: "one green or white mana",
+        appears_in_mana_costs: true,
+    },
+    SymbologyEntry {
+        code: "{G/U}",
+        svg: "GU",
+        english: "one green or blue mana",
+        appears_in_mana_costs: true,
+    },
+    SymbologyEntry {
+        code: "{B/G/P}",
+        svg: "BGP",
+        english: "one black mana, one green mana, or 2 life",
+        appears_in_mana_costs: true,
+    },
+    SymbologyEntry {
+        code: "{B/R/P}",
+        svg: "BRP",
+        english: "one black mana, one red mana, or 2 life",
+        appears_in_mana_costs: true,
+    },
+    SymbologyEntry {
+        code: "{G/U/P}",
+        svg: "GUP",
+        english: "one green mana, one blue mana, or 2 life",
+        appears_in_mana_costs: true,
+    },
+    SymbologyEntry {
+        code: "{G/W/P}",
+        svg: "GWP",
+        english: "one green mana, one white mana, or 2 life",
+        appears_in_mana_costs: true,
+    },
+    SymbologyEntry {
+        code: "{R/G/P}",
+        svg: "RGP",
+        english: "one red mana, one green mana, or 2 life",
+        appears_in_mana_costs: true,
+    },
+    SymbologyEntry {
+        code: "{R/W/P}",
+        svg: "RWP",
+        english: "one red mana, one white mana, or 2 life",
+        appears_in_mana_costs: true,
+    },
+    SymbologyEntry {
+        code: "{U/B/P}",
+        svg: "UBP",
+        english: "one blue mana, one black mana, or 2 life",
+        appears_in_mana_costs: true,
+    },
+    SymbologyEntry {
+        code: "{U/R/P}",
+        svg: "URP",
+        english: "one blue mana, one red mana, or 2 life",
+        appears_in_mana_costs: true,
+    },
+    SymbologyEntry {
+        code: "{W/B/P}",
+        svg: "WBP",
+        english: "one white mana, one black mana, or 2 life",
+        appears_in_mana_costs: true,
+    },
+    SymbologyEntry {
+        code: "{W/U/P}",
+        svg: "WUP",
+        english: "one white mana, one blue mana, or 2 life",
+        appears_in_mana_costs: true,
+    },
+    SymbologyEntry {
+        code: "{C/W}",
+        svg: "CW",
+        english: "one colorless mana or one white mana",
+        appears_in_mana_costs: true,
+    },
+    SymbologyEntry {
+        code: "{C/U}",
+        svg: "CU",
+        english: "one colorless mana or one blue mana",
+        appears_in_mana_costs: true,
+    },
+    SymbologyEntry {
+        code: "{C/B}",
+        svg: "CB",
+        english: "one colorless mana or one black mana",
+        appears_in_mana_costs: true,
+    },
+    SymbologyEntry {
+        code: "{C/R}",
+        svg: "CR",
+        english: "one colorless mana or one red mana",
+        appears_in_mana_costs: true,
+    },
+    SymbologyEntry {
+        code: "{C/G}",
+        svg: "CG",
+        english: "one colorless mana or one green mana",
+        appears_in_mana_costs: true,
+    },
+    SymbologyEntry {
+        code: "{2/W}",
+        svg: "2W",
+        english: "two generic mana or one white mana",
+        appears_in_mana_costs: true,
+    },
+    SymbologyEntry {
+        code: "{2/U}",
+        svg: "2U",
+        english: "two generic mana or one blue mana",
+        appears_in_mana_costs: true,
+    },
+    SymbologyEntry {
+        code: "{2/B}",
+        svg: "2B",
+        english: "two generic mana or one black mana",
+        appears_in_mana_costs: true,
+    },
+    SymbologyEntry {
+        code: "{2/R}",
+        svg: "2R",
+        english: "two generic mana or one red mana",
+        appears_in_mana_costs: true,
+    },
+    SymbologyEntry {
+        code: "{2/G}",
+        svg: "2G",
+        english: "two generic mana or one green mana",
+        appears_in_mana_costs: true,
+    },
+    SymbologyEntry {
+        code: "{W/P}",
+        svg: "WP",
+        english: "one white mana or two life",
+        appears_in_mana_costs: true,
+    },
+    SymbologyEntry {
+        code: "{U/P}",
+        svg: "UP",
+        english: "one blue mana or two life",
+        appears_in_mana_costs: true,
+    },
+    SymbologyEntry {
+        code: "{B/P}",
+        svg: "BP",
+        english: "one black mana or two life",
+        appears_in_mana_costs: true,
+    },
+    SymbologyEntry {
+        code: "{R/P}",
+        svg: "RP",
+        english: "one red mana or two life",
+        appears_in_mana_costs: true,
+    },
+    SymbologyEntry {
+        code: "{G/P}",
+        svg: "GP",
+        english: "one green mana or two life",
+        appears_in_mana_costs: true,
+    },
+    SymbologyEntry {
+        code: "{C/P}",
+        svg: "CP",
+        english: "one colorless mana or two life",
+        appears_in_mana_costs: true,
+    },
+    SymbologyEntry {
+        code: "{HW}",
+        svg: "HW",
+        english: "one-half white mana",
+        appears_in_mana_costs: true,
+    },
+    SymbologyEntry {
+        code: "{HR}",
+        svg: "HR",
+        english: "one-half red mana",
+        appears_in_mana_costs: true,
+    },
+    SymbologyEntry {
+        code: "{W}",
+        svg: "W",
+        english: "one white mana",
+        appears_in_mana_costs: true,
+    },
+    SymbologyEntry {
+        code: "{U}",
+        svg: "U",
+        english: "one blue mana",
+        appears_in_mana_costs: true,
+    },
+    SymbologyEntry {
+        code: "{B}",
+        svg: "B",
+        english: "one black mana",
+        appears_in_mana_costs: true,
+    },
+    SymbologyEntry {
+        code: "{R}",
+        svg: "R",
+        english: "one red mana",
+        appears_in_mana_costs: true,
+    },
+    SymbologyEntry {
+        code: "{G}",
+        svg: "G",
+        english: "one green mana",
+        appears_in_mana_costs: true,
+    },
+    SymbologyEntry {
+        code: "{C}",
+        svg: "C",
+        english: "one colorless mana",
+        appears_in_mana_costs: true,
+    },
+    SymbologyEntry {
+        code: "{S}",
+        svg: "S",
+        english: "one snow mana",
+        appears_in_mana_costs: true,
+    },
+    SymbologyEntry {
+        code: "{L}",
+        svg: "L",
+        english: "one legendary mana",
+        appears_in_mana_costs: false,
+    },
+    SymbologyEntry {
+        code: "{D}",
+        svg: "D",
+        english: "a land drop",
+        appears_in_mana_costs: false,
+    },
+];
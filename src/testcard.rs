@@ -0,0 +1,41 @@
+//! A stress-test card for asset packs and themes (`mtg-gen testcard`)
+//!
+//! One synthetic card that pushes every part of the normal frame at once: a
+//! name and a cost as long as the title bar holds, hybrid, twobrid,
+//! Phyrexian and colorless symbols, rules text with inline symbols and
+//! reminder text filling the text box, flavor text, a wide power/toughness,
+//! a collector number and a set code. Rendering it after swapping assets or
+//! editing a style file shows at a glance whether anything overflows or
+//! fails to load.
+//!
+//! Legendary crowns, foil stamps and watermarks aren't rendered yet, so the
+//! card has none.
+
+use crate::card::Card;
+
+/// The test card's YAML
+pub const TEST_CARD_YAML: &str = r#"type: normal
+name: "Grand Archmage Vexilinda of the Ninefold Spire"
+mana_cost: "{X}{10}{W/U}{B/G}{2/R}{G/U/P}{C}{S}"
+type_line: "Legendary Artifact Creature — Human Wizard Advisor"
+rules_text: "Flying, vigilance, trample, ward {2}\nWhen Grand Archmage Vexilinda enters, draw X cards. (X is the amount of mana spent on {X}.)\n{T}, Pay {W/P}: Add {W}{U}{B}{R}{G}. Spend this mana only to cast spells with mana value 10 or greater.\n{3}{Q}: Return target nonland permanent to its owner's hand. Activate only as a sorcery."
+flavor_text: "\"Nine towers, nine paths, and only one way down.\""
+power: "12"
+toughness: "1+*"
+rarity: mythic
+collector_number: "999"
+set_code: "TST"
+"#;
+
+/// Returns the test card.
+///
+/// # Examples
+///
+/// ```
+/// let card = mtg_gen::testcard::test_card();
+/// assert_eq!(card.base().collector_number.as_deref(), Some("999"));
+/// ```
+#[must_use]
+pub fn test_card() -> Card {
+    facet_yaml::from_str(TEST_CARD_YAML).expect("the test card is valid YAML")
+}
@@ -0,0 +1,312 @@
+//! Publishing rendered outputs to an S3-compatible bucket or an HTTP server
+//!
+//! Built with `--features upload`. After a run, every image in the render
+//! manifest is uploaded and the manifest is rewritten with the public URL of
+//! each image, so the manifest can be shared as-is.
+//!
+//! Destinations:
+//!
+//! - `s3://bucket/prefix`: signed (SigV4) `PUT`s to an S3-compatible
+//!   endpoint. Credentials come from `AWS_ACCESS_KEY_ID` and
+//!   `AWS_SECRET_ACCESS_KEY`, the region from `AWS_REGION` (default
+//!   `us-east-1`), and the endpoint from `S3_ENDPOINT` (default AWS).
+//! - `http://...` or `https://...`: plain `PUT` of each file below that URL,
+//!   with `UPLOAD_TOKEN` sent as a bearer token if set.
+
+use crate::output::{ImageFormat, MANIFEST_FILE_NAME, RenderManifest};
+use anyhow::{Context, Result, anyhow, bail};
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Credentials for an S3-compatible bucket
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct S3Credentials {
+    pub access_key_id: String,
+    pub secret_access_key: String,
+}
+
+/// Where rendered outputs are uploaded to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UploadTarget {
+    /// `PUT` each file to `{base_url}/{path}`
+    Http {
+        base_url: String,
+        token: Option<String>,
+    },
+    /// Signed `PUT` of each file to `{endpoint}/{bucket}/{prefix}{path}`
+    S3 {
+        endpoint: String,
+        bucket: String,
+        prefix: String,
+        region: String,
+        credentials: S3Credentials,
+    },
+}
+
+impl UploadTarget {
+    /// Parse an upload destination, reading credentials through `env`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error for an unknown scheme or missing S3 credentials.
+    pub fn from_destination(
+        destination: &str,
+        env: impl Fn(&str) -> Option<String>,
+    ) -> Result<Self> {
+        if let Some(rest) = destination.strip_prefix("s3://") {
+            let (bucket, prefix) = rest.split_once('/').unwrap_or((rest, ""));
+            if bucket.is_empty() {
+                bail!("S3 destination {:?} has no bucket", destination);
+            }
+            let mut prefix = prefix.trim_matches('/').to_string();
+            if !prefix.is_empty() {
+                prefix.push('/');
+            }
+            let region = env("AWS_REGION").unwrap_or_else(|| "us-east-1".to_string());
+            let endpoint = env("S3_ENDPOINT")
+                .unwrap_or_else(|| format!("https://s3.{}.amazonaws.com", region));
+            Ok(UploadTarget::S3 {
+                endpoint: endpoint.trim_end_matches('/').to_string(),
+                bucket: bucket.to_string(),
+                prefix,
+                region,
+                credentials: S3Credentials {
+                    access_key_id: env("AWS_ACCESS_KEY_ID")
+                        .ok_or_else(|| anyhow!("AWS_ACCESS_KEY_ID is not set"))?,
+                    secret_access_key: env("AWS_SECRET_ACCESS_KEY")
+                        .ok_or_else(|| anyhow!("AWS_SECRET_ACCESS_KEY is not set"))?,
+                },
+            })
+        } else if destination.starts_with("http://") || destination.starts_with("https://") {
+            Ok(UploadTarget::Http {
+                base_url: destination.trim_end_matches('/').to_string(),
+                token: env("UPLOAD_TOKEN"),
+            })
+        } else {
+            bail!(
+                "Unknown upload destination {:?} (expected s3://, http:// or https://)",
+                destination
+            )
+        }
+    }
+
+    /// Returns the object key for a file at `path` (relative to the output
+    /// directory): the path itself, behind the prefix for S3.
+    #[must_use]
+    pub fn object_key(&self, path: &str) -> String {
+        match self {
+            UploadTarget::Http { .. } => path.to_string(),
+            UploadTarget::S3 { prefix, .. } => format!("{}{}", prefix, path),
+        }
+    }
+
+    /// Returns the URL a file at `path` is uploaded to.
+    #[must_use]
+    pub fn upload_url(&self, path: &str) -> String {
+        let key = encode_path(&self.object_key(path));
+        match self {
+            UploadTarget::Http { base_url, .. } => format!("{}/{}", base_url, key),
+            UploadTarget::S3 {
+                endpoint, bucket, ..
+            } => format!("{}/{}/{}", endpoint, bucket, key),
+        }
+    }
+
+    /// Returns the public URL of an uploaded file. With `public_base`, the
+    /// URL is `{public_base}/{key}`; otherwise it's the upload URL.
+    #[must_use]
+    pub fn public_url(&self, path: &str, public_base: Option<&str>) -> String {
+        match public_base {
+            Some(base) => format!(
+                "{}/{}",
+                base.trim_end_matches('/'),
+                encode_path(&self.object_key(path))
+            ),
+            None => self.upload_url(path),
+        }
+    }
+}
+
+/// Uploads files to an [`UploadTarget`]
+pub struct Uploader {
+    target: UploadTarget,
+    public_base: Option<String>,
+    client: reqwest::Client,
+}
+
+impl Uploader {
+    #[must_use]
+    pub fn new(target: UploadTarget, public_base: Option<String>) -> Self {
+        Self {
+            target,
+            public_base,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Upload every image in `manifest`, set each output's public URL, then
+    /// upload the rewritten manifest.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a file can't be read or an upload is rejected.
+    pub async fn publish(&self, output_dir: &Path, manifest: &mut RenderManifest) -> Result<()> {
+        for output in manifest.cards.iter_mut().flat_map(|c| c.outputs.iter_mut()) {
+            let body = tokio::fs::read(output_dir.join(&output.path)).await?;
+            let format = Path::new(&output.path)
+                .extension()
+                .and_then(|ext| ImageFormat::from_extension(&ext.to_string_lossy()))
+                .unwrap_or_default();
+            self.put(&output.path, body, format.content_type()).await?;
+            output.url = Some(
+                self.target
+                    .public_url(&output.path, self.public_base.as_deref()),
+            );
+        }
+        self.put(
+            MANIFEST_FILE_NAME,
+            manifest.to_json().into_bytes(),
+            "application/json",
+        )
+        .await
+    }
+
+    async fn put(&self, path: &str, body: Vec<u8>, content_type: &str) -> Result<()> {
+        let url = self.target.upload_url(path);
+        let mut request = self
+            .client
+            .put(&url)
+            .header(reqwest::header::CONTENT_TYPE, content_type);
+
+        match &self.target {
+            UploadTarget::Http { token, .. } => {
+                if let Some(token) = token {
+                    request = request.bearer_auth(token);
+                }
+            }
+            UploadTarget::S3 {
+                region,
+                credentials,
+                ..
+            } => {
+                let parsed = reqwest::Url::parse(&url)?;
+                let host = match (parsed.host_str(), parsed.port()) {
+                    (Some(host), Some(port)) => format!("{}:{}", host, port),
+                    (Some(host), None) => host.to_string(),
+                    (None, _) => bail!("Upload URL {:?} has no host", url),
+                };
+                let amz_date = amz_date(SystemTime::now());
+                let authorization = sign_put(credentials, region, &host, parsed.path(), &amz_date);
+                request = request
+                    .header("x-amz-date", &amz_date)
+                    .header("x-amz-content-sha256", UNSIGNED_PAYLOAD)
+                    .header(reqwest::header::AUTHORIZATION, authorization);
+            }
+        }
+
+        let response = request
+            .body(body)
+            .send()
+            .await
+            .with_context(|| format!("Failed to upload {}", path))?;
+        if !response.status().is_success() {
+            bail!("Upload of {} failed: HTTP {}", path, response.status());
+        }
+        Ok(())
+    }
+}
+
+const UNSIGNED_PAYLOAD: &str = "UNSIGNED-PAYLOAD";
+
+/// Build the SigV4 `Authorization` header for an S3 `PUT` of `path`.
+///
+/// `amz_date` is the request time as `YYYYMMDDTHHMMSSZ`, and must match the
+/// `x-amz-date` header sent with the request.
+#[must_use]
+pub fn sign_put(
+    credentials: &S3Credentials,
+    region: &str,
+    host: &str,
+    path: &str,
+    amz_date: &str,
+) -> String {
+    let date = &amz_date[..8];
+    let scope = format!("{}/{}/s3/aws4_request", date, region);
+    let signed_headers = "host;x-amz-content-sha256;x-amz-date";
+
+    let canonical_request = format!(
+        "PUT\n{}\n\nhost:{}\nx-amz-content-sha256:{}\nx-amz-date:{}\n\n{}\n{}",
+        path, host, UNSIGNED_PAYLOAD, amz_date, signed_headers, UNSIGNED_PAYLOAD
+    );
+    let string_to_sign = format!(
+        "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+        amz_date,
+        scope,
+        hex(&Sha256::digest(canonical_request.as_bytes()))
+    );
+
+    let key = format!("AWS4{}", credentials.secret_access_key);
+    let key = hmac_sha256(key.as_bytes(), date.as_bytes());
+    let key = hmac_sha256(&key, region.as_bytes());
+    let key = hmac_sha256(&key, b"s3");
+    let key = hmac_sha256(&key, b"aws4_request");
+    let signature = hex(&hmac_sha256(&key, string_to_sign.as_bytes()));
+
+    format!(
+        "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+        credentials.access_key_id, scope, signed_headers, signature
+    )
+}
+
+fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Percent-encode each segment of a `/`-separated path.
+fn encode_path(path: &str) -> String {
+    path.split('/')
+        .map(|segment| {
+            segment
+                .bytes()
+                .map(|b| match b {
+                    b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                        (b as char).to_string()
+                    }
+                    _ => format!("%{:02X}", b),
+                })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Format a time as `YYYYMMDDTHHMMSSZ` in UTC.
+#[must_use]
+pub fn amz_date(time: SystemTime) -> String {
+    let secs = time
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+    let days = (secs / 86_400) as i64;
+    let rem = secs % 86_400;
+
+    let (year, month, day) = crate::utils::civil_from_days(days);
+
+    format!(
+        "{:04}{:02}{:02}T{:02}{:02}{:02}Z",
+        year,
+        month,
+        day,
+        rem / 3600,
+        (rem % 3600) / 60,
+        rem % 60
+    )
+}
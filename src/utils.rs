@@ -39,6 +39,48 @@ pub fn sanitize_card_name(name: &str) -> String {
         .join("_")
 }
 
+/// Converts a number to its roman numeral representation.
+///
+/// Used for saga chapter markers, which are printed as roman numerals.
+/// Returns an empty string for zero, which has no roman numeral.
+///
+/// # Examples
+///
+/// ```
+/// use mtg_gen::to_roman_numeral;
+///
+/// assert_eq!(to_roman_numeral(3), "III");
+/// assert_eq!(to_roman_numeral(4), "IV");
+/// assert_eq!(to_roman_numeral(9), "IX");
+/// ```
+#[must_use]
+pub fn to_roman_numeral(mut n: u32) -> String {
+    const NUMERALS: [(u32, &str); 13] = [
+        (1000, "M"),
+        (900, "CM"),
+        (500, "D"),
+        (400, "CD"),
+        (100, "C"),
+        (90, "XC"),
+        (50, "L"),
+        (40, "XL"),
+        (10, "X"),
+        (9, "IX"),
+        (5, "V"),
+        (4, "IV"),
+        (1, "I"),
+    ];
+
+    let mut result = String::new();
+    for (value, numeral) in NUMERALS {
+        while n >= value {
+            result.push_str(numeral);
+            n -= value;
+        }
+    }
+    result
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -102,4 +144,50 @@ mod tests {
             "phyrexian_fleshgorger"
         );
     }
+
+    #[test]
+    fn test_roman_numeral_basic_chapters() {
+        assert_eq!(to_roman_numeral(1), "I");
+        assert_eq!(to_roman_numeral(2), "II");
+        assert_eq!(to_roman_numeral(3), "III");
+    }
+
+    #[test]
+    fn test_roman_numeral_extended_chapters() {
+        assert_eq!(to_roman_numeral(4), "IV");
+        assert_eq!(to_roman_numeral(5), "V");
+        assert_eq!(to_roman_numeral(6), "VI");
+        assert_eq!(to_roman_numeral(14), "XIV");
+    }
+
+    #[test]
+    fn test_roman_numeral_zero() {
+        assert_eq!(to_roman_numeral(0), "");
+    }
+}
+
+/// Converts a count of days since 1970-01-01 to a `(year, month, day)`
+/// date in the proleptic Gregorian calendar, with months and days from 1.
+///
+/// # Examples
+///
+/// ```
+/// use mtg_gen::utils::civil_from_days;
+///
+/// assert_eq!(civil_from_days(0), (1970, 1, 1));
+/// assert_eq!(civil_from_days(20_560), (2026, 4, 17));
+/// ```
+#[must_use]
+pub fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    // Howard Hinnant's algorithm
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
 }
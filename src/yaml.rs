@@ -0,0 +1,412 @@
+//! Writing cards back to YAML
+//!
+//! [`card_to_yaml`] is the inverse of parsing: every field a card sets is
+//! written the way card files write it, and unset fields are left out, so
+//! reading the output back gives an equal card.
+//!
+//! Rules text is written as the text it displays as. Reminder text, ability
+//! words and paragraph breaks from the Scryfall importer therefore read back
+//! as plain text; rules text in the form [`RulesText::parse`] produces reads
+//! back segment for segment. A mana cost with no symbols is written as `""`,
+//! which reads back as no mana cost.
+//!
+//! [`sniff`] goes the other way only far enough to tell card files from the
+//! other YAML an input directory may hold, like a style file, and
+//! `find_card_files` uses it to list an input's cards.
+//!
+//! [`RulesText::parse`]: crate::RulesText::parse
+
+use crate::card::{
+    AdventureSpell, Card, CardBase, CardFace, ClassLevel, LevelerRange, LoyaltyAbility,
+    ReminderTextMode, SagaChapter, Translation,
+};
+use std::fmt::{self, Write};
+use std::path::Path;
+
+/// Write `card` as YAML.
+///
+/// # Examples
+///
+/// ```
+/// use mtg_gen::Card;
+/// use mtg_gen::yaml::card_to_yaml;
+///
+/// let card: Card = facet_yaml::from_str(
+///     "type: normal\nname: Shock\nmana_cost: \"{R}\"\ntype_line: Instant\nrarity: common\n",
+/// )
+/// .unwrap();
+/// let yaml = card_to_yaml(&card);
+/// assert!(yaml.starts_with("type: normal\nname: \"Shock\"\nmana_cost: \"{R}\"\n"));
+/// assert_eq!(facet_yaml::from_str::<Card>(&yaml).unwrap(), card);
+/// ```
+#[must_use]
+pub fn card_to_yaml(card: &Card) -> String {
+    let mut yaml = Mapping::default();
+    yaml.scalar("type", card.layout_name());
+    write_base(&mut yaml, card.base());
+
+    match card {
+        Card::Normal(_) => {}
+        Card::Planeswalker(card) => {
+            yaml.text("loyalty", &card.loyalty);
+            yaml.sequence(
+                "loyalty_abilities",
+                card.loyalty_abilities.iter().map(loyalty_ability),
+            );
+        }
+        Card::Saga(card) => {
+            yaml.sequence("chapters", card.chapters.iter().map(saga_chapter));
+            if let Some(read_ahead) = card.read_ahead {
+                yaml.scalar("read_ahead", read_ahead);
+            }
+        }
+        Card::Class(card) => yaml.sequence("levels", card.levels.iter().map(class_level)),
+        Card::Adventure(card) => yaml.mapping("adventure", adventure(&card.adventure)),
+        Card::Split(card) => {
+            yaml.sequence("faces", card.faces.iter().map(face));
+            if let Some(fuse) = card.fuse {
+                yaml.scalar("fuse", fuse);
+            }
+            if let Some(aftermath) = card.aftermath {
+                yaml.scalar("aftermath", aftermath);
+            }
+        }
+        Card::Flip(card) => yaml.sequence("faces", card.faces.iter().map(face)),
+        Card::Transform(card) => {
+            yaml.sequence("faces", card.faces.iter().map(face));
+            if let Some(icons) = card.dfc_icons {
+                yaml.scalar("dfc_icons", icons.as_str());
+            }
+        }
+        Card::ModalDfc(card) => yaml.sequence("faces", card.faces.iter().map(face)),
+        Card::Meld(card) => yaml.sequence("faces", card.faces.iter().map(face)),
+        Card::Battle(card) => {
+            yaml.scalar("defense", card.defense);
+            yaml.string("backside_name", &card.backside_name);
+            yaml.string("backside_type_line", &card.backside_type_line);
+            yaml.text("backside_rules_text", &card.backside_rules_text);
+        }
+        Card::Leveler(card) => yaml.sequence(
+            "leveler_ranges",
+            card.leveler_ranges.iter().map(leveler_range),
+        ),
+        Card::Prototype(card) => yaml.mapping("prototype", face(&card.prototype)),
+        Card::Token(card) => {
+            if !card.colors.is_empty() {
+                yaml.scalar("colors", flow(card.colors.iter().map(|c| quote(c))));
+            }
+        }
+    }
+
+    yaml.out
+}
+
+/// Double-quote `value` for YAML, escaping anything that isn't printable.
+#[must_use]
+pub fn quote(value: &str) -> String {
+    let mut out = String::with_capacity(value.len() + 2);
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            '\r' => out.push_str("\\r"),
+            c if is_printable(c) => out.push(c),
+            // Every non-printable character is in the Basic Multilingual Plane
+            c => {
+                let _ = write!(out, "\\u{:04X}", c as u32);
+            }
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// What a YAML file holds, as far as [`sniff`] can tell without parsing it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum YamlKind {
+    /// A card: a mapping with a top-level `type`
+    Card,
+    /// The `set.yaml` of a set's directory
+    Set,
+    /// Anything else, like a style file
+    Other,
+}
+
+/// Tell whether the YAML file at `path` holding `content` is a card, so
+/// a directory render can skip other YAML quietly instead of failing it.
+/// Cards have a top-level `type` key; `set.yaml` never is a card.
+///
+/// # Examples
+///
+/// ```
+/// use mtg_gen::yaml::{YamlKind, sniff};
+/// use std::path::Path;
+///
+/// let card = "name: Shock\ntype: normal\n";
+/// assert_eq!(sniff(Path::new("cards/shock.yaml"), card), YamlKind::Card);
+/// assert_eq!(sniff(Path::new("cards/set.yaml"), card), YamlKind::Set);
+/// let style = "symbols:\n  type: inline\n";
+/// assert_eq!(sniff(Path::new("cards/style.yaml"), style), YamlKind::Other);
+/// ```
+#[must_use]
+pub fn sniff(path: &Path, content: &str) -> YamlKind {
+    let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+    if file_name.eq_ignore_ascii_case("set.yaml") || file_name.eq_ignore_ascii_case("set.yml") {
+        return YamlKind::Set;
+    }
+    let has_type = content.lines().any(|line| {
+        line.strip_prefix("type")
+            .or_else(|| line.strip_prefix("\"type\""))
+            .or_else(|| line.strip_prefix("'type'"))
+            .is_some_and(|rest| rest.trim_start().starts_with(':'))
+    });
+    if has_type {
+        YamlKind::Card
+    } else {
+        YamlKind::Other
+    }
+}
+
+/// The YAML files at an input, see [`find_card_files`]
+#[cfg(feature = "cli")]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CardFiles {
+    /// The card files, in path order
+    pub cards: Vec<std::path::PathBuf>,
+    /// YAML files that aren't cards, like a style file, relative to the
+    /// input. The set file isn't one of them.
+    pub skipped: Vec<std::path::PathBuf>,
+}
+
+/// Find the card files at `input`, which may be a file or a directory. A
+/// directory's YAML files are told apart with [`sniff`]; a file that can't
+/// be read counts as a card, so loading it reports why.
+///
+/// # Errors
+///
+/// Returns an error if the directory can't be walked.
+#[cfg(feature = "cli")]
+pub fn find_card_files(input: &Path) -> anyhow::Result<CardFiles> {
+    let mut files = CardFiles::default();
+    if input.is_file() {
+        files.cards.push(input.to_path_buf());
+        return Ok(files);
+    }
+    for entry in walkdir::WalkDir::new(input).sort_by_file_name() {
+        let entry = entry?;
+        let path = entry.path();
+        let is_yaml = path
+            .extension()
+            .is_some_and(|ext| ext == "yaml" || ext == "yml");
+        if !entry.file_type().is_file() || !is_yaml {
+            continue;
+        }
+        let kind =
+            std::fs::read_to_string(path).map_or(YamlKind::Card, |content| sniff(path, &content));
+        match kind {
+            YamlKind::Card => files.cards.push(path.to_path_buf()),
+            YamlKind::Other => files
+                .skipped
+                .push(path.strip_prefix(input).unwrap_or(path).to_path_buf()),
+            YamlKind::Set => {}
+        }
+    }
+    Ok(files)
+}
+
+/// YAML's printable characters, which may appear unescaped in a document
+fn is_printable(c: char) -> bool {
+    matches!(
+        c,
+        '\u{20}'..='\u{7E}'
+            | '\u{A0}'..='\u{D7FF}'
+            | '\u{E000}'..='\u{FEFE}'
+            | '\u{FF00}'..='\u{FFFD}'
+            | '\u{10000}'..
+    )
+}
+
+fn write_base(yaml: &mut Mapping, base: &CardBase) {
+    yaml.string("name", &base.name);
+    yaml.optional_text("mana_cost", base.mana_cost.as_ref());
+    yaml.string("type_line", &base.type_line);
+    yaml.optional_text("rules_text", base.rules_text.as_ref());
+    yaml.optional_text("flavor_text", base.flavor_text.as_ref());
+    yaml.optional_text("power", base.power.as_ref());
+    yaml.optional_text("toughness", base.toughness.as_ref());
+    yaml.scalar("rarity", base.rarity.as_str());
+    yaml.optional_text("collector_number", base.collector_number.as_ref());
+    if let Some(mode) = base.reminder_text {
+        yaml.scalar(
+            "reminder_text",
+            match mode {
+                ReminderTextMode::Auto => "auto",
+                ReminderTextMode::None => "none",
+            },
+        );
+    }
+    if !base.translations.is_empty() {
+        let mut translations = Mapping::default();
+        for (language, text) in &base.translations {
+            translations.mapping(&quote(language), translation(text));
+        }
+        yaml.mapping("translations", translations);
+    }
+    yaml.optional_text("art", base.art.as_ref());
+    if let Some(fit) = base.art_fit {
+        yaml.scalar("art_fit", fit.as_str());
+    }
+    yaml.optional_text("artist", base.artist.as_ref());
+    if let Some(size) = base.size {
+        yaml.scalar("size", size.as_str());
+    }
+    if !base.frame_effects.is_empty() {
+        yaml.scalar(
+            "frame_effects",
+            flow(base.frame_effects.iter().map(|effect| effect.as_str())),
+        );
+    }
+    yaml.optional_text("set_code", base.set_code.as_ref());
+    yaml.optional_text("set_symbol", base.set_symbol.as_ref());
+    if let Some(colors) = base.frame_colors {
+        yaml.scalar("frame_colors", colors.as_str());
+    }
+}
+
+fn face(face: &CardFace) -> Mapping {
+    let mut yaml = Mapping::default();
+    yaml.optional_text("name", face.name.as_ref());
+    yaml.optional_text("mana_cost", face.mana_cost.as_ref());
+    yaml.optional_text("type_line", face.type_line.as_ref());
+    yaml.optional_text("rules_text", face.rules_text.as_ref());
+    yaml.optional_text("flavor_text", face.flavor_text.as_ref());
+    yaml.optional_text("power", face.power.as_ref());
+    yaml.optional_text("toughness", face.toughness.as_ref());
+    if let Some(colors) = &face.color_indicator {
+        yaml.scalar("color_indicator", flow(colors.iter().map(|c| quote(c))));
+    }
+    yaml.optional_text("art", face.art.as_ref());
+    yaml.optional_text("artist", face.artist.as_ref());
+    yaml
+}
+
+fn translation(translation: &Translation) -> Mapping {
+    let mut yaml = Mapping::default();
+    yaml.optional_text("name", translation.name.as_ref());
+    yaml.optional_text("type_line", translation.type_line.as_ref());
+    yaml.optional_text("rules_text", translation.rules_text.as_ref());
+    yaml.optional_text("flavor_text", translation.flavor_text.as_ref());
+    yaml
+}
+
+fn loyalty_ability(ability: &LoyaltyAbility) -> Mapping {
+    let mut yaml = Mapping::default();
+    yaml.text("cost", &ability.cost);
+    yaml.text("text", &ability.text);
+    yaml
+}
+
+fn saga_chapter(chapter: &SagaChapter) -> Mapping {
+    let mut yaml = Mapping::default();
+    yaml.scalar("chapters", flow(chapter.chapters.iter()));
+    yaml.text("text", &chapter.text);
+    yaml
+}
+
+fn class_level(level: &ClassLevel) -> Mapping {
+    let mut yaml = Mapping::default();
+    yaml.scalar("level", level.level);
+    yaml.optional_text("cost", level.cost.as_ref());
+    yaml.text("text", &level.text);
+    yaml
+}
+
+fn adventure(adventure: &AdventureSpell) -> Mapping {
+    let mut yaml = Mapping::default();
+    yaml.string("name", &adventure.name);
+    yaml.text("mana_cost", &adventure.mana_cost);
+    yaml.string("type_line", &adventure.type_line);
+    yaml.text("rules_text", &adventure.rules_text);
+    yaml.optional_text("flavor_text", adventure.flavor_text.as_ref());
+    yaml.optional_text("artist", adventure.artist.as_ref());
+    yaml
+}
+
+fn leveler_range(range: &LevelerRange) -> Mapping {
+    let mut yaml = Mapping::default();
+    yaml.text("range", &range.range);
+    yaml.optional_text("power", range.power.as_ref());
+    yaml.optional_text("toughness", range.toughness.as_ref());
+    yaml.optional_text("text", range.text.as_ref());
+    yaml
+}
+
+/// A flow sequence like `[1, 2]`
+fn flow(items: impl Iterator<Item = impl fmt::Display>) -> String {
+    let items: Vec<String> = items.map(|item| item.to_string()).collect();
+    format!("[{}]", items.join(", "))
+}
+
+/// A YAML mapping, built one `key: value` line at a time. Every string is
+/// quoted with its line breaks escaped, so each line of `out` is one line of
+/// structure.
+#[derive(Default)]
+struct Mapping {
+    out: String,
+}
+
+impl Mapping {
+    /// Write a value as is: numbers, booleans and enum names.
+    fn scalar(&mut self, key: &str, value: impl fmt::Display) {
+        let _ = writeln!(self.out, "{}: {}", key, value);
+    }
+
+    fn string(&mut self, key: &str, value: &str) {
+        self.scalar(key, quote(value));
+    }
+
+    /// Write a value as the quoted string it displays as: costs, rules text
+    /// and the other types parsed from strings.
+    fn text(&mut self, key: &str, value: &impl fmt::Display) {
+        self.string(key, &value.to_string());
+    }
+
+    fn optional_text(&mut self, key: &str, value: Option<&impl fmt::Display>) {
+        if let Some(value) = value {
+            self.text(key, value);
+        }
+    }
+
+    fn mapping(&mut self, key: &str, value: Mapping) {
+        if value.out.is_empty() {
+            self.scalar(key, "{}");
+            return;
+        }
+        let _ = writeln!(self.out, "{}:", key);
+        for line in value.out.lines() {
+            let _ = writeln!(self.out, "  {}", line);
+        }
+    }
+
+    fn sequence(&mut self, key: &str, items: impl Iterator<Item = Mapping>) {
+        let items: Vec<Mapping> = items.collect();
+        if items.is_empty() {
+            self.scalar(key, "[]");
+            return;
+        }
+        let _ = writeln!(self.out, "{}:", key);
+        for item in items {
+            if item.out.is_empty() {
+                self.out.push_str("  - {}\n");
+                continue;
+            }
+            for (i, line) in item.out.lines().enumerate() {
+                let prefix = if i == 0 { "  - " } else { "    " };
+                let _ = writeln!(self.out, "{}{}", prefix, line);
+            }
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use mtg_gen::alt_text::{AltText, AltTextFormat, spoken_cost};
+use mtg_gen::*;
+
+fn fixture(name: &str) -> Card {
+    let yaml = std::fs::read_to_string(format!("tests/fixtures/{name}.yaml")).unwrap();
+    facet_yaml::from_str(&yaml).unwrap()
+}
+
+#[test]
+fn test_creature_alt_text() {
+    let alt = AltText::from_card(&fixture("normal_creature"));
+    assert_eq!(alt.faces.len(), 1);
+
+    let face = &alt.faces[0];
+    assert_eq!(face.name, "Llanowar Elves");
+    assert_eq!(face.mana_cost.as_deref(), Some("one green mana"));
+    assert_eq!(face.stats.as_deref(), Some("Power 1, toughness 1"));
+    assert_eq!(face.rules_text, ["Tap this permanent: Add one green mana."]);
+
+    assert_eq!(
+        alt.description,
+        "Llanowar Elves. Mana cost: one green mana. Creature — Elf Druid. \
+         Power 1, toughness 1. Tap this permanent: Add one green mana. \
+         Flavor text: One bone broken for every twig snapped underfoot."
+    );
+    assert_eq!(
+        alt.to_string(),
+        "Llanowar Elves\n\
+         Mana cost: one green mana\n\
+         Creature — Elf Druid\n\
+         Power 1, toughness 1\n\
+         Tap this permanent: Add one green mana.\n\
+         Flavor text: One bone broken for every twig snapped underfoot.\n"
+    );
+}
+
+#[test]
+fn test_spoken_cost() {
+    let card = fixture("planeswalker");
+    assert_eq!(
+        spoken_cost(card.base().mana_cost.as_ref().unwrap()),
+        "2 generic mana, one blue mana, one blue mana"
+    );
+}
+
+#[test]
+fn test_planeswalker_alt_text() {
+    let alt = AltText::from_card(&fixture("planeswalker"));
+    let face = &alt.faces[0];
+    assert_eq!(face.stats.as_deref(), Some("Loyalty 3"));
+    assert_eq!(face.rules_text.len(), 4);
+    assert_eq!(
+        face.rules_text[2],
+        "-1: Return target creature to its owner's hand."
+    );
+}
+
+#[test]
+fn test_double_faced_alt_text() {
+    let alt = AltText::from_card(&fixture("transform"));
+    let names: Vec<&str> = alt.faces.iter().map(|face| face.name.as_str()).collect();
+    assert_eq!(names, ["Delver of Secrets", "Insectile Aberration"]);
+    assert!(alt.description.ends_with(
+        " // Insectile Aberration. Creature — Human Insect. Power 3, toughness 2. Flying."
+    ));
+}
+
+#[test]
+fn test_alt_text_format() {
+    assert_eq!(AltTextFormat::parse("txt"), Ok(AltTextFormat::Txt));
+    assert_eq!(AltTextFormat::parse("JSON"), Ok(AltTextFormat::Json));
+    assert!(AltTextFormat::parse("html").is_err());
+    assert_eq!(AltTextFormat::Json.extension(), "json");
+}
+
+#[test]
+fn test_alt_text_json_roundtrip() {
+    let alt = AltText::from_card(&fixture("normal_creature"));
+    let parsed: AltText = facet_json::from_str(&alt.to_json()).unwrap();
+    assert_eq!(parsed, alt);
+}
@@ -0,0 +1,132 @@
+use mtg_gen::render::{card_to_html, render_art};
+use mtg_gen::*;
+use std::path::Path;
+
+fn with_art(art: &str) -> Card {
+    facet_yaml::from_str(&format!(
+        "name: Test\ntype: normal\ntype_line: Instant\nrarity: common\nart: \"{art}\"\n"
+    ))
+    .unwrap()
+}
+
+#[test]
+fn test_parse_art() {
+    let card: Card = facet_yaml::from_str(
+        "name: Test\ntype: normal\ntype_line: Instant\nrarity: common\nart: art/test.png\nart_fit: contain\n",
+    )
+    .unwrap();
+    assert_eq!(card.base().art.as_deref(), Some("art/test.png"));
+    assert_eq!(card.base().art_fit, Some(ArtFit::Contain));
+
+    let yaml = std::fs::read_to_string("tests/fixtures/normal_creature.yaml").unwrap();
+    let card: Card = facet_yaml::from_str(&yaml).unwrap();
+    assert_eq!(card.art_paths().count(), 0);
+}
+
+#[test]
+fn test_resolve_art_paths() {
+    let mut card = with_art("art/test.png");
+    card.resolve_art_paths(Path::new("/cards"));
+    assert_eq!(card.base().art.as_deref(), Some("/cards/art/test.png"));
+
+    let mut card = with_art("/elsewhere/test.png");
+    card.resolve_art_paths(Path::new("/cards"));
+    assert_eq!(card.base().art.as_deref(), Some("/elsewhere/test.png"));
+
+    let mut card = with_art("https://example.com/art/test.png");
+    card.resolve_art_paths(Path::new("/cards"));
+    assert_eq!(
+        card.base().art.as_deref(),
+        Some("https://example.com/art/test.png")
+    );
+}
+
+#[test]
+fn test_is_art_url() {
+    assert!(card::is_art_url("https://example.com/test.png"));
+    assert!(card::is_art_url("http://example.com/test.png"));
+    assert!(!card::is_art_url("art/https.png"));
+    assert!(!card::is_art_url("/cards/test.png"));
+}
+
+#[test]
+fn test_face_art_paths() {
+    let yaml = std::fs::read_to_string("tests/fixtures/transform.yaml").unwrap();
+    let mut card: Card = facet_yaml::from_str(&yaml).unwrap();
+    if let Card::Transform(transform) = &mut card {
+        transform.faces[1].art = Some("back.png".to_string());
+    }
+    card.base_mut().art = Some("front.png".to_string());
+    card.resolve_art_paths(Path::new("/cards"));
+    assert_eq!(
+        card.art_paths().collect::<Vec<_>>(),
+        ["/cards/front.png", "/cards/back.png"]
+    );
+}
+
+#[test]
+fn test_render_art() {
+    assert_eq!(
+        render_art("art-box", None, None).into_string(),
+        "<div class=\"art-box\">[Art]</div>"
+    );
+    let html = render_art("art-box", Some("/cards/test.png"), Some(ArtFit::Fill)).into_string();
+    assert!(html.contains("src=\"file:///cards/test.png\""));
+    assert!(html.contains("object-fit: fill;"));
+
+    let html = card_to_html(&with_art("/cards/test.png"));
+    assert!(html.contains("object-fit: cover;"));
+    assert!(!html.contains("[Art]"));
+}
+
+#[test]
+fn test_image_size() {
+    let mut png = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR".to_vec();
+    png.extend_from_slice(&1200u32.to_be_bytes());
+    png.extend_from_slice(&800u32.to_be_bytes());
+    assert_eq!(art_crop::image_size(&png), Some((1200, 800)));
+
+    // An APP0 segment, then a baseline frame header: 600 tall, 900 wide
+    let jpeg = [
+        0xff, 0xd8, 0xff, 0xe0, 0x00, 0x04, 0x00, 0x00, 0xff, 0xc0, 0x00, 0x11, 0x08, 0x02, 0x58,
+        0x03, 0x84,
+    ];
+    assert_eq!(art_crop::image_size(&jpeg), Some((900, 600)));
+
+    assert_eq!(art_crop::image_size(&png[..20]), None);
+}
+
+#[test]
+fn test_cover_crop() {
+    let region = style::ArtRegion::STANDARD;
+    // Wider than the box: the sides are cut off
+    let crop = art_crop::Crop::cover(2000, 460, &region).unwrap();
+    assert_eq!(
+        (crop.x, crop.y, crop.width, crop.height),
+        (664, 0, 672, 460)
+    );
+    // A pixel off isn't worth a warning
+    assert_eq!(art_crop::Crop::cover(673, 460, &region), None);
+}
+
+#[test]
+fn test_art_crops() {
+    let dir = tempfile::tempdir().unwrap();
+    let art = dir.path().join("square.gif");
+    std::fs::write(&art, b"GIF89a\xe8\x03\xe8\x03").unwrap();
+
+    let card = with_art(&art.display().to_string());
+    let crops = art_crop::art_crops(&card, &style::StyleConfig::default());
+    assert_eq!(crops.len(), 1);
+    assert_eq!(crops[0].crop.to_string(), "1000×685 at (0, 157)");
+    assert!(crops[0].warning().contains("normal art box is 672×460"));
+
+    let mut contained = card.clone();
+    contained.base_mut().art_fit = Some(ArtFit::Contain);
+    assert!(art_crop::art_crops(&contained, &style::StyleConfig::default()).is_empty());
+
+    assert_eq!(
+        art_crop::cropped_path(&art),
+        dir.path().join("square.crop.gif")
+    );
+}
@@ -0,0 +1,62 @@
+#![cfg(feature = "remote-art")]
+
+use mtg_gen::art_cache::ArtCache;
+use std::path::{Path, PathBuf};
+
+#[test]
+fn test_path_for_is_keyed_by_url() {
+    let cache = ArtCache::new(PathBuf::from("/cache"));
+    let bolt = cache.path_for("https://example.com/art/bolt.PNG?size=large");
+    assert_eq!(
+        bolt,
+        cache.path_for("https://example.com/art/bolt.PNG?size=large")
+    );
+    assert_ne!(bolt, cache.path_for("https://example.com/art/bolt.PNG"));
+    assert_eq!(bolt.parent(), Some(Path::new("/cache")));
+    assert_eq!(bolt.extension().unwrap(), "png");
+
+    // A host is never taken for an extension
+    let root = cache.path_for("https://example.com");
+    assert_eq!(root.extension(), None);
+    assert_eq!(root.file_name().unwrap().len(), 64);
+}
+
+#[test]
+fn test_default_dir() {
+    let env = |vars: &'static [(&'static str, &'static str)]| {
+        move |name: &str| {
+            vars.iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| value.to_string())
+        }
+    };
+    assert_eq!(
+        ArtCache::default_dir(env(&[("XDG_CACHE_HOME", "/xdg"), ("HOME", "/home/me")])),
+        Path::new("/xdg/mtg-gen/art")
+    );
+    assert_eq!(
+        ArtCache::default_dir(env(&[("HOME", "/home/me")])),
+        Path::new("/home/me/.cache/mtg-gen/art")
+    );
+}
+
+#[tokio::test]
+async fn test_fetch_reuses_cached_art() {
+    let dir = tempfile::tempdir().unwrap();
+    let cache = ArtCache::new(dir.path().to_path_buf());
+    // The host doesn't resolve, so this only passes without a download
+    let url = "https://art.invalid/bolt.png";
+    std::fs::write(cache.path_for(url), b"png").unwrap();
+    assert_eq!(cache.fetch(url).await.unwrap(), cache.path_for(url));
+}
+
+#[tokio::test]
+async fn test_fetch_requires_https() {
+    let dir = tempfile::tempdir().unwrap();
+    let cache = ArtCache::new(dir.path().to_path_buf());
+    let error = cache
+        .fetch("http://art.invalid/bolt.png")
+        .await
+        .unwrap_err();
+    assert!(error.to_string().contains("https"));
+}
@@ -0,0 +1,120 @@
+use mtg_gen::cockatrice::{CockatriceSet, cockatrice_cards, image_paths};
+use mtg_gen::*;
+use std::path::Path;
+
+fn fixture(name: &str) -> Card {
+    let yaml = std::fs::read_to_string(format!("tests/fixtures/{name}.yaml")).unwrap();
+    facet_yaml::from_str(&yaml).unwrap()
+}
+
+#[test]
+fn test_creature_entry() {
+    let cards = cockatrice_cards(&fixture("normal_creature"), &["elves.png".to_string()]);
+    assert_eq!(cards.len(), 1);
+
+    let card = &cards[0];
+    assert_eq!(card.name, "Llanowar Elves");
+    assert_eq!(card.mana_cost.as_deref(), Some("G"));
+    assert_eq!(card.mana_value, 1.0);
+    assert_eq!(card.colors, "G");
+    assert_eq!(card.pt.as_deref(), Some("1/1"));
+    assert_eq!(card.text, "{T}: Add {G}.");
+    assert_eq!(card.main_type(), "Creature");
+    assert_eq!(card.table_row(), 2);
+    assert_eq!(card.image, "elves.png");
+}
+
+#[test]
+fn test_planeswalker_entry() {
+    let cards = cockatrice_cards(&fixture("planeswalker"), &["jace.png".to_string()]);
+    let card = &cards[0];
+    assert_eq!(card.layout, "normal");
+    assert_eq!(card.loyalty.as_deref(), Some("3"));
+    assert!(card.text.starts_with("+2: Look at the top card"));
+    assert!(card.text.contains("\n-12: Exile all cards"));
+    assert_eq!(card.table_row(), 1);
+}
+
+#[test]
+fn test_split_entry() {
+    let cards = cockatrice_cards(&fixture("split"), &["fire_ice.png".to_string()]);
+    assert_eq!(cards.len(), 1);
+
+    let card = &cards[0];
+    assert_eq!(card.name, "Fire // Ice");
+    assert_eq!(card.mana_cost.as_deref(), Some("1R // 1U"));
+    assert_eq!(card.mana_value, 4.0);
+    assert_eq!(card.color_identity, "UR");
+    assert!(card.text.contains("\n\n---\n\nTap target permanent."));
+    assert_eq!(card.table_row(), 3);
+}
+
+#[test]
+fn test_transform_entries() {
+    let images = [
+        "delver_front.png".to_string(),
+        "delver_back.png".to_string(),
+    ];
+    let cards = cockatrice_cards(&fixture("transform"), &images);
+    assert_eq!(cards.len(), 2);
+
+    let (front, back) = (&cards[0], &cards[1]);
+    assert_eq!(front.side, "front");
+    assert_eq!(front.related, vec!["Insectile Aberration"]);
+    assert_eq!(back.name, "Insectile Aberration");
+    assert_eq!(back.side, "back");
+    assert_eq!(back.image, "delver_back.png");
+    assert_eq!(back.colors, "U");
+    assert_eq!(back.mana_cost, None);
+    assert_eq!(back.mana_value, 1.0);
+    assert_eq!(back.rarity, Rarity::Common);
+}
+
+#[test]
+fn test_set_xml() {
+    let mut set = CockatriceSet::new("TST", "Test & Set");
+    set.add(
+        &fixture("transform"),
+        &["a.png".to_string(), "b.png".to_string()],
+    );
+    let xml = set.to_xml();
+
+    assert!(xml.starts_with(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<cockatrice_carddatabase version=\"4\">"
+    ));
+    assert!(xml.contains("<longname>Test &amp; Set</longname>"));
+    assert!(xml.contains("<set rarity=\"common\" picurl=\"b.png\">TST</set>"));
+    assert!(xml.contains("<related attach=\"transform\">Insectile Aberration</related>"));
+    assert!(xml.contains("<side>back</side>"));
+    assert!(xml.trim_end().ends_with("</cockatrice_carddatabase>"));
+}
+
+#[test]
+fn test_export_with_kind_subfolders() {
+    let naming = OutputNaming {
+        face_template: "{face}_{name}".to_string(),
+        subfolders: true,
+        ..OutputNaming::default()
+    };
+    let image = |name: &str, card_path: &str| -> Vec<String> {
+        image_paths(&fixture(name), Path::new(card_path), &naming)
+            .iter()
+            .map(|path| path.display().to_string())
+            .collect()
+    };
+    assert_eq!(
+        image("normal_creature", "green/elves.png"),
+        ["green/cards/elves.png"]
+    );
+    assert_eq!(
+        image("transform", "delver.png"),
+        ["fronts/front_delver.png", "backs/back_delver.png"]
+    );
+
+    let mut set = CockatriceSet::new("TST", "Test");
+    set.add(
+        &fixture("normal_creature"),
+        &image("normal_creature", "green/elves.png"),
+    );
+    assert!(set.to_xml().contains("picurl=\"green/cards/elves.png\""));
+}
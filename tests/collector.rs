@@ -0,0 +1,204 @@
+use mtg_gen::collector::{ColorGroup, auto_numbers, numbering_csv, set_collector_number};
+use mtg_gen::order::{SortEntry, SortOrder, sort_entries};
+use mtg_gen::render::{card_to_html, with_rarity_letter};
+use mtg_gen::*;
+use std::path::{Path, PathBuf};
+
+fn card(name: &str, mana_cost: Option<&str>, type_line: &str) -> Card {
+    let mut yaml = format!("name: {name}\ntype: normal\ntype_line: {type_line}\nrarity: common\n");
+    if let Some(cost) = mana_cost {
+        yaml.push_str(&format!("mana_cost: \"{cost}\"\n"));
+    }
+    facet_yaml::from_str(&yaml).unwrap()
+}
+
+#[test]
+fn test_color_groups() {
+    let group = |cost, type_line| ColorGroup::of(&card("Test", cost, type_line));
+
+    assert_eq!(group(Some("{1}{W}"), "Creature"), ColorGroup::White);
+    assert_eq!(group(Some("{G}{G}"), "Creature"), ColorGroup::Green);
+    assert_eq!(group(Some("{W}{U}"), "Instant"), ColorGroup::Multicolor);
+    assert_eq!(group(Some("{R/G}"), "Instant"), ColorGroup::Multicolor);
+    assert_eq!(group(Some("{3}"), "Artifact"), ColorGroup::Artifact);
+    assert_eq!(
+        group(Some("{C}"), "Creature — Eldrazi"),
+        ColorGroup::Artifact
+    );
+    assert_eq!(group(None, "Land — Forest"), ColorGroup::Land);
+    assert_eq!(group(None, "Artifact Land"), ColorGroup::Land);
+}
+
+#[test]
+fn test_split_card_halves_count_for_color() {
+    let yaml = std::fs::read_to_string("tests/fixtures/split.yaml").unwrap();
+    let card: Card = facet_yaml::from_str(&yaml).unwrap();
+    assert_eq!(ColorGroup::of(&card), ColorGroup::Multicolor);
+}
+
+#[test]
+fn test_canonical_order() {
+    let cards = [
+        ("land.yaml", card("Forest", None, "Basic Land — Forest")),
+        (
+            "gold.yaml",
+            card("Azorius Charm", Some("{W}{U}"), "Instant"),
+        ),
+        ("bolt.yaml", card("Lightning Bolt", Some("{R}"), "Instant")),
+        (
+            "angel.yaml",
+            card("Serra Angel", Some("{3}{W}{W}"), "Creature"),
+        ),
+        (
+            "ajani.yaml",
+            card("ajani's Pridemate", Some("{1}{W}"), "Creature"),
+        ),
+        (
+            "golem.yaml",
+            card("Golem", Some("{4}"), "Artifact Creature"),
+        ),
+        ("shock.yaml", card("Shock", Some("{R}"), "Instant")),
+    ];
+    let mut entries: Vec<SortEntry> = cards
+        .iter()
+        .map(|(path, card)| SortEntry::new(PathBuf::from(path), Some(card)))
+        .collect();
+    sort_entries(&mut entries, SortOrder::Canonical);
+
+    let names: Vec<&str> = entries
+        .iter()
+        .map(|entry| entry.name.as_deref().unwrap())
+        .collect();
+    assert_eq!(
+        names,
+        [
+            "ajani's Pridemate",
+            "Serra Angel",
+            "Lightning Bolt",
+            "Shock",
+            "Azorius Charm",
+            "Golem",
+            "Forest"
+        ]
+    );
+}
+
+#[test]
+fn test_set_collector_number_after_rarity() {
+    let yaml = "# A bear\nname: Bear\ntype_line: Creature\nrarity: common\npower: \"2\"\n";
+    assert_eq!(
+        set_collector_number(yaml, "12"),
+        "# A bear\nname: Bear\ntype_line: Creature\nrarity: common\ncollector_number: \"12\"\npower: \"2\"\n"
+    );
+}
+
+#[test]
+fn test_set_collector_number_replaces_existing() {
+    let yaml = "name: Bear\ncollector_number: \"3\"\nrarity: common";
+    let updated = set_collector_number(yaml, "7");
+    assert_eq!(
+        updated,
+        "name: Bear\ncollector_number: \"7\"\nrarity: common\n"
+    );
+
+    let card: Card =
+        facet_yaml::from_str(&format!("{updated}type: normal\ntype_line: Creature\n")).unwrap();
+    assert_eq!(card.base().collector_number.as_deref(), Some("7"));
+}
+
+#[test]
+fn test_rarity_letter_without_collector_number() {
+    let card = card("Shock", Some("{R}"), "Instant");
+    let html = with_rarity_letter(&card_to_html(&card), Rarity::Common);
+    assert!(html.contains(".rarity-indicator { display: none; }"));
+    assert!(html.contains("<div class=\"collector-line\">C</div></body>"));
+}
+
+#[test]
+fn test_rarity_letter_keeps_collector_line() {
+    let mut card = card("Shock", Some("{R}"), "Instant");
+    if let Card::Normal(normal) = &mut card {
+        normal.base.collector_number = Some("12".to_string());
+    }
+    let html = with_rarity_letter(&card_to_html(&card), Rarity::Common);
+    assert_eq!(html.matches("class=\"collector-line\"").count(), 1);
+    assert!(html.contains(">12 C</div>"));
+}
+
+#[test]
+fn test_set_symbol_in_type_line() {
+    let mut card = card("Shock", Some("{R}"), "Instant");
+    let html = card_to_html(&card);
+    assert!(html.contains("<div class=\"rarity-indicator rarity-common\"></div></div>"));
+
+    let base = card.base_mut();
+    base.rarity = Rarity::Mythic;
+    base.set_code = Some("DOM".to_string());
+    base.set_symbol = Some("symbols/dom.svg".to_string());
+    card.resolve_art_paths(std::path::Path::new("/sets/dominaria"));
+    let html = card_to_html(&card);
+    assert!(html.contains(
+        "<svg class=\"rarity-indicator set-symbol-image rarity-mythic\" viewBox=\"0 0 40 30\"><title>DOM</title>"
+    ));
+    assert!(html.contains("<image href=\"file:///sets/dominaria/symbols/dom.svg\""));
+    // The symbol's shape masks its rarity's gradient
+    assert!(html.contains("fill=\"url(#set-symbol-gradient)\" mask=\"url(#set-symbol-mask)\""));
+    assert!(html.contains("stop-color: var(--rarity-center);"));
+    assert!(html.contains(".rarity-mythic { --rarity-center: #f7a33c;"));
+}
+
+#[test]
+fn test_artist_on_collector_line() {
+    let mut card = card("Shock", Some("{R}"), "Instant");
+    card.base_mut().artist = Some("Rebecca Guay".to_string());
+    let html = card_to_html(&card);
+    assert!(html.contains(
+        "<div class=\"collector-line\"><span class=\"artist-credit\">Illus. Rebecca Guay</span></div>"
+    ));
+    let html = with_rarity_letter(&html, Rarity::Common);
+    assert_eq!(html.matches("class=\"collector-line\"").count(), 1);
+    assert!(html.contains(">C • <span class=\"artist-credit\">"));
+
+    card.base_mut().collector_number = Some("12".to_string());
+    assert!(card_to_html(&card).contains(">12 C • <span class=\"artist-credit\">"));
+}
+
+#[test]
+fn test_auto_numbers_and_csv() {
+    let entries = vec![
+        SortEntry::new(
+            PathBuf::from("cards/red/shock.yaml"),
+            Some(&card("Shock", Some("{R}"), "Instant")),
+        ),
+        SortEntry::new(PathBuf::from("cards/broken.yaml"), None),
+        SortEntry::new(
+            PathBuf::from("cards/white/ajani.yaml"),
+            Some(&card("Ajani, Pridemate", Some("{1}{W}"), "Creature")),
+        ),
+        SortEntry::new(
+            PathBuf::from("cards/forest.yaml"),
+            Some(&card("Forest", None, "Basic Land — Forest")),
+        ),
+    ];
+    let numbers = auto_numbers(entries);
+    let assigned: Vec<(&str, &str)> = numbers
+        .iter()
+        .map(|number| (number.name.as_str(), number.number.as_str()))
+        .collect();
+    assert_eq!(
+        assigned,
+        [
+            ("Ajani, Pridemate", "1/3"),
+            ("Shock", "2/3"),
+            ("Forest", "3/3")
+        ]
+    );
+
+    assert_eq!(
+        numbering_csv(&numbers, Path::new("cards")),
+        "number,name,group,source\n\
+         1/3,\"Ajani, Pridemate\",white,white/ajani.yaml\n\
+         2/3,Shock,red,red/shock.yaml\n\
+         3/3,Forest,land,forest.yaml\n"
+    );
+}
@@ -0,0 +1,150 @@
+use mtg_gen::color_chips::{Color, card_colors, render_color_chips, with_color_chips};
+use mtg_gen::render::{derive_frame_color, face_frame_color};
+use mtg_gen::*;
+
+fn fixture(name: &str) -> Card {
+    let yaml = std::fs::read_to_string(format!("tests/fixtures/{name}.yaml")).unwrap();
+    facet_yaml::from_str(&yaml).unwrap()
+}
+
+fn with_cost(cost: &str) -> Card {
+    facet_yaml::from_str(&format!(
+        "name: Test\ntype: normal\ntype_line: Instant\nrarity: common\nmana_cost: \"{cost}\"\n"
+    ))
+    .unwrap()
+}
+
+#[test]
+fn test_card_colors() {
+    assert_eq!(card_colors(&fixture("normal_creature")), [Color::Green]);
+    assert_eq!(card_colors(&fixture("split")), [Color::Blue, Color::Red]);
+    assert_eq!(
+        card_colors(&with_cost("{G}{W/U}{2/B}")),
+        [Color::White, Color::Blue, Color::Black, Color::Green]
+    );
+    assert!(card_colors(&with_cost("{3}{C}")).is_empty());
+    assert_eq!(card_colors(&fixture("token")), [Color::Green]);
+}
+
+#[test]
+fn test_frame_color_matches_chips() {
+    let frame = |cost: &str| derive_frame_color(&CastingManaCost::parse(cost).ok());
+    assert_eq!(frame("{1}{W}"), "white");
+    assert_eq!(frame("{R/P}"), "red");
+    assert_eq!(frame("{W/U}"), "gold");
+    assert_eq!(frame("{C}"), "colorless");
+    assert_eq!(frame("{4}"), "artifact");
+}
+
+#[test]
+fn test_back_face_frame_follows_color_indicator() {
+    let card = fixture("transform");
+    let back = back_face(&card).unwrap();
+    assert_eq!(back.back.name.as_deref(), Some("Insectile Aberration"));
+    assert_eq!(face_frame_color(back.back), "blue");
+    assert_eq!(back.colors(), [Color::Blue]);
+
+    let mut face = back.back.clone();
+    face.color_indicator = Some(vec!["G".to_string(), "white".to_string()]);
+    assert_eq!(face_frame_color(&face), "gold");
+    assert_eq!(Color::from_name("Red"), Some(Color::Red));
+    assert_eq!(Color::from_name("purple"), None);
+
+    assert!(back_face(&fixture("normal_creature")).is_none());
+    assert!(back_face(&fixture("flip")).is_none());
+}
+
+#[test]
+fn test_chips_are_added_to_the_body() {
+    let colors = card_colors(&fixture("split"));
+    let html = with_color_chips("<html><body><div>card</div></body></html>", &colors);
+    assert!(html.ends_with("</div></body></html>"));
+
+    let chips = html.find("color-chips").unwrap();
+    assert!(chips > html.find("card</div>").unwrap());
+    assert!(html[chips..].find(">U<").unwrap() < html[chips..].find(">R<").unwrap());
+}
+
+#[test]
+fn test_colorless_cards_have_no_chips() {
+    assert!(render_color_chips(&[]).into_string().is_empty());
+    let html = "<html><body></body></html>";
+    assert_eq!(with_color_chips(html, &[]), html);
+}
+
+#[test]
+fn test_custom_color_frames() {
+    let frame = |cost: &str| derive_frame_color(&CastingManaCost::parse(cost).ok());
+    assert_eq!(frame("{1}{O}"), "custom-o");
+    assert_eq!(frame("{O}{O}"), "custom-o");
+    assert_eq!(frame("{O}{G}"), "gold");
+    assert_eq!(frame("{O}{V}"), "gold");
+}
+
+#[test]
+fn test_identity_frames_count_rules_text_mana() {
+    use mtg_gen::render::card_frame_color;
+
+    let land = |frame_colors: &str| -> Card {
+        facet_yaml::from_str(&format!(
+            "name: Island Tower\ntype: normal\ntype_line: Land\nrarity: rare\nrules_text: \"{{T}}: Add {{U}}{{U}}. (Spend {{R}} on it.)\"\n{frame_colors}"
+        ))
+        .unwrap()
+    };
+    assert_eq!(card_frame_color(land("").base()), "land");
+    assert_eq!(
+        card_frame_color(land("frame_colors: cost\n").base()),
+        "land"
+    );
+    assert_eq!(
+        card_frame_color(land("frame_colors: identity\n").base()),
+        "blue"
+    );
+
+    let mut card = with_cost("{1}{G}");
+    card.base_mut().rules_text = Some(RulesText::parse("{W}: Gain 1 life.").unwrap());
+    assert_eq!(card_frame_color(card.base()), "green");
+    card.base_mut().frame_colors = Some(FrameColors::Identity);
+    assert_eq!(card_frame_color(card.base()), "gold");
+}
+
+#[test]
+fn test_hybrid_sub_costs_blend_their_boxes() {
+    use mtg_gen::render::{hybrid_colors, render_cost_box};
+
+    let hybrid = |cost: &str| hybrid_colors(&CastingManaCost::parse(cost).ok());
+    assert_eq!(hybrid("{R/G}"), Some([Color::Red, Color::Green]));
+    assert_eq!(hybrid("{2}{W/U}{W/U/P}"), Some([Color::White, Color::Blue]));
+    assert_eq!(hybrid("{R/G}{G}"), None);
+    assert_eq!(hybrid("{R/G}{W/U}"), None);
+    assert_eq!(hybrid("{2/R}"), None);
+    assert_eq!(hybrid("{3}"), None);
+
+    let cost_box = |cost: &str| {
+        render_cost_box(
+            "split-text-box",
+            &CastingManaCost::parse(cost).ok(),
+            maud::html! {},
+        )
+        .into_string()
+    };
+    let blended = cost_box("{1}{R/G}");
+    assert!(blended.contains("hybrid-box text-box-bg-red"));
+    assert!(blended.contains("hybrid-blend text-box-bg-green"));
+    assert_eq!(
+        cost_box("{U}"),
+        r#"<div class="split-text-box text-box-bg-blue"></div>"#
+    );
+}
+
+#[test]
+fn test_adventure_bookmark_follows_its_own_cost() {
+    let mut card = fixture("adventure");
+    let Card::Adventure(ref mut adventure) = card else {
+        panic!("expected an adventure");
+    };
+    adventure.adventure.mana_cost = CastingManaCost::parse("{1}{W/U}").unwrap();
+    let html = mtg_gen::render::card_to_html(&card);
+    assert!(html.contains("adventure-left hybrid-box text-box-bg-white"));
+    assert!(html.contains("hybrid-blend text-box-bg-blue"));
+}
@@ -0,0 +1,83 @@
+use mtg_gen::decklist::{CardLibrary, DecklistEntry, parse_decklist};
+use std::path::{Path, PathBuf};
+
+fn entry(count: u32, name: &str, set: Option<&str>, number: Option<&str>) -> DecklistEntry {
+    DecklistEntry {
+        count,
+        name: name.to_string(),
+        set: set.map(str::to_string),
+        collector_number: number.map(str::to_string),
+    }
+}
+
+#[test]
+fn test_parse_decklist() {
+    let text = "\
+# Burn
+Deck
+4 Lightning Bolt
+4x Lava Spike
+1 Counterspell (MH2) 267
+2 Fire // Ice (MH2)
+Island
+
+Sideboard:
+SB: 3 Smash to Smithereens
+";
+    assert_eq!(
+        parse_decklist(text).unwrap(),
+        [
+            entry(4, "Lightning Bolt", None, None),
+            entry(4, "Lava Spike", None, None),
+            entry(1, "Counterspell", Some("mh2"), Some("267")),
+            entry(2, "Fire // Ice", Some("mh2"), None),
+            entry(1, "Island", None, None),
+            entry(3, "Smash to Smithereens", None, None),
+        ]
+    );
+}
+
+#[test]
+fn test_names_starting_with_numbers() {
+    let deck = parse_decklist("1 1996 World Champion\n2 Borrowing 100,000 Arrows").unwrap();
+    assert_eq!(deck[0], entry(1, "1996 World Champion", None, None));
+    assert_eq!(deck[1], entry(2, "Borrowing 100,000 Arrows", None, None));
+}
+
+#[test]
+fn test_decklist_errors() {
+    let error = parse_decklist("4 Lightning Bolt\n0 Shock").unwrap_err();
+    assert_eq!(error.line, 2);
+    assert_eq!(error.to_string(), "line 2: count must be at least 1");
+}
+
+#[test]
+fn test_scryfall_url_for_set_only() {
+    let deck = parse_decklist("Fire // Ice (MH2)").unwrap();
+    assert_eq!(
+        deck[0].scryfall_url(),
+        "https://api.scryfall.com/cards/named?exact=Fire%20%2F%2F%20Ice&set=mh2"
+    );
+}
+
+#[test]
+fn test_card_library_finds_cards_by_any_name() {
+    let mut library = CardLibrary::new();
+    library.insert("Fire // Ice", PathBuf::from("fire_ice.yaml"));
+    library.insert("Grizzly Bears", PathBuf::from("bears.yaml"));
+    library.insert("Grizzly Bears", PathBuf::from("bears_again.yaml"));
+    assert_eq!(library.len(), 4);
+
+    let find = |name: &str| library.find(&entry(1, name, None, None));
+    assert_eq!(find("Fire // Ice"), Some(Path::new("fire_ice.yaml")));
+    assert_eq!(find("ICE"), Some(Path::new("fire_ice.yaml")));
+    assert_eq!(find(" grizzly bears "), Some(Path::new("bears.yaml")));
+    assert_eq!(find("Lightning Bolt"), None);
+}
+
+#[test]
+fn test_proxy_file_names_carry_the_count() {
+    let deck = parse_decklist("4 Lightning Bolt\nFire // Ice").unwrap();
+    assert_eq!(deck[0].file_name("Lightning Bolt"), "4x_lightning_bolt.png");
+    assert_eq!(deck[1].file_name("Fire // Ice"), "1x_fire_ice.png");
+}
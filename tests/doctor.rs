@@ -0,0 +1,42 @@
+use mtg_gen::doctor::{CheckStatus, REQUIRED_ASSETS, check_assets};
+use std::fs;
+
+#[test]
+fn test_missing_asset_directory() {
+    let dir = tempfile::tempdir().unwrap();
+    let results = check_assets(&dir.path().join("missing"));
+
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].status, CheckStatus::Fail);
+    assert!(results[0].fix.is_some());
+}
+
+#[test]
+fn test_missing_fonts_reported_separately() {
+    let dir = tempfile::tempdir().unwrap();
+    for asset in REQUIRED_ASSETS.iter().filter(|a| !a.starts_with("fonts/")) {
+        let path = dir.path().join(asset);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, b"").unwrap();
+    }
+
+    let results = check_assets(dir.path());
+    let fonts = results.iter().find(|r| r.name == "fonts").unwrap();
+    let images = results.iter().find(|r| r.name == "images").unwrap();
+    assert_eq!(fonts.status, CheckStatus::Fail);
+    assert!(fonts.detail.contains("mplantin.ttf"));
+    assert_eq!(images.status, CheckStatus::Pass);
+}
+
+#[test]
+fn test_all_assets_present() {
+    let dir = tempfile::tempdir().unwrap();
+    for asset in REQUIRED_ASSETS {
+        let path = dir.path().join(asset);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, b"").unwrap();
+    }
+
+    let results = check_assets(dir.path());
+    assert!(results.iter().all(|r| r.status == CheckStatus::Pass));
+}
@@ -0,0 +1,90 @@
+use mtg_gen::editor::{CardForm, EditorError, FieldValue};
+use mtg_gen::schema::layouts;
+
+#[test]
+fn test_new_form_round_trips_for_every_layout() {
+    for layout in layouts() {
+        let form = CardForm::new(layout).unwrap();
+        assert_eq!(form.layout, layout);
+        assert!(
+            form.problems().is_empty(),
+            "{}: {:?}",
+            layout,
+            form.problems()
+        );
+
+        let card = form
+            .to_card()
+            .unwrap_or_else(|e| panic!("{} form failed to parse: {}", layout, e));
+        assert_eq!(card.layout_name(), layout);
+        assert_eq!(CardForm::from_yaml(&form.to_yaml()).unwrap(), form);
+    }
+}
+
+#[test]
+fn test_mana_cost_is_validated() {
+    let mut form = CardForm::new("normal").unwrap();
+    form.set("mana_cost", "{2}{K}");
+    let problem = form.field("mana_cost").unwrap().problem().unwrap();
+    assert!(problem.contains('K'), "{}", problem);
+    assert!(form.to_card().is_err());
+
+    form.set("mana_cost", "{2}{G}");
+    assert!(form.field("mana_cost").unwrap().problem().is_none());
+    assert!(form.to_card().is_ok());
+}
+
+#[test]
+fn test_required_fields_are_reported() {
+    let mut form = CardForm::new("battle").unwrap();
+    form.set("name", "");
+    form.set("defense", "lots");
+
+    let problems: Vec<&str> = form.problems().iter().map(|(name, _)| *name).collect();
+    assert_eq!(problems, ["name", "defense"]);
+}
+
+#[test]
+fn test_nested_fields_are_kept_verbatim() {
+    let yaml = include_str!("fixtures/planeswalker_emblem.yaml");
+    let form = CardForm::from_yaml(yaml).unwrap();
+    assert_eq!(form.layout, "planeswalker");
+
+    let Some(FieldValue::Block(abilities)) = form.field("loyalty_abilities").map(|f| &f.value)
+    else {
+        panic!("loyalty abilities should be a block");
+    };
+    assert!(abilities.contains("emblem"));
+    assert!(form.to_card().is_ok());
+}
+
+#[test]
+fn test_values_are_quoted_on_save() {
+    let mut form = CardForm::new("normal").unwrap();
+    form.set("name", r#"Jaya's "Immolating" Inferno"#);
+    form.set("rules_text", "Flying\n{T}: Add {R}.");
+
+    let reloaded = CardForm::from_yaml(&form.to_yaml()).unwrap();
+    assert_eq!(reloaded, form);
+    let card = reloaded.to_card().unwrap();
+    assert_eq!(card.name(), r#"Jaya's "Immolating" Inferno"#);
+}
+
+#[test]
+fn test_load_errors() {
+    assert_eq!(
+        CardForm::from_yaml("name: Bear\n"),
+        Err(EditorError::MissingType)
+    );
+    assert_eq!(
+        CardForm::from_yaml("type: vanguard\n"),
+        Err(EditorError::UnknownLayout("vanguard".to_string()))
+    );
+    assert_eq!(
+        CardForm::from_yaml("type: normal\nname: Bear\nloyalty: 3\n"),
+        Err(EditorError::UnknownField {
+            layout: "normal".to_string(),
+            field: "loyalty".to_string(),
+        })
+    );
+}
@@ -0,0 +1,49 @@
+use facet_yaml::from_str;
+use mtg_gen::examples::{example_yaml, layouts};
+use mtg_gen::*;
+
+#[test]
+fn test_every_example_parses_as_its_layout() {
+    for layout in layouts() {
+        let yaml = example_yaml(layout).unwrap();
+        let card: Card =
+            from_str(yaml).unwrap_or_else(|e| panic!("{} example failed to parse: {}", layout, e));
+        assert_eq!(card.layout_name(), layout);
+        card.validate()
+            .unwrap_or_else(|e| panic!("{} example is invalid: {}", layout, e));
+    }
+}
+
+#[test]
+fn test_every_layout_has_an_example() {
+    let expected = [
+        "normal",
+        "planeswalker",
+        "saga",
+        "class",
+        "adventure",
+        "split",
+        "flip",
+        "transform",
+        "modal_dfc",
+        "battle",
+        "meld",
+        "leveler",
+        "prototype",
+        "token",
+    ];
+    assert_eq!(layouts().collect::<Vec<_>>(), expected);
+    assert!(example_yaml("vanguard").is_none());
+}
+
+#[test]
+fn test_examples_are_commented() {
+    for layout in layouts() {
+        let yaml = example_yaml(layout).unwrap();
+        assert!(
+            yaml.starts_with('#'),
+            "{} example has no header comment",
+            layout
+        );
+    }
+}
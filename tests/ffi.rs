@@ -0,0 +1,66 @@
+#![cfg(feature = "ffi")]
+
+use mtg_gen::ffi::*;
+use std::ffi::{CStr, CString, c_char};
+use std::ptr;
+
+/// Take ownership of a string returned by the library.
+fn take_string(string: *mut c_char) -> String {
+    assert!(!string.is_null());
+    let owned = unsafe { CStr::from_ptr(string) }
+        .to_str()
+        .unwrap()
+        .to_string();
+    unsafe { mtg_gen_string_free(string) };
+    owned
+}
+
+#[test]
+fn test_parse_card_yaml() {
+    let yaml =
+        CString::new(std::fs::read_to_string("tests/fixtures/normal_creature.yaml").unwrap())
+            .unwrap();
+    let mut error = ptr::null_mut();
+    let card = unsafe { mtg_gen_card_parse_yaml(yaml.as_ptr(), &mut error) };
+    assert!(!card.is_null());
+    assert!(error.is_null());
+
+    let name = take_string(unsafe { mtg_gen_card_name(card) });
+    assert!(!name.is_empty());
+    unsafe { mtg_gen_card_free(card) };
+}
+
+#[test]
+fn test_parse_error_is_reported() {
+    let yaml = CString::new("type: normal\nname: [").unwrap();
+    let mut error = ptr::null_mut();
+    let card = unsafe { mtg_gen_card_parse_yaml(yaml.as_ptr(), &mut error) };
+    assert!(card.is_null());
+    assert!(!take_string(error).is_empty());
+
+    // A null error pointer is allowed
+    let card = unsafe { mtg_gen_card_parse_yaml(yaml.as_ptr(), ptr::null_mut()) };
+    assert!(card.is_null());
+}
+
+#[test]
+fn test_null_arguments_are_errors() {
+    let mut error = ptr::null_mut();
+    assert!(unsafe { mtg_gen_card_parse_yaml(ptr::null(), &mut error) }.is_null());
+    assert_eq!(take_string(error), "string is null");
+
+    let mut len = 0;
+    let mut error = ptr::null_mut();
+    let png =
+        unsafe { mtg_gen_render_card_to_png_bytes(ptr::null(), ptr::null(), &mut len, &mut error) };
+    assert!(png.is_null());
+    assert_eq!(take_string(error), "renderer is null");
+
+    // Freeing null does nothing
+    unsafe {
+        mtg_gen_card_free(ptr::null_mut());
+        mtg_gen_renderer_free(ptr::null_mut());
+        mtg_gen_bytes_free(ptr::null_mut(), 0);
+        mtg_gen_string_free(ptr::null_mut());
+    }
+}
@@ -0,0 +1,54 @@
+use mtg_gen::filter::{CardFilter, glob_match, parse_rarities};
+use mtg_gen::*;
+use std::fs;
+
+fn fixture(name: &str) -> Card {
+    let yaml = fs::read_to_string(format!("tests/fixtures/{}.yaml", name)).unwrap();
+    facet_yaml::from_str(&yaml).unwrap()
+}
+
+#[test]
+fn test_glob_match() {
+    assert!(glob_match("*", "Anything"));
+    assert!(glob_match("grave*", "Gravecrawler"));
+    assert!(glob_match("*of*", "Delver of Secrets"));
+    assert!(glob_match("b?ar", "Bear"));
+    assert!(!glob_match("b?ar", "Bar"));
+    assert!(!glob_match("delver", "Delver of Secrets"));
+    assert!(glob_match("", ""));
+}
+
+#[test]
+fn test_parse_rarities() {
+    assert_eq!(
+        parse_rarities("uncommon+"),
+        Ok(vec![Rarity::Uncommon, Rarity::Rare, Rarity::Mythic])
+    );
+    assert_eq!(parse_rarities("Mythic"), Ok(vec![Rarity::Mythic]));
+    assert!(parse_rarities("special+").is_err());
+    assert!(parse_rarities("legendary").is_err());
+}
+
+#[test]
+fn test_filter_matches() {
+    let planeswalker = fixture("planeswalker");
+    let creature = fixture("normal_creature");
+    let saga = fixture("saga");
+
+    assert!(CardFilter::default().is_empty());
+    assert!(CardFilter::default().matches(&creature));
+
+    let filter = CardFilter::parse(None, Some("planeswalker, saga"), None).unwrap();
+    assert!(filter.matches(&planeswalker));
+    assert!(filter.matches(&saga));
+    assert!(!filter.matches(&creature));
+
+    let filter = CardFilter::parse(Some("jace*"), None, None).unwrap();
+    assert!(filter.matches(&planeswalker));
+    assert!(!filter.matches(&creature));
+
+    let filter = CardFilter::parse(None, None, Some("rare+")).unwrap();
+    assert!(filter.matches(&planeswalker));
+    assert!(!filter.matches(&saga));
+    assert!(!filter.matches(&creature));
+}
@@ -0,0 +1,64 @@
+use mtg_gen::frame_effects::{card_seed, render_frame_effects, with_frame_effects};
+use mtg_gen::style::StyleConfig;
+use mtg_gen::*;
+
+#[test]
+fn test_parse_frame_effects() {
+    let yaml = "name: Test\ntype: normal\ntype_line: Instant\nrarity: common\nframe_effects: [worn, grunge]\n";
+    let card: Card = facet_yaml::from_str(yaml).unwrap();
+    assert_eq!(
+        card.base().frame_effects,
+        vec![FrameEffect::Worn, FrameEffect::Grunge]
+    );
+    assert_eq!(card.frame_effects(), card.base().frame_effects);
+}
+
+#[test]
+fn test_frame_effect_opacity() {
+    let style: StyleConfig = facet_yaml::from_str(
+        "frame_effects:\n  worn: { opacity: 0.25 }\n  grunge: { opacity: 3 }\n",
+    )
+    .unwrap();
+    assert_eq!(style.frame_effect_opacity(FrameEffect::Worn), 0.25);
+    assert_eq!(style.frame_effect_opacity(FrameEffect::Grunge), 1.0);
+    assert_eq!(
+        StyleConfig::default().frame_effect_opacity(FrameEffect::Worn),
+        FrameEffect::Worn.default_opacity()
+    );
+}
+
+#[test]
+fn test_effects_are_layered_over_the_card() {
+    let style = StyleConfig::default();
+    let html = with_frame_effects(
+        "<html><body><div>card</div></body></html>",
+        &[FrameEffect::Worn, FrameEffect::Grunge],
+        &style,
+        None,
+    );
+    assert!(html.ends_with("</div></body></html>"));
+    let worn = html.find("frame-effect frame-effect-worn").unwrap();
+    assert!(worn > html.find("card</div>").unwrap());
+    assert!(html[worn..].contains("frame-effect-grunge"));
+    assert!(html.contains("opacity: 0.7;"));
+
+    assert!(html.contains("seed='7'"));
+    assert!(html.contains("seed='3'"));
+
+    assert!(
+        render_frame_effects(&[], &style, None)
+            .into_string()
+            .is_empty()
+    );
+}
+
+#[test]
+fn test_seeded_effects_vary_by_card() {
+    let style = StyleConfig::default();
+    let render = |seed| render_frame_effects(&[FrameEffect::Worn], &style, seed).into_string();
+    let bolt = render(Some(card_seed(1, "Bolt")));
+    assert!(!bolt.contains("seed='7'"));
+    assert_eq!(bolt, render(Some(card_seed(1, "Bolt"))));
+    assert_ne!(bolt, render(Some(card_seed(1, "Shock"))));
+    assert_ne!(bolt, render(Some(card_seed(2, "Bolt"))));
+}
@@ -0,0 +1,68 @@
+#![cfg(all(unix, feature = "cli"))]
+
+use mtg_gen::hooks::{PostRenderHook, command_line};
+use std::fs;
+use std::path::Path;
+
+#[test]
+fn test_output_path_is_quoted() {
+    assert_eq!(
+        command_line("optipng {output}", Path::new("out/Jace's Card.png")),
+        r"optipng 'out/Jace'\''s Card.png'"
+    );
+    assert_eq!(
+        command_line("echo no placeholder", Path::new("a.png")),
+        "echo no placeholder"
+    );
+}
+
+#[tokio::test]
+async fn test_hook_runs_on_every_output() {
+    let dir = tempfile::tempdir().unwrap();
+    let outputs: Vec<_> = (0..5)
+        .map(|i| dir.path().join(format!("card {}.png", i)))
+        .collect();
+    for output in &outputs {
+        fs::write(output, b"png").unwrap();
+    }
+
+    let mut hook = PostRenderHook::new("mv {output} {output}.done", 2);
+    for output in &outputs {
+        hook.run(output.clone());
+    }
+    let (ran, failures) = hook.finish().await;
+
+    assert_eq!(ran, 5);
+    assert!(failures.is_empty(), "{:?}", failures);
+    for output in &outputs {
+        assert!(!output.exists());
+        assert!(output.with_extension("png.done").exists());
+    }
+}
+
+#[tokio::test]
+async fn test_hook_failures_are_reported() {
+    let mut hook = PostRenderHook::new("echo broken >&2; exit 3", 1);
+    hook.run("ok.png".into());
+    let (ran, failures) = hook.finish().await;
+
+    assert_eq!(ran, 1);
+    assert_eq!(failures.len(), 1);
+    assert_eq!(failures[0].output, Path::new("ok.png"));
+    assert!(failures[0].reason.contains('3'), "{}", failures[0].reason);
+    assert_eq!(failures[0].stderr, "broken");
+}
+
+#[tokio::test]
+async fn test_dropping_hook_kills_commands() {
+    let dir = tempfile::tempdir().unwrap();
+    let output = dir.path().join("card.png");
+
+    let mut hook = PostRenderHook::new("sleep 1 && touch {output}", 1);
+    hook.run(output.clone());
+    tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+    drop(hook);
+
+    tokio::time::sleep(std::time::Duration::from_millis(1500)).await;
+    assert!(!output.exists());
+}
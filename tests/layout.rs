@@ -0,0 +1,346 @@
+//! Frame alignment across layouts, measured in Chrome.
+//!
+//! Run with `cargo test --features chrome-tests` from the repository root,
+//! so the mtgrender assets resolve.
+#![cfg(feature = "chrome-tests")]
+
+use mtg_gen::render::ElementBox;
+use mtg_gen::*;
+
+/// Allowed drift, in CSS pixels
+const TOLERANCE: f64 = 0.5;
+
+const TITLE: &str = ".card-header .card-name";
+const TEXT_BOX: &str = ".text-box";
+const PT_BOX: &str = ".pt-box";
+
+/// Layouts drawn on the standard frame; adventure and split cards have
+/// frames of their own.
+const STANDARD_FRAME: &[&str] = &[
+    "battle",
+    "class",
+    "flip",
+    "leveler",
+    "modal_dfc",
+    "planeswalker",
+    "prototype",
+    "saga",
+    "transform",
+];
+
+/// Layouts with the standard text box
+const STANDARD_TEXT_BOX: &[&str] = &["battle", "leveler", "modal_dfc", "prototype", "transform"];
+
+/// Layouts with a power/toughness box on the face that's rendered
+const WITH_PT_BOX: &[&str] = &["prototype", "transform"];
+
+fn fixture(name: &str) -> Card {
+    let yaml = std::fs::read_to_string(format!("tests/fixtures/{name}.yaml")).unwrap();
+    facet_yaml::from_str(&yaml).unwrap()
+}
+
+async fn measure(renderer: &Renderer, name: &str, selector: &str) -> ElementBox {
+    renderer
+        .element_boxes(&fixture(name), &[selector])
+        .await
+        .unwrap()
+        .remove(0)
+        .unwrap_or_else(|| panic!("{name}: no element matches {selector}"))
+}
+
+fn assert_near(layout: &str, what: &str, actual: f64, expected: f64) {
+    assert!(
+        (actual - expected).abs() <= TOLERANCE,
+        "{layout}: {what} is at {actual}px, the normal frame has it at {expected}px"
+    );
+}
+
+#[tokio::test]
+async fn test_title_baseline_matches_normal_frame() {
+    let renderer = Renderer::new().await.unwrap();
+    let normal = measure(&renderer, "normal_creature", TITLE).await;
+    for &layout in STANDARD_FRAME {
+        let title = measure(&renderer, layout, TITLE).await;
+        assert_near(layout, "title baseline", title.baseline, normal.baseline);
+        assert_near(layout, "title left edge", title.x, normal.x);
+    }
+    renderer.close().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_text_box_top_matches_normal_frame() {
+    let renderer = Renderer::new().await.unwrap();
+    let normal = measure(&renderer, "normal_creature", TEXT_BOX).await;
+    for &layout in STANDARD_TEXT_BOX {
+        let text_box = measure(&renderer, layout, TEXT_BOX).await;
+        assert_near(layout, "text box top", text_box.y, normal.y);
+    }
+    renderer.close().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_pt_box_corner_matches_normal_frame() {
+    let renderer = Renderer::new().await.unwrap();
+    let normal = measure(&renderer, "normal_creature", PT_BOX).await;
+    for &layout in WITH_PT_BOX {
+        let pt_box = measure(&renderer, layout, PT_BOX).await;
+        assert_near(layout, "P/T box right edge", pt_box.right(), normal.right());
+        assert_near(
+            layout,
+            "P/T box bottom edge",
+            pt_box.bottom(),
+            normal.bottom(),
+        );
+    }
+    renderer.close().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_missing_elements_are_none() {
+    let renderer = Renderer::new().await.unwrap();
+    let boxes = renderer
+        .element_boxes(&fixture("planeswalker"), &[PT_BOX, ".loyalty-counter"])
+        .await
+        .unwrap();
+    assert!(boxes[0].is_none());
+    assert!(boxes[1].is_some());
+    renderer.close().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_planeswalker_ability_bars_span_text_box() {
+    let renderer = Renderer::new().await.unwrap();
+    let boxes = renderer
+        .element_boxes(
+            &fixture("planeswalker"),
+            &[
+                ".planeswalker-text-box",
+                ".loyalty-ability",
+                ".loyalty-badge",
+            ],
+        )
+        .await
+        .unwrap();
+    let (text_box, bar, badge) = (boxes[0].unwrap(), boxes[1].unwrap(), boxes[2].unwrap());
+    assert_near("planeswalker", "ability bar left edge", bar.x, text_box.x);
+    assert_near(
+        "planeswalker",
+        "ability bar width",
+        bar.width,
+        text_box.width,
+    );
+    // The cost badge hangs over the frame's left edge
+    assert!(badge.x < bar.x);
+    renderer.close().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_saga_chapters_left_of_art() {
+    let renderer = Renderer::new().await.unwrap();
+    // Chapter II-III is the reminder's second sibling
+    let boxes = renderer
+        .element_boxes(
+            &fixture("saga_read_ahead"),
+            &[
+                ".saga-text-box",
+                ".art-box",
+                ".saga-chapter:nth-child(3) .saga-chapter-numeral:nth-child(1)",
+                ".saga-chapter:nth-child(3) .saga-chapter-numeral:nth-child(2)",
+            ],
+        )
+        .await
+        .unwrap();
+    let (text_box, art) = (boxes[0].unwrap(), boxes[1].unwrap());
+    let (first, second) = (boxes[2].unwrap(), boxes[3].unwrap());
+    assert!(text_box.x + text_box.width <= art.x + 1.0);
+    assert!(art.height > art.width);
+    // Combined chapters stack their numerals
+    assert_near("saga_read_ahead", "numeral left edge", second.x, first.x);
+    assert!(second.y >= first.y + first.height);
+    renderer.close().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_flip_card_halves_surround_art() {
+    let renderer = Renderer::new().await.unwrap();
+    let boxes = renderer
+        .element_boxes(
+            &fixture("flip"),
+            &[
+                ".flip-half .flip-text-box",
+                ".art-box",
+                ".flip-bottom .flip-text-box",
+                ".flip-bottom .card-name",
+            ],
+        )
+        .await
+        .unwrap();
+    let (top_text, art) = (boxes[0].unwrap(), boxes[1].unwrap());
+    let (bottom_text, bottom_name) = (boxes[2].unwrap(), boxes[3].unwrap());
+    assert!(top_text.bottom() <= art.y);
+    assert!(bottom_text.y >= art.bottom());
+    // Turned upside down, the flipped face's name is at the card's bottom
+    assert!(bottom_name.y > bottom_text.bottom());
+    renderer.close().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_leveler_strips_stack_with_their_own_pt() {
+    let renderer = Renderer::new().await.unwrap();
+    let boxes = renderer
+        .element_boxes(
+            &fixture("leveler"),
+            &[
+                ".leveler-text-box",
+                ".leveler-strip:nth-child(2)",
+                ".leveler-strip:nth-child(3)",
+                ".leveler-strip:nth-child(3) .leveler-badge",
+                ".leveler-strip:nth-child(3) .leveler-pt",
+            ],
+        )
+        .await
+        .unwrap();
+    let text_box = boxes[0].unwrap();
+    let (second, third) = (boxes[1].unwrap(), boxes[2].unwrap());
+    let (badge, pt) = (boxes[3].unwrap(), boxes[4].unwrap());
+    assert!(third.y >= second.bottom() - 1.0);
+    assert!(third.bottom() <= text_box.bottom() + 1.0);
+    assert!(badge.right() <= pt.x);
+    assert_near(
+        "leveler",
+        "strip P/T right edge",
+        pt.right(),
+        text_box.right() - 14.0,
+    );
+    renderer.close().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_prototype_inset_beside_rules_text() {
+    let renderer = Renderer::new().await.unwrap();
+    let boxes = renderer
+        .element_boxes(
+            &fixture("prototype"),
+            &[
+                ".text-box",
+                ".prototype-inset",
+                ".prototype-cost",
+                ".prototype-pt",
+                ".text-box .rules-text",
+            ],
+        )
+        .await
+        .unwrap();
+    let (text_box, inset) = (boxes[0].unwrap(), boxes[1].unwrap());
+    let (cost, pt, rules) = (boxes[2].unwrap(), boxes[3].unwrap(), boxes[4].unwrap());
+    assert!(inset.x >= text_box.x && inset.y >= text_box.y);
+    assert!(inset.bottom() <= text_box.bottom());
+    assert!(pt.y >= cost.bottom());
+    assert!(rules.x >= inset.right());
+    renderer.close().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_set_symbol_sits_in_type_line() {
+    let renderer = Renderer::new().await.unwrap();
+    for &layout in ["normal_creature"].iter().chain(STANDARD_FRAME) {
+        let boxes = renderer
+            .element_boxes(
+                &fixture(layout),
+                &[".type-line", ".type-line .rarity-indicator"],
+            )
+            .await
+            .unwrap();
+        let (type_line, symbol) = (boxes[0].unwrap(), boxes[1].unwrap());
+        assert!(
+            symbol.x >= type_line.x && symbol.right() <= type_line.right(),
+            "{layout}: set symbol sticks out of the type line"
+        );
+        assert!(
+            symbol.y >= type_line.y && symbol.bottom() <= type_line.bottom(),
+            "{layout}: set symbol sticks out of the type line"
+        );
+    }
+    renderer.close().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_long_rules_text_shrinks_to_fit() {
+    let renderer = Renderer::new().await.unwrap();
+    let mut card = fixture("normal_creature");
+    let long = "Flying, first strike, vigilance, trample, haste. ".repeat(20);
+    card.base_mut().rules_text = Some(RulesText::parse(&long).unwrap());
+    let boxes = renderer
+        .element_boxes(&card, &[TEXT_BOX, ".text-box .flavor-text"])
+        .await
+        .unwrap();
+    let (text_box, flavor) = (boxes[0].unwrap(), boxes[1].unwrap());
+    assert!(
+        flavor.bottom() <= text_box.bottom() + TOLERANCE,
+        "flavor text ends at {}px, below the text box at {}px",
+        flavor.bottom(),
+        text_box.bottom()
+    );
+    renderer.close().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_strict_fails_cards_with_missing_images() {
+    let mut card = fixture("normal_creature");
+    card.base_mut().art = Some("/nonexistent/art.png".to_string());
+
+    let mut renderer = Renderer::new().await.unwrap();
+    let png = renderer.render_card_png(&card).await.unwrap();
+    assert!(png.warnings[0].contains("/nonexistent/art.png"));
+    renderer.set_strict(true);
+    let error = renderer.render_card_png(&card).await.unwrap_err();
+    assert!(
+        error.to_string().contains("/nonexistent/art.png"),
+        "{error}"
+    );
+    renderer.close().await.unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_render_outcome_lists_missing_images() {
+    let mut card = fixture("normal_creature");
+    card.base_mut().art = Some("/nonexistent/art.png".to_string());
+    let dir = tempfile::tempdir().unwrap();
+    let output = dir.path().join("card.png");
+
+    let renderer = Renderer::new().await.unwrap();
+    let outcome = renderer.render_card(&card, &output).await.unwrap();
+    assert_eq!(outcome.output_paths, vec![output]);
+    assert!(
+        outcome
+            .warnings
+            .iter()
+            .any(|warning| warning.contains("/nonexistent/art.png")),
+        "{:?}",
+        outcome.warnings
+    );
+    assert!(outcome.timings.total_ms() > 0);
+    renderer.close().await.unwrap();
+}
+
+#[test]
+fn test_render_card_blocking() {
+    let renderer = Renderer::new_blocking().unwrap();
+    let dir = tempfile::tempdir().unwrap();
+    let output = dir.path().join("normal_creature.png");
+    renderer
+        .render_card_blocking(&fixture("normal_creature"), &output)
+        .unwrap();
+    assert!(std::fs::metadata(&output).unwrap().len() > 0);
+    renderer.close_blocking().unwrap();
+}
+
+#[test]
+fn test_renderer_works_without_tokio() {
+    futures::executor::block_on(async {
+        let renderer = Renderer::new().await.unwrap();
+        let title = measure(&renderer, "normal_creature", TITLE).await;
+        assert!(title.width > 0.0);
+        renderer.close().await.unwrap();
+    });
+}
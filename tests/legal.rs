@@ -0,0 +1,55 @@
+use mtg_gen::legal::{NOT_FOR_SALE, legal_line, with_legal_line};
+use mtg_gen::style::{LegalStyle, StyleConfig};
+
+fn style() -> LegalStyle {
+    let style: StyleConfig = facet_yaml::from_str(
+        "legal:\n  template: \"™ & © {year} {studio}\"\n  studio: Cramt Games\n  sets:\n    ABC: \"{set} ™ & © {year} Someone Else\"\n",
+    )
+    .unwrap();
+    style.legal.unwrap()
+}
+
+#[test]
+fn test_legal_templates_per_set() {
+    let style = style();
+    assert_eq!(
+        legal_line(Some(&style), Some("DOM"), false, 2026).as_deref(),
+        Some("™ & © 2026 Cramt Games")
+    );
+    assert_eq!(
+        legal_line(Some(&style), Some("abc"), false, 2026).as_deref(),
+        Some("abc ™ & © 2026 Someone Else")
+    );
+
+    let fixed = LegalStyle {
+        year: Some(1993),
+        ..style
+    };
+    assert_eq!(
+        legal_line(Some(&fixed), None, false, 2026).as_deref(),
+        Some("™ & © 1993 Cramt Games")
+    );
+}
+
+#[test]
+fn test_proxies_are_not_for_sale() {
+    let style = style();
+    assert_eq!(
+        legal_line(Some(&style), None, true, 2026).as_deref(),
+        Some("™ & © 2026 Cramt Games — NOT FOR SALE")
+    );
+    assert_eq!(
+        legal_line(None, None, true, 2026).as_deref(),
+        Some(NOT_FOR_SALE)
+    );
+    assert_eq!(
+        legal_line(Some(&LegalStyle::default()), None, false, 2026),
+        None
+    );
+}
+
+#[test]
+fn test_legal_line_is_added_to_the_body() {
+    let html = with_legal_line("<html><body><div>card</div></body></html>", "™ & © 2026");
+    assert!(html.ends_with("<div class=\"legal-line\">™ &amp; © 2026</div></body></html>"));
+}
@@ -0,0 +1,56 @@
+use mtg_gen::legend::{SYMBOLS_PER_CARD, legend_cards, parse_symbols, supported_symbols};
+use mtg_gen::render::card_to_html;
+use mtg_gen::*;
+
+#[test]
+fn test_supported_symbols_are_listed_once() {
+    let entries = supported_symbols();
+    for (i, entry) in entries.iter().enumerate() {
+        assert!(
+            !entries[..i].iter().any(|e| e.symbol == entry.symbol),
+            "{} listed twice",
+            entry.symbol
+        );
+    }
+    let energy = entries
+        .iter()
+        .find(|entry| entry.symbol == ManaSymbol::Energy)
+        .unwrap();
+    assert_eq!(energy.meaning, "an energy counter");
+}
+
+#[test]
+fn test_parse_symbols() {
+    let entries = parse_symbols("{E} {CHAOS}{E}").unwrap();
+    let symbols: Vec<ManaSymbol> = entries.iter().map(|entry| entry.symbol).collect();
+    assert_eq!(symbols, vec![ManaSymbol::Energy, ManaSymbol::Chaos]);
+    assert!(parse_symbols("{E} and {T}").is_err());
+    assert!(parse_symbols("{NOPE}").is_err());
+}
+
+#[test]
+fn test_legend_splits_over_cards() {
+    let entries = supported_symbols();
+    let count = entries.len();
+    let cards = legend_cards(entries);
+    assert_eq!(cards.len(), count.div_ceil(SYMBOLS_PER_CARD));
+    assert!(
+        cards
+            .iter()
+            .all(|card| card.entries.len() <= SYMBOLS_PER_CARD)
+    );
+    assert_eq!(cards[1].page_label(), Some(format!("2 / {}", cards.len())));
+
+    let single = legend_cards(parse_symbols("{E}").unwrap());
+    assert_eq!(single.len(), 1);
+    assert_eq!(single[0].page_label(), None);
+}
+
+#[test]
+fn test_legend_html_lists_meanings() {
+    let cards = legend_cards(parse_symbols("{E}{T}").unwrap());
+    let html = card_to_html(&cards[0]);
+    assert!(html.contains("Symbol Reference"));
+    assert!(html.contains("an energy counter"));
+    assert!(html.contains("E.svg"));
+}
@@ -423,3 +423,341 @@ fn test_rules_text_unknown_symbol_error() {
     let result = RulesText::parse("{INVALID}");
     assert!(result.is_err());
 }
+
+// ============================================================================
+// Extended Symbology Tests
+// ============================================================================
+
+#[test]
+fn test_parse_hybrid_phyrexian() {
+    assert_eq!(
+        CastingManaCost::parse("{W/U/P}").unwrap().symbols,
+        vec![CastingManaSymbol::PhyrexianWhiteBlue]
+    );
+    assert_eq!(
+        CastingManaCost::parse("{G/W/P}").unwrap().symbols,
+        vec![CastingManaSymbol::PhyrexianWhiteGreen]
+    );
+    assert_eq!(
+        CastingManaCost::parse("{R/G/P}").unwrap().to_string(),
+        "{R/G/P}"
+    );
+}
+
+#[test]
+fn test_parse_colorless_hybrid_and_half_mana() {
+    assert_eq!(
+        CastingManaCost::parse("{C/W}{HR}{½}{∞}").unwrap().symbols,
+        vec![
+            CastingManaSymbol::ColorlessWhite,
+            CastingManaSymbol::HalfRed,
+            CastingManaSymbol::Half,
+            CastingManaSymbol::Infinity,
+        ]
+    );
+}
+
+#[test]
+fn test_rules_text_only_symbols() {
+    let cost = ActionCost::parse("{P}{PW}{A}{TK}{L}{D}").unwrap();
+    assert_eq!(
+        cost.symbols,
+        vec![
+            ManaSymbol::Phyrexian,
+            ManaSymbol::Planeswalker,
+            ManaSymbol::Acorn,
+            ManaSymbol::Ticket,
+            ManaSymbol::Legendary,
+            ManaSymbol::LandDrop,
+        ]
+    );
+    assert_eq!(cost.to_string(), "{P}{PW}{A}{TK}{L}{D}");
+}
+
+#[test]
+fn test_generic_phyrexian_not_a_casting_symbol() {
+    let err = CastingManaCost::parse("{P}").unwrap_err();
+    assert!(matches!(err, ManaCostParseError::UnknownSymbol { .. }));
+}
+
+#[test]
+fn test_symbology_snapshot_fully_supported() {
+    assert_eq!(
+        mtg_gen::symbology::unsupported_symbols(),
+        Vec::<&str>::new()
+    );
+}
+
+#[test]
+fn test_symbology_lookup_ignores_hybrid_order() {
+    let entry = mtg_gen::symbology::lookup(ManaSymbol::Casting(CastingManaSymbol::WhiteRed))
+        .expect("hybrid symbol in snapshot");
+    assert_eq!(entry.code, "{R/W}");
+    assert_eq!(entry.svg, "RW");
+}
+
+#[test]
+fn test_unexpected_multibyte_character() {
+    let err = CastingManaCost::parse("{1}é{U}").unwrap_err();
+    assert_eq!(
+        err,
+        ManaCostParseError::UnexpectedCharacter {
+            character: 'é',
+            position: 3
+        }
+    );
+
+    let err = ActionCost::parse("{T}→").unwrap_err();
+    assert!(matches!(
+        err,
+        ManaCostParseError::UnexpectedCharacter {
+            character: '→', ..
+        }
+    ));
+}
+
+#[test]
+fn test_multibyte_symbols_inside_braces() {
+    assert_eq!(
+        CastingManaCost::parse("{½}{∞}").unwrap().symbols,
+        vec![CastingManaSymbol::Half, CastingManaSymbol::Infinity]
+    );
+    let err = CastingManaCost::parse("{日本}").unwrap_err();
+    assert!(matches!(err, ManaCostParseError::UnknownSymbol { symbol } if symbol == "日本"));
+    assert!(RulesText::parse("ダメージ{ü").is_err());
+}
+
+#[test]
+fn test_generic_cost_saturates() {
+    let cost = CastingManaCost::parse("{4294967295}{1}").unwrap();
+    assert_eq!(cost.generic_cost(), u32::MAX);
+}
+
+#[test]
+fn test_rules_text_word_count() {
+    let text = RulesText::parse("{T}: Add {G}{G}.\nFlying").unwrap();
+    assert_eq!(text.word_count(), 4);
+    assert_eq!(RulesText::default().word_count(), 0);
+}
+
+#[test]
+fn test_rules_text_estimated_lines() {
+    assert_eq!(RulesText::default().estimated_lines(40), 0);
+
+    let text = RulesText::parse("Draw a card.").unwrap();
+    assert_eq!(text.estimated_lines(40), 1);
+    assert_eq!(text.estimated_lines(6), 2);
+    // The symbol and colon are one word of width 3
+    assert_eq!(RulesText::parse("{T}:").unwrap().estimated_lines(3), 1);
+    // Words longer than a line are broken
+    assert_eq!(
+        RulesText::parse("abcdefghij").unwrap().estimated_lines(4),
+        3
+    );
+    assert_eq!(RulesText::parse("a\n\nb").unwrap().estimated_lines(40), 3);
+    assert_eq!(text.estimated_lines(0), text.estimated_lines(1));
+}
+
+#[test]
+fn test_parse_custom_mana() {
+    let cost = CastingManaCost::parse("{2}{O}{O}").unwrap();
+    assert_eq!(
+        cost.symbols[1..],
+        [
+            CastingManaSymbol::Custom(CustomMana::O),
+            CastingManaSymbol::Custom(CustomMana::O),
+        ]
+    );
+    assert_eq!(cost.to_string(), "{2}{O}{O}");
+    assert_eq!(CustomMana::from_letter("V"), Some(CustomMana::V));
+    assert_eq!(CustomMana::from_letter("OO"), None);
+    // Letters real symbols use are never custom
+    assert!(CastingManaCost::parse("{T}").is_err());
+    assert!(CastingManaCost::parse("{Q}").is_err());
+}
+
+#[test]
+fn test_reminder_text_renders_in_italics() {
+    let rules = RulesText::parse(
+        "Flying (This creature can't be blocked except by creatures with flying or reach.)",
+    )
+    .unwrap();
+    assert!(matches!(
+        rules.segments.last(),
+        Some(RulesTextSegment::Reminder(reminder))
+            if reminder == &[ReminderSegment::Text("This creature can't be blocked except by creatures with flying or reach.".to_string())]
+    ));
+    let html = mtg_gen::render::render_rules_text(&rules).into_string();
+    assert!(html.contains("Flying <span class=\"reminder-text\">(This creature"));
+}
+
+#[test]
+fn test_reminder_text_needs_closing_on_its_line() {
+    let rules = RulesText::parse("Draw a card (or two\nthen discard).").unwrap();
+    assert!(
+        rules
+            .segments
+            .iter()
+            .all(|segment| !matches!(segment, RulesTextSegment::Reminder(_)))
+    );
+    assert_eq!(rules.to_string(), "Draw a card (or two\nthen discard).");
+
+    let rules = RulesText::parse("Ward {2} ({T}: Nope.) then").unwrap();
+    assert!(matches!(
+        &rules.segments[3],
+        RulesTextSegment::Reminder(reminder) if reminder[0] == ReminderSegment::Symbol(ManaSymbol::Tap)
+    ));
+    assert_eq!(rules.to_string(), "Ward {2} ({T}: Nope.) then");
+}
+
+#[test]
+fn test_rules_text_markup() {
+    let rules =
+        RulesText::parse("Search for a card named *Ashen Ghoul*, then **discard** it.").unwrap();
+    assert_eq!(
+        rules.segments,
+        vec![
+            RulesTextSegment::Text("Search for a card named ".to_string()),
+            RulesTextSegment::Italic("Ashen Ghoul".to_string()),
+            RulesTextSegment::Text(", then ".to_string()),
+            RulesTextSegment::Bold("discard".to_string()),
+            RulesTextSegment::Text(" it.".to_string()),
+        ]
+    );
+    assert_eq!(
+        rules.to_string(),
+        "Search for a card named *Ashen Ghoul*, then **discard** it."
+    );
+
+    let rules = RulesText::parse("Draw <i>two</i>, {T}: <b>all</b>").unwrap();
+    assert_eq!(
+        rules.segments[1],
+        RulesTextSegment::Italic("two".to_string())
+    );
+    assert_eq!(rules.segments[3], RulesTextSegment::Symbol(ManaSymbol::Tap));
+    assert_eq!(rules.segments[5], RulesTextSegment::Bold("all".to_string()));
+
+    let rules = RulesText::parse("Power becomes 2 * 3 and * alone").unwrap();
+    assert_eq!(
+        rules.segments,
+        vec![RulesTextSegment::Text(
+            "Power becomes 2 * 3 and * alone".to_string()
+        )]
+    );
+}
+
+#[test]
+fn test_flavor_text_markup() {
+    assert_eq!(
+        mtg_gen::mana::parse_markup("As *Urza* wrote: **no**."),
+        vec![
+            RulesTextSegment::Text("As ".to_string()),
+            RulesTextSegment::Italic("Urza".to_string()),
+            RulesTextSegment::Text(" wrote: ".to_string()),
+            RulesTextSegment::Bold("no".to_string()),
+            RulesTextSegment::Text(".".to_string()),
+        ]
+    );
+    assert_eq!(
+        mtg_gen::mana::strip_markup("As *Urza* wrote"),
+        "As Urza wrote"
+    );
+
+    let html = mtg_gen::render::render_flavor_text("As *Urza* wrote").into_string();
+    assert_eq!(html, "As <em>Urza</em> wrote");
+}
+
+#[test]
+fn test_rules_text_paragraphs() {
+    let rules = RulesText::parse("Flying\n{T}: Add {G}.\n\nTrample").unwrap();
+    assert_eq!(
+        rules.segments,
+        vec![
+            RulesTextSegment::Text("Flying".to_string()),
+            RulesTextSegment::ParagraphBreak,
+            RulesTextSegment::Symbol(ManaSymbol::Tap),
+            RulesTextSegment::Text(": Add ".to_string()),
+            RulesTextSegment::Symbol(ManaSymbol::Casting(CastingManaSymbol::Green)),
+            RulesTextSegment::Text(".".to_string()),
+            RulesTextSegment::ParagraphBreak,
+            RulesTextSegment::ParagraphBreak,
+            RulesTextSegment::Text("Trample".to_string()),
+        ]
+    );
+    assert_eq!(rules.to_string(), "Flying\n{T}: Add {G}.\n\nTrample");
+    // The blank line is no paragraph of its own
+    assert_eq!(rules.paragraphs().count(), 3);
+
+    let html = mtg_gen::render::render_rules_text(&rules).into_string();
+    assert_eq!(html.matches("<p class=\"rules-paragraph\">").count(), 3);
+}
+
+#[test]
+fn test_symbol_costs_stay_on_one_line() {
+    let rules = RulesText::parse("{E}{E}, {T}: Draw a card.\n{2}{G}: Regenerate.").unwrap();
+    let html = mtg_gen::render::render_rules_text(&rules).into_string();
+    assert_eq!(html.matches("class=\"symbol-cost\"").count(), 1);
+    // Mana-only costs are left alone
+    assert!(html.contains(":</span> Draw a card."));
+
+    // Costs with words in them wrap as usual
+    let rules = RulesText::parse("{T}, Sacrifice a creature: Draw a card.").unwrap();
+    let html = mtg_gen::render::render_rules_text(&rules).into_string();
+    assert!(!html.contains("symbol-cost"));
+}
+
+#[test]
+fn test_rules_text_symbols_are_defined_once() {
+    use mtg_gen::render::with_symbol_defs;
+
+    let rules = RulesText::parse("{T}: Add {G}.\n{T}, {E}: Add {G}{G}. ({T} again.)").unwrap();
+    let html = mtg_gen::render::render_rules_text(&rules).into_string();
+    assert_eq!(html.matches("<use href=\"#symbol-symbols--T\"").count(), 3);
+    assert!(!html.contains("<img"));
+
+    let html = with_symbol_defs(&format!("<html><body>{}</body></html>", html));
+    for id in ["symbols--T", "symbols--E", "archives_symbols--G"] {
+        assert_eq!(
+            html.matches(&format!("<symbol id=\"symbol-{}\"", id))
+                .count(),
+            1,
+            "{}",
+            id
+        );
+    }
+    assert_eq!(html.matches("T.svg").count(), 1);
+    assert!(html.ends_with("</svg></body></html>"));
+
+    // Documents without symbols are left alone
+    assert_eq!(
+        with_symbol_defs("<body>Flying</body>"),
+        "<body>Flying</body>"
+    );
+}
+
+#[test]
+fn test_casting_symbol_metadata() {
+    use mtg_gen::color_chips::Color;
+
+    assert_eq!(CastingManaSymbol::Green.colors(), [Color::Green]);
+    assert_eq!(
+        CastingManaSymbol::PhyrexianBlueRed.colors(),
+        [Color::Blue, Color::Red]
+    );
+    assert!(CastingManaSymbol::Generic(2).colors().is_empty());
+    assert!(CastingManaSymbol::Custom(CustomMana::O).colors().is_empty());
+
+    assert!(CastingManaSymbol::WhiteBlue.is_hybrid());
+    assert!(CastingManaSymbol::TwoRed.is_hybrid());
+    assert!(CastingManaSymbol::ColorlessGreen.is_hybrid());
+    assert!(CastingManaSymbol::PhyrexianWhiteBlack.is_hybrid());
+    assert!(!CastingManaSymbol::PhyrexianWhite.is_hybrid());
+
+    assert!(CastingManaSymbol::PhyrexianWhite.is_phyrexian());
+    assert!(CastingManaSymbol::PhyrexianColorless.is_phyrexian());
+    assert!(!CastingManaSymbol::WhiteBlue.is_phyrexian());
+
+    let cost = CastingManaCost::parse("{X}{3}{2/G}{W/U}{B/P}{HR}").unwrap();
+    assert_eq!(cost.mana_value(), 7.5);
+    assert_eq!(CastingManaCost::default().mana_value(), 0.0);
+}
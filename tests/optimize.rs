@@ -0,0 +1,82 @@
+#![cfg(feature = "cli")]
+
+use mtg_gen::optimize::{OptimizeStats, optimize_png};
+use std::fs;
+use std::io::Write;
+
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+fn chunk(out: &mut Vec<u8>, kind: &[u8], data: &[u8]) {
+    out.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    let mut crc = flate2::Crc::new();
+    crc.update(kind);
+    crc.update(data);
+    out.extend_from_slice(kind);
+    out.extend_from_slice(data);
+    out.extend_from_slice(&crc.sum().to_be_bytes());
+}
+
+/// A solid-color RGBA PNG stored without compression, like a worst-case
+/// screenshot.
+fn uncompressed_png(width: u32, height: u32) -> Vec<u8> {
+    let mut ihdr = Vec::new();
+    ihdr.extend_from_slice(&width.to_be_bytes());
+    ihdr.extend_from_slice(&height.to_be_bytes());
+    ihdr.extend_from_slice(&[8, 6, 0, 0, 0]);
+
+    let mut raw = Vec::new();
+    for _ in 0..height {
+        raw.push(0);
+        for _ in 0..width {
+            raw.extend_from_slice(&[0x20, 0x40, 0x80, 0xff]);
+        }
+    }
+    let mut encoder = flate2::write::ZlibEncoder::new(Vec::new(), flate2::Compression::none());
+    encoder.write_all(&raw).unwrap();
+
+    let mut png = PNG_SIGNATURE.to_vec();
+    chunk(&mut png, b"IHDR", &ihdr);
+    chunk(&mut png, b"IDAT", &encoder.finish().unwrap());
+    chunk(&mut png, b"IEND", &[]);
+    png
+}
+
+#[test]
+fn test_optimize_shrinks_png_in_place() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("card.png");
+    let png = uncompressed_png(64, 64);
+    fs::write(&path, &png).unwrap();
+
+    let (before, after) = optimize_png(&path).unwrap();
+    assert_eq!(before, png.len() as u64);
+    assert!(after < before, "{} -> {}", before, after);
+
+    let optimized = fs::read(&path).unwrap();
+    assert_eq!(optimized.len() as u64, after);
+    assert!(optimized.starts_with(PNG_SIGNATURE));
+}
+
+#[test]
+fn test_optimize_rejects_non_png() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("card.png");
+    fs::write(&path, b"not a png").unwrap();
+
+    assert!(optimize_png(&path).is_err());
+    assert_eq!(fs::read(&path).unwrap(), b"not a png");
+}
+
+#[test]
+fn test_optimize_stats() {
+    let mut stats = OptimizeStats::default();
+    stats.add(4096, 1024);
+    stats.add(2048, 2048);
+
+    assert_eq!(stats.files, 2);
+    assert_eq!(stats.saved(), 3072);
+    assert_eq!(
+        stats.to_string(),
+        "Optimized 2 image(s): 6 KB -> 3 KB (50.0% smaller)"
+    );
+}
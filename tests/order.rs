@@ -0,0 +1,90 @@
+use mtg_gen::order::{SortEntry, SortOrder, compare_collector_numbers, sort_entries};
+use std::cmp::Ordering;
+use std::path::PathBuf;
+
+fn entry(path: &str, name: Option<&str>, collector_number: Option<&str>) -> SortEntry {
+    SortEntry {
+        path: PathBuf::from(path),
+        name: name.map(str::to_string),
+        collector_number: collector_number.map(str::to_string),
+        color_group: None,
+    }
+}
+
+fn entries() -> Vec<SortEntry> {
+    vec![
+        entry("white/b.yaml", Some("Serra Angel"), Some("10")),
+        entry("blue/a.yaml", Some("counterspell"), None),
+        entry("broken.yaml", None, None),
+        entry("white/a.yaml", Some("Ajani's Pridemate"), Some("9")),
+        entry("red/bolt.yaml", Some("Lightning Bolt"), Some("10a")),
+    ]
+}
+
+fn sorted_paths(order: SortOrder) -> Vec<String> {
+    let mut entries = entries();
+    sort_entries(&mut entries, order);
+    entries
+        .iter()
+        .map(|entry| entry.path.display().to_string())
+        .collect()
+}
+
+#[test]
+fn test_sort_by_path() {
+    assert_eq!(
+        sorted_paths(SortOrder::Path),
+        [
+            "blue/a.yaml",
+            "broken.yaml",
+            "red/bolt.yaml",
+            "white/a.yaml",
+            "white/b.yaml"
+        ]
+    );
+}
+
+#[test]
+fn test_sort_by_name_ignores_case() {
+    assert_eq!(
+        sorted_paths(SortOrder::Name),
+        [
+            "white/a.yaml",
+            "blue/a.yaml",
+            "red/bolt.yaml",
+            "white/b.yaml",
+            "broken.yaml"
+        ]
+    );
+}
+
+#[test]
+fn test_sort_by_collector_number() {
+    assert_eq!(
+        sorted_paths(SortOrder::Collector),
+        [
+            "white/a.yaml",
+            "white/b.yaml",
+            "red/bolt.yaml",
+            "blue/a.yaml",
+            "broken.yaml"
+        ]
+    );
+}
+
+#[test]
+fn test_collector_numbers_compare_numerically() {
+    assert_eq!(compare_collector_numbers("9", "10"), Ordering::Less);
+    assert_eq!(compare_collector_numbers("12", "12a"), Ordering::Less);
+    assert_eq!(compare_collector_numbers("12b", "12A"), Ordering::Greater);
+    assert_eq!(compare_collector_numbers("300", "★"), Ordering::Less);
+    assert_eq!(compare_collector_numbers("007", "7"), Ordering::Equal);
+}
+
+#[test]
+fn test_parse_order() {
+    assert_eq!(SortOrder::parse("Name"), Ok(SortOrder::Name));
+    assert_eq!(SortOrder::parse("collector"), Ok(SortOrder::Collector));
+    assert_eq!(SortOrder::parse("path"), Ok(SortOrder::Path));
+    assert!(SortOrder::parse("color").is_err());
+}
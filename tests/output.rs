@@ -0,0 +1,289 @@
+use mtg_gen::*;
+use std::path::Path;
+
+#[test]
+fn test_default_naming() {
+    let naming = OutputNaming::default();
+    let card = Path::new("set/delver.png");
+
+    assert_eq!(
+        naming.path_for(card, OutputKind::Card, 0, 1),
+        Path::new("set/delver.png")
+    );
+    assert_eq!(
+        naming.path_for(card, OutputKind::Front, 0, 1),
+        Path::new("set/delver_front.png")
+    );
+    assert_eq!(
+        naming.path_for(card, OutputKind::Back, 0, 1),
+        Path::new("set/delver_back.png")
+    );
+    assert_eq!(
+        naming.path_for(card, OutputKind::Emblem, 0, 1),
+        Path::new("set/delver_emblem.png")
+    );
+}
+
+#[test]
+fn test_custom_templates_and_subfolders() {
+    let naming = OutputNaming {
+        face_template: "{name}-{face}".to_string(),
+        emblem_template: "{name}-emblem-{index}".to_string(),
+        subfolders: true,
+    };
+    let card = Path::new("delver.png");
+
+    assert_eq!(
+        naming.path_for(card, OutputKind::Card, 0, 1),
+        Path::new("cards/delver.png")
+    );
+    assert_eq!(
+        naming.path_for(card, OutputKind::Back, 0, 1),
+        Path::new("backs/delver-back.png")
+    );
+    assert_eq!(
+        naming.path_for(card, OutputKind::Emblem, 0, 1),
+        Path::new("emblems/delver-emblem-1.png")
+    );
+}
+
+#[test]
+fn test_multiple_outputs_get_unique_names() {
+    let naming = OutputNaming::default();
+    let card = Path::new("elspeth.png");
+
+    let first = naming.path_for(card, OutputKind::Emblem, 0, 2);
+    let second = naming.path_for(card, OutputKind::Emblem, 1, 2);
+    assert_eq!(first, Path::new("elspeth_emblem_1.png"));
+    assert_eq!(second, Path::new("elspeth_emblem_2.png"));
+}
+
+#[test]
+fn test_manifest_round_trip() {
+    let manifest = RenderManifest {
+        provenance: None,
+        seed: None,
+        uses_face_down: false,
+        cards: vec![ManifestEntry {
+            source: "delver.yaml".to_string(),
+            name: "Delver of Secrets".to_string(),
+            outputs: vec![
+                ManifestOutput {
+                    kind: OutputKind::Front,
+                    path: "delver_front.png".to_string(),
+                    url: None,
+                    timings: Some(RenderTimings {
+                        html_ms: 2,
+                        navigation_ms: 40,
+                        wait_ms: 3000,
+                        screenshot_ms: 250,
+                        encode_ms: 12,
+                        js_heap_bytes: Some(1_500_000),
+                    }),
+                    language: None,
+                    warnings: Vec::new(),
+                },
+                ManifestOutput {
+                    kind: OutputKind::Back,
+                    path: "delver_back.png".to_string(),
+                    url: None,
+                    timings: None,
+                    language: None,
+                    warnings: Vec::new(),
+                },
+            ],
+        }],
+        failed: Vec::new(),
+    };
+
+    let parsed = RenderManifest::from_json(&manifest.to_json()).unwrap();
+    assert_eq!(parsed, manifest);
+    assert_eq!(
+        parsed.cards[0].output(OutputKind::Back),
+        Some("delver_back.png")
+    );
+}
+
+fn entry(source: &str, path: &str) -> ManifestEntry {
+    ManifestEntry {
+        source: source.to_string(),
+        name: source.to_string(),
+        outputs: vec![ManifestOutput {
+            kind: OutputKind::Card,
+            path: path.to_string(),
+            url: None,
+            timings: None,
+            language: None,
+            warnings: Vec::new(),
+        }],
+    }
+}
+
+#[test]
+fn test_manifest_save_merges_with_earlier_runs() {
+    let dir = tempfile::tempdir().unwrap();
+    for image in ["bolt.png", "delver.png", "forest.png"] {
+        std::fs::write(dir.path().join(image), b"png").unwrap();
+    }
+    let full_run = RenderManifest {
+        provenance: None,
+        seed: None,
+        uses_face_down: false,
+        cards: vec![
+            entry("bolt.yaml", "bolt.png"),
+            entry("delver.yaml", "delver.png"),
+            entry("gone.yaml", "gone.png"),
+        ],
+        failed: Vec::new(),
+    };
+    full_run.save(dir.path()).unwrap();
+
+    // Re-rendering one card keeps the others
+    let single_card = RenderManifest {
+        provenance: None,
+        seed: None,
+        uses_face_down: false,
+        cards: vec![
+            entry("delver.yaml", "delver.png"),
+            entry("forest.yaml", "forest.png"),
+        ],
+        failed: Vec::new(),
+    };
+    let merged = single_card.save(dir.path()).unwrap();
+    let sources: Vec<&str> = merged.cards.iter().map(|c| c.source.as_str()).collect();
+    // An earlier card whose image is missing is dropped
+    assert_eq!(sources, ["bolt.yaml", "delver.yaml", "forest.yaml"]);
+
+    let json = std::fs::read_to_string(dir.path().join(MANIFEST_FILE_NAME)).unwrap();
+    assert_eq!(RenderManifest::from_json(&json).unwrap(), merged);
+}
+
+fn failure(source: &str) -> ManifestFailure {
+    ManifestFailure {
+        source: source.to_string(),
+        error: "Page didn't finish within 60s, so it was killed".to_string(),
+    }
+}
+
+#[test]
+fn test_manifest_keeps_failures_until_retried() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("bolt.png"), b"png").unwrap();
+    let first_run = RenderManifest {
+        provenance: None,
+        seed: None,
+        uses_face_down: false,
+        cards: Vec::new(),
+        failed: vec![failure("bolt.yaml"), failure("hang.yaml")],
+    };
+    let first = first_run.merged_into(RenderManifest::default(), dir.path());
+    assert_eq!(first.failed.len(), 2);
+
+    // Rendering one of them clears its failure; the other stays listed
+    let second_run = RenderManifest {
+        provenance: None,
+        seed: None,
+        uses_face_down: false,
+        cards: vec![entry("bolt.yaml", "bolt.png")],
+        failed: Vec::new(),
+    };
+    let merged = second_run.merged_into(first, dir.path());
+    assert_eq!(merged.cards.len(), 1);
+    assert_eq!(merged.failed, [failure("hang.yaml")]);
+
+    let parsed = RenderManifest::from_json(&merged.to_json()).unwrap();
+    assert_eq!(parsed, merged);
+}
+
+#[test]
+fn test_manifest_flags_face_down_sets() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("bolt.png"), b"png").unwrap();
+    let manifest = RenderManifest {
+        provenance: None,
+        seed: None,
+        uses_face_down: false,
+        cards: vec![entry("bolt.yaml", "bolt.png")],
+        failed: Vec::new(),
+    };
+    assert!(
+        !manifest
+            .merged_into(RenderManifest::default(), dir.path())
+            .uses_face_down
+    );
+
+    let mut morph = entry("hidden.yaml", "hidden.png");
+    morph.outputs.push(ManifestOutput {
+        kind: OutputKind::FaceDown,
+        path: "hidden_face_down.png".to_string(),
+        url: None,
+        timings: None,
+        language: None,
+        warnings: Vec::new(),
+    });
+    let manifest = RenderManifest {
+        cards: vec![morph],
+        ..manifest
+    };
+    assert!(
+        manifest
+            .merged_into(RenderManifest::default(), dir.path())
+            .uses_face_down
+    );
+}
+
+#[test]
+fn test_output_lock_is_exclusive() {
+    let dir = tempfile::tempdir().unwrap();
+    let output = dir.path().join("out");
+
+    let lock = OutputLock::try_acquire(&output).unwrap();
+    assert!(lock.is_some());
+    assert!(output.join(LOCK_FILE_NAME).is_file());
+    assert!(OutputLock::try_acquire(&output).unwrap().is_none());
+
+    // A leftover lock file doesn't block once the lock is released
+    drop(lock);
+    assert!(OutputLock::try_acquire(&output).unwrap().is_some());
+}
+
+#[test]
+fn test_image_formats() {
+    assert_eq!(ImageFormat::parse("png"), Ok(ImageFormat::Png));
+    assert_eq!(ImageFormat::parse("JPG"), Ok(ImageFormat::Jpeg));
+    assert_eq!(ImageFormat::parse(" webp "), Ok(ImageFormat::Webp));
+    assert!(ImageFormat::parse("gif").is_err());
+    assert_eq!(ImageFormat::default(), ImageFormat::Png);
+
+    assert_eq!(ImageFormat::Jpeg.extension(), "jpg");
+    assert_eq!(ImageFormat::from_extension("jpg"), Some(ImageFormat::Jpeg));
+    assert_eq!(ImageFormat::Webp.content_type(), "image/webp");
+    assert!(!ImageFormat::Png.is_lossy());
+    assert!(ImageFormat::Jpeg.is_lossy());
+
+    let naming = OutputNaming::default();
+    assert_eq!(
+        naming.path_for(Path::new("set/delver.jpg"), OutputKind::Back, 0, 1),
+        Path::new("set/delver_back.jpg")
+    );
+}
+
+#[test]
+fn test_card_json() {
+    let yaml = std::fs::read_to_string("tests/fixtures/normal_creature.yaml").unwrap();
+    let card: Card = facet_yaml::from_str(&yaml).unwrap();
+    let entry = entry("green/elves.yaml", "green/cards/elves.png");
+
+    let json = output::CardJson::new(&entry, card);
+    assert_eq!(
+        output::CardJson::path(&entry.source),
+        Path::new("green/elves.card.json")
+    );
+    assert_eq!(json.source, "green/elves.yaml");
+    assert_eq!(json.images[0].kind, OutputKind::Card);
+    assert_eq!(json.images[0].path, "cards/elves.png");
+
+    let text = json.to_json();
+    assert!(text.contains("\"name\":\"Llanowar Elves\""));
+    assert!(text.contains("Elf Druid"));
+}
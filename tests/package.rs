@@ -0,0 +1,92 @@
+#![cfg(feature = "cli")]
+
+use mtg_gen::package::{PackageFormat, archive_path, package_outputs};
+use mtg_gen::*;
+use std::fs::{self, File};
+
+fn write_outputs(dir: &std::path::Path) -> RenderManifest {
+    fs::create_dir_all(dir.join("emblems")).unwrap();
+    fs::write(dir.join("elspeth.png"), b"card").unwrap();
+    fs::write(dir.join("emblems/elspeth_emblem.png"), b"emblem").unwrap();
+
+    let manifest = RenderManifest {
+        provenance: None,
+        seed: None,
+        uses_face_down: false,
+        cards: vec![ManifestEntry {
+            source: "elspeth.yaml".to_string(),
+            name: "Elspeth, Knight-Errant".to_string(),
+            outputs: vec![
+                ManifestOutput {
+                    kind: OutputKind::Card,
+                    path: "elspeth.png".to_string(),
+                    url: None,
+                    timings: None,
+                    language: None,
+                    warnings: Vec::new(),
+                },
+                ManifestOutput {
+                    kind: OutputKind::Emblem,
+                    path: "emblems/elspeth_emblem.png".to_string(),
+                    url: None,
+                    timings: None,
+                    language: None,
+                    warnings: Vec::new(),
+                },
+            ],
+        }],
+        failed: Vec::new(),
+    };
+    fs::write(dir.join(MANIFEST_FILE_NAME), manifest.to_json()).unwrap();
+    manifest
+}
+
+#[test]
+fn test_parse_package_format() {
+    assert_eq!(PackageFormat::parse("zip"), Ok(PackageFormat::Zip));
+    assert_eq!(PackageFormat::parse("tar.gz"), Ok(PackageFormat::TarGz));
+    assert_eq!(PackageFormat::parse("TGZ"), Ok(PackageFormat::TarGz));
+    assert!(PackageFormat::parse("rar").is_err());
+}
+
+#[test]
+fn test_package_zip() {
+    let dir = tempfile::tempdir().unwrap();
+    let manifest = write_outputs(dir.path());
+
+    let archive = archive_path(dir.path(), "TST", PackageFormat::Zip);
+    assert!(archive.ends_with("TST.zip"));
+    package_outputs(dir.path(), &manifest, PackageFormat::Zip, &archive).unwrap();
+
+    let mut zip = zip::ZipArchive::new(File::open(&archive).unwrap()).unwrap();
+    assert_eq!(zip.len(), 3);
+    assert!(zip.by_name("elspeth.png").is_ok());
+    assert!(zip.by_name("emblems/elspeth_emblem.png").is_ok());
+    assert!(zip.by_name(MANIFEST_FILE_NAME).is_ok());
+}
+
+#[test]
+fn test_package_tar_gz() {
+    let dir = tempfile::tempdir().unwrap();
+    let manifest = write_outputs(dir.path());
+
+    let archive = archive_path(dir.path(), "TST", PackageFormat::TarGz);
+    assert!(archive.ends_with("TST.tar.gz"));
+    package_outputs(dir.path(), &manifest, PackageFormat::TarGz, &archive).unwrap();
+
+    let mut tar = tar::Archive::new(flate2::read::GzDecoder::new(File::open(&archive).unwrap()));
+    let mut names: Vec<String> = tar
+        .entries()
+        .unwrap()
+        .map(|entry| entry.unwrap().path().unwrap().display().to_string())
+        .collect();
+    names.sort();
+    assert_eq!(
+        names,
+        vec![
+            "elspeth.png",
+            "emblems/elspeth_emblem.png",
+            MANIFEST_FILE_NAME
+        ]
+    );
+}
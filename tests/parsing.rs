@@ -76,6 +76,33 @@ fn test_parse_saga() {
     }
 }
 
+#[test]
+fn test_parse_saga_read_ahead() {
+    let yaml = read_fixture("saga_read_ahead");
+    let card: Card = from_str(&yaml).expect("Failed to parse read ahead saga");
+
+    if let Card::Saga(saga) = card {
+        assert!(saga.is_read_ahead());
+        assert_eq!(saga.chapters.len(), 4);
+        assert_eq!(saga.chapters[1].chapters, vec![2, 3]);
+        assert_eq!(saga.final_chapter(), 5);
+    } else {
+        panic!("Expected Saga variant");
+    }
+}
+
+#[test]
+fn test_saga_read_ahead_reminder_text() {
+    let yaml = read_fixture("saga_read_ahead");
+    let card: Card = from_str(&yaml).expect("Failed to parse read ahead saga");
+
+    let html = card.render_html().into_string();
+    assert!(html.contains(
+        "Read ahead (Choose a chapter and start with that many lore counters. \
+         Add one after your draw step. Skipped chapters don't trigger. Sacrifice after V.)"
+    ));
+}
+
 #[test]
 fn test_parse_class() {
     let yaml = read_fixture("class");
@@ -96,6 +123,53 @@ fn test_parse_class() {
     }
 }
 
+#[test]
+fn test_validate_class_levels() {
+    let yaml = read_fixture("class");
+    let card: Card = from_str(&yaml).expect("Failed to parse class");
+    assert_eq!(card.validate(), Ok(()));
+
+    let gap = r#"
+name: "Broken Class"
+mana_cost: "{1}{G}"
+type_line: "Enchantment — Class"
+rarity: rare
+type: class
+levels:
+  - level: 1
+    text: "First."
+  - level: 3
+    cost: "{G}"
+    text: "Third."
+"#;
+    let card: Card = from_str(gap).expect("Failed to parse class");
+    assert_eq!(
+        card.validate(),
+        Err(CardValidationError::NonContiguousClassLevels {
+            expected: 2,
+            found: 3
+        })
+    );
+
+    let missing_cost = r#"
+name: "Free Class"
+mana_cost: "{1}{G}"
+type_line: "Enchantment — Class"
+rarity: rare
+type: class
+levels:
+  - level: 1
+    text: "First."
+  - level: 2
+    text: "Second."
+"#;
+    let card: Card = from_str(missing_cost).expect("Failed to parse class");
+    assert_eq!(
+        card.validate(),
+        Err(CardValidationError::MissingClassLevelCost { level: 2 })
+    );
+}
+
 #[test]
 fn test_parse_adventure() {
     let yaml = read_fixture("adventure");
@@ -134,6 +208,60 @@ fn test_parse_split() {
     }
 }
 
+#[test]
+fn test_split_and_adventure_credit_each_half() {
+    let split = r#"
+name: "Fire // Ice"
+type: split
+type_line: "Instant // Instant"
+rarity: uncommon
+faces:
+  - name: Fire
+    mana_cost: "{1}{R}"
+    type_line: Instant
+    rules_text: "Fire deals 2 damage divided as you choose."
+    flavor_text: "Burn bright."
+    artist: Franz Vohwinkel
+  - name: Ice
+    mana_cost: "{1}{U}"
+    type_line: Instant
+    rules_text: "Tap target permanent."
+"#;
+    let card: Card = from_str(split).expect("Failed to parse split");
+    let html = mtg_gen::render::card_to_html(&card);
+    assert!(html.contains(r#"<div class="split-artist">Illus. Franz Vohwinkel</div>"#));
+    assert_eq!(html.matches("split-artist\"").count(), 1);
+    assert!(html.contains("Burn bright."));
+
+    let adventure = r#"
+name: Bonecrusher Giant
+type: adventure
+mana_cost: "{2}{R}"
+type_line: "Creature — Giant"
+rarity: rare
+power: "4"
+toughness: "3"
+adventure:
+  name: Stomp
+  mana_cost: "{1}{R}"
+  type_line: "Instant — Adventure"
+  rules_text: "Stomp deals 2 damage to any target."
+  flavor_text: "Fee, fi, fo, fum."
+  artist: Victor Adame Minguez
+"#;
+    let card: Card = from_str(adventure).expect("Failed to parse adventure");
+    let Card::Adventure(ref adventure_card) = card else {
+        panic!("Expected Adventure variant");
+    };
+    assert_eq!(
+        adventure_card.adventure.artist.as_deref(),
+        Some("Victor Adame Minguez")
+    );
+    let html = mtg_gen::render::card_to_html(&card);
+    assert!(html.contains(r#"<div class="adventure-artist">Illus. Victor Adame Minguez</div>"#));
+    assert!(html.contains("Fee, fi, fo, fum."));
+}
+
 #[test]
 fn test_parse_transform_dfc() {
     let yaml = read_fixture("transform");
@@ -185,6 +313,8 @@ fn test_parse_battle() {
     if let Card::Battle(battle) = card {
         assert_eq!(battle.base.name, "Invasion of Gobakhan");
         assert_eq!(battle.defense, 3);
+        assert!(battle.is_siege());
+        assert!(battle.base.auto_reminder_text());
     } else {
         panic!("Expected Battle variant");
     }
@@ -220,11 +350,76 @@ fn test_parse_leveler() {
             Some("Flying".to_string())
         );
         assert!(leveler.leveler_ranges[2].text.is_some());
+        assert_eq!(
+            leveler.leveler_ranges[0].range,
+            LevelRange {
+                min: 0,
+                max: Some(3)
+            }
+        );
+        assert_eq!(
+            leveler.leveler_ranges[2].range,
+            LevelRange { min: 8, max: None }
+        );
+        assert_eq!(leveler.validate(), Ok(()));
     } else {
         panic!("Expected Leveler variant");
     }
 }
 
+#[test]
+fn test_level_range_parse_and_display() {
+    assert_eq!(
+        LevelRange::parse("1-3"),
+        Ok(LevelRange {
+            min: 1,
+            max: Some(3)
+        })
+    );
+    assert_eq!(
+        LevelRange::parse("4+"),
+        Ok(LevelRange { min: 4, max: None })
+    );
+    assert_eq!(
+        LevelRange::parse("5"),
+        Ok(LevelRange {
+            min: 5,
+            max: Some(5)
+        })
+    );
+    assert!(LevelRange::parse("3-1").is_err());
+    assert!(LevelRange::parse("potato").is_err());
+
+    for original in ["0-3", "4+", "5"] {
+        assert_eq!(LevelRange::parse(original).unwrap().to_string(), original);
+    }
+}
+
+#[test]
+fn test_validate_overlapping_level_ranges() {
+    let yaml = r#"
+name: "Overlapping Leveler"
+mana_cost: "{R}"
+type_line: "Creature — Human Warrior"
+rarity: common
+type: leveler
+leveler_ranges:
+  - range: "1-4"
+  - range: "4+"
+"#;
+    let card: Card = from_str(yaml).expect("Failed to parse leveler");
+    assert_eq!(
+        card.validate(),
+        Err(CardValidationError::OverlappingLevelRanges {
+            previous: LevelRange {
+                min: 1,
+                max: Some(4)
+            },
+            next: LevelRange { min: 4, max: None },
+        })
+    );
+}
+
 #[test]
 fn test_parse_prototype() {
     let yaml = read_fixture("prototype");
@@ -252,3 +447,222 @@ fn test_parse_saga_with_combined_chapters() {
     let yaml = read_fixture("saga");
     let _card: Card = from_str(&yaml).expect("Failed to parse saga");
 }
+
+#[test]
+fn test_extract_planeswalker_emblem() {
+    let yaml = read_fixture("planeswalker_emblem");
+    let card: Card = from_str(&yaml).expect("Failed to parse planeswalker");
+
+    let emblems = extract_emblems(&card);
+    assert_eq!(emblems.len(), 1);
+    assert_eq!(emblems[0].source_name, "Elspeth, Knight-Errant");
+    assert_eq!(emblems[0].type_line(), "Emblem — Elspeth");
+    assert_eq!(
+        emblems[0].rules_text.to_string(),
+        "Artifacts, creatures, enchantments, and lands you control have indestructible."
+    );
+}
+
+#[test]
+fn test_extract_no_emblems() {
+    let yaml = read_fixture("planeswalker");
+    let card: Card = from_str(&yaml).expect("Failed to parse planeswalker");
+    assert!(extract_emblems(&card).is_empty());
+
+    let yaml = read_fixture("normal_creature");
+    let card: Card = from_str(&yaml).expect("Failed to parse normal creature");
+    assert!(extract_emblems(&card).is_empty());
+}
+
+#[test]
+fn test_extract_face_down() {
+    let card = |rules_text: &str| -> Card {
+        from_str(&format!(
+            "name: Test\ntype: normal\ntype_line: Creature\nrarity: common\nrules_text: \"{rules_text}\"\n"
+        ))
+        .unwrap()
+    };
+    let kind =
+        |rules_text: &str| extract_face_down(&card(rules_text)).map(|face_down| face_down.kind);
+
+    assert_eq!(kind("Megamorph {5}{G}"), Some(FaceDownKind::Morph));
+    assert_eq!(
+        kind("Manifest the top card of your library."),
+        Some(FaceDownKind::Morph)
+    );
+    assert_eq!(kind("Disguise {3}{U}"), Some(FaceDownKind::Disguise));
+    assert_eq!(
+        kind("Morph {2}. Cloak the top card of your library."),
+        Some(FaceDownKind::Disguise)
+    );
+    assert_eq!(kind("Morphling isn't face down."), None);
+
+    let disguise = FaceDown {
+        kind: FaceDownKind::Disguise,
+    };
+    assert_eq!(disguise.title(), "Disguise");
+    assert!(disguise.rules_text().to_string().starts_with("Ward {2}"));
+}
+
+#[test]
+fn test_parse_extended_rarities() {
+    for (name, rarity) in [
+        ("special", Rarity::Special),
+        ("bonus", Rarity::Bonus),
+        ("land", Rarity::Land),
+    ] {
+        let yaml = format!(
+            "name: \"Forest\"\ntype: normal\ntype_line: \"Basic Land — Forest\"\nrarity: {}\n",
+            name
+        );
+        let card: Card = from_str(&yaml).expect("Failed to parse rarity");
+        assert_eq!(card.rarity(), rarity);
+        assert_eq!(card.rarity().to_string(), name);
+        assert!(!card.rarity().is_main_sheet());
+    }
+}
+
+#[test]
+fn test_rarity_counts_bucket_extended_rarities() {
+    let cards: Vec<Card> = ["normal_creature", "planeswalker", "saga"]
+        .iter()
+        .map(|name| from_str(&read_fixture(name)).expect("Failed to parse fixture"))
+        .collect();
+    let counts = stats::RarityCounts::from_cards(&cards);
+    assert_eq!(counts.total(), 3);
+    assert_eq!(counts.main_sheet_total(), 3);
+    assert_eq!(counts.get(Rarity::Special), 0);
+    assert_eq!(counts.get(Rarity::Common), 1);
+}
+
+#[test]
+fn test_translations() {
+    let yaml = r#"
+name: "Lightning Bolt"
+type: normal
+mana_cost: "{R}"
+type_line: "Instant"
+rules_text: "Lightning Bolt deals 3 damage to any target."
+rarity: common
+translations:
+  jp:
+    name: "稲妻"
+  de:
+    name: "Blitzschlag"
+    type_line: "Spontanzauber"
+    rules_text: "Der Blitzschlag fügt einem Ziel deiner Wahl 3 Schadenspunkte zu."
+"#;
+    let card: Card = from_str(yaml).expect("Failed to parse translations");
+    assert_eq!(card.languages().collect::<Vec<_>>(), ["de", "jp"]);
+
+    let german = card.translated("de").unwrap();
+    assert_eq!(german.name(), "Blitzschlag");
+    assert_eq!(german.base().type_line, "Spontanzauber");
+    assert_eq!(
+        german.base().rules_text.as_ref().map(ToString::to_string),
+        Some("Der Blitzschlag fügt einem Ziel deiner Wahl 3 Schadenspunkte zu.".to_string())
+    );
+    assert_eq!(german.base().mana_cost, card.base().mana_cost);
+    assert!(german.base().translations.is_empty());
+
+    // Fields a translation leaves out keep the card's own text
+    let japanese = card.translated("jp").unwrap();
+    assert_eq!(japanese.name(), "稲妻");
+    assert_eq!(japanese.base().type_line, "Instant");
+
+    assert!(card.translated("fr").is_none());
+}
+
+#[test]
+fn test_text_density() {
+    let card: Card = from_str(&read_fixture("normal_creature")).unwrap();
+    // One line of rules text and one of flavor text
+    assert_eq!(
+        card.base().text_density(),
+        2.0 / CardBase::TEXT_BOX_LINES as f64
+    );
+
+    let mut base = card.base().clone();
+    base.flavor_text = None;
+    base.rules_text =
+        Some(RulesText::parse(&"Flying\n".repeat(CardBase::TEXT_BOX_LINES * 2)).unwrap());
+    assert!(base.text_density() > 1.0);
+}
+
+#[test]
+fn test_parse_token() {
+    let yaml = read_fixture("token");
+    let card: Card = from_str(&yaml).expect("Failed to parse token");
+    assert_eq!(card.layout_name(), "token");
+    assert_eq!(card.validate(), Ok(()));
+
+    if let Card::Token(token) = &card {
+        assert_eq!(token.colors, vec!["green".to_string()]);
+        assert_eq!(token.base.mana_cost, None);
+    } else {
+        panic!("Expected Token variant");
+    }
+
+    let html = mtg_gen::render::card_to_html(&card);
+    assert!(html.contains("card token bg-green"));
+    assert!(!html.contains("mana-cost-container"));
+}
+
+#[test]
+fn test_tokens_have_no_mana_cost() {
+    let yaml = "type: token\nname: Treasure\ntype_line: Token Artifact — Treasure\nrarity: common\nmana_cost: \"{1}\"\n";
+    let card: Card = from_str(yaml).expect("Failed to parse token");
+    assert_eq!(card.validate(), Err(CardValidationError::TokenManaCost));
+
+    let Card::Token(mut token) = card else {
+        panic!("Expected Token variant");
+    };
+    token.base.mana_cost = None;
+    assert_eq!(mtg_gen::render::token_frame_color(&token), "artifact");
+}
+
+#[test]
+fn test_tokens_name_real_colors() {
+    let yaml = "type: token\nname: Spirit\ntype_line: Token Creature — Spirit\nrarity: common\ncolors: [W, purple]\n";
+    let card: Card = from_str(yaml).expect("Failed to parse token");
+    assert_eq!(
+        card.validate(),
+        Err(CardValidationError::UnknownTokenColor {
+            name: "purple".to_string()
+        })
+    );
+}
+
+#[test]
+fn test_transform_icons() {
+    let yaml = read_fixture("transform");
+    let card: Card = from_str(&yaml).unwrap();
+    let Card::Transform(transform) = &card else {
+        panic!("expected a transform card");
+    };
+    assert_eq!(transform.dfc_icons, None);
+    assert_eq!(transform.icons(), DfcIcons::SunMoon);
+    assert!(mtg_gen::render::card_to_html(transform).contains("dfc-icon-sun"));
+
+    assert_eq!(
+        DfcIcons::for_back_type_line("Legendary Planeswalker — Jace"),
+        DfcIcons::Spark
+    );
+    assert_eq!(
+        DfcIcons::for_back_type_line("Legendary Land"),
+        DfcIcons::CompassLand
+    );
+    assert_eq!(
+        DfcIcons::for_back_type_line("Creature — Eldrazi Horror"),
+        DfcIcons::MoonEldrazi
+    );
+
+    let card: Card = from_str(&format!("{}dfc_icons: compass_land\n", yaml)).unwrap();
+    let Card::Transform(transform) = &card else {
+        panic!("expected a transform card");
+    };
+    assert_eq!(transform.icons(), DfcIcons::CompassLand);
+    assert!(mtg_gen::render::card_to_html(transform).contains("dfc-icon-compass"));
+    let back = back_face(&card).unwrap();
+    assert!(mtg_gen::render::card_to_html(&back).contains("dfc-icon-land"));
+}
@@ -0,0 +1,40 @@
+use mtg_gen::render::card_to_html;
+use mtg_gen::*;
+
+fn fixture(name: &str) -> Card {
+    let yaml = std::fs::read_to_string(format!("tests/fixtures/{name}.yaml")).unwrap();
+    facet_yaml::from_str(&yaml).unwrap()
+}
+
+#[test]
+fn test_playtest_creature() {
+    let card = PlaytestCard::from_card(&fixture("normal_creature"));
+    assert_eq!(card.faces.len(), 1);
+
+    let face = &card.faces[0];
+    assert_eq!(face.name, "Llanowar Elves");
+    assert_eq!(face.mana_cost.as_deref(), Some("{G}"));
+    assert_eq!(face.type_line.as_deref(), Some("Creature — Elf Druid"));
+    assert_eq!(face.rules_text, ["{T}: Add {G}."]);
+    assert_eq!(face.stats.as_deref(), Some("1/1"));
+    assert_eq!(card.rarity, Rarity::Common);
+}
+
+#[test]
+fn test_playtest_writes_every_face() {
+    let card = PlaytestCard::from_card(&fixture("transform"));
+    let names: Vec<&str> = card.faces.iter().map(|face| face.name.as_str()).collect();
+    assert_eq!(names, ["Delver of Secrets", "Insectile Aberration"]);
+    assert_eq!(card.faces[1].stats.as_deref(), Some("3/2"));
+
+    let leveler = PlaytestCard::from_card(&fixture("leveler"));
+    assert_eq!(leveler.faces[0].rules_text[0], "Level up {R}");
+    assert_eq!(leveler.faces[0].rules_text[2], "LEVEL 4-7 [4/4] Flying");
+}
+
+#[test]
+fn test_playtest_html_loads_no_assets() {
+    let html = card_to_html(&PlaytestCard::from_card(&fixture("adventure")));
+    assert!(html.contains("playtest-card"));
+    assert!(!html.contains("file://"));
+}
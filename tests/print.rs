@@ -0,0 +1,70 @@
+use mtg_gen::CardSize;
+use mtg_gen::print::{CARDS_PER_PAGE, PaperSize, cards_per_page, grid, page_count, sheet_html};
+use mtg_gen::render::{BLEED_HEIGHT, BLEED_WIDTH, card_to_html, with_bleed};
+use std::path::PathBuf;
+
+#[test]
+fn test_sheet_pages() {
+    let images: Vec<PathBuf> = (0..10)
+        .map(|i| PathBuf::from(format!("/cards/{}.png", i)))
+        .collect();
+    let html = sheet_html(&images, PaperSize::Letter, CardSize::Standard, true).into_string();
+
+    assert_eq!(html.matches("class=\"page\"").count(), 2);
+    assert_eq!(html.matches("<img").count(), 10);
+    assert!(html.contains("src=\"file:///cards/9.png\""));
+    assert!(html.contains("size: 215.9mm 279.4mm"));
+}
+
+#[test]
+fn test_page_count() {
+    let count = |cards| page_count(cards, PaperSize::A4, CardSize::Standard);
+    assert_eq!(count(0), 0);
+    assert_eq!(count(CARDS_PER_PAGE), 1);
+    assert_eq!(count(CARDS_PER_PAGE + 1), 2);
+}
+
+#[test]
+fn test_sheet_packs_by_card_size() {
+    for paper in [PaperSize::A4, PaperSize::Letter] {
+        assert_eq!(cards_per_page(paper, CardSize::Standard), CARDS_PER_PAGE);
+        assert_eq!(grid(paper, CardSize::Mini), (4, 4));
+        assert_eq!(grid(paper, CardSize::Jumbo), (1, 1));
+    }
+
+    let images = vec![PathBuf::from("/cards/mini.png"); 20];
+    let html = sheet_html(&images, PaperSize::A4, CardSize::Mini, true).into_string();
+    assert_eq!(html.matches("class=\"page\"").count(), 2);
+    assert!(html.contains("repeat(4, 42mm)"));
+}
+
+#[test]
+fn test_parse_paper_size() {
+    assert_eq!(PaperSize::parse("A4"), Ok(PaperSize::A4));
+    assert_eq!(PaperSize::parse("letter"), Ok(PaperSize::Letter));
+    assert!(PaperSize::parse("legal").is_err());
+}
+
+#[test]
+fn test_sheet_without_cut_lines() {
+    let images = vec![PathBuf::from("/cards/bolt.jpg"); 9];
+    let with_lines = sheet_html(&images, PaperSize::A4, CardSize::Standard, true).into_string();
+    let without = sheet_html(&images, PaperSize::A4, CardSize::Standard, false).into_string();
+
+    assert!(with_lines.contains("background: #bbb"));
+    assert!(!without.contains("background: #bbb"));
+    assert_eq!(without.matches("class=\"page\"").count(), 1);
+    assert!(without.contains("repeat(3, 63mm)"));
+}
+
+#[test]
+fn test_bleed_surrounds_the_card() {
+    let yaml = std::fs::read_to_string("tests/fixtures/normal_creature.yaml").unwrap();
+    let card: mtg_gen::Card = facet_yaml::from_str(&yaml).unwrap();
+    let html = with_bleed(&card_to_html(&card));
+
+    assert_eq!((BLEED_WIDTH, BLEED_HEIGHT), (816, 1110));
+    assert!(html.contains("html { width: 816px; height: 1110px; background: #000; }"));
+    assert!(html.contains("margin: 35px 36px;"));
+    assert!(html.contains(".card { border-radius: 0; }</style></head>"));
+}
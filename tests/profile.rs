@@ -0,0 +1,98 @@
+use mtg_gen::profile::ProfileReport;
+use mtg_gen::*;
+
+fn timings(wait_ms: u64, screenshot_ms: u64) -> RenderTimings {
+    RenderTimings {
+        html_ms: 1,
+        navigation_ms: 50,
+        wait_ms,
+        screenshot_ms,
+        encode_ms: 9,
+        js_heap_bytes: Some(2048 * 1024),
+    }
+}
+
+fn output(path: &str, timings: Option<RenderTimings>) -> ManifestOutput {
+    ManifestOutput {
+        kind: OutputKind::Card,
+        path: path.to_string(),
+        url: None,
+        timings,
+        language: None,
+        warnings: Vec::new(),
+    }
+}
+
+fn manifest() -> RenderManifest {
+    RenderManifest {
+        provenance: None,
+        seed: None,
+        uses_face_down: false,
+        cards: vec![
+            ManifestEntry {
+                source: "bolt.yaml".to_string(),
+                name: "Lightning Bolt".to_string(),
+                outputs: vec![output("bolt.png", Some(timings(3000, 200)))],
+            },
+            ManifestEntry {
+                source: "elspeth.yaml".to_string(),
+                name: "Elspeth, Knight-Errant".to_string(),
+                outputs: vec![
+                    output("elspeth.png", Some(timings(3000, 400))),
+                    output("elspeth_emblem.png", None),
+                ],
+            },
+        ],
+        failed: Vec::new(),
+    }
+}
+
+#[test]
+fn test_report_collects_timed_outputs() {
+    let report = ProfileReport::from_manifest(&manifest());
+
+    assert_eq!(report.samples.len(), 2);
+    assert_eq!(report.averages(), [1, 50, 3000, 300, 9]);
+    let slowest: Vec<&str> = report.slowest().iter().map(|(p, _)| p.as_str()).collect();
+    assert_eq!(slowest, ["elspeth.png", "bolt.png"]);
+}
+
+#[test]
+fn test_report_display() {
+    let mut report = ProfileReport::from_manifest(&manifest());
+    report.peak_rss_bytes = Some(64 * 1024 * 1024);
+    let text = report.to_string();
+
+    assert!(text.starts_with("Profile of 2 image(s)\n"));
+    assert!(
+        text.contains("  screenshot        300      400        600\n"),
+        "{}",
+        text
+    );
+    assert!(text.contains("peak page JS heap: 2048 KB"));
+    assert!(text.contains("peak mtg-gen memory: 64 MB"));
+    assert!(text.contains("      3460 ms  elspeth.png"));
+}
+
+#[test]
+fn test_empty_report() {
+    let report = ProfileReport::default();
+    assert_eq!(report.averages(), [0; 5]);
+    assert!(report.slowest().is_empty());
+}
+
+#[test]
+fn test_median_timings() {
+    assert_eq!(profile::median_timings(&[]), None);
+
+    let samples = [timings(900, 10), timings(100, 10), timings(300, 10)];
+    assert_eq!(
+        profile::median_timings(&samples).map(|t| t.wait_ms),
+        Some(300)
+    );
+    let samples = [timings(400, 10), timings(200, 10)];
+    assert_eq!(
+        profile::median_timings(&samples).map(|t| t.wait_ms),
+        Some(200)
+    );
+}
@@ -0,0 +1,72 @@
+use mtg_gen::progress::{JOURNAL_FILE_NAME, ProgressJournal, completed_entries};
+use mtg_gen::*;
+use std::fs;
+
+fn entry(name: &str) -> ManifestEntry {
+    ManifestEntry {
+        source: format!("{}.yaml", name),
+        name: name.to_string(),
+        outputs: vec![ManifestOutput {
+            kind: OutputKind::Card,
+            path: format!("{}.png", name),
+            url: None,
+            timings: None,
+            language: None,
+            warnings: Vec::new(),
+        }],
+    }
+}
+
+#[test]
+fn test_resume_returns_recorded_cards() {
+    let dir = tempfile::tempdir().unwrap();
+    let (mut journal, done) = ProgressJournal::open(dir.path(), true).unwrap();
+    assert!(done.is_empty());
+
+    for name in ["bolt", "counterspell"] {
+        fs::write(dir.path().join(format!("{}.png", name)), b"png").unwrap();
+        journal.record(&entry(name)).unwrap();
+    }
+    // Simulate a crash: the journal is never finished
+    drop(journal);
+
+    let (_, done) = ProgressJournal::open(dir.path(), true).unwrap();
+    assert_eq!(done, [entry("bolt"), entry("counterspell")]);
+}
+
+#[test]
+fn test_fresh_run_discards_journal() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("bolt.png"), b"png").unwrap();
+    let (mut journal, _) = ProgressJournal::open(dir.path(), false).unwrap();
+    journal.record(&entry("bolt")).unwrap();
+    drop(journal);
+
+    let (_, done) = ProgressJournal::open(dir.path(), false).unwrap();
+    assert!(done.is_empty());
+}
+
+#[test]
+fn test_finish_removes_journal() {
+    let dir = tempfile::tempdir().unwrap();
+    let (journal, _) = ProgressJournal::open(dir.path(), false).unwrap();
+    assert!(dir.path().join(JOURNAL_FILE_NAME).exists());
+
+    journal.finish().unwrap();
+    assert!(!dir.path().join(JOURNAL_FILE_NAME).exists());
+}
+
+#[test]
+fn test_incomplete_entries_are_rendered_again() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("bolt.png"), b"png").unwrap();
+
+    let journal = format!(
+        "{}\n{}\n{{\"source\": \"shock.ya",
+        facet_json::to_string(&entry("bolt")),
+        facet_json::to_string(&entry("giant_growth"))
+    );
+
+    // giant_growth.png is missing and the last line was cut short
+    assert_eq!(completed_entries(&journal, dir.path()), [entry("bolt")]);
+}
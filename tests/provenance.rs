@@ -0,0 +1,83 @@
+use mtg_gen::provenance::{PNG_KEYWORD, Provenance, read_text_chunk, with_text_chunk};
+
+/// A 1x1 PNG: signature, IHDR, IDAT and IEND
+const TINY_PNG: &[u8] = &[
+    0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a, 0x00, 0x00, 0x00, 0x0d, 0x49, 0x48, 0x44, 0x52,
+    0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x08, 0x06, 0x00, 0x00, 0x00, 0x1f, 0x15, 0xc4,
+    0x89, 0x00, 0x00, 0x00, 0x0b, 0x49, 0x44, 0x41, 0x54, 0x78, 0x9c, 0x63, 0x60, 0x00, 0x02, 0x00,
+    0x00, 0x05, 0x00, 0x01, 0x7a, 0x5e, 0xab, 0x3f, 0x00, 0x00, 0x00, 0x00, 0x49, 0x45, 0x4e, 0x44,
+    0xae, 0x42, 0x60, 0x82,
+];
+
+fn provenance() -> Provenance {
+    Provenance {
+        version: "0.1.0".to_string(),
+        commit: Some("0123abc".to_string()),
+        dirty: Some(true),
+    }
+}
+
+#[test]
+fn test_text_chunk_round_trip() {
+    assert_eq!(read_text_chunk(TINY_PNG, "key"), None);
+
+    let png = with_text_chunk(TINY_PNG, "key", "first").unwrap();
+    assert_eq!(read_text_chunk(&png, "key").as_deref(), Some("first"));
+    // The chunk goes right after IHDR
+    assert_eq!(&png[33 + 4..33 + 8], b"tEXt");
+
+    let png = with_text_chunk(&png, "key", "second").unwrap();
+    assert_eq!(read_text_chunk(&png, "key").as_deref(), Some("second"));
+    assert_eq!(png.len(), TINY_PNG.len() + 12 + "key\0second".len());
+    assert_eq!(&png[png.len() - 12..], &TINY_PNG[TINY_PNG.len() - 12..]);
+}
+
+#[test]
+fn test_text_chunk_rejects_non_png() {
+    assert_eq!(with_text_chunk(b"not a png", "key", "text"), None);
+    assert_eq!(read_text_chunk(b"not a png", "key"), None);
+    // Truncated chunk
+    assert_eq!(with_text_chunk(&TINY_PNG[..40], "key", "text"), None);
+}
+
+#[test]
+fn test_provenance_embed_and_read() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("card.png");
+    std::fs::write(&path, TINY_PNG).unwrap();
+    assert_eq!(Provenance::read(&path).unwrap(), None);
+
+    provenance().embed(&path).unwrap();
+    assert_eq!(Provenance::read(&path).unwrap(), Some(provenance()));
+    let png = std::fs::read(&path).unwrap();
+    assert!(
+        read_text_chunk(&png, PNG_KEYWORD)
+            .unwrap()
+            .contains("0123abc")
+    );
+}
+
+#[test]
+fn test_provenance_display() {
+    assert_eq!(
+        provenance().to_string(),
+        "mtg-gen 0.1.0\ncommit 0123abc (uncommitted changes)"
+    );
+    let outside = Provenance {
+        version: "0.1.0".to_string(),
+        ..Provenance::default()
+    };
+    assert_eq!(
+        outside.to_string(),
+        "mtg-gen 0.1.0\nnot in a git repository"
+    );
+}
+
+#[test]
+fn test_detect_outside_git() {
+    let dir = tempfile::tempdir().unwrap();
+    let provenance = Provenance::detect(dir.path());
+    assert_eq!(provenance.version, env!("CARGO_PKG_VERSION"));
+    assert_eq!(provenance.commit, None);
+    assert_eq!(provenance.dirty, None);
+}
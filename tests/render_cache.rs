@@ -0,0 +1,114 @@
+#![cfg(feature = "cli")]
+
+use mtg_gen::order::SortEntry;
+use mtg_gen::render_cache::{RenderCache, content_hash};
+use mtg_gen::*;
+
+const YAML: &str = "name: Bolt\ntype: normal\ntype_line: Instant\nrarity: common\n";
+
+fn entry(source: &str, path: &str) -> ManifestEntry {
+    ManifestEntry {
+        source: source.to_string(),
+        name: source.to_string(),
+        outputs: vec![ManifestOutput {
+            kind: OutputKind::Card,
+            path: path.to_string(),
+            url: None,
+            timings: None,
+            language: None,
+            warnings: Vec::new(),
+        }],
+    }
+}
+
+#[test]
+fn test_content_hash_covers_yaml_art_and_settings() {
+    let dir = tempfile::tempdir().unwrap();
+    let art = dir.path().join("bolt.png");
+    std::fs::write(&art, b"art").unwrap();
+    let mut card: Card = facet_yaml::from_str(YAML).unwrap();
+    card.base_mut().art = Some(art.display().to_string());
+
+    let hash = content_hash(YAML, &card, "format=png");
+    assert_eq!(hash.len(), 64);
+    assert_eq!(content_hash(YAML, &card, "format=png"), hash);
+    assert_ne!(content_hash(YAML, &card, "format=jpeg"), hash);
+    assert_ne!(
+        content_hash(&format!("{YAML}flavor_text: Zap\n"), &card, "format=png"),
+        hash
+    );
+
+    // Editing the art re-renders the card
+    std::fs::write(&art, b"new art").unwrap();
+    assert_ne!(content_hash(YAML, &card, "format=png"), hash);
+}
+
+#[test]
+fn test_renumbered_card_is_rendered_again() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("shock.png"), b"png").unwrap();
+    let card = |name: &str, cost: &str| -> Card {
+        facet_yaml::from_str(&format!(
+            "name: {name}\ntype: normal\ntype_line: Instant\nrarity: common\nmana_cost: \"{cost}\"\n"
+        ))
+        .unwrap()
+    };
+    let shock_yaml =
+        "name: Shock\ntype: normal\ntype_line: Instant\nrarity: common\nmana_cost: \"{R}\"\n";
+    // Number a set the way `--auto-number` does, returning Shock's hash
+    let shock_hash = |cards: &[(&str, Card)]| {
+        let entries = cards
+            .iter()
+            .map(|(path, card)| SortEntry::new((*path).into(), Some(card)))
+            .collect();
+        let number = collector::auto_numbers(entries)
+            .into_iter()
+            .find(|number| number.name == "Shock")
+            .unwrap();
+        let mut shock = card("Shock", "{R}");
+        shock.base_mut().collector_number = Some(number.number);
+        content_hash(shock_yaml, &shock, "format=png")
+    };
+
+    let before = shock_hash(&[("shock.yaml", card("Shock", "{R}"))]);
+    let mut cache = RenderCache::default();
+    cache.insert(before.clone(), entry("shock.yaml", "shock.png"));
+    assert!(cache.fresh("shock.yaml", &before, dir.path()).is_some());
+
+    // A white card comes first, so Shock goes from 1/1 to 2/2
+    let after = shock_hash(&[
+        ("shock.yaml", card("Shock", "{R}")),
+        ("ajani.yaml", card("Ajani's Pridemate", "{1}{W}")),
+    ]);
+    assert_ne!(after, before);
+    assert!(cache.fresh("shock.yaml", &after, dir.path()).is_none());
+}
+
+#[test]
+fn test_cache_reuses_unchanged_cards_with_images() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("bolt.png"), b"png").unwrap();
+
+    let mut cache = RenderCache::default();
+    cache.insert("abc".to_string(), entry("bolt.yaml", "bolt.png"));
+    cache.insert("def".to_string(), entry("gone.yaml", "gone.png"));
+    cache.save(dir.path()).unwrap();
+
+    let cache = RenderCache::load(dir.path());
+    assert_eq!(cache.cards.len(), 2);
+    assert!(cache.fresh("bolt.yaml", "abc", dir.path()).is_some());
+    // A changed card, or one whose image is gone, is rendered again
+    assert!(cache.fresh("bolt.yaml", "xyz", dir.path()).is_none());
+    assert!(cache.fresh("gone.yaml", "def", dir.path()).is_none());
+
+    let mut cache = cache;
+    cache.insert("xyz".to_string(), entry("bolt.yaml", "bolt.png"));
+    assert_eq!(cache.cards.len(), 2);
+    assert!(cache.fresh("bolt.yaml", "xyz", dir.path()).is_some());
+}
+
+#[test]
+fn test_missing_cache_is_empty() {
+    let dir = tempfile::tempdir().unwrap();
+    assert_eq!(RenderCache::load(dir.path()), RenderCache::default());
+}
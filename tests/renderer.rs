@@ -0,0 +1,98 @@
+//! Sharing one renderer between tasks, in Chrome.
+//!
+//! Run with `cargo test --features chrome-tests` from the repository root,
+//! so the mtgrender assets resolve.
+#![cfg(feature = "chrome-tests")]
+
+use mtg_gen::*;
+
+fn fixture(name: &str) -> Card {
+    let yaml = std::fs::read_to_string(format!("tests/fixtures/{name}.yaml")).unwrap();
+    facet_yaml::from_str(&yaml).unwrap()
+}
+
+#[test]
+fn test_renderer_is_shareable() {
+    fn assert_shareable<T: Clone + Send + Sync + 'static>() {}
+    assert_shareable::<Renderer>();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_clones_share_chrome() {
+    let renderer = Renderer::with_max_pages(2).await.unwrap();
+    let card = fixture("normal_creature");
+
+    let tasks: Vec<_> = (0..4)
+        .map(|_| {
+            let renderer = renderer.clone();
+            let card = card.clone();
+            tokio::spawn(async move { renderer.render_card_png(&card).await.unwrap().bytes })
+        })
+        .collect();
+    for task in tasks {
+        assert!(task.await.unwrap().starts_with(b"\x89PNG"));
+    }
+
+    // Chrome stays open until the last clone closes
+    let clone = renderer.clone();
+    clone.close().await.unwrap();
+    assert!(
+        !renderer
+            .render_card_png(&card)
+            .await
+            .unwrap()
+            .bytes
+            .is_empty()
+    );
+    renderer.recycle().await.unwrap();
+    assert!(
+        !renderer
+            .render_card_png(&card)
+            .await
+            .unwrap()
+            .bytes
+            .is_empty()
+    );
+    renderer.close().await.unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_render_in_memory() {
+    let mut renderer = Renderer::new().await.unwrap();
+    renderer.set_image_format(ImageFormat::Jpeg, 90);
+    let card = fixture("normal_creature");
+
+    assert!(renderer.render_card_html(&card).contains("card-name"));
+    let jpeg = renderer.render_card_to_bytes(&card).await.unwrap();
+    assert!(jpeg.bytes.starts_with(b"\xff\xd8"));
+    assert!(jpeg.warnings.is_empty());
+    assert!(
+        renderer
+            .render_card_png(&card)
+            .await
+            .unwrap()
+            .bytes
+            .starts_with(b"\x89PNG")
+    );
+    renderer.close().await.unwrap();
+}
+
+#[cfg(feature = "megapage")]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_tiled_cards_match_their_own_pages() {
+    use image::GenericImageView;
+
+    let mut renderer = Renderer::with_max_pages(4).await.unwrap();
+    let cards = [fixture("normal_creature"), fixture("normal_creature")];
+    let alone = renderer.render_card_png(&cards[0]).await.unwrap().bytes;
+
+    renderer.set_cards_per_page(2);
+    let (first, second) = tokio::join!(
+        renderer.render_card_png(&cards[0]),
+        renderer.render_card_png(&cards[1])
+    );
+    let size = |png: &[u8]| image::load_from_memory(png).unwrap().dimensions();
+    assert_eq!(size(&first.unwrap().bytes), size(&alone));
+    assert_eq!(size(&second.unwrap().bytes), size(&alone));
+    renderer.close().await.unwrap();
+}
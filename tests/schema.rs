@@ -0,0 +1,120 @@
+use facet::Facet;
+use mtg_gen::examples::example_yaml;
+use mtg_gen::schema::{card_schema, layout_fields, layout_schema, layouts};
+use mtg_gen::*;
+use std::collections::HashMap;
+
+#[derive(Facet)]
+struct SchemaDoc {
+    required: Vec<String>,
+    definitions: HashMap<String, LayoutSchema>,
+}
+
+#[derive(Facet)]
+struct LayoutSchema {
+    required: Vec<String>,
+}
+
+/// Top-level keys of a YAML document, skipping comments and nested lines.
+fn top_level_keys(yaml: &str) -> Vec<&str> {
+    yaml.lines()
+        .filter(|line| !line.starts_with([' ', '#', '-']))
+        .filter_map(|line| line.split_once(':').map(|(key, _)| key))
+        .collect()
+}
+
+/// Remove a top-level key and everything nested below it.
+fn without_key(yaml: &str, key: &str) -> String {
+    let mut out = String::new();
+    let mut skipping = false;
+    for line in yaml.lines() {
+        if !line.starts_with([' ', '-']) {
+            skipping = line.split_once(':').is_some_and(|(k, _)| k == key);
+        }
+        if !skipping {
+            out.push_str(line);
+            out.push('\n');
+        }
+    }
+    out
+}
+
+#[test]
+fn test_schema_covers_every_example_layout() {
+    assert_eq!(
+        layouts().collect::<Vec<_>>(),
+        mtg_gen::examples::layouts().collect::<Vec<_>>()
+    );
+    assert!(layout_schema("vanguard").is_none());
+}
+
+#[test]
+fn test_example_fields_are_in_schema() {
+    for layout in layouts() {
+        let fields = layout_fields(layout).unwrap();
+        for key in top_level_keys(example_yaml(layout).unwrap()) {
+            assert!(
+                fields.iter().any(|field| field.name == key),
+                "{} example uses {:?}, which the schema doesn't list",
+                layout,
+                key
+            );
+        }
+    }
+}
+
+#[test]
+fn test_required_fields_match_card_model() {
+    for layout in layouts() {
+        let yaml = example_yaml(layout).unwrap();
+        for field in layout_fields(layout).unwrap() {
+            let name = field.name;
+            let parsed = facet_yaml::from_str::<Card>(&without_key(yaml, name));
+            if field.required {
+                assert!(
+                    parsed.is_err(),
+                    "{} parsed without {:?}, but the schema requires it",
+                    layout,
+                    name
+                );
+            } else {
+                assert!(
+                    parsed.is_ok(),
+                    "{} failed to parse without {:?}, but the schema marks it optional",
+                    layout,
+                    name
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn test_card_schema_is_valid_json() {
+    let doc: SchemaDoc = facet_json::from_str(&card_schema()).unwrap();
+    assert_eq!(doc.required, ["type"]);
+    assert_eq!(doc.definitions.len(), layouts().count());
+
+    let planeswalker = &doc.definitions["planeswalker"];
+    for field in [
+        "type",
+        "name",
+        "type_line",
+        "rarity",
+        "loyalty",
+        "loyalty_abilities",
+    ] {
+        assert!(planeswalker.required.iter().any(|r| r == field));
+    }
+    assert!(!planeswalker.required.iter().any(|r| r == "mana_cost"));
+}
+
+#[test]
+fn test_layout_schema_is_valid_json() {
+    for layout in layouts() {
+        let json = layout_schema(layout).unwrap();
+        let schema: LayoutSchema = facet_json::from_str(&json)
+            .unwrap_or_else(|e| panic!("{} schema is not valid JSON: {}", layout, e));
+        assert_eq!(schema.required[0], "type");
+    }
+}
@@ -0,0 +1,172 @@
+use mtg_gen::scryfall::{ScryfallCard, oracle_text_to_rules_text};
+use mtg_gen::*;
+
+#[test]
+fn test_oracle_text_paragraphs_and_reminders() {
+    let oracle = "Flying\nTrample (This creature can deal excess combat damage to the player or planeswalker it's attacking.)\n{T}: Add {G}.";
+    let rules = oracle_text_to_rules_text(oracle).unwrap();
+
+    let breaks = rules
+        .segments
+        .iter()
+        .filter(|s| matches!(s, RulesTextSegment::ParagraphBreak))
+        .count();
+    assert_eq!(breaks, 2);
+    assert!(matches!(&rules.segments[2], RulesTextSegment::Text(s) if s == "Trample "));
+    assert!(matches!(
+        &rules.segments[3],
+        RulesTextSegment::Reminder(r) if matches!(&r[0], ReminderSegment::Text(s) if s.starts_with("This creature"))
+    ));
+    assert_eq!(rules.segments[5], RulesTextSegment::Symbol(ManaSymbol::Tap));
+
+    // Display restores the original oracle text
+    assert_eq!(rules.to_string(), oracle);
+}
+
+#[test]
+fn test_oracle_text_reminder_with_symbols() {
+    let rules = oracle_text_to_rules_text(
+        "Kicker {2}{R} (You may pay an additional {2}{R} as you cast this spell.)",
+    )
+    .unwrap();
+    let RulesTextSegment::Reminder(reminder) = rules.segments.last().unwrap() else {
+        panic!("expected reminder text");
+    };
+    assert!(
+        reminder.contains(&ReminderSegment::Symbol(ManaSymbol::Casting(
+            CastingManaSymbol::Red
+        )))
+    );
+}
+
+#[test]
+fn test_oracle_text_ability_words() {
+    let rules = oracle_text_to_rules_text(
+        "Landfall — Whenever a land you control enters, you gain 1 life.",
+    )
+    .unwrap();
+    assert_eq!(
+        rules.segments[0],
+        RulesTextSegment::AbilityWord("Landfall".to_string())
+    );
+    assert!(
+        matches!(&rules.segments[1], RulesTextSegment::Text(s) if s.starts_with(" — Whenever"))
+    );
+
+    // Keyword costs written with an em dash aren't ability words
+    let rules = oracle_text_to_rules_text("Ward—Pay 2 life.").unwrap();
+    assert!(matches!(&rules.segments[0], RulesTextSegment::Text(_)));
+}
+
+#[test]
+fn test_import_normal_card() {
+    let json = r#"{
+        "object": "card",
+        "name": "Llanowar Elves",
+        "layout": "normal",
+        "rarity": "common",
+        "mana_cost": "{G}",
+        "type_line": "Creature — Elf Druid",
+        "oracle_text": "{T}: Add {G}.",
+        "power": "1",
+        "toughness": "1",
+        "collector_number": "266",
+        "colors": ["G"]
+    }"#;
+    let card = ScryfallCard::from_json(json).unwrap().to_card().unwrap();
+
+    let Card::Normal(normal) = card else {
+        panic!("expected a normal card");
+    };
+    assert_eq!(normal.base.name, "Llanowar Elves");
+    assert_eq!(normal.base.type_line, "Creature — Elf Druid");
+    assert_eq!(normal.base.collector_number.as_deref(), Some("266"));
+    assert_eq!(
+        normal.base.rules_text.map(|r| r.to_string()),
+        Some("{T}: Add {G}.".to_string())
+    );
+}
+
+#[test]
+fn test_import_planeswalker_abilities() {
+    let json = r#"{
+        "name": "Jace Beleren",
+        "layout": "normal",
+        "rarity": "mythic",
+        "mana_cost": "{1}{U}{U}",
+        "type_line": "Legendary Planeswalker — Jace",
+        "oracle_text": "+2: Each player draws a card.\n−1: Target player draws a card.\n−10: Target player mills twenty cards.",
+        "loyalty": "3"
+    }"#;
+    let card = ScryfallCard::from_json(json).unwrap().to_card().unwrap();
+
+    let Card::Planeswalker(pw) = card else {
+        panic!("expected a planeswalker");
+    };
+    assert_eq!(pw.loyalty, LoyaltyValue::Numeric(3));
+    assert_eq!(pw.loyalty_abilities.len(), 3);
+    assert_eq!(pw.loyalty_abilities[0].cost, LoyaltyCost::Plus(2));
+    assert_eq!(pw.loyalty_abilities[1].cost, LoyaltyCost::Minus(1));
+    assert_eq!(pw.loyalty_abilities[2].cost, LoyaltyCost::Minus(10));
+    assert!(pw.base.rules_text.is_none());
+}
+
+#[test]
+fn test_import_saga_chapters() {
+    let json = r#"{
+        "name": "The Eldest Reborn",
+        "layout": "saga",
+        "rarity": "uncommon",
+        "mana_cost": "{4}{B}",
+        "type_line": "Enchantment — Saga",
+        "oracle_text": "(As this Saga enters and after your draw step, add a lore counter. Sacrifice after III.)\nI — Each opponent sacrifices a creature or planeswalker.\nII — Each opponent discards a card.\nIII — Put target creature or planeswalker card from a graveyard onto the battlefield under your control."
+    }"#;
+    let card = ScryfallCard::from_json(json).unwrap().to_card().unwrap();
+
+    let Card::Saga(saga) = card else {
+        panic!("expected a saga");
+    };
+    assert_eq!(saga.chapters.len(), 3);
+    assert_eq!(saga.chapters[2].chapters, vec![3]);
+    assert!(!saga.is_read_ahead());
+    assert!(saga.base.rules_text.is_none());
+}
+
+#[test]
+fn test_import_transform_faces() {
+    let json = r#"{
+        "name": "Delver of Secrets // Insectile Aberration",
+        "layout": "transform",
+        "rarity": "common",
+        "card_faces": [
+            {
+                "name": "Delver of Secrets",
+                "mana_cost": "{U}",
+                "type_line": "Creature — Human Wizard",
+                "oracle_text": "At the beginning of your upkeep, look at the top card of your library. You may reveal that card. If an instant or sorcery card is revealed this way, transform Delver of Secrets.",
+                "power": "1",
+                "toughness": "1"
+            },
+            {
+                "name": "Insectile Aberration",
+                "mana_cost": "",
+                "type_line": "Creature — Human Insect",
+                "oracle_text": "Flying",
+                "power": "3",
+                "toughness": "2",
+                "color_indicator": ["U"]
+            }
+        ]
+    }"#;
+    let card = ScryfallCard::from_json(json).unwrap().to_card().unwrap();
+
+    let Card::Transform(transform) = card else {
+        panic!("expected a transform card");
+    };
+    assert_eq!(transform.faces.len(), 2);
+    assert!(transform.faces[1].mana_cost.is_none());
+    assert_eq!(
+        transform.faces[1].color_indicator,
+        Some(vec!["blue".to_string()])
+    );
+}
@@ -0,0 +1,76 @@
+#![cfg(feature = "cli")]
+
+use mtg_gen::serve::{GalleryCard, Route, card_paths, gallery_html, set_version};
+use std::path::PathBuf;
+
+#[test]
+fn test_routes() {
+    assert_eq!(Route::parse("GET", "/"), Route::Gallery);
+    assert_eq!(Route::parse("GET", "/version"), Route::Version);
+    assert_eq!(
+        Route::parse("GET", "/card/bolt.png"),
+        Route::Card(PathBuf::from("bolt"))
+    );
+    assert_eq!(
+        Route::parse("GET", "/card/red/lightning%20bolt.png?v=1-2"),
+        Route::Card(PathBuf::from("red/lightning bolt"))
+    );
+    assert_eq!(Route::parse("POST", "/"), Route::NotFound);
+    assert_eq!(Route::parse("GET", "/card/bolt.jpg"), Route::NotFound);
+    assert_eq!(Route::parse("GET", "/favicon.ico"), Route::NotFound);
+}
+
+#[test]
+fn test_routes_stay_inside_the_set() {
+    for target in [
+        "/card/../secret.png",
+        "/card/red/../../secret.png",
+        "/card/%2E%2E/secret.png",
+        "/card//etc/passwd.png",
+        "/card/./bolt.png",
+    ] {
+        assert_eq!(Route::parse("GET", target), Route::NotFound, "{}", target);
+    }
+}
+
+#[test]
+fn test_card_paths_and_version() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::create_dir(dir.path().join("red")).unwrap();
+    std::fs::write(dir.path().join("red/bolt.yaml"), "name: Bolt\ntype: normal").unwrap();
+    std::fs::write(dir.path().join("wrath.yml"), "name: Wrath\ntype: normal").unwrap();
+    std::fs::write(dir.path().join("art.png"), "").unwrap();
+    std::fs::write(dir.path().join("set.yaml"), "set_code: DSK").unwrap();
+    std::fs::write(dir.path().join("style.yaml"), "legal:\n  studio: Cramt").unwrap();
+
+    assert_eq!(
+        card_paths(dir.path()).unwrap(),
+        vec![PathBuf::from("red/bolt"), PathBuf::from("wrath")]
+    );
+
+    let version = set_version(dir.path());
+    assert_eq!(set_version(dir.path()), version);
+    std::fs::write(dir.path().join("giant.yaml"), "name: Giant").unwrap();
+    assert_ne!(set_version(dir.path()), version);
+}
+
+#[test]
+fn test_gallery_links_every_card() {
+    let cards = [
+        GalleryCard {
+            path: PathBuf::from("red/lightning bolt"),
+            name: Ok("Lightning Bolt".to_string()),
+        },
+        GalleryCard {
+            path: PathBuf::from("broken"),
+            name: Err("missing field `type_line`".to_string()),
+        },
+    ];
+    let html = gallery_html("My Set", &cards, "3-17").into_string();
+
+    assert!(html.contains(r#"src="/card/red/lightning%20bolt.png?v=3-17""#));
+    assert!(html.contains("Lightning Bolt"));
+    assert!(html.contains("missing field `type_line`"));
+    assert!(html.contains(r#"const version = "3-17";"#));
+    assert!(html.contains("(2 cards)"));
+}
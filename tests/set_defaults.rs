@@ -0,0 +1,71 @@
+use mtg_gen::set_defaults::{Numbering, SetDefaults};
+use mtg_gen::style::StyleConfig;
+use mtg_gen::*;
+use std::path::Path;
+
+fn defaults() -> SetDefaults {
+    facet_yaml::from_str(
+        "set_code: DSK\nset_symbol: symbols/dsk.svg\nartist: Rebecca Guay\ncopyright: \"™ & © {year} {studio}\"\nframe_effects: [worn]\nframe_colors: identity\nnumbering: canonical\n",
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_cards_inherit_what_they_leave_out() {
+    let mut card: Card = facet_yaml::from_str(
+        "name: Bolt\ntype: normal\ntype_line: Instant\nrarity: common\nartist: Someone Else\nframe_effects: [grunge]\n",
+    )
+    .unwrap();
+    let mut defaults = defaults();
+    defaults.resolve_paths(Path::new("/sets/dsk"));
+    defaults.apply(&mut card);
+
+    let base = card.base();
+    assert_eq!(base.set_code.as_deref(), Some("DSK"));
+    assert_eq!(
+        base.set_symbol.as_deref(),
+        Some("/sets/dsk/symbols/dsk.svg")
+    );
+    assert_eq!(base.frame_colors, Some(FrameColors::Identity));
+    assert_eq!(base.artist.as_deref(), Some("Someone Else"));
+    assert_eq!(base.frame_effects, vec![FrameEffect::Grunge]);
+    assert_eq!(defaults.numbering, Numbering::Canonical);
+}
+
+#[test]
+fn test_copyright_unless_style_has_a_template() {
+    let defaults = defaults();
+    let mut style = StyleConfig::default();
+    defaults.apply_to_style(&mut style);
+    let legal = style.legal.unwrap();
+    assert_eq!(legal.template.as_deref(), Some("™ & © {year} {studio}"));
+
+    let mut style: StyleConfig =
+        facet_yaml::from_str("legal:\n  template: \"© {studio}\"\n  studio: Cramt\n").unwrap();
+    defaults.apply_to_style(&mut style);
+    assert_eq!(style.legal.unwrap().template.as_deref(), Some("© {studio}"));
+}
+
+#[test]
+fn test_numbering_defaults_to_manual() {
+    let defaults: SetDefaults = facet_yaml::from_str("set_code: DSK\n").unwrap();
+    assert_eq!(defaults.numbering, Numbering::Manual);
+}
+
+#[test]
+fn test_load_beside_a_card_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let card = dir.path().join("bolt.yaml");
+    std::fs::write(&card, "name: Bolt\ntype: normal\n").unwrap();
+    assert_eq!(SetDefaults::load(&card).unwrap(), None);
+
+    std::fs::write(dir.path().join("set.yml"), "set_symbol: dsk.svg\n").unwrap();
+    let symbol = dir.path().canonicalize().unwrap().join("dsk.svg");
+    for input in [dir.path(), card.as_path()] {
+        let set = SetDefaults::load(input).unwrap().unwrap();
+        assert_eq!(set.set_symbol, Some(symbol.display().to_string()));
+    }
+
+    std::fs::write(dir.path().join("set.yml"), "numbering: sometimes\n").unwrap();
+    assert!(SetDefaults::load(dir.path()).is_err());
+}
@@ -0,0 +1,144 @@
+//! HTML snapshots of every fixture, checked without a browser.
+//!
+//! Each fixture's rendered `<body>` is compared against
+//! `tests/snapshots/<fixture>.html`, and the shared stylesheet against
+//! `tests/snapshots/style.css`. A missing snapshot fails like a changed one;
+//! after adding a fixture or an intended rendering change, record them all
+//! with `UPDATE_SNAPSHOTS=1 cargo test --test snapshots`, review the diff and
+//! commit the snapshots.
+
+use mtg_gen::render::{assets_base, card_to_html, generate_css};
+use mtg_gen::*;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const SNAPSHOT_DIR: &str = "tests/snapshots";
+
+/// Make the HTML independent of the checkout location and put each tag on
+/// its own line, so a snapshot diff points at the element that changed.
+fn normalize(html: &str) -> String {
+    let assets = assets_base().display().to_string();
+    let mut normalized = html.replace(&assets, "{assets}").replace("><", ">\n<");
+    normalized.push('\n');
+    normalized
+}
+
+/// Returns the contents of the document's `<body>`.
+fn body(html: &str) -> &str {
+    let start = html.find("<body>").expect("document has a body") + "<body>".len();
+    let end = html.rfind("</body>").expect("body is closed");
+    &html[start..end]
+}
+
+/// Compare `actual` against the snapshot `name`, returning a description of
+/// the first difference, or record it with `UPDATE_SNAPSHOTS` set.
+fn check_snapshot(name: &str, actual: &str) -> Result<(), String> {
+    let path = Path::new(SNAPSHOT_DIR).join(name);
+    let actual = normalize(actual);
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        fs::create_dir_all(SNAPSHOT_DIR).unwrap();
+        fs::write(&path, &actual).unwrap();
+        return Ok(());
+    }
+    if !path.exists() {
+        return Err(format!(
+            "{} is missing; record it with UPDATE_SNAPSHOTS=1",
+            path.display()
+        ));
+    }
+
+    let expected = fs::read_to_string(&path).unwrap();
+    if expected == actual {
+        return Ok(());
+    }
+    let (line, expected_line, actual_line) = expected
+        .lines()
+        .zip(actual.lines())
+        .enumerate()
+        .find(|(_, (expected, actual))| expected != actual)
+        .map(|(i, (expected, actual))| (i + 1, expected, actual))
+        .unwrap_or((
+            expected.lines().count().min(actual.lines().count()) + 1,
+            "<end of snapshot>",
+            "<end of output>",
+        ));
+    Err(format!(
+        "{}:{}\n  expected: {}\n    actual: {}",
+        path.display(),
+        line,
+        expected_line,
+        actual_line
+    ))
+}
+
+fn fixtures() -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = fs::read_dir("tests/fixtures")
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "yaml"))
+        .collect();
+    paths.sort();
+    paths
+}
+
+#[test]
+fn test_card_html_snapshots() {
+    let mut failures = Vec::new();
+    for path in fixtures() {
+        let yaml = fs::read_to_string(&path).unwrap();
+        let card: Card = facet_yaml::from_str(&yaml).unwrap();
+        let name = format!("{}.html", path.file_stem().unwrap().to_string_lossy());
+        if let Err(failure) = check_snapshot(&name, body(&card_to_html(&card))) {
+            failures.push(failure);
+        }
+    }
+    assert!(
+        failures.is_empty(),
+        "{} snapshot(s) changed; re-record with UPDATE_SNAPSHOTS=1 if intended:\n{}",
+        failures.len(),
+        failures.join("\n")
+    );
+}
+
+#[test]
+fn test_emblem_html_snapshot() {
+    let yaml = fs::read_to_string("tests/fixtures/planeswalker_emblem.yaml").unwrap();
+    let card: Card = facet_yaml::from_str(&yaml).unwrap();
+    let emblem = extract_emblems(&card).into_iter().next().unwrap();
+    check_snapshot(
+        "planeswalker_emblem.emblem.html",
+        body(&card_to_html(&emblem)),
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_back_face_html_snapshots() {
+    for name in ["transform", "modal_dfc"] {
+        let yaml = fs::read_to_string(format!("tests/fixtures/{name}.yaml")).unwrap();
+        let card: Card = facet_yaml::from_str(&yaml).unwrap();
+        let back = back_face(&card).unwrap();
+        check_snapshot(&format!("{name}.back.html"), body(&card_to_html(&back))).unwrap();
+    }
+}
+
+#[test]
+fn test_stylesheet_snapshot() {
+    check_snapshot("style.css", &generate_css().into_string()).unwrap();
+}
+
+/// Symbols, fonts and frames all come from the local mtgrender assets, so a
+/// render never waits on the network
+#[test]
+fn test_cards_render_offline() {
+    for path in fixtures() {
+        let yaml = fs::read_to_string(&path).unwrap();
+        let card: Card = facet_yaml::from_str(&yaml).unwrap();
+        let html = card_to_html(&card);
+        assert!(
+            !html.contains("http://") && !html.contains("https://"),
+            "{} references a remote URL",
+            path.display()
+        );
+    }
+}
@@ -0,0 +1,248 @@
+use mtg_gen::render::card_to_html;
+use mtg_gen::style::{
+    ArtRegion, FrameTextStyle, LargePrint, StyleConfig, SymbolStyle, TextScale, with_css,
+};
+use mtg_gen::*;
+
+#[test]
+fn test_parse_style() {
+    let style: StyleConfig = facet_yaml::from_str(
+        "rules_symbols:\n  size: 24\n  baseline: -3\nadventure_symbols:\n  spacing: 1.5\n",
+    )
+    .unwrap();
+    assert_eq!(
+        style.rules_symbols,
+        Some(SymbolStyle {
+            size: Some(24.0),
+            baseline: Some(-3.0),
+            spacing: None,
+        })
+    );
+    assert_eq!(style.reminder_symbols, None);
+    assert_eq!(style.adventure_symbols.unwrap().spacing, Some(1.5));
+}
+
+#[test]
+fn test_style_css() {
+    assert_eq!(StyleConfig::default().css(), "");
+
+    let style = StyleConfig {
+        rules_symbols: Some(SymbolStyle {
+            size: Some(24.0),
+            baseline: Some(-3.0),
+            spacing: None,
+        }),
+        reminder_symbols: Some(SymbolStyle::default()),
+        adventure_symbols: Some(SymbolStyle {
+            spacing: Some(1.5),
+            ..SymbolStyle::default()
+        }),
+        ..StyleConfig::default()
+    };
+    assert_eq!(
+        style.css(),
+        ".rules-text .mana-symbol { width: 24px; height: 24px; vertical-align: -3px; }\n\
+         .adventure-text .mana-symbol { margin: 0 1.5px; }\n"
+    );
+}
+
+#[test]
+fn test_large_print_css() {
+    let style: StyleConfig = facet_yaml::from_str("large_print: {}\n").unwrap();
+    assert_eq!(style.large_print, Some(LargePrint::default()));
+    assert_eq!(
+        style.css(),
+        ".rules-text-inner, .flavor-text, .adventure-flavor { zoom: 1.3; }\n"
+    );
+
+    let style = StyleConfig {
+        large_print: Some(LargePrint {
+            scale: Some(1.5),
+            drop_flavor_text: true,
+        }),
+        ..StyleConfig::default()
+    };
+    assert_eq!(
+        style.css(),
+        ".rules-text-inner, .flavor-text, .adventure-flavor { zoom: 1.5; }\n\
+         .flavor-text, .adventure-flavor { display: none; }\n"
+    );
+}
+
+#[test]
+fn test_frame_text_css() {
+    let style: StyleConfig = facet_yaml::from_str(
+        "frame_text:\n  black: { color: white, outline: black, outline_width: 1.5 }\n  gold: { shadow: 0 2px 6px black }\n",
+    )
+    .unwrap();
+    assert_eq!(
+        style.frame_text["black"],
+        FrameTextStyle {
+            color: Some("white".to_string()),
+            outline: Some("black".to_string()),
+            outline_width: Some(1.5),
+            shadow: None,
+        }
+    );
+    let css = style.css();
+    let black = css.lines().find(|line| line.contains(".bg-black")).unwrap();
+    assert!(
+        black.starts_with(":is(.bg-black, .frame-black) :is(.card-name,"),
+        "{black}"
+    );
+    assert!(black.contains("color: white;"), "{black}");
+    assert!(black.contains("-1.5px -1.5px 0 black"), "{black}");
+    assert!(black.contains("1.5px 1.5px 0 black"), "{black}");
+    let gold = css.lines().find(|line| line.contains(".bg-gold")).unwrap();
+    assert!(gold.contains("text-shadow: 0 2px 6px black;"), "{gold}");
+    assert!(!gold.contains("color:"), "{gold}");
+}
+
+#[test]
+fn test_style_applies_after_stylesheet() {
+    let yaml = std::fs::read_to_string("tests/fixtures/normal_creature.yaml").unwrap();
+    let card: Card = facet_yaml::from_str(&yaml).unwrap();
+    let html = card_to_html(&card);
+    assert_eq!(with_css(&html, &StyleConfig::default().css()), html);
+
+    let style = StyleConfig {
+        rules_symbols: Some(SymbolStyle {
+            size: Some(30.0),
+            ..SymbolStyle::default()
+        }),
+        ..StyleConfig::default()
+    };
+    let styled = with_css(&html, &style.css());
+    let overrides = styled.find(&style.css()).unwrap();
+    assert!(overrides > styled.find(".rules-text .mana-symbol").unwrap());
+    assert!(overrides < styled.find("</head>").unwrap());
+}
+
+#[test]
+fn test_text_scale_fit() {
+    let fixed = TextScale {
+        scale: Some(0.9),
+        ..TextScale::default()
+    };
+    assert_eq!(fixed.fit(50, 8, |_| 100), 0.9);
+
+    let shrink = TextScale {
+        auto_shrink: true,
+        ..TextScale::default()
+    };
+    assert_eq!(shrink.fit(50, 8, |_| 8), 1.0);
+    // 10 lines fit in 8 once the text is 0.8 of its size
+    assert!((shrink.fit(50, 8, |_| 10) - 0.8).abs() < 1e-9);
+    assert_eq!(shrink.fit(50, 8, |_| 100), 0.6);
+}
+
+#[test]
+fn test_adventure_css() {
+    let yaml = std::fs::read_to_string("tests/fixtures/adventure.yaml").unwrap();
+    let Card::Adventure(card) = facet_yaml::from_str::<Card>(&yaml).unwrap() else {
+        panic!("Expected Adventure variant");
+    };
+    assert_eq!(StyleConfig::default().adventure_css(&card), "");
+
+    let style = StyleConfig {
+        adventure_text: Some(TextScale {
+            scale: Some(0.5),
+            ..TextScale::default()
+        }),
+        ..StyleConfig::default()
+    };
+    assert_eq!(
+        style.adventure_css(&card),
+        ".adventure-text { font-size: 7px; }\n"
+    );
+    assert!(
+        Card::Adventure(card)
+            .style_css(&style)
+            .contains(".adventure-text { font-size: 7px; }")
+    );
+}
+
+#[test]
+fn test_art_regions() {
+    let style: StyleConfig = facet_yaml::from_str(
+        "art_regions:\n  saga:\n    top: 75\n    left: 372\n    width: 336\n    height: 850\n",
+    )
+    .unwrap();
+    let saga = style.art_region("saga");
+    assert_eq!(saga.left, 372.0);
+    assert!((saga.aspect_ratio() - 336.0 / 850.0).abs() < 1e-9);
+    assert_eq!(style.art_region("battle"), ArtRegion::FULL_BLEED);
+    assert_eq!(style.art_region("normal"), ArtRegion::STANDARD);
+    assert_eq!(StyleConfig::default().art_region("saga"), ArtRegion::SAGA);
+
+    assert_eq!(
+        style.art_css("saga", false),
+        ".art-box { top: 75px; left: 372px; width: 336px; height: 850px; }\n"
+    );
+    assert_eq!(style.art_css("normal", true), "");
+    assert_eq!(style.art_css("battle", false), "");
+    assert_eq!(
+        style.art_css("battle", true),
+        ".art-box { top: 0px; left: 0px; width: 744px; height: 1040px; }\n"
+    );
+
+    let yaml = std::fs::read_to_string("tests/fixtures/saga.yaml").unwrap();
+    let card: Card = facet_yaml::from_str(&yaml).unwrap();
+    assert!(card.style_css(&style).contains("left: 372px"));
+}
+
+#[test]
+fn test_custom_color_css() {
+    let mut style: StyleConfig = facet_yaml::from_str(
+        "colors:\n  purple:\n    symbol: O\n    like: blue\n    mana_symbol: purple.svg\n    frame: purple_frame.png\n",
+    )
+    .unwrap();
+    style.resolve_paths(std::path::Path::new("/sets/mine"));
+    let css = style.css();
+    assert!(css.contains(
+        ".frame-custom-o { background-image: url('file:///sets/mine/purple_frame.png'); }"
+    ));
+    assert!(
+        css.contains(".mana-custom-o { background-image: url('file:///sets/mine/purple.svg');")
+    );
+    // Assets not given are borrowed from the built-in color
+    assert!(css.contains("img/bg/U.png"));
+    assert!(css.contains("img/pt_boxes/U.png"));
+}
+
+#[test]
+fn test_rarity_color_css() {
+    let style: StyleConfig = facet_yaml::from_str(
+        "rarity_colors:\n  common: [\"#222\"]\n  rare: [\"#fff2c0\", \"#7a5a1c\"]\n  mythic: [\"#ffd\", \"#f80\", \"#c40\", \"#600\"]\n",
+    )
+    .unwrap();
+    assert_eq!(
+        style.css(),
+        ".rarity-common { --rarity-center: #222; --rarity-middle: #222; --rarity-edge: #222; }\n\
+         .rarity-mythic { --rarity-center: #ffd; --rarity-middle: #c40; --rarity-edge: #600; }\n\
+         .rarity-rare { --rarity-center: #fff2c0; --rarity-middle: color-mix(in srgb, #fff2c0, #7a5a1c); --rarity-edge: #7a5a1c; }\n"
+    );
+}
+
+#[test]
+fn test_card_html_applies_settings_without_chrome() {
+    let card: Card = facet_yaml::from_str(
+        "name: Bolt\ntype: normal\nmana_cost: \"{R}\"\ntype_line: Instant\nrarity: common\n",
+    )
+    .unwrap();
+    let plain = CardHtml::new().to_html(&card);
+    assert!(plain.contains("Bolt"));
+    assert!(!plain.contains("color-chips"));
+    assert!(!plain.contains("NOT FOR SALE"));
+
+    let mut template = CardHtml::new();
+    template.set_color_chips(true);
+    template.set_not_for_sale(true);
+    template.set_bleed(true);
+    template.set_style(facet_yaml::from_str("rules_symbols:\n  size: 24\n").unwrap());
+    let html = template.to_html(&card);
+    assert!(html.contains("class=\"color-chips\""));
+    assert!(html.contains("NOT FOR SALE"));
+    assert!(html.contains("width: 816px"));
+    assert!(html.contains(".rules-text .mana-symbol { width: 24px; height: 24px;"));
+}
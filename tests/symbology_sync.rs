@@ -0,0 +1,71 @@
+#![cfg(feature = "symbols-sync")]
+
+use mtg_gen::symbology::SYMBOLOGY;
+use mtg_gen::symbology::sync::{ScryfallSymbol, SyncReport, compare, generate_table};
+
+fn symbol(code: &str, svg: &str, english: &str, appears_in_mana_costs: bool) -> ScryfallSymbol {
+    ScryfallSymbol {
+        symbol: code.to_string(),
+        svg_uri: Some(format!("https://svgs.scryfall.io/card-symbols/{svg}.svg")),
+        english: english.to_string(),
+        appears_in_mana_costs,
+    }
+}
+
+fn live() -> Vec<ScryfallSymbol> {
+    vec![
+        symbol("{W}", "W", "one white mana", true),
+        symbol("{ZZ}", "ZZ", "a symbol from the future", false),
+    ]
+}
+
+#[test]
+fn test_compare_against_snapshot() {
+    let report: SyncReport = compare(&live());
+    assert!(!report.is_clean());
+
+    let unsupported: Vec<&str> = report
+        .unsupported
+        .iter()
+        .map(|s| s.symbol.as_str())
+        .collect();
+    assert_eq!(unsupported, ["{ZZ}"]);
+    assert_eq!(report.missing_from_snapshot, ["{ZZ}"]);
+
+    // Everything but {W} is in the snapshot and not in the live list
+    assert_eq!(report.removed_from_scryfall.len(), SYMBOLOGY.len() - 1);
+    assert!(!report.removed_from_scryfall.contains(&"{W}"));
+    assert!(report.removed_from_scryfall.contains(&"{T}"));
+}
+
+#[test]
+fn test_snapshot_compares_clean() {
+    let live: Vec<ScryfallSymbol> = SYMBOLOGY
+        .iter()
+        .map(|entry| {
+            symbol(
+                entry.code,
+                entry.svg,
+                entry.english,
+                entry.appears_in_mana_costs,
+            )
+        })
+        .collect();
+    assert!(compare(&live).is_clean());
+}
+
+#[test]
+fn test_generate_table() {
+    assert_eq!(
+        generate_table(&live()[..1]),
+        "/// Snapshot of Scryfall's /symbology endpoint, in Scryfall's order.\n\
+         pub static SYMBOLOGY: &[SymbologyEntry] = &[\n\
+         \x20   SymbologyEntry {\n\
+         \x20       code: \"{W}\",\n\
+         \x20       svg: \"W\",\n\
+         \x20       english: \"one white mana\",\n\
+         \x20       appears_in_mana_costs: true,\n\
+         \x20   },\n\
+         ];\n"
+    );
+}
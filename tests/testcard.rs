@@ -0,0 +1,25 @@
+use mtg_gen::render::card_to_html;
+use mtg_gen::testcard::test_card;
+
+#[test]
+fn test_test_card_is_valid() {
+    let card = test_card();
+    card.validate().unwrap();
+    assert_eq!(card.layout_name(), "normal");
+    let base = card.base();
+    assert!(base.mana_cost.as_ref().unwrap().symbols.len() >= 8);
+    assert!(base.flavor_text.is_some());
+    assert!(base.power.is_some() && base.toughness.is_some());
+}
+
+#[test]
+fn test_test_card_uses_every_kind_of_symbol() {
+    let html = card_to_html(&test_card());
+    for symbol in [
+        "WU.svg", "BG.svg", "2R.svg", "GUP.svg", "C.svg", "S.svg", "X.svg",
+    ] {
+        assert!(html.contains(symbol), "no {symbol}");
+    }
+    assert!(html.contains("reminder-text"));
+    assert!(html.contains("mana-generic"));
+}
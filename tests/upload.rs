@@ -0,0 +1,99 @@
+#![cfg(feature = "upload")]
+
+use mtg_gen::upload::{S3Credentials, UploadTarget, amz_date, sign_put};
+use std::time::{Duration, UNIX_EPOCH};
+
+fn env(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+    let vars: Vec<(String, String)> = vars
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+    move |name| vars.iter().find(|(k, _)| k == name).map(|(_, v)| v.clone())
+}
+
+#[test]
+fn test_s3_destination() {
+    let target = UploadTarget::from_destination(
+        "s3://my-bucket/sets/abc/",
+        env(&[
+            ("AWS_ACCESS_KEY_ID", "AKID"),
+            ("AWS_SECRET_ACCESS_KEY", "secret"),
+            ("S3_ENDPOINT", "http://localhost:9000/"),
+        ]),
+    )
+    .unwrap();
+
+    assert_eq!(
+        target.upload_url("emblems/jace emblem.png"),
+        "http://localhost:9000/my-bucket/sets/abc/emblems/jace%20emblem.png"
+    );
+    assert_eq!(
+        target.public_url("bolt.png", Some("https://cdn.example.com/")),
+        "https://cdn.example.com/sets/abc/bolt.png"
+    );
+}
+
+#[test]
+fn test_s3_destination_requires_credentials() {
+    assert!(UploadTarget::from_destination("s3://my-bucket", env(&[])).is_err());
+}
+
+#[test]
+fn test_http_destination() {
+    let target = UploadTarget::from_destination(
+        "https://cards.example.com/upload/",
+        env(&[("UPLOAD_TOKEN", "t0ken")]),
+    )
+    .unwrap();
+
+    assert_eq!(
+        target,
+        UploadTarget::Http {
+            base_url: "https://cards.example.com/upload".to_string(),
+            token: Some("t0ken".to_string()),
+        }
+    );
+    assert_eq!(
+        target.public_url("bolt.png", None),
+        "https://cards.example.com/upload/bolt.png"
+    );
+    assert!(UploadTarget::from_destination("ftp://example.com", env(&[])).is_err());
+}
+
+#[test]
+fn test_amz_date() {
+    let time = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+    assert_eq!(amz_date(time), "20231114T221320Z");
+}
+
+#[test]
+fn test_sign_put_format() {
+    let credentials = S3Credentials {
+        access_key_id: "AKID".to_string(),
+        secret_access_key: "secret".to_string(),
+    };
+    let header = sign_put(
+        &credentials,
+        "us-east-1",
+        "s3.us-east-1.amazonaws.com",
+        "/bucket/bolt.png",
+        "20231114T221320Z",
+    );
+
+    assert!(header.starts_with(
+        "AWS4-HMAC-SHA256 Credential=AKID/20231114/us-east-1/s3/aws4_request, SignedHeaders=host;x-amz-content-sha256;x-amz-date, Signature="
+    ));
+    let signature = header.rsplit('=').next().unwrap();
+    assert_eq!(signature.len(), 64);
+    // Signing is deterministic for the same request
+    assert_eq!(
+        header,
+        sign_put(
+            &credentials,
+            "us-east-1",
+            "s3.us-east-1.amazonaws.com",
+            "/bucket/bolt.png",
+            "20231114T221320Z",
+        )
+    );
+}
@@ -0,0 +1,444 @@
+use mtg_gen::yaml::{card_to_yaml, quote};
+use mtg_gen::*;
+use proptest::collection::{btree_map, vec};
+use proptest::option;
+use proptest::prelude::*;
+use std::fs;
+
+fn parse(yaml: &str) -> Card {
+    facet_yaml::from_str(yaml).unwrap_or_else(|e| panic!("{e}\n{yaml}"))
+}
+
+#[test]
+fn test_fixtures_round_trip() {
+    for entry in fs::read_dir("tests/fixtures").unwrap() {
+        let path = entry.unwrap().path();
+        let card = parse(&fs::read_to_string(&path).unwrap());
+        assert_eq!(parse(&card_to_yaml(&card)), card, "{}", path.display());
+    }
+}
+
+#[test]
+fn test_empty_rules_text_is_kept() {
+    let card = parse(
+        "type: normal\nname: Vanilla\ntype_line: Creature\nrarity: common\nrules_text: \"\"\n",
+    );
+    assert_eq!(card.base().rules_text, Some(RulesText::default()));
+    assert_eq!(parse(&card_to_yaml(&card)), card);
+}
+
+#[test]
+fn test_quote_escapes() {
+    assert_eq!(quote("say \"hi\"\\"), r#""say \"hi\"\\""#);
+    assert_eq!(quote("a\nb\tc"), r#""a\nb\tc""#);
+    assert_eq!(quote("\u{7}\u{feff}"), r#""\u0007\uFEFF""#);
+    assert_eq!(quote("Æther — ½"), "\"Æther — ½\"");
+}
+
+// ============================================================================
+// Strategies
+// ============================================================================
+
+/// Every symbol in the Scryfall snapshot, plus any generic amount
+fn mana_symbol() -> impl Strategy<Value = ManaSymbol> {
+    let symbols: Vec<ManaSymbol> = SYMBOLOGY
+        .iter()
+        .filter_map(|entry| entry.parse().ok())
+        .collect();
+    prop_oneof![
+        any::<u32>().prop_map(|n| ManaSymbol::Casting(CastingManaSymbol::Generic(n))),
+        prop::sample::select(symbols),
+    ]
+}
+
+fn casting_symbol() -> impl Strategy<Value = CastingManaSymbol> {
+    mana_symbol().prop_filter_map("not a casting symbol", |symbol| match symbol {
+        ManaSymbol::Casting(symbol) => Some(symbol),
+        _ => None,
+    })
+}
+
+fn mana_cost(min_symbols: usize) -> impl Strategy<Value = CastingManaCost> {
+    vec(casting_symbol(), min_symbols..5).prop_map(|symbols| CastingManaCost { symbols })
+}
+
+/// A mana cost that may be left out. A cost with no symbols is written the
+/// same as no cost, so present costs have at least one symbol.
+fn optional_mana_cost() -> impl Strategy<Value = Option<CastingManaCost>> {
+    option::of(mana_cost(1))
+}
+
+/// Rules text as [`RulesText::parse`] produces it: text without braces or
+/// newlines, never empty and never next to more text, between symbols and
+/// paragraph breaks
+fn rules_text() -> impl Strategy<Value = RulesText> {
+    let segment = prop_oneof![
+        any::<String>().prop_map(|text| RulesTextSegment::Text(
+            text.replace(['{', '}', '(', ')', '*', '<', '\n'], "")
+        )),
+        mana_symbol().prop_map(RulesTextSegment::Symbol),
+        Just(RulesTextSegment::ParagraphBreak),
+    ];
+    vec(segment, 0..6).prop_map(|parts| {
+        let mut segments: Vec<RulesTextSegment> = Vec::new();
+        for part in parts {
+            match (segments.last_mut(), part) {
+                (_, RulesTextSegment::Text(text)) if text.is_empty() => {}
+                (Some(RulesTextSegment::Text(previous)), RulesTextSegment::Text(text)) => {
+                    previous.push_str(&text);
+                }
+                (_, part) => segments.push(part),
+            }
+        }
+        RulesText { segments }
+    })
+}
+
+fn text() -> impl Strategy<Value = Option<String>> {
+    option::of(any::<String>())
+}
+
+fn translation() -> impl Strategy<Value = Translation> {
+    (text(), text(), option::of(rules_text()), text()).prop_map(
+        |(name, type_line, rules_text, flavor_text)| Translation {
+            name,
+            type_line,
+            rules_text,
+            flavor_text,
+        },
+    )
+}
+
+fn base() -> impl Strategy<Value = CardBase> {
+    let text_fields = (
+        any::<String>(),
+        optional_mana_cost(),
+        any::<String>(),
+        option::of(rules_text()),
+        text(),
+        text(),
+        text(),
+    );
+    let other_fields = (
+        prop::sample::select(Rarity::ALL.to_vec()),
+        text(),
+        option::of(prop_oneof![
+            Just(ReminderTextMode::Auto),
+            Just(ReminderTextMode::None)
+        ]),
+        btree_map(any::<String>(), translation(), 0..3),
+        text(),
+        option::of(prop_oneof![
+            Just(ArtFit::Cover),
+            Just(ArtFit::Contain),
+            Just(ArtFit::Fill)
+        ]),
+        text(),
+        option::of(prop_oneof![
+            Just(CardSize::Standard),
+            Just(CardSize::Mini),
+            Just(CardSize::Jumbo)
+        ]),
+        vec(
+            prop_oneof![Just(FrameEffect::Worn), Just(FrameEffect::Grunge)],
+            0..3,
+        ),
+        text(),
+        text(),
+        option::of(prop_oneof![
+            Just(FrameColors::Cost),
+            Just(FrameColors::Identity)
+        ]),
+    );
+    (text_fields, other_fields).prop_map(
+        |(
+            (name, mana_cost, type_line, rules_text, flavor_text, power, toughness),
+            (
+                rarity,
+                collector_number,
+                reminder_text,
+                translations,
+                art,
+                art_fit,
+                artist,
+                size,
+                frame_effects,
+                set_code,
+                set_symbol,
+                frame_colors,
+            ),
+        )| CardBase {
+            name,
+            mana_cost,
+            type_line,
+            rules_text,
+            flavor_text,
+            power,
+            toughness,
+            rarity,
+            collector_number,
+            reminder_text,
+            translations,
+            art,
+            art_fit,
+            artist,
+            size,
+            frame_effects,
+            set_code,
+            set_symbol,
+            frame_colors,
+        },
+    )
+}
+
+fn face() -> impl Strategy<Value = CardFace> {
+    (
+        text(),
+        optional_mana_cost(),
+        text(),
+        option::of(rules_text()),
+        text(),
+        text(),
+        text(),
+        option::of(vec(any::<String>(), 0..3)),
+        text(),
+        text(),
+    )
+        .prop_map(
+            |(
+                name,
+                mana_cost,
+                type_line,
+                rules_text,
+                flavor_text,
+                power,
+                toughness,
+                colors,
+                art,
+                artist,
+            )| {
+                CardFace {
+                    name,
+                    mana_cost,
+                    type_line,
+                    rules_text,
+                    flavor_text,
+                    power,
+                    toughness,
+                    color_indicator: colors,
+                    art,
+                    artist,
+                }
+            },
+        )
+}
+
+fn faces() -> impl Strategy<Value = Vec<CardFace>> {
+    vec(face(), 0..3)
+}
+
+fn loyalty_cost() -> impl Strategy<Value = LoyaltyCost> {
+    prop_oneof![
+        any::<u8>().prop_map(LoyaltyCost::Plus),
+        any::<u8>().prop_map(LoyaltyCost::Minus),
+        Just(LoyaltyCost::Zero),
+        Just(LoyaltyCost::PlusX),
+        Just(LoyaltyCost::MinusX),
+    ]
+}
+
+fn level_range() -> impl Strategy<Value = LevelRange> {
+    (any::<u32>(), option::of(any::<u32>())).prop_map(|(a, b)| match b {
+        Some(b) => LevelRange {
+            min: a.min(b),
+            max: Some(a.max(b)),
+        },
+        None => LevelRange { min: a, max: None },
+    })
+}
+
+fn card() -> impl Strategy<Value = Card> {
+    prop_oneof![
+        base().prop_map(|base| Card::Normal(NormalCard { base })),
+        (
+            base(),
+            prop_oneof![
+                any::<u32>().prop_map(LoyaltyValue::Numeric),
+                Just(LoyaltyValue::X)
+            ],
+            vec(
+                (loyalty_cost(), rules_text())
+                    .prop_map(|(cost, text)| LoyaltyAbility { cost, text }),
+                0..4
+            ),
+        )
+            .prop_map(|(base, loyalty, loyalty_abilities)| {
+                Card::Planeswalker(PlaneswalkerCard {
+                    base,
+                    loyalty,
+                    loyalty_abilities,
+                })
+            }),
+        (
+            base(),
+            vec(
+                (vec(any::<u32>(), 0..3), rules_text())
+                    .prop_map(|(chapters, text)| SagaChapter { chapters, text }),
+                0..4
+            ),
+            option::of(any::<bool>()),
+        )
+            .prop_map(|(base, chapters, read_ahead)| Card::Saga(SagaCard {
+                base,
+                chapters,
+                read_ahead
+            })),
+        (
+            base(),
+            vec(
+                (any::<u32>(), option::of(mana_cost(0)), rules_text())
+                    .prop_map(|(level, cost, text)| ClassLevel { level, cost, text }),
+                0..4
+            ),
+        )
+            .prop_map(|(base, levels)| Card::Class(ClassCard { base, levels })),
+        (
+            base(),
+            any::<String>(),
+            mana_cost(0),
+            any::<String>(),
+            rules_text(),
+            text(),
+            text()
+        )
+            .prop_map(
+                |(base, name, mana_cost, type_line, rules_text, flavor_text, artist)| {
+                    Card::Adventure(AdventureCard {
+                        base,
+                        adventure: AdventureSpell {
+                            name,
+                            mana_cost,
+                            type_line,
+                            rules_text,
+                            flavor_text,
+                            artist,
+                        },
+                    })
+                },
+            ),
+        (
+            base(),
+            faces(),
+            option::of(any::<bool>()),
+            option::of(any::<bool>())
+        )
+            .prop_map(|(base, faces, fuse, aftermath)| Card::Split(SplitCard {
+                base,
+                faces,
+                fuse,
+                aftermath
+            })),
+        (base(), faces()).prop_map(|(base, faces)| Card::Flip(FlipCard { base, faces })),
+        (
+            base(),
+            faces(),
+            option::of(prop_oneof![
+                Just(DfcIcons::SunMoon),
+                Just(DfcIcons::CompassLand),
+                Just(DfcIcons::Spark),
+                Just(DfcIcons::MoonEldrazi),
+            ])
+        )
+            .prop_map(|(base, faces, dfc_icons)| Card::Transform(TransformCard {
+                base,
+                faces,
+                dfc_icons
+            })),
+        (base(), faces()).prop_map(|(base, faces)| Card::ModalDfc(ModalDfcCard { base, faces })),
+        (base(), faces()).prop_map(|(base, faces)| Card::Meld(MeldCard { base, faces })),
+        (
+            base(),
+            any::<u32>(),
+            any::<String>(),
+            any::<String>(),
+            rules_text()
+        )
+            .prop_map(
+                |(base, defense, backside_name, backside_type_line, backside_rules_text)| {
+                    Card::Battle(BattleCard {
+                        base,
+                        defense,
+                        backside_name,
+                        backside_type_line,
+                        backside_rules_text,
+                    })
+                }
+            ),
+        (
+            base(),
+            vec(
+                (level_range(), text(), text(), option::of(rules_text())).prop_map(
+                    |(range, power, toughness, text)| LevelerRange {
+                        range,
+                        power,
+                        toughness,
+                        text,
+                    }
+                ),
+                0..4
+            ),
+        )
+            .prop_map(|(base, leveler_ranges)| Card::Leveler(LevelerCard {
+                base,
+                leveler_ranges
+            })),
+        (base(), face())
+            .prop_map(|(base, prototype)| Card::Prototype(PrototypeCard { base, prototype })),
+        (base(), vec(any::<String>(), 0..3))
+            .prop_map(|(base, colors)| Card::Token(TokenCard { base, colors })),
+    ]
+}
+
+proptest! {
+    #[test]
+    fn test_any_card_round_trips(card in card()) {
+        let yaml = card_to_yaml(&card);
+        let parsed: Card = facet_yaml::from_str(&yaml)
+            .map_err(|e| TestCaseError::fail(format!("{e}\n{yaml}")))?;
+        prop_assert_eq!(parsed, card, "{}", yaml);
+    }
+
+    #[test]
+    fn test_any_mana_cost_round_trips(cost in mana_cost(0)) {
+        prop_assert_eq!(CastingManaCost::parse(&cost.to_string()).unwrap(), cost);
+    }
+
+    #[test]
+    fn test_any_rules_text_round_trips(text in rules_text()) {
+        prop_assert_eq!(RulesText::parse(&text.to_string()).unwrap(), text);
+    }
+}
+
+#[test]
+fn test_sniff_card_files() {
+    use mtg_gen::yaml::{YamlKind, sniff};
+    use std::path::Path;
+
+    for entry in fs::read_dir("tests/fixtures").unwrap() {
+        let path = entry.unwrap().path();
+        if path.extension().is_some_and(|ext| ext == "yaml") {
+            let content = fs::read_to_string(&path).unwrap();
+            assert_eq!(sniff(&path, &content), YamlKind::Card, "{:?}", path);
+        }
+    }
+
+    let set = "set_code: TST\nname: Test Set\n";
+    assert_eq!(sniff(Path::new("set.yml"), set), YamlKind::Set);
+    assert_eq!(sniff(Path::new("notes.yaml"), set), YamlKind::Other);
+    assert_eq!(
+        sniff(Path::new("quoted.yaml"), "\"type\" : normal\n"),
+        YamlKind::Card
+    );
+    let style = "art_regions:\n  normal:\n    top: 0\ntype_scale: 1\n";
+    assert_eq!(sniff(Path::new("style.yaml"), style), YamlKind::Other);
+}